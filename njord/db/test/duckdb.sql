@@ -0,0 +1,29 @@
+-- Loaded into a fresh in-memory database by every DuckDB test
+
+-- Table: users
+CREATE SEQUENCE users_id_seq START 1;
+
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY DEFAULT nextval('users_id_seq'),  -- Auto incrementing primary key for the user ID
+    username VARCHAR NOT NULL,             -- Username field
+    email VARCHAR NOT NULL,                -- Email field
+    address VARCHAR                        -- Address field
+);
+
+-- Table: categories
+CREATE TABLE categories (
+    id INTEGER PRIMARY KEY,                -- Primary key for categories
+    name VARCHAR NOT NULL                  -- Name of the category
+);
+
+-- Table: products
+CREATE TABLE products (
+    id INTEGER PRIMARY KEY,                -- Primary key for products
+    name VARCHAR NOT NULL,                 -- Product name
+    description VARCHAR,                   -- Product description
+    price DECIMAL(10, 2) NOT NULL,         -- Price with up to two decimal places
+    stock_quantity INTEGER NOT NULL,       -- Stock quantity
+    category_id INTEGER NOT NULL,          -- Foreign key to categories (one-to-one relationship)
+    discount DECIMAL(5, 2) DEFAULT 0.00,   -- Discount field with default value
+    FOREIGN KEY (category_id) REFERENCES categories(id)  -- Foreign key constraint to categories table
+);
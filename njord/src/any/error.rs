@@ -0,0 +1,291 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#[cfg(feature = "duckdb")]
+use duckdb::Error as DuckDbLibError;
+#[cfg(any(feature = "mysql", feature = "mariadb"))]
+use mysql::Error as MySqlLibError;
+#[cfg(feature = "oracle")]
+use oracle::Error as OracleLibError;
+#[cfg(feature = "sqlite")]
+use rusqlite::Error as RusqliteError;
+#[cfg(feature = "mssql")]
+use tiberius::error::Error as MSSQLLibError;
+#[cfg(feature = "postgres")]
+use tokio_postgres::Error as PostgresLibError;
+
+use crate::{builder::BuildError, row::DecodeError};
+
+/// Represents errors that can occur when working with an `AnyConnection`.
+#[derive(Debug)]
+pub enum AnyError {
+    /// The database URL could not be parsed.
+    InvalidUrl(String),
+    /// The URL scheme is unknown, or its backend feature is not enabled.
+    UnsupportedBackend(String),
+    /// The backend rejected a query without reporting a driver error.
+    Query(String),
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    Decode(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    Build(BuildError),
+    /// Error reported by the SQLite driver.
+    #[cfg(feature = "sqlite")]
+    Sqlite(RusqliteError),
+    /// Error reported by the MySQL/MariaDB driver.
+    #[cfg(any(feature = "mysql", feature = "mariadb"))]
+    MySql(MySqlLibError),
+    /// Error reported by the Oracle driver.
+    #[cfg(feature = "oracle")]
+    Oracle(OracleLibError),
+    /// Error reported by the MSSQL driver.
+    #[cfg(feature = "mssql")]
+    Mssql(MSSQLLibError),
+    /// Error reported by the PostgreSQL driver.
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresLibError),
+    /// Error reported by the DuckDB driver.
+    #[cfg(feature = "duckdb")]
+    DuckDb(DuckDbLibError),
+}
+
+impl From<DecodeError> for AnyError {
+    /// Converts a `DecodeError` into an `AnyError`.
+    fn from(error: DecodeError) -> Self {
+        AnyError::Decode(error)
+    }
+}
+
+impl From<BuildError> for AnyError {
+    /// Converts a `BuildError` into an `AnyError`.
+    fn from(error: BuildError) -> Self {
+        AnyError::Build(error)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<RusqliteError> for AnyError {
+    /// Converts a `rusqlite::Error` into an `AnyError`.
+    fn from(error: RusqliteError) -> Self {
+        AnyError::Sqlite(error)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<crate::sqlite::SqliteError> for AnyError {
+    /// Converts a `SqliteError` into an `AnyError`.
+    fn from(error: crate::sqlite::SqliteError) -> Self {
+        use crate::sqlite::SqliteError;
+
+        match error {
+            SqliteError::SelectError(error)
+            | SqliteError::InsertError(error)
+            | SqliteError::UpdateError(error)
+            | SqliteError::DeleteError(error)
+            | SqliteError::TransactionError(error) => AnyError::Sqlite(error),
+            SqliteError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            SqliteError::DecodeError(error) => AnyError::Decode(error),
+            SqliteError::BuildError(error) => AnyError::Build(error),
+        }
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "mariadb"))]
+impl From<MySqlLibError> for AnyError {
+    /// Converts a `mysql::Error` into an `AnyError`.
+    fn from(error: MySqlLibError) -> Self {
+        AnyError::MySql(error)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<crate::mysql::MySqlError> for AnyError {
+    /// Converts a `MySqlError` into an `AnyError`.
+    fn from(error: crate::mysql::MySqlError) -> Self {
+        use crate::mysql::MySqlError;
+
+        match error {
+            MySqlError::SelectError(error)
+            | MySqlError::InsertError(error)
+            | MySqlError::UpdateError(error)
+            | MySqlError::DeleteError(error)
+            | MySqlError::TransactionError(error) => AnyError::MySql(error),
+            MySqlError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            MySqlError::DecodeError(error) => AnyError::Decode(error),
+            MySqlError::BuildError(error) => AnyError::Build(error),
+        }
+    }
+}
+
+#[cfg(feature = "mariadb")]
+impl From<crate::mariadb::MariaDBError> for AnyError {
+    /// Converts a `MariaDBError` into an `AnyError`.
+    fn from(error: crate::mariadb::MariaDBError) -> Self {
+        use crate::mariadb::MariaDBError;
+
+        match error {
+            MariaDBError::SelectError(error)
+            | MariaDBError::InsertError(error)
+            | MariaDBError::UpdateError(error)
+            | MariaDBError::DeleteError(error)
+            | MariaDBError::TransactionError(error) => AnyError::MySql(error),
+            MariaDBError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            MariaDBError::DecodeError(error) => AnyError::Decode(error),
+            MariaDBError::BuildError(error) => AnyError::Build(error),
+        }
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl From<OracleLibError> for AnyError {
+    /// Converts an `oracle::Error` into an `AnyError`.
+    fn from(error: OracleLibError) -> Self {
+        AnyError::Oracle(error)
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl From<crate::oracle::OracleError> for AnyError {
+    /// Converts an `OracleError` into an `AnyError`.
+    fn from(error: crate::oracle::OracleError) -> Self {
+        use crate::oracle::OracleError;
+
+        match error {
+            OracleError::SelectError(error)
+            | OracleError::InsertError(error)
+            | OracleError::UpdateError(error)
+            | OracleError::DeleteError(error)
+            | OracleError::TransactionError(error) => AnyError::Oracle(error),
+            OracleError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            OracleError::DecodeError(error) => AnyError::Decode(error),
+            OracleError::BuildError(error) => AnyError::Build(error),
+        }
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl From<MSSQLLibError> for AnyError {
+    /// Converts a `tiberius::Error` into an `AnyError`.
+    fn from(error: MSSQLLibError) -> Self {
+        AnyError::Mssql(error)
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl From<crate::mssql::MSSQLError> for AnyError {
+    /// Converts a `MSSQLError` into an `AnyError`.
+    fn from(error: crate::mssql::MSSQLError) -> Self {
+        use crate::mssql::MSSQLError;
+
+        match error {
+            MSSQLError::SelectError(error)
+            | MSSQLError::InsertError(error)
+            | MSSQLError::UpdateError(error)
+            | MSSQLError::DeleteError(error)
+            | MSSQLError::TransactionError(error) => AnyError::Mssql(error),
+            MSSQLError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            MSSQLError::DecodeError(error) => AnyError::Decode(error),
+            MSSQLError::BuildError(error) => AnyError::Build(error),
+            MSSQLError::InvalidQuery => AnyError::Query("invalid query".to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<PostgresLibError> for AnyError {
+    /// Converts a `tokio_postgres::Error` into an `AnyError`.
+    fn from(error: PostgresLibError) -> Self {
+        AnyError::Postgres(error)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl From<crate::postgres::PostgresError> for AnyError {
+    /// Converts a `PostgresError` into an `AnyError`.
+    fn from(error: crate::postgres::PostgresError) -> Self {
+        use crate::postgres::PostgresError;
+
+        match error {
+            PostgresError::SelectError(error)
+            | PostgresError::InsertError(error)
+            | PostgresError::UpdateError(error)
+            | PostgresError::DeleteError(error)
+            | PostgresError::TransactionError(error) => AnyError::Postgres(error),
+            PostgresError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            PostgresError::DecodeError(error) => AnyError::Decode(error),
+            PostgresError::BuildError(error) => AnyError::Build(error),
+        }
+    }
+}
+
+#[cfg(feature = "duckdb")]
+impl From<DuckDbLibError> for AnyError {
+    /// Converts a `duckdb::Error` into an `AnyError`.
+    fn from(error: DuckDbLibError) -> Self {
+        AnyError::DuckDb(error)
+    }
+}
+
+#[cfg(feature = "duckdb")]
+impl From<crate::duckdb::DuckDbError> for AnyError {
+    /// Converts a `DuckDbError` into an `AnyError`.
+    fn from(error: crate::duckdb::DuckDbError) -> Self {
+        use crate::duckdb::DuckDbError;
+
+        match error {
+            DuckDbError::SelectError(error)
+            | DuckDbError::InsertError(error)
+            | DuckDbError::UpdateError(error)
+            | DuckDbError::DeleteError(error)
+            | DuckDbError::TransactionError(error) => AnyError::DuckDb(error),
+            DuckDbError::UnexpectedRowCount { expected, actual } => {
+                AnyError::UnexpectedRowCount { expected, actual }
+            }
+            DuckDbError::DecodeError(error) => AnyError::Decode(error),
+            DuckDbError::BuildError(error) => AnyError::Build(error),
+            DuckDbError::NestedTransactionError => {
+                AnyError::Query("nested transactions are not supported".to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder::{self, BulkInsertProgress},
+    dialect::AnyDialect,
+    table::Table,
+};
+
+#[cfg(feature = "duckdb")]
+use crate::dialect::DuckDbDialect;
+#[cfg(feature = "mariadb")]
+use crate::dialect::MariaDbDialect;
+#[cfg(feature = "mssql")]
+use crate::dialect::MssqlDialect;
+#[cfg(feature = "mysql")]
+use crate::dialect::MySqlDialect;
+#[cfg(feature = "oracle")]
+use crate::dialect::OracleDialect;
+#[cfg(feature = "postgres")]
+use crate::dialect::PostgresDialect;
+#[cfg(feature = "sqlite")]
+use crate::dialect::SqliteDialect;
+
+use super::{AnyConnection, AnyError};
+
+/// Inserts rows into a table of any backend.
+///
+/// # Arguments
+///
+/// * `conn` - The connection to insert the rows on.
+/// * `table_rows` - A vector of objects implementing the `Table` trait representing
+///   the rows to be inserted into the database.
+///
+/// # Returns
+///
+/// A `Result` containing the inserted rows with their generated keys set
+/// if the insertion is successful, or an `AnyError` if an error occurs.
+pub async fn insert<T: Table>(
+    conn: &mut AnyConnection,
+    table_rows: Vec<T>,
+) -> Result<Vec<T>, AnyError> {
+    match conn {
+        #[cfg(feature = "sqlite")]
+        AnyConnection::Sqlite(conn) => Ok(crate::sqlite::insert(conn, table_rows)?),
+        #[cfg(feature = "mysql")]
+        AnyConnection::MySql(conn) => Ok(crate::mysql::insert(conn, table_rows)?),
+        #[cfg(feature = "mariadb")]
+        AnyConnection::MariaDb(conn) => Ok(crate::mariadb::insert(conn, table_rows)?),
+        #[cfg(feature = "oracle")]
+        AnyConnection::Oracle(conn) => Ok(crate::oracle::insert(conn, table_rows)?),
+        #[cfg(feature = "mssql")]
+        AnyConnection::Mssql(conn) => Ok(crate::mssql::insert(conn, table_rows).await?),
+        #[cfg(feature = "postgres")]
+        AnyConnection::Postgres(conn) => Ok(crate::postgres::insert(conn, table_rows).await?),
+        #[cfg(feature = "duckdb")]
+        AnyConnection::DuckDb(conn) => Ok(crate::duckdb::insert(conn, table_rows)?),
+    }
+}
+
+/// Constructs a new bulk insert builder.
+///
+/// # Arguments
+///
+/// * `rows` - The table rows to insert, e.g. an iterator reading them from a file.
+///
+/// # Returns
+///
+/// A `BulkInsertBuilder` instance.
+pub fn bulk_insert<'a, R>(rows: R) -> BulkInsertBuilder<'a, R::IntoIter>
+where
+    R: IntoIterator,
+    R::Item: Table,
+{
+    BulkInsertBuilder::new(rows)
+}
+
+/// A builder for inserting a large number of rows on an `AnyConnection`.
+pub type BulkInsertBuilder<'a, I> = builder::BulkInsertBuilder<'a, I, AnyDialect>;
+
+impl<'a, T: Table, I: Iterator<Item = T>> BulkInsertBuilder<'a, I> {
+    /// Builds and executes the INSERT statements of all chunks in one transaction,
+    /// or in a savepoint when a transaction is already open, with the dialect of
+    /// the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The connection to insert the rows on.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the final progress, with the total number of rows
+    /// inserted, or an `AnyError` if an error occurs. Nothing is inserted then.
+    pub async fn build(self, conn: &mut AnyConnection) -> Result<BulkInsertProgress, AnyError> {
+        let progress = match conn {
+            #[cfg(feature = "sqlite")]
+            AnyConnection::Sqlite(conn) => self.into_dialect(SqliteDialect).build(conn)?,
+            #[cfg(feature = "mysql")]
+            AnyConnection::MySql(conn) => self.into_dialect(MySqlDialect).build(conn)?,
+            #[cfg(feature = "mariadb")]
+            AnyConnection::MariaDb(conn) => self.into_dialect(MariaDbDialect).build(conn)?,
+            #[cfg(feature = "oracle")]
+            AnyConnection::Oracle(conn) => self.into_dialect(OracleDialect).build(conn)?,
+            #[cfg(feature = "mssql")]
+            AnyConnection::Mssql(conn) => self.into_dialect(MssqlDialect).build(conn).await?,
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(conn) => self.into_dialect(PostgresDialect).build(conn).await?,
+            #[cfg(feature = "duckdb")]
+            AnyConnection::DuckDb(conn) => self.into_dialect(DuckDbDialect).build(conn)?,
+        };
+
+        Ok(progress)
+    }
+}
@@ -0,0 +1,173 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder, column::Column, dialect::AnyDialect, relation::Related, row::FromRow, table::Table,
+};
+
+#[cfg(feature = "duckdb")]
+use crate::dialect::DuckDbDialect;
+#[cfg(feature = "mariadb")]
+use crate::dialect::MariaDbDialect;
+#[cfg(feature = "mssql")]
+use crate::dialect::MssqlDialect;
+#[cfg(feature = "mysql")]
+use crate::dialect::MySqlDialect;
+#[cfg(feature = "oracle")]
+use crate::dialect::OracleDialect;
+#[cfg(feature = "postgres")]
+use crate::dialect::PostgresDialect;
+#[cfg(feature = "sqlite")]
+use crate::dialect::SqliteDialect;
+
+use super::{AnyConnection, AnyError};
+
+/// Constructs a new SELECT query builder.
+///
+/// # Arguments
+///
+/// * `columns` - A vector of columns to be selected.
+///
+/// # Returns
+///
+/// A `SelectQueryBuilder` instance.
+pub fn select<'a, T: Table + Default>(columns: Vec<Column<'a>>) -> SelectQueryBuilder<'a, T> {
+    SelectQueryBuilder::new(columns)
+}
+
+/// A builder for constructing SELECT queries on an `AnyConnection`.
+pub type SelectQueryBuilder<'a, T> = builder::SelectQueryBuilder<'a, T, AnyDialect>;
+
+impl<'a, T: Table + FromRow + Default> SelectQueryBuilder<'a, T> {
+    /// Builds and executes the SELECT query with the dialect of the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The connection to execute the query on.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of selected table rows if successful,
+    /// or an `AnyError` if an error occurs during the execution.
+    pub async fn build(self, conn: &mut AnyConnection) -> Result<Vec<T>, AnyError> {
+        match conn {
+            #[cfg(feature = "sqlite")]
+            AnyConnection::Sqlite(conn) => Ok(self.into_dialect(SqliteDialect).build(conn)?),
+            #[cfg(feature = "mysql")]
+            AnyConnection::MySql(conn) => Ok(self.into_dialect(MySqlDialect).build(conn)?),
+            #[cfg(feature = "mariadb")]
+            AnyConnection::MariaDb(conn) => Ok(self.into_dialect(MariaDbDialect).build(conn)?),
+            #[cfg(feature = "oracle")]
+            AnyConnection::Oracle(conn) => Ok(self.into_dialect(OracleDialect).build(conn)?),
+            #[cfg(feature = "mssql")]
+            AnyConnection::Mssql(conn) => Ok(self.into_dialect(MssqlDialect).build(conn).await?),
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(conn) => {
+                Ok(self.into_dialect(PostgresDialect).build(conn).await?)
+            }
+            #[cfg(feature = "duckdb")]
+            AnyConnection::DuckDb(conn) => Ok(self.into_dialect(DuckDbDialect).build(conn)?),
+        }
+    }
+
+    /// Builds and executes the SELECT query with the dialect of the connection,
+    /// decoding the rows into `R` instead of `T`.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The connection to execute the query on.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of the decoded rows if successful,
+    /// or an `AnyError` if the query fails or a row cannot be decoded.
+    pub async fn build_as<R: FromRow>(self, conn: &mut AnyConnection) -> Result<Vec<R>, AnyError> {
+        match conn {
+            #[cfg(feature = "sqlite")]
+            AnyConnection::Sqlite(conn) => Ok(self.into_dialect(SqliteDialect).build_as(conn)?),
+            #[cfg(feature = "mysql")]
+            AnyConnection::MySql(conn) => Ok(self.into_dialect(MySqlDialect).build_as(conn)?),
+            #[cfg(feature = "mariadb")]
+            AnyConnection::MariaDb(conn) => Ok(self.into_dialect(MariaDbDialect).build_as(conn)?),
+            #[cfg(feature = "oracle")]
+            AnyConnection::Oracle(conn) => Ok(self.into_dialect(OracleDialect).build_as(conn)?),
+            #[cfg(feature = "mssql")]
+            AnyConnection::Mssql(conn) => {
+                Ok(self.into_dialect(MssqlDialect).build_as(conn).await?)
+            }
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(conn) => {
+                Ok(self.into_dialect(PostgresDialect).build_as(conn).await?)
+            }
+            #[cfg(feature = "duckdb")]
+            AnyConnection::DuckDb(conn) => Ok(self.into_dialect(DuckDbDialect).build_as(conn)?),
+        }
+    }
+}
+
+/// A builder for SELECT queries on an `AnyConnection` loading related rows, created by `with`.
+pub type EagerQueryBuilder<'a, T, R> = builder::EagerQueryBuilder<'a, T, R, AnyDialect>;
+
+impl<T, R> EagerQueryBuilder<'_, T, R>
+where
+    T: Related<R> + FromRow + Default,
+    R: Table + FromRow + Default + Clone,
+{
+    /// Builds and executes the SELECT query with the dialect of the connection, then
+    /// loads the related rows with one batched query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The connection to execute the query on.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the selected table rows, each paired with its related rows,
+    /// or an `AnyError` if a query fails or a row cannot be decoded.
+    pub async fn build(self, conn: &mut AnyConnection) -> Result<Vec<(T, Vec<R>)>, AnyError> {
+        match conn {
+            #[cfg(feature = "sqlite")]
+            AnyConnection::Sqlite(conn) => Ok(self.into_dialect(SqliteDialect).build(conn)?),
+            #[cfg(feature = "mysql")]
+            AnyConnection::MySql(conn) => Ok(self.into_dialect(MySqlDialect).build(conn)?),
+            #[cfg(feature = "mariadb")]
+            AnyConnection::MariaDb(conn) => Ok(self.into_dialect(MariaDbDialect).build(conn)?),
+            #[cfg(feature = "oracle")]
+            AnyConnection::Oracle(conn) => Ok(self.into_dialect(OracleDialect).build(conn)?),
+            #[cfg(feature = "mssql")]
+            AnyConnection::Mssql(conn) => Ok(self.into_dialect(MssqlDialect).build(conn).await?),
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(conn) => {
+                Ok(self.into_dialect(PostgresDialect).build(conn).await?)
+            }
+            #[cfg(feature = "duckdb")]
+            AnyConnection::DuckDb(conn) => Ok(self.into_dialect(DuckDbDialect).build(conn)?),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::ops::{Deref, DerefMut};
+
+use log::error;
+
+use crate::transaction::{BoxFuture, IsolationLevel, TransactionStatements};
+
+use super::{AnyConnection, AnyError};
+
+/// A transaction running on an `AnyConnection`.
+///
+/// The transaction dereferences to its connection, so every query builder can be
+/// run inside it. A transaction whose future is dropped before it finished stays
+/// open, and the connection should not be used any further.
+pub struct Transaction<'c> {
+    conn: &'c mut AnyConnection,
+    depth: usize,
+    statements: TransactionStatements,
+    autocommit: Option<bool>,
+}
+
+impl<'c> Transaction<'c> {
+    async fn begin(
+        conn: &'c mut AnyConnection,
+        depth: usize,
+        isolation_level: Option<IsolationLevel>,
+    ) -> Result<Self, AnyError> {
+        let statements = TransactionStatements::new(&conn.dialect(), depth, isolation_level)
+            .ok_or_else(|| AnyError::Query("nested transactions are not supported".to_string()))?;
+
+        let autocommit = disable_autocommit(conn);
+
+        if let Err(err) = execute(conn, &statements.begin).await {
+            restore_autocommit(conn, autocommit);
+            return Err(err);
+        }
+
+        Ok(Transaction {
+            conn,
+            depth,
+            statements,
+            autocommit,
+        })
+    }
+
+    /// Runs a closure inside a transaction nested in this one.
+    ///
+    /// The nested transaction is a savepoint. Rolling it back keeps the changes made
+    /// by the outer transaction before it started. DuckDB has no savepoints, nesting
+    /// a transaction there fails with `AnyError::Query`.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run. It returns its future boxed, e.g. with `Box::pin`.
+    ///
+    /// # Returns
+    ///
+    /// The value returned by the closure, or the error the closure or the database
+    /// returned.
+    pub async fn transaction<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        E: From<AnyError>,
+        F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+    {
+        run(
+            Transaction::begin(self.conn, self.depth + 1, None).await?,
+            f,
+        )
+        .await
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = AnyConnection;
+
+    fn deref(&self) -> &AnyConnection {
+        self.conn
+    }
+}
+
+impl DerefMut for Transaction<'_> {
+    fn deref_mut(&mut self) -> &mut AnyConnection {
+        self.conn
+    }
+}
+
+/// Runs a closure inside a transaction.
+///
+/// The transaction is committed if the closure returns `Ok`, and rolled back if it
+/// returns `Err`.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `f` - The closure to run. It gets the transaction to run its queries on and
+///   returns its future boxed, e.g. with `Box::pin`.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub async fn transaction<T, E, F>(conn: &mut AnyConnection, f: F) -> Result<T, E>
+where
+    E: From<AnyError>,
+    F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+{
+    run(Transaction::begin(conn, 0, None).await?, f).await
+}
+
+/// Runs a closure inside a transaction with the given isolation level.
+///
+/// Backends without the level run the transaction with the next stricter one
+/// they have.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `isolation_level` - The isolation level to run the transaction with.
+/// * `f` - The closure to run. It gets the transaction to run its queries on and
+///   returns its future boxed, e.g. with `Box::pin`.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub async fn transaction_with<T, E, F>(
+    conn: &mut AnyConnection,
+    isolation_level: IsolationLevel,
+    f: F,
+) -> Result<T, E>
+where
+    E: From<AnyError>,
+    F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+{
+    run(Transaction::begin(conn, 0, Some(isolation_level)).await?, f).await
+}
+
+async fn run<T, E, F>(mut tx: Transaction<'_>, f: F) -> Result<T, E>
+where
+    E: From<AnyError>,
+    F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+{
+    let result = f(&mut tx).await;
+
+    let finished = match result {
+        Ok(_) => execute(tx.conn, &tx.statements.commit).await,
+        Err(_) => {
+            roll_back(tx.conn, &tx.statements).await;
+            Ok(())
+        }
+    };
+
+    // A failed commit leaves the transaction open
+    if finished.is_err() {
+        roll_back(tx.conn, &tx.statements).await;
+    }
+    restore_autocommit(tx.conn, tx.autocommit);
+    finished?;
+
+    result
+}
+
+/// Rolls a transaction back, only logging a failure so the error that caused it is kept.
+async fn roll_back(conn: &mut AnyConnection, statements: &TransactionStatements) {
+    if let Err(err) = execute(conn, &statements.rollback).await {
+        error!("Failed to roll back the transaction: {:?}", err);
+    }
+}
+
+async fn execute(conn: &mut AnyConnection, statements: &[String]) -> Result<(), AnyError> {
+    match conn {
+        #[cfg(feature = "sqlite")]
+        AnyConnection::Sqlite(conn) => Ok(crate::sqlite::transaction::execute(conn, statements)?),
+        #[cfg(feature = "mysql")]
+        AnyConnection::MySql(conn) => Ok(crate::mysql::transaction::execute(conn, statements)?),
+        #[cfg(feature = "mariadb")]
+        AnyConnection::MariaDb(conn) => Ok(crate::mariadb::transaction::execute(conn, statements)?),
+        #[cfg(feature = "oracle")]
+        AnyConnection::Oracle(conn) => Ok(crate::oracle::transaction::execute(conn, statements)?),
+        #[cfg(feature = "mssql")]
+        AnyConnection::Mssql(conn) => {
+            Ok(crate::mssql::transaction::execute(conn, statements).await?)
+        }
+        #[cfg(feature = "postgres")]
+        AnyConnection::Postgres(conn) => {
+            Ok(crate::postgres::transaction::execute(conn, statements).await?)
+        }
+        #[cfg(feature = "duckdb")]
+        AnyConnection::DuckDb(conn) => Ok(crate::duckdb::transaction::execute(conn, statements)?),
+    }
+}
+
+/// Turns off autocommit for the duration of a transaction, for the backends that
+/// have it. Returns the previous setting.
+#[cfg_attr(not(feature = "oracle"), allow(unused_variables))]
+fn disable_autocommit(conn: &mut AnyConnection) -> Option<bool> {
+    #[cfg(feature = "oracle")]
+    if let AnyConnection::Oracle(conn) = conn {
+        let autocommit = conn.autocommit();
+        conn.set_autocommit(false);
+        return Some(autocommit);
+    }
+
+    None
+}
+
+/// Restores the autocommit setting returned by `disable_autocommit`.
+#[cfg_attr(not(feature = "oracle"), allow(unused_variables))]
+fn restore_autocommit(conn: &mut AnyConnection, autocommit: Option<bool>) {
+    #[cfg(feature = "oracle")]
+    if let (AnyConnection::Oracle(conn), Some(autocommit)) = (conn, autocommit) {
+        conn.set_autocommit(autocommit);
+    }
+}
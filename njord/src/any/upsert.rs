@@ -0,0 +1,96 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{builder, dialect::AnyDialect, table::Table};
+
+#[cfg(feature = "duckdb")]
+use crate::dialect::DuckDbDialect;
+#[cfg(feature = "mariadb")]
+use crate::dialect::MariaDbDialect;
+#[cfg(feature = "mssql")]
+use crate::dialect::MssqlDialect;
+#[cfg(feature = "mysql")]
+use crate::dialect::MySqlDialect;
+#[cfg(feature = "oracle")]
+use crate::dialect::OracleDialect;
+#[cfg(feature = "postgres")]
+use crate::dialect::PostgresDialect;
+#[cfg(feature = "sqlite")]
+use crate::dialect::SqliteDialect;
+
+use super::{AnyConnection, AnyError};
+
+/// Constructs a new upsert builder.
+///
+/// # Arguments
+///
+/// * `rows` - The table rows to insert, or to update the conflicting rows with.
+///
+/// # Returns
+///
+/// An `UpsertQueryBuilder` instance.
+pub fn upsert<T: Table>(rows: Vec<T>) -> UpsertQueryBuilder<T> {
+    UpsertQueryBuilder::new().values(rows)
+}
+
+/// A builder for constructing upserts on an `AnyConnection`.
+pub type UpsertQueryBuilder<T> = builder::UpsertQueryBuilder<T, AnyDialect>;
+
+impl<T: Table> UpsertQueryBuilder<T> {
+    /// Builds and executes the upsert with the dialect of the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The connection to execute the query on.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of inserted or updated rows if successful,
+    /// or an `AnyError` if an error occurs. MySQL and MariaDB count an updated row twice.
+    pub async fn build(self, conn: &mut AnyConnection) -> Result<u64, AnyError> {
+        let rows = match conn {
+            #[cfg(feature = "sqlite")]
+            AnyConnection::Sqlite(conn) => self.into_dialect(SqliteDialect).build(conn)?,
+            #[cfg(feature = "mysql")]
+            AnyConnection::MySql(conn) => self.into_dialect(MySqlDialect).build(conn)?,
+            #[cfg(feature = "mariadb")]
+            AnyConnection::MariaDb(conn) => self.into_dialect(MariaDbDialect).build(conn)?,
+            #[cfg(feature = "oracle")]
+            AnyConnection::Oracle(conn) => self.into_dialect(OracleDialect).build(conn)?,
+            #[cfg(feature = "mssql")]
+            AnyConnection::Mssql(conn) => self.into_dialect(MssqlDialect).build(conn).await?,
+            #[cfg(feature = "postgres")]
+            AnyConnection::Postgres(conn) => self.into_dialect(PostgresDialect).build(conn).await?,
+            #[cfg(feature = "duckdb")]
+            AnyConnection::DuckDb(conn) => self.into_dialect(DuckDbDialect).build(conn)?,
+        };
+
+        Ok(rows)
+    }
+}
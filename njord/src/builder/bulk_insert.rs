@@ -0,0 +1,321 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::iter::Peekable;
+
+use crate::{
+    builder::insert::{inserted_columns, InsertQueryBuilder},
+    dialect::Dialect,
+    table::Table,
+};
+
+/// The progress of a bulk insert.
+///
+/// It is reported after every chunk, and returned once all rows are inserted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BulkInsertProgress {
+    /// The number of rows inserted so far.
+    pub rows_inserted: u64,
+    /// The number of INSERT statements executed so far.
+    pub chunks: u64,
+}
+
+/// A callback receiving the progress of a bulk insert.
+type ProgressCallback<'a> = Box<dyn FnMut(&BulkInsertProgress) + 'a>;
+
+/// A builder for inserting a large number of rows.
+///
+/// The rows are pulled from an iterator and split into chunks fitting the limits
+/// of the dialect `D`, see [`Dialect::max_bind_parameters`], [`Dialect::max_insert_rows`]
+/// and [`Dialect::max_statement_bytes`]. Each chunk is inserted with a single
+/// multi-row INSERT; each backend module adds a `build` method running all of them
+/// in one transaction.
+pub struct BulkInsertBuilder<'a, I: Iterator, D: Dialect> {
+    dialect: D,
+    rows: Peekable<I>,
+    chunk_size: Option<usize>,
+    on_progress: Option<ProgressCallback<'a>>,
+    progress: BulkInsertProgress,
+}
+
+impl<'a, T: Table, I: Iterator<Item = T>, D: Dialect + Default> BulkInsertBuilder<'a, I, D> {
+    /// Creates a new `BulkInsertBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The table rows to insert.
+    pub fn new<R: IntoIterator<IntoIter = I>>(rows: R) -> Self {
+        Self::with_dialect(D::default(), rows)
+    }
+}
+
+impl<'a, T: Table, I: Iterator<Item = T>, D: Dialect> BulkInsertBuilder<'a, I, D> {
+    /// Creates a new `BulkInsertBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `rows` - The table rows to insert.
+    pub fn with_dialect<R: IntoIterator<IntoIter = I>>(dialect: D, rows: R) -> Self {
+        BulkInsertBuilder {
+            dialect,
+            rows: rows.into_iter().peekable(),
+            chunk_size: None,
+            on_progress: None,
+            progress: BulkInsertProgress::default(),
+        }
+    }
+
+    /// Sets the most rows inserted by a single statement.
+    ///
+    /// Chunks are still kept within the limits of the dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The maximum number of rows per chunk.
+    pub fn chunk_size(mut self, rows: usize) -> Self {
+        self.chunk_size = Some(rows.max(1));
+        self
+    }
+
+    /// Sets a callback receiving the progress after every inserted chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The callback to run.
+    pub fn on_progress<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&BulkInsertProgress) + 'a,
+    {
+        self.on_progress = Some(Box::new(f));
+        self
+    }
+
+    /// Returns the progress made so far.
+    pub fn progress(&self) -> BulkInsertProgress {
+        self.progress
+    }
+
+    /// Records that a chunk was inserted and reports the new progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The number of rows the chunk inserted.
+    pub fn record_chunk(&mut self, rows: usize) {
+        self.progress.rows_inserted += rows as u64;
+        self.progress.chunks += 1;
+
+        if let Some(on_progress) = self.on_progress.as_mut() {
+            on_progress(&self.progress);
+        }
+    }
+
+    /// Returns the dialect the queries are rendered through.
+    pub fn dialect(&self) -> &D {
+        &self.dialect
+    }
+
+    /// Converts the builder into one rendering through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the queries through.
+    pub fn into_dialect<E: Dialect>(self, dialect: E) -> BulkInsertBuilder<'a, I, E> {
+        BulkInsertBuilder {
+            dialect,
+            rows: self.rows,
+            chunk_size: self.chunk_size,
+            on_progress: self.on_progress,
+            progress: self.progress,
+        }
+    }
+
+    /// Returns the most rows the next chunk may hold.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The number of columns inserted for each row.
+    fn max_chunk_rows(&self, columns: usize) -> usize {
+        let mut rows = self.dialect.max_bind_parameters() / columns.max(1);
+
+        if let Some(max_rows) = self.dialect.max_insert_rows() {
+            rows = rows.min(max_rows);
+        }
+        if let Some(chunk_size) = self.chunk_size {
+            rows = rows.min(chunk_size);
+        }
+
+        rows.max(1)
+    }
+}
+
+impl<'a, T: Table, I: Iterator<Item = T>, D: Dialect + Clone> BulkInsertBuilder<'a, I, D> {
+    /// Takes the rows of the next chunk from the iterator.
+    ///
+    /// # Returns
+    ///
+    /// An INSERT query for the chunk, or `None` once all rows are taken.
+    pub fn next_chunk(&mut self) -> Option<InsertQueryBuilder<'a, T, D>> {
+        let first_row = self.rows.next()?;
+        let columns = inserted_columns(&first_row);
+        let max_rows = self.max_chunk_rows(columns.len());
+        let max_bytes = self.dialect.max_statement_bytes();
+
+        let mut bytes = row_bytes(&first_row);
+        let mut rows = vec![first_row];
+
+        while rows.len() < max_rows {
+            // A row setting other columns, e.g. its `AutoIncrementPrimaryKey`, starts a new chunk
+            let fits = |row: &T| {
+                max_bytes.map_or(true, |max| bytes + row_bytes(row) <= max)
+                    && inserted_columns(row) == columns
+            };
+
+            match self.rows.next_if(fits) {
+                Some(row) => {
+                    bytes += row_bytes(&row);
+                    rows.push(row);
+                }
+                None => break,
+            }
+        }
+
+        Some(InsertQueryBuilder::with_dialect(self.dialect.clone()).values(rows))
+    }
+}
+
+/// Estimates the number of bytes the values of a row take up in a statement.
+///
+/// # Arguments
+///
+/// * `row` - The table row being inserted.
+fn row_bytes<T: Table>(row: &T) -> usize {
+    row.get_column_values()
+        .iter()
+        .map(|value| value.to_string().len() + 1)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MssqlDialect, MySqlDialect, SqliteDialect};
+    use crate::keys::AutoIncrementPrimaryKey;
+    use njord_derive::Table;
+
+    #[derive(Table)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    fn users(count: usize) -> impl Iterator<Item = User> {
+        (0..count).map(|index| User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: format!("user{}", index),
+            email: format!("user{}@example.com", index),
+        })
+    }
+
+    fn chunk_sizes<D: Dialect + Clone>(
+        mut builder: BulkInsertBuilder<'_, impl Iterator<Item = User>, D>,
+    ) -> Vec<usize> {
+        let mut sizes = Vec::new();
+        while let Some(chunk) = builder.next_chunk() {
+            sizes.push(chunk.rows().len());
+        }
+        sizes
+    }
+
+    #[test]
+    fn chunks_by_bind_parameters() {
+        // Two columns are bound per row, id is left to the database
+        let sizes = chunk_sizes(BulkInsertBuilder::<_, SqliteDialect>::new(users(40_000)));
+
+        assert_eq!(sizes, vec![16383, 16383, 7234]);
+    }
+
+    #[test]
+    fn chunks_by_insert_rows() {
+        let sizes = chunk_sizes(BulkInsertBuilder::<_, MssqlDialect>::new(users(2500)));
+
+        assert_eq!(sizes, vec![1000, 1000, 500]);
+    }
+
+    #[test]
+    fn chunks_by_statement_bytes() {
+        let rows = (0..3).map(|index| User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: format!("user{}", index),
+            email: "x".repeat(2 * 1024 * 1024),
+        });
+
+        let sizes = chunk_sizes(BulkInsertBuilder::<_, MySqlDialect>::new(rows));
+
+        assert_eq!(sizes, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn chunks_by_inserted_columns() {
+        let rows = users(4).enumerate().map(|(index, mut user)| {
+            if index == 2 {
+                user.id = AutoIncrementPrimaryKey::new(Some(100));
+            }
+            user
+        });
+
+        let sizes = chunk_sizes(BulkInsertBuilder::<_, SqliteDialect>::new(rows));
+
+        assert_eq!(sizes, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn chunk_size_and_progress() {
+        let mut reported = Vec::new();
+        let mut builder = BulkInsertBuilder::<_, SqliteDialect>::new(users(5))
+            .chunk_size(2)
+            .on_progress(|progress| reported.push(progress.rows_inserted));
+
+        while let Some(chunk) = builder.next_chunk() {
+            builder.record_chunk(chunk.rows().len());
+        }
+
+        assert_eq!(
+            builder.progress(),
+            BulkInsertProgress {
+                rows_inserted: 5,
+                chunks: 3
+            }
+        );
+        drop(builder);
+        assert_eq!(reported, vec![2, 4, 5]);
+    }
+}
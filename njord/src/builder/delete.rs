@@ -0,0 +1,234 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder::{
+        util::{concat_clauses, generate_dml_target},
+        BuildError,
+    },
+    condition::Condition,
+    dialect::Dialect,
+    keys::key_condition,
+    query::QueryParams,
+    table::Table,
+};
+
+/// A builder for constructing DELETE queries.
+///
+/// The query is rendered through the dialect `D`; each backend module adds a
+/// `build` method executing it on its own connection type.
+pub struct DeleteQueryBuilder<'a, T: Table + Default, D: Dialect> {
+    dialect: D,
+    table: Option<T>,
+    where_condition: Option<Condition<'a>>,
+    order_by: Option<Vec<(Vec<String>, String)>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    expected_rows: Option<u64>,
+}
+
+impl<'a, T: Table + Default, D: Dialect + Default> Default for DeleteQueryBuilder<'a, T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Table + Default, D: Dialect + Default> DeleteQueryBuilder<'a, T, D> {
+    /// Creates a new `DeleteQueryBuilder` instance.
+    pub fn new() -> Self {
+        Self::with_dialect(D::default())
+    }
+}
+
+impl<'a, T: Table + Default, D: Dialect> DeleteQueryBuilder<'a, T, D> {
+    /// Creates a new `DeleteQueryBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    pub fn with_dialect(dialect: D) -> Self {
+        DeleteQueryBuilder {
+            dialect,
+            table: None,
+            where_condition: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+            expected_rows: None,
+        }
+    }
+
+    /// Sets the table from which to delete data.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table from which to delete data.
+    pub fn from(mut self, table: T) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    /// Sets the WHERE clause condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the WHERE clause.
+    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
+        self.where_condition = Some(condition);
+        self
+    }
+
+    /// Restricts the query to the row with the primary key of the table row passed to `from`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the restricted query, or a `BuildError` if `from` was not
+    /// called or the table has no primary key.
+    pub fn by_key(mut self) -> Result<Self, BuildError> {
+        let table = self.table.as_ref().ok_or(BuildError::MissingTable)?;
+
+        self.where_condition = Some(key_condition(table, table.primary_key_values())?);
+        Ok(self)
+    }
+
+    /// Sets the ORDER BY clause columns and order direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_and_order` - Pairs of columns and their order direction, e.g. a `HashMap` or
+    ///   `[User::ID.desc()]`.
+    pub fn order_by<I>(mut self, col_and_order: I) -> Self
+    where
+        I: IntoIterator<Item = (Vec<String>, String)>,
+    {
+        self.order_by = Some(col_and_order.into_iter().collect());
+        self
+    }
+
+    /// Sets the LIMIT clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The maximum number of rows to be deleted.
+    pub fn limit(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+        self
+    }
+
+    /// Sets the OFFSET clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset from which to start deleting rows.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Makes the query fail unless exactly `count` rows are deleted.
+    ///
+    /// The statement runs in a transaction of its own, or in a savepoint inside of
+    /// a transaction, which is rolled back when the check fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows the query is expected to affect.
+    pub fn expect_rows(mut self, count: u64) -> Self {
+        self.expected_rows = Some(count);
+        self
+    }
+
+    /// Returns the number of rows the query is expected to affect, if set.
+    pub fn expected_rows(&self) -> Option<u64> {
+        self.expected_rows
+    }
+
+    /// Builds the query string, this function should be used internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values bound by the query.
+    pub fn build_query(&self, params: &mut QueryParams) -> String {
+        let table_name = self
+            .table
+            .as_ref()
+            .map(|t| self.dialect.identifier(t.get_name()))
+            .unwrap_or_default();
+
+        let target = generate_dml_target(
+            &self.dialect,
+            &table_name,
+            self.where_condition.clone(),
+            &self.order_by,
+            self.limit,
+            self.offset,
+            params,
+        );
+
+        concat_clauses(&[
+            target.with_clause,
+            format!("DELETE FROM {}", target.table),
+            target.tail,
+        ])
+    }
+
+    /// Renders the query together with its bound values.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the SQL text and the values bound to its placeholders.
+    pub fn render(&self) -> (String, QueryParams) {
+        let mut params = QueryParams::new(self.dialect.placeholder_style());
+        let sql = self.build_query(&mut params);
+
+        (sql, params)
+    }
+
+    /// Returns the dialect the query is rendered through.
+    pub fn dialect(&self) -> &D {
+        &self.dialect
+    }
+
+    /// Converts the query into one rendered through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query through.
+    pub fn into_dialect<E: Dialect>(self, dialect: E) -> DeleteQueryBuilder<'a, T, E> {
+        DeleteQueryBuilder {
+            dialect,
+            table: self.table,
+            where_condition: self.where_condition,
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            expected_rows: self.expected_rows,
+        }
+    }
+}
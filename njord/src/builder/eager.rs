@@ -0,0 +1,264 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, HashSet},
+    mem::Discriminant,
+    sync::Arc,
+};
+
+use crate::{
+    builder::{select::SelectQueryBuilder, BuildError},
+    column::Column,
+    condition::{Condition, Value},
+    dialect::Dialect,
+    query::Param,
+    relation::{Related, Relation},
+    table::Table,
+    util::JoinType,
+};
+
+/// A builder for SELECT queries loading the related rows of `R` together with the rows.
+///
+/// Created by `SelectQueryBuilder::with`. Each backend module adds a `build` method
+/// running the query, then loading the related rows of all returned rows in one
+/// batched query, so the rows come back paired with their related rows without a
+/// query per row.
+pub struct EagerQueryBuilder<'a, T: Table + Default, R: Table, D: Dialect> {
+    select: SelectQueryBuilder<'a, T, D>,
+    related: R,
+}
+
+impl<'a, T, R, D> EagerQueryBuilder<'a, T, R, D>
+where
+    T: Related<R> + Default,
+    R: Table + Default,
+    D: Dialect + Clone,
+{
+    /// Creates a new `EagerQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `select` - The query selecting the rows.
+    /// * `related` - An instance of the related table.
+    pub fn new(select: SelectQueryBuilder<'a, T, D>, related: R) -> Self {
+        EagerQueryBuilder { select, related }
+    }
+
+    /// Returns the query selecting the rows.
+    pub fn select(&self) -> &SelectQueryBuilder<'a, T, D> {
+        &self.select
+    }
+
+    /// Builds the queries loading the related rows of `parents`.
+    ///
+    /// The keys of all parents go into a single query, which is only split when they
+    /// exceed the bind parameter or `IN` list limit of the dialect. Each query selects the key a
+    /// row is matched on followed by the columns of `R`, so its rows decode as
+    /// `(Param, R)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `parents` - The rows returned by the query.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the queries to run, none if no parent has a key, or a
+    /// `BuildError` if the relation needs the primary key of a table that has none or
+    /// a composite one.
+    pub fn related_queries(
+        &self,
+        parents: &[T],
+    ) -> Result<Vec<SelectQueryBuilder<'static, R, D>>, BuildError> {
+        let dialect = self.select.dialect();
+        let related_table = dialect.identifier(self.related.get_name());
+
+        let mut seen = HashSet::new();
+        let keys: Vec<Param> = parents
+            .iter()
+            .map(|parent| parent.relation_key())
+            .filter(|key| !matches!(key, Param::Null) && seen.insert(group_key(key)))
+            .collect();
+
+        let mut columns: Vec<String> = self.related.get_columns().into_keys().collect();
+        columns.sort();
+        let columns: Vec<Column<'static>> = columns
+            .iter()
+            .map(|column| Column::Text(format!("{}.{}", related_table, column)))
+            .collect();
+
+        let key_column = match T::relation() {
+            Relation::BelongsTo { .. } => {
+                format!("{}.{}", related_table, single_key(&self.related)?)
+            }
+            Relation::HasMany { foreign_key } => format!("{}.{}", related_table, foreign_key),
+            Relation::ManyToMany {
+                through,
+                foreign_key,
+                ..
+            } => format!("{}.{}", dialect.identifier(&through), foreign_key),
+        };
+
+        let related_primary_key = match T::relation() {
+            Relation::ManyToMany { .. } => Some(single_key(&self.related)?),
+            _ => None,
+        };
+
+        let chunk_size = match dialect.max_in_list() {
+            Some(max_in_list) => max_in_list.min(dialect.max_bind_parameters()),
+            None => dialect.max_bind_parameters(),
+        };
+
+        let queries = keys
+            .chunks(chunk_size.max(1))
+            .map(|chunk| {
+                let mut query_columns = vec![Column::Text(key_column.clone())];
+                query_columns.extend(columns.iter().cloned());
+
+                let mut query = SelectQueryBuilder::with_dialect(dialect.clone(), query_columns)
+                    .from(R::default());
+
+                if let (
+                    Relation::ManyToMany {
+                        through,
+                        related_key,
+                        ..
+                    },
+                    Some(key),
+                ) = (T::relation(), &related_primary_key)
+                {
+                    let on_condition = Condition::Eq(
+                        format!("{}.{}", dialect.identifier(&through), related_key),
+                        Value::Column(format!("{}.{}", related_table, key)),
+                    );
+                    query = query.join(JoinType::Inner, Arc::new(JoinTable(through)), on_condition);
+                }
+
+                query.where_clause(Condition::In(
+                    key_column.clone(),
+                    chunk.iter().cloned().map(Value::from).collect(),
+                ))
+            })
+            .collect();
+
+        Ok(queries)
+    }
+
+    /// Pairs every parent with the related rows matching its key.
+    ///
+    /// # Arguments
+    ///
+    /// * `parents` - The rows returned by the query.
+    /// * `rows` - The rows returned by the `related_queries`.
+    ///
+    /// # Returns
+    ///
+    /// The parents in their original order, each with its related rows.
+    pub fn group(&self, parents: Vec<T>, rows: Vec<(Param, R)>) -> Vec<(T, Vec<R>)>
+    where
+        R: Clone,
+    {
+        let mut related: HashMap<(Discriminant<Param>, String), Vec<R>> = HashMap::new();
+        for (key, row) in rows {
+            related.entry(group_key(&key)).or_default().push(row);
+        }
+
+        parents
+            .into_iter()
+            .map(|parent| {
+                let key = parent.relation_key();
+                let rows = match key {
+                    Param::Null => Vec::new(),
+                    key => related.get(&group_key(&key)).cloned().unwrap_or_default(),
+                };
+                (parent, rows)
+            })
+            .collect()
+    }
+
+    /// Converts the query into one rendered through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query through.
+    pub fn into_dialect<E: Dialect + Clone>(self, dialect: E) -> EagerQueryBuilder<'a, T, R, E> {
+        EagerQueryBuilder {
+            select: self.select.into_dialect(dialect),
+            related: self.related,
+        }
+    }
+}
+
+/// Returns the key related rows are grouped by, which tells e.g. `Int(1)` and
+/// `Text("1")` apart.
+fn group_key(key: &Param) -> (Discriminant<Param>, String) {
+    (std::mem::discriminant(key), key.to_string())
+}
+
+/// Returns the column of the single-column primary key of `table`.
+///
+/// # Arguments
+///
+/// * `table` - The table whose key is looked up.
+///
+/// # Returns
+///
+/// A `Result` containing the key column, or a `BuildError` if the table has no
+/// primary key or a composite one.
+fn single_key(table: &dyn Table) -> Result<String, BuildError> {
+    let mut key = table.primary_key();
+    match key.len() {
+        0 => Err(BuildError::MissingPrimaryKey(table.get_name().to_string())),
+        1 => Ok(key.remove(0)),
+        _ => Err(BuildError::CompositePrimaryKey(
+            table.get_name().to_string(),
+        )),
+    }
+}
+
+/// A join table, known to a `ManyToMany` relation only by its name.
+struct JoinTable(String);
+
+impl Table for JoinTable {
+    fn get_name(&self) -> &str {
+        &self.0
+    }
+
+    fn get_columns(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    fn get_column_fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn get_column_values(&self) -> Vec<Param> {
+        Vec::new()
+    }
+}
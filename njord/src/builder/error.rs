@@ -0,0 +1,78 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+
+/// Represents errors found while building a query, before it is executed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BuildError {
+    /// A row sets other columns than the first row of the same statement,
+    /// e.g. because only some rows leave their `AutoIncrementPrimaryKey` to the database.
+    MixedColumns { row: usize },
+    /// The table has no column with the given name.
+    UnknownColumn(String),
+    /// The column is read, but never written, e.g. a generated or read-only column.
+    ReadOnlyColumn(String),
+    /// The table has no primary key to look its rows up by.
+    MissingPrimaryKey(String),
+    /// The table has a composite primary key where a single column is needed.
+    CompositePrimaryKey(String),
+    /// A different number of values than the primary key has columns was given.
+    KeyValueCount { expected: usize, actual: usize },
+    /// The query has no table, e.g. because `from` was not called.
+    MissingTable,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::MixedColumns { row } => write!(
+                f,
+                "row {} sets other columns than the first row, insert them with separate statements",
+                row
+            ),
+            BuildError::UnknownColumn(column) => write!(f, "no column named '{}'", column),
+            BuildError::ReadOnlyColumn(column) => write!(f, "column '{}' is read-only", column),
+            BuildError::MissingPrimaryKey(table) => {
+                write!(f, "table '{}' has no primary key", table)
+            }
+            BuildError::CompositePrimaryKey(table) => {
+                write!(f, "table '{}' needs a single-column primary key", table)
+            }
+            BuildError::KeyValueCount { expected, actual } => write!(
+                f,
+                "the primary key has {} columns, but {} values were given",
+                expected, actual
+            ),
+            BuildError::MissingTable => write!(f, "the query has no table, call `from` first"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
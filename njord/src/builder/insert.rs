@@ -0,0 +1,425 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder::BuildError,
+    dialect::{Dialect, ReturningStyle},
+    query::{Param, QueryBuilder, QueryParams},
+    row::DecodeError,
+    table::Table,
+};
+
+/// A builder for constructing INSERT queries.
+///
+/// Rows are inserted either from `Table` values or from the result of a subquery.
+/// The query is rendered through the dialect `D`; each backend module executes it
+/// on its own connection type.
+pub struct InsertQueryBuilder<'a, T: Table, D: Dialect> {
+    dialect: D,
+    table_name: String,
+    columns: Vec<String>,
+    rows: Vec<T>,
+    subquery: Option<Box<dyn QueryBuilder<'a> + 'a>>,
+    returning: Vec<String>,
+}
+
+impl<'a, T: Table, D: Dialect + Default> Default for InsertQueryBuilder<'a, T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Table, D: Dialect + Default> InsertQueryBuilder<'a, T, D> {
+    /// Creates a new `InsertQueryBuilder` instance.
+    pub fn new() -> Self {
+        Self::with_dialect(D::default())
+    }
+}
+
+impl<'a, T: Table, D: Dialect> InsertQueryBuilder<'a, T, D> {
+    /// Creates a new `InsertQueryBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    pub fn with_dialect(dialect: D) -> Self {
+        InsertQueryBuilder {
+            dialect,
+            table_name: String::new(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            subquery: None,
+            returning: Vec::new(),
+        }
+    }
+
+    /// Sets the rows to be inserted.
+    ///
+    /// The columns are taken from the first row. Fields holding an
+    /// `AutoIncrementPrimaryKey` without a value are left to the database,
+    /// so rows with and without a key cannot share a statement, see `validate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The table rows to insert.
+    pub fn values(mut self, rows: Vec<T>) -> Self {
+        if let Some(first_row) = rows.first() {
+            self.table_name = first_row.get_name().to_string();
+            self.columns = inserted_columns(first_row);
+        }
+
+        self.rows = rows;
+        self.subquery = None;
+        self
+    }
+
+    /// Sets the columns the query returns for each inserted row, e.g. a generated id.
+    ///
+    /// How the values are handed back depends on the dialect's [`ReturningStyle`].
+    /// With `ReturningStyle::Output` the rows come back in any order, so each one
+    /// starts with the position of its inserted row.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to return.
+    pub fn returning<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.returning = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the rows to be inserted.
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Consumes the builder, returning the rows to be inserted.
+    pub fn into_rows(self) -> Vec<T> {
+        self.rows
+    }
+
+    /// Returns the column whose values the database generates for the inserted rows.
+    ///
+    /// This is the `AutoIncrementPrimaryKey` of the rows, if it is left to the database.
+    pub fn generated_key(&self) -> Option<String> {
+        let column = self.rows.first()?.get_auto_increment_primary_key()?;
+
+        if self.columns.iter().any(|c| c == column) {
+            None
+        } else {
+            Some(column.to_string())
+        }
+    }
+
+    /// Consumes the builder, returning the rows to be inserted with their generated keys set.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The generated keys as read from the database, in the order of the rows.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the rows, or a `DecodeError` if there isn't exactly one
+    /// key for each row or a key cannot be decoded into the `AutoIncrementPrimaryKey`
+    /// of its row.
+    pub fn into_rows_with_keys<I>(self, keys: I) -> Result<Vec<T>, DecodeError>
+    where
+        I: IntoIterator<Item = Param>,
+    {
+        let mut rows = self.rows;
+        let keys: Vec<Param> = keys.into_iter().collect();
+
+        if keys.len() != rows.len() {
+            return Err(DecodeError::KeyCount {
+                expected: rows.len(),
+                actual: keys.len(),
+            });
+        }
+
+        for (row, key) in rows.iter_mut().zip(keys) {
+            row.set_auto_increment_primary_key(key)?;
+        }
+
+        Ok(rows)
+    }
+
+    /// Checks that the query can be executed as a single statement.
+    ///
+    /// Every row must set the same columns as the first one; a row holding an
+    /// `AutoIncrementPrimaryKey` sets one more than a row leaving it to the database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that is a `BuildError` naming the first row setting other columns.
+    pub fn validate(&self) -> Result<(), BuildError> {
+        check_inserted_columns(&self.columns, &self.rows)
+    }
+
+    /// Builds the query string, this function should be used internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values bound by the query.
+    pub fn build_query(&self, params: &mut QueryParams) -> String {
+        let table_name = self.dialect.identifier(&self.table_name);
+        let columns_str = self.columns.join(", ");
+        let is_insert_all = self.subquery.is_none()
+            && self.rows.len() > 1
+            && !self.dialect.supports_multi_row_insert();
+
+        let sql = if let Some(subquery) = &self.subquery {
+            format!(
+                "INSERT INTO {} ({}) {}",
+                table_name,
+                columns_str,
+                subquery.to_sql(params)
+            )
+        } else if is_insert_all {
+            let targets: Vec<String> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    format!(
+                        "INTO {} ({}) VALUES ({})",
+                        table_name,
+                        columns_str,
+                        bind_row_values(&self.columns, row, params).join(", ")
+                    )
+                })
+                .collect();
+
+            format!("INSERT ALL {} SELECT 1 FROM DUAL", targets.join(" "))
+        } else if self.dialect.returning_style() == ReturningStyle::Output
+            && !self.returning.is_empty()
+        {
+            // The OUTPUT rows of an INSERT come in any order, so a MERGE inserts
+            // the rows and outputs the position of each source row with its values
+            let values_str: Vec<String> = self
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(index, row)| {
+                    let mut values = vec![index.to_string()];
+                    values.extend(bind_row_values(&self.columns, row, params));
+                    format!("({})", values.join(", "))
+                })
+                .collect();
+            let source_columns: Vec<String> = self
+                .columns
+                .iter()
+                .map(|column| format!("source.{}", column))
+                .collect();
+            let inserted: Vec<String> = self
+                .returning
+                .iter()
+                .map(|column| format!("INSERTED.{}", column))
+                .collect();
+
+            format!(
+                "MERGE INTO {} USING (VALUES {}) AS source (njord_row, {}) ON 1 = 0 \
+                 WHEN NOT MATCHED THEN INSERT ({}) VALUES ({}) \
+                 OUTPUT source.njord_row, {};",
+                table_name,
+                values_str.join(", "),
+                columns_str,
+                columns_str,
+                source_columns.join(", "),
+                inserted.join(", ")
+            )
+        } else {
+            let values_str: Vec<String> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    format!(
+                        "({})",
+                        bind_row_values(&self.columns, row, params).join(", ")
+                    )
+                })
+                .collect();
+
+            format!(
+                "INSERT INTO {} ({}) VALUES {}",
+                table_name,
+                columns_str,
+                values_str.join(", ")
+            )
+        };
+
+        let sql = match self.dialect.returning_style() {
+            // INSERT ALL can't return anything
+            _ if self.returning.is_empty() || is_insert_all => sql,
+            ReturningStyle::Returning => {
+                format!("{} RETURNING {}", sql, self.returning.join(", "))
+            }
+            ReturningStyle::ReturningInto => {
+                // The placeholders are reserved for the out parameters the
+                // backend binds when executing the statement.
+                let targets: Vec<String> = self
+                    .returning
+                    .iter()
+                    .map(|_| params.push(Param::Null))
+                    .collect();
+                format!(
+                    "{} RETURNING {} INTO {}",
+                    sql,
+                    self.returning.join(", "),
+                    targets.join(", ")
+                )
+            }
+            ReturningStyle::None | ReturningStyle::Output => sql,
+        };
+
+        sql
+    }
+
+    /// Renders the query together with its bound values.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the SQL text and the values bound to its placeholders.
+    pub fn render(&self) -> (String, QueryParams) {
+        let mut params = QueryParams::new(self.dialect.placeholder_style());
+        let sql = self.build_query(&mut params);
+
+        (sql, params)
+    }
+
+    /// Returns the dialect the query is rendered through.
+    pub fn dialect(&self) -> &D {
+        &self.dialect
+    }
+
+    /// Converts the query into one rendered through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query through.
+    pub fn into_dialect<E: Dialect>(self, dialect: E) -> InsertQueryBuilder<'a, T, E> {
+        InsertQueryBuilder {
+            dialect,
+            table_name: self.table_name,
+            columns: self.columns,
+            rows: self.rows,
+            subquery: self.subquery,
+            returning: self.returning,
+        }
+    }
+}
+
+impl<'a, T: Table + Default, D: Dialect> InsertQueryBuilder<'a, T, D> {
+    /// Sets a subquery whose result rows are inserted.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns of the table the subquery's columns are inserted into.
+    /// * `subquery` - The query selecting the rows to insert.
+    pub fn select(
+        mut self,
+        columns: Vec<String>,
+        subquery: Box<dyn QueryBuilder<'a> + 'a>,
+    ) -> Self {
+        self.table_name = T::default().get_name().to_string();
+        self.columns = columns;
+        self.rows = Vec::new();
+        self.subquery = Some(subquery);
+        self
+    }
+}
+
+/// Returns the columns an INSERT of `row` sets.
+///
+/// Fields holding an `AutoIncrementPrimaryKey` without a value are left out.
+///
+/// # Arguments
+///
+/// * `row` - The table row being inserted.
+pub(crate) fn inserted_columns<T: Table>(row: &T) -> Vec<String> {
+    let values = row.get_column_values();
+    let generated = row.get_auto_increment_primary_key();
+
+    row.get_column_fields()
+        .into_iter()
+        .zip(values.iter())
+        .filter(|(column, value)| !(generated == Some(column.as_str()) && **value == Param::Null))
+        .map(|(column, _)| column)
+        .collect()
+}
+
+/// Checks that every row sets exactly `columns`.
+///
+/// # Arguments
+///
+/// * `columns` - The columns of the statement, taken from the first row.
+/// * `rows` - The table rows being inserted.
+///
+/// # Returns
+///
+/// A `Result` that is a `BuildError` naming the first row setting other columns.
+pub(crate) fn check_inserted_columns<T: Table>(
+    columns: &[String],
+    rows: &[T],
+) -> Result<(), BuildError> {
+    match rows.iter().position(|row| inserted_columns(row) != columns) {
+        Some(row) => Err(BuildError::MixedColumns { row }),
+        None => Ok(()),
+    }
+}
+
+/// Binds the values `row` holds for `columns` and returns their placeholders.
+///
+/// # Arguments
+///
+/// * `columns` - The columns whose values are bound, in order.
+/// * `row` - The table row being inserted.
+/// * `params` - The parameter list receiving the values of the row.
+pub(crate) fn bind_row_values<T: Table>(
+    columns: &[String],
+    row: &T,
+    params: &mut QueryParams,
+) -> Vec<String> {
+    let fields = row.get_column_fields();
+    let values = row.get_column_values();
+
+    columns
+        .iter()
+        .map(|column| {
+            let value = fields
+                .iter()
+                .position(|field| field == column)
+                .and_then(|index| values.get(index))
+                .cloned()
+                .unwrap_or(Param::Null);
+            params.push(value)
+        })
+        .collect()
+}
@@ -0,0 +1,53 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Query builders shared by all backends.
+//!
+//! The builders render their SQL through a [`Dialect`](crate::dialect::Dialect).
+//! The backend modules export them with their dialect filled in, e.g.
+//! `sqlite::select::SelectQueryBuilder`, and add the methods executing them.
+
+pub mod bulk_insert;
+pub mod delete;
+pub mod eager;
+pub mod error;
+pub mod insert;
+pub mod select;
+pub mod update;
+pub mod upsert;
+pub(crate) mod util;
+
+pub use bulk_insert::{BulkInsertBuilder, BulkInsertProgress};
+pub use delete::DeleteQueryBuilder;
+pub use eager::EagerQueryBuilder;
+pub use error::BuildError;
+pub use insert::InsertQueryBuilder;
+pub use select::SelectQueryBuilder;
+pub use update::UpdateQueryBuilder;
+pub use upsert::UpsertQueryBuilder;
@@ -0,0 +1,461 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::Arc;
+
+use crate::{
+    builder::{
+        eager::EagerQueryBuilder,
+        util::{
+            concat_clauses, generate_group_by_str, generate_having_str, generate_order_by_str,
+            generate_where_condition_str,
+        },
+        BuildError,
+    },
+    column::Column,
+    condition::Condition,
+    dialect::Dialect,
+    keys::{key_condition, KeyValues},
+    query::{QueryBuilder, QueryParams},
+    relation::Related,
+    table::Table,
+    util::{Join, JoinType},
+};
+
+/// A builder for constructing SELECT queries.
+///
+/// The query is rendered through the dialect `D`; each backend module adds a
+/// `build` method executing it on its own connection type.
+#[derive(Clone)]
+pub struct SelectQueryBuilder<'a, T: Table + Default, D: Dialect> {
+    dialect: D,
+    table: Option<T>,
+    columns: Vec<Column<'a>>,
+    where_condition: Option<Condition<'a>>,
+    distinct: bool,
+    group_by: Option<Vec<String>>,
+    order_by: Option<Vec<(Vec<String>, String)>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    having_condition: Option<Condition<'a>>,
+    except_clauses: Option<Vec<SelectQueryBuilder<'a, T, D>>>,
+    union_clauses: Option<Vec<SelectQueryBuilder<'a, T, D>>>,
+    joins: Option<Vec<Join<'a>>>,
+}
+
+impl<'a, T: Table + Default, D: Dialect + Default> SelectQueryBuilder<'a, T, D> {
+    /// Creates a new `SelectQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A vector of strings representing the columns to be selected.
+    pub fn new(columns: Vec<Column<'a>>) -> Self {
+        Self::with_dialect(D::default(), columns)
+    }
+}
+
+impl<'a, T: Table + Default, D: Dialect> SelectQueryBuilder<'a, T, D> {
+    /// Creates a new `SelectQueryBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `columns` - A vector of strings representing the columns to be selected.
+    pub fn with_dialect(dialect: D, columns: Vec<Column<'a>>) -> Self {
+        SelectQueryBuilder {
+            dialect,
+            table: None,
+            columns,
+            where_condition: None,
+            distinct: false,
+            group_by: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+            having_condition: None,
+            except_clauses: None,
+            union_clauses: None,
+            joins: None,
+        }
+    }
+
+    /// Sets the columns to be selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A vector of strings representing the columns to be selected.
+    pub fn select(mut self, columns: Vec<Column<'a>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the DISTINCT keyword for the query.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Sets the table from which to select data.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table from which to select data.
+    pub fn from(mut self, table: T) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    /// Sets the WHERE clause condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the WHERE clause.
+    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
+        self.where_condition = Some(condition);
+        self
+    }
+
+    /// Restricts the query to the row with the given primary key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value of the primary key, or a tuple with the values of a
+    ///   composite key in the order of `Table::primary_key`.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the restricted query, or a `BuildError` if the table has no
+    /// primary key or `key` holds a different number of values.
+    pub fn find(mut self, key: impl KeyValues) -> Result<Self, BuildError> {
+        let condition = match &self.table {
+            Some(table) => key_condition(table, key.key_values())?,
+            None => key_condition(&T::default(), key.key_values())?,
+        };
+
+        self.where_condition = Some(condition);
+        Ok(self)
+    }
+
+    /// Loads the rows of a related table together with the selected rows.
+    ///
+    /// The related rows of all selected rows are read with one extra query, and
+    /// `build` returns every row paired with the related rows matching it.
+    ///
+    /// # Arguments
+    ///
+    /// * `related` - An instance of the related table, declared on `T` with
+    ///   `belongs_to`, `has_many` or `many_to_many`.
+    pub fn with<R>(self, related: R) -> EagerQueryBuilder<'a, T, R, D>
+    where
+        T: Related<R>,
+        R: Table + Default,
+        D: Clone,
+    {
+        EagerQueryBuilder::new(self, related)
+    }
+
+    /// Sets the GROUP BY clause columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to be grouped by, as strings or typed column handles.
+    pub fn group_by<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.group_by = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the ORDER BY clause columns and order direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_and_order` - Pairs of columns and their order direction, e.g. a `HashMap` or
+    ///   `[User::ID.desc()]`.
+    pub fn order_by<I>(mut self, col_and_order: I) -> Self
+    where
+        I: IntoIterator<Item = (Vec<String>, String)>,
+    {
+        self.order_by = Some(col_and_order.into_iter().collect());
+        self
+    }
+
+    /// Sets the LIMIT clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows to limit the result to.
+    pub fn limit(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+        self
+    }
+
+    /// Sets the OFFSET clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The number of rows to skip.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sets the HAVING clause condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the HAVING clause.
+    pub fn having(mut self, condition: Condition<'a>) -> Self {
+        self.having_condition = Some(condition);
+        self
+    }
+
+    /// Adds an EXCEPT clause to the query, allowing you to exclude results from another query.
+    ///
+    /// This method modifies the current query builder to exclude the results of the specified
+    /// `other_query`. If there are already existing EXCEPT clauses, the new clause will be added
+    /// to the list. If no EXCEPT clauses exist, a new list will be created with the provided
+    /// query.
+    ///
+    /// # Arguments
+    ///
+    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
+    ///   should be excluded from the current query.
+    ///
+    /// # Returns
+    ///
+    /// Returns the modified `SelectQueryBuilder` instance with the new EXCEPT clause added.
+    pub fn except(mut self, other_query: SelectQueryBuilder<'a, T, D>) -> Self {
+        match self.except_clauses {
+            Some(ref mut clauses) => clauses.push(other_query),
+            None => self.except_clauses = Some(vec![other_query]),
+        }
+        self
+    }
+
+    /// Adds a UNION clause to the query, allowing you to combine results from another query.
+    ///
+    /// This method modifies the current query builder to include the results of the specified
+    /// `other_query`. If there are already existing UNION clauses, the new clause will be added
+    /// to the list. If no UNION clauses exist, a new list will be created with the provided
+    /// query.
+    ///
+    /// # Arguments
+    ///
+    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
+    ///   should be combined with the current query.
+    ///
+    /// # Returns
+    ///
+    /// Returns the modified `SelectQueryBuilder` instance with the new UNION clause added.
+    pub fn union(mut self, other_query: SelectQueryBuilder<'a, T, D>) -> Self {
+        match self.union_clauses {
+            Some(ref mut clauses) => clauses.push(other_query),
+            None => self.union_clauses = Some(vec![other_query]),
+        }
+        self
+    }
+
+    /// Adds a JOIN clause to the query, allowing you to combine rows from two or more tables based on a related column.
+    ///
+    /// This method modifies the current query builder to include a join clause with the specified join type,
+    /// target table, and condition for the join. If there are already existing JOIN clauses, the new clause
+    /// will be added to the list. If no JOIN clauses exist, a new list will be created with the provided
+    /// join information.
+    ///
+    /// # Arguments
+    ///
+    /// * `join_type` - The type of join to perform (e.g., INNER, LEFT, RIGHT, FULL).
+    /// * `table` - The table to join with the current table.
+    /// * `on_condition` - The condition that specifies how the tables are related (the ON clause).
+    ///
+    /// # Returns
+    ///
+    /// Returns the modified `SelectQueryBuilder` instance with the new JOIN clause added.
+    pub fn join(
+        mut self,
+        join_type: JoinType,
+        table: Arc<dyn Table>,
+        on_condition: Condition<'a>,
+    ) -> Self {
+        match self.joins {
+            Some(ref mut joins) => joins.push(Join::new(join_type, table, on_condition)),
+            None => self.joins = Some(vec![Join::new(join_type, table, on_condition)]),
+        }
+        self
+    }
+
+    /// Builds the query string, this function should be used internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values bound by the query.
+    pub fn build_query(&self, params: &mut QueryParams) -> String {
+        let columns_str = self
+            .columns
+            .iter()
+            .map(|c| c.build(params))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let table_name = self
+            .table
+            .as_ref()
+            .map(|t| self.dialect.identifier(t.get_name()))
+            .unwrap_or_default();
+
+        // Generate JOIN clauses, if any
+        let join_clauses: Vec<String> = match &self.joins {
+            Some(joins) => joins
+                .iter()
+                .map(|join| {
+                    let join_type_str = match join.join_type {
+                        JoinType::Inner => "INNER JOIN",
+                        JoinType::Left => "LEFT JOIN",
+                        JoinType::Right => "RIGHT JOIN",
+                        JoinType::Full => "FULL OUTER JOIN",
+                    };
+                    format!(
+                        "{} {} ON {}",
+                        join_type_str,
+                        self.dialect.identifier(join.table.get_name()),
+                        join.on_condition.build(params)
+                    )
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let distinct_str = if self.distinct { "DISTINCT " } else { "" };
+        let where_condition_str =
+            generate_where_condition_str(self.where_condition.clone(), params);
+        let group_by_str = generate_group_by_str(&self.group_by);
+        let having_str = generate_having_str(
+            self.group_by.is_some(),
+            self.having_condition.as_ref(),
+            params,
+        );
+        let order_by_str = generate_order_by_str(&self.order_by);
+        let pagination_str =
+            self.dialect
+                .pagination(self.limit, self.offset, !order_by_str.is_empty());
+
+        let mut query = concat_clauses(&[
+            format!("SELECT {}{} FROM {}", distinct_str, columns_str, table_name),
+            join_clauses.join(" "),
+            where_condition_str,
+            group_by_str,
+            having_str,
+            order_by_str,
+            pagination_str,
+        ]);
+
+        // Handle EXCEPT clauses
+        if let Some(except_clauses) = &self.except_clauses {
+            for except_query in except_clauses {
+                let except_sql = except_query.build_query(params);
+                query = format!("{} EXCEPT {}", query, except_sql);
+            }
+        }
+
+        // Handle UNION clauses
+        if let Some(union_clauses) = &self.union_clauses {
+            for union_query in union_clauses {
+                let union_sql = union_query.build_query(params);
+                query = format!("{} UNION {}", query, union_sql);
+            }
+        }
+
+        query
+    }
+
+    /// Renders the query together with its bound values.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the SQL text and the values bound to its placeholders.
+    pub fn render(&self) -> (String, QueryParams) {
+        let mut params = QueryParams::new(self.dialect.placeholder_style());
+        let sql = self.build_query(&mut params);
+
+        (sql, params)
+    }
+
+    /// Returns the dialect the query is rendered through.
+    pub fn dialect(&self) -> &D {
+        &self.dialect
+    }
+
+    /// Converts the query into one rendered through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query through.
+    pub fn into_dialect<E: Dialect + Clone>(self, dialect: E) -> SelectQueryBuilder<'a, T, E> {
+        let convert = |queries: Option<Vec<SelectQueryBuilder<'a, T, D>>>| {
+            queries.map(|queries| {
+                queries
+                    .into_iter()
+                    .map(|query| query.into_dialect(dialect.clone()))
+                    .collect()
+            })
+        };
+
+        SelectQueryBuilder {
+            dialect: dialect.clone(),
+            table: self.table,
+            columns: self.columns,
+            where_condition: self.where_condition,
+            distinct: self.distinct,
+            group_by: self.group_by,
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            having_condition: self.having_condition,
+            except_clauses: convert(self.except_clauses),
+            union_clauses: convert(self.union_clauses),
+            joins: self.joins,
+        }
+    }
+}
+
+/// Implement `QueryBuilder` for `SelectQueryBuilder`
+///
+/// The where statement ensures the T is long lived
+impl<'a, T, D> QueryBuilder<'a> for SelectQueryBuilder<'a, T, D>
+where
+    T: Table + Default + Clone + 'a, // Added 'a bound here
+    D: Dialect + Clone + 'a,
+{
+    fn to_sql(&self, params: &mut QueryParams) -> String {
+        self.build_query(params)
+    }
+}
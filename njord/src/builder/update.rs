@@ -0,0 +1,319 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use crate::{
+    builder::{
+        select::SelectQueryBuilder,
+        util::{concat_clauses, generate_dml_target},
+        BuildError,
+    },
+    condition::Condition,
+    dialect::Dialect,
+    keys::key_condition,
+    query::{Param, QueryParams},
+    table::Table,
+};
+
+/// A builder for constructing UPDATE queries.
+///
+/// The query is rendered through the dialect `D`; each backend module adds a
+/// `build` method executing it on its own connection type.
+pub struct UpdateQueryBuilder<'a, T: Table + Default, D: Dialect> {
+    dialect: D,
+    table: Option<T>,
+    columns: Vec<String>,
+    sub_queries: HashMap<String, SelectQueryBuilder<'a, T, D>>,
+    where_condition: Option<Condition<'a>>,
+    order_by: Option<Vec<(Vec<String>, String)>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    expected_rows: Option<u64>,
+}
+
+impl<'a, T: Table + Default, D: Dialect + Default> UpdateQueryBuilder<'a, T, D> {
+    /// Creates a new `UpdateQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table to be updated.
+    pub fn new(table: T) -> Self {
+        Self::with_dialect(D::default(), table)
+    }
+}
+
+impl<'a, T: Table + Default, D: Dialect> UpdateQueryBuilder<'a, T, D> {
+    /// Creates a new `UpdateQueryBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `table` - An instance of the table to be updated.
+    pub fn with_dialect(dialect: D, table: T) -> Self {
+        UpdateQueryBuilder {
+            dialect,
+            table: Some(table),
+            columns: Vec::new(),
+            sub_queries: HashMap::new(),
+            where_condition: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+            expected_rows: None,
+        }
+    }
+
+    /// Sets the columns and values to be updated.
+    ///
+    /// The values are taken from the table row. Columns the table does not write,
+    /// such as read-only ones, make the query fail, see `validate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to be updated, as strings or typed column handles.
+    pub fn set<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the columns and values (as subquery) to be updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - A hashmap representing the columns and their subqueries.
+    pub fn set_subqueries(
+        mut self,
+        columns: HashMap<String, SelectQueryBuilder<'a, T, D>>,
+    ) -> Self {
+        self.sub_queries = columns;
+        self
+    }
+
+    /// Sets the WHERE clause condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the WHERE clause.
+    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
+        self.where_condition = Some(condition);
+        self
+    }
+
+    /// Restricts the query to the row with the primary key of the table row being updated.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the restricted query, or a `BuildError` if the table has no
+    /// primary key.
+    pub fn by_key(mut self) -> Result<Self, BuildError> {
+        let table = self.table.as_ref().ok_or(BuildError::MissingTable)?;
+
+        self.where_condition = Some(key_condition(table, table.primary_key_values())?);
+        Ok(self)
+    }
+
+    /// Sets the ORDER BY clause columns and order direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_and_order` - Pairs of columns and their order direction, e.g. a `HashMap` or
+    ///   `[User::ID.desc()]`.
+    pub fn order_by<I>(mut self, col_and_order: I) -> Self
+    where
+        I: IntoIterator<Item = (Vec<String>, String)>,
+    {
+        self.order_by = Some(col_and_order.into_iter().collect());
+        self
+    }
+
+    /// Sets the LIMIT clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The maximum number of rows to be updated.
+    pub fn limit(mut self, count: usize) -> Self {
+        self.limit = Some(count);
+        self
+    }
+
+    /// Sets the OFFSET clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset from which to start updating rows.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Makes the query fail unless exactly `count` rows are updated.
+    ///
+    /// The statement runs in a transaction of its own, or in a savepoint inside of
+    /// a transaction, which is rolled back when the check fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows the query is expected to affect.
+    pub fn expect_rows(mut self, count: u64) -> Self {
+        self.expected_rows = Some(count);
+        self
+    }
+
+    /// Checks that every column passed to `set` is written by UPDATE statements.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that is a `BuildError` naming the first column the table has
+    /// no such column for, or that is read-only, generated or defaulted.
+    pub fn validate(&self) -> Result<(), BuildError> {
+        let Some(table) = &self.table else {
+            return Ok(());
+        };
+        let fields = table.get_column_fields();
+        let columns = table.get_columns();
+
+        match self.columns.iter().find(|column| !fields.contains(column)) {
+            Some(column) if columns.contains_key(column) => {
+                Err(BuildError::ReadOnlyColumn(column.clone()))
+            }
+            Some(column) => Err(BuildError::UnknownColumn(column.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the number of rows the query is expected to affect, if set.
+    pub fn expected_rows(&self) -> Option<u64> {
+        self.expected_rows
+    }
+
+    /// Builds the query string, this function should be used internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values bound by the query.
+    pub fn build_query(&self, params: &mut QueryParams) -> String {
+        let table_name = self
+            .table
+            .as_ref()
+            .map(|t| self.dialect.identifier(t.get_name()))
+            .unwrap_or_default();
+
+        // Generate SET clause
+        let set = if let Some(table) = &self.table {
+            let mut set_fields = Vec::new();
+            let fields = table.get_column_fields();
+            let values = table.get_column_values();
+
+            for column in &self.columns {
+                // Check if column exists in the table's fields
+                // Columns that are not written are rejected by `validate`
+                if let Some(index) = fields.iter().position(|c| column == c) {
+                    let formatted_value = match values.get(index) {
+                        Some(Param::Null) | None => "NULL".to_string(),
+                        Some(value) => params.push(value.clone()),
+                    };
+                    set_fields.push(format!("{} = {}", column, formatted_value));
+                }
+            }
+
+            // Generate subqueries
+            for (column_name, sub_query) in &self.sub_queries {
+                let formatted_value = format!("({})", sub_query.build_query(params));
+                set_fields.push(format!("{} = {}", column_name, formatted_value));
+            }
+
+            set_fields.join(", ")
+        } else {
+            String::new()
+        };
+
+        let target = generate_dml_target(
+            &self.dialect,
+            &table_name,
+            self.where_condition.clone(),
+            &self.order_by,
+            self.limit,
+            self.offset,
+            params,
+        );
+
+        concat_clauses(&[
+            target.with_clause,
+            format!("UPDATE {} SET {}", target.table, set),
+            target.tail,
+        ])
+    }
+
+    /// Renders the query together with its bound values.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the SQL text and the values bound to its placeholders.
+    pub fn render(&self) -> (String, QueryParams) {
+        let mut params = QueryParams::new(self.dialect.placeholder_style());
+        let sql = self.build_query(&mut params);
+
+        (sql, params)
+    }
+
+    /// Returns the dialect the query is rendered through.
+    pub fn dialect(&self) -> &D {
+        &self.dialect
+    }
+
+    /// Converts the query into one rendered through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query through.
+    pub fn into_dialect<E: Dialect + Clone>(self, dialect: E) -> UpdateQueryBuilder<'a, T, E> {
+        let sub_queries = self
+            .sub_queries
+            .into_iter()
+            .map(|(column, query)| (column, query.into_dialect(dialect.clone())))
+            .collect();
+
+        UpdateQueryBuilder {
+            dialect,
+            table: self.table,
+            columns: self.columns,
+            sub_queries,
+            where_condition: self.where_condition,
+            order_by: self.order_by,
+            limit: self.limit,
+            offset: self.offset,
+            expected_rows: self.expected_rows,
+        }
+    }
+}
@@ -0,0 +1,389 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder::{
+        insert::{bind_row_values, check_inserted_columns, inserted_columns},
+        BuildError,
+    },
+    dialect::{Dialect, UpsertStyle},
+    query::QueryParams,
+    table::Table,
+};
+
+/// A builder for constructing upserts, INSERT queries updating the rows they conflict with.
+///
+/// A row conflicts with an existing one if they share the values of the conflict
+/// target set with `on_conflict`. By default the conflicting rows get all inserted
+/// columns outside the conflict target updated; `update` and `do_nothing` change that.
+/// The query is rendered through the dialect `D` according to its [`UpsertStyle`].
+pub struct UpsertQueryBuilder<T: Table, D: Dialect> {
+    dialect: D,
+    table_name: String,
+    columns: Vec<String>,
+    rows: Vec<T>,
+    conflict_columns: Vec<String>,
+    update_columns: Option<Vec<String>>,
+}
+
+impl<T: Table, D: Dialect + Default> Default for UpsertQueryBuilder<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Table, D: Dialect + Default> UpsertQueryBuilder<T, D> {
+    /// Creates a new `UpsertQueryBuilder` instance.
+    pub fn new() -> Self {
+        Self::with_dialect(D::default())
+    }
+}
+
+impl<T: Table, D: Dialect> UpsertQueryBuilder<T, D> {
+    /// Creates a new `UpsertQueryBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    pub fn with_dialect(dialect: D) -> Self {
+        UpsertQueryBuilder {
+            dialect,
+            table_name: String::new(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            conflict_columns: Vec::new(),
+            update_columns: None,
+        }
+    }
+
+    /// Sets the rows to be inserted or updated.
+    ///
+    /// The columns are taken from the first row. Fields holding an
+    /// `AutoIncrementPrimaryKey` without a value are left to the database,
+    /// so rows with and without a key cannot share a statement, see `validate`.
+    /// Unless `on_conflict` is called, the conflict target is the primary key
+    /// of the rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The table rows to upsert.
+    pub fn values(mut self, rows: Vec<T>) -> Self {
+        if let Some(first_row) = rows.first() {
+            self.table_name = first_row.get_name().to_string();
+            self.columns = inserted_columns(first_row);
+
+            if self.conflict_columns.is_empty() {
+                self.conflict_columns = first_row.primary_key();
+            }
+        }
+
+        self.rows = rows;
+        self
+    }
+
+    /// Sets the conflict target, the columns of a primary key or unique constraint.
+    ///
+    /// MySQL and MariaDB match any unique key of the table instead, so the target
+    /// only decides which columns are updated by default there.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns identifying a conflicting row.
+    pub fn on_conflict<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.conflict_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the columns updated with the new values when a row conflicts.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to update.
+    pub fn update<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.update_columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Keeps conflicting rows unchanged, inserting only the rows without a conflict.
+    pub fn do_nothing(mut self) -> Self {
+        self.update_columns = Some(Vec::new());
+        self
+    }
+
+    /// Returns the rows to be inserted or updated.
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    /// Checks that the query can be executed as a single statement.
+    ///
+    /// Every row must set the same columns as the first one; a row holding an
+    /// `AutoIncrementPrimaryKey` sets one more than a row leaving it to the database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` that is a `BuildError` naming the first row setting other columns.
+    pub fn validate(&self) -> Result<(), BuildError> {
+        check_inserted_columns(&self.columns, &self.rows)
+    }
+
+    /// Returns the columns updated when a row conflicts.
+    ///
+    /// Unless set with `update` or `do_nothing`, these are all inserted columns
+    /// outside the conflict target.
+    pub fn update_columns(&self) -> Vec<&str> {
+        match &self.update_columns {
+            Some(columns) => columns.iter().map(String::as_str).collect(),
+            None => self
+                .columns
+                .iter()
+                .filter(|column| !self.conflict_columns.contains(column))
+                .map(String::as_str)
+                .collect(),
+        }
+    }
+
+    /// Builds the query string, this function should be used internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values bound by the query.
+    pub fn build_query(&self, params: &mut QueryParams) -> String {
+        match self.dialect.upsert_style() {
+            UpsertStyle::OnConflict => self.build_on_conflict(params),
+            UpsertStyle::OnDuplicateKey => self.build_on_duplicate_key(params),
+            UpsertStyle::Merge | UpsertStyle::MergeFromDual => self.build_merge(params),
+        }
+    }
+
+    /// Renders the query together with its bound values.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of the SQL text and the values bound to its placeholders.
+    pub fn render(&self) -> (String, QueryParams) {
+        let mut params = QueryParams::new(self.dialect.placeholder_style());
+        let sql = self.build_query(&mut params);
+
+        (sql, params)
+    }
+
+    /// Returns the dialect the query is rendered through.
+    pub fn dialect(&self) -> &D {
+        &self.dialect
+    }
+
+    /// Converts the query into one rendered through another dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query through.
+    pub fn into_dialect<E: Dialect>(self, dialect: E) -> UpsertQueryBuilder<T, E> {
+        UpsertQueryBuilder {
+            dialect,
+            table_name: self.table_name,
+            columns: self.columns,
+            rows: self.rows,
+            conflict_columns: self.conflict_columns,
+            update_columns: self.update_columns,
+        }
+    }
+
+    /// Renders `INSERT INTO table (columns) VALUES (...), (...)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values of the rows.
+    fn build_insert(&self, params: &mut QueryParams) -> String {
+        let values_str: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                format!(
+                    "({})",
+                    bind_row_values(&self.columns, row, params).join(", ")
+                )
+            })
+            .collect();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.dialect.identifier(&self.table_name),
+            self.columns.join(", "),
+            values_str.join(", ")
+        )
+    }
+
+    /// Renders an INSERT with an `ON CONFLICT` clause.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values of the rows.
+    fn build_on_conflict(&self, params: &mut QueryParams) -> String {
+        let target = if self.conflict_columns.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", self.conflict_columns.join(", "))
+        };
+
+        let update_columns = self.update_columns();
+        let action = if update_columns.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            let assignments: Vec<String> = update_columns
+                .iter()
+                .map(|column| format!("{} = excluded.{}", column, column))
+                .collect();
+            format!("DO UPDATE SET {}", assignments.join(", "))
+        };
+
+        format!(
+            "{} ON CONFLICT{} {}",
+            self.build_insert(params),
+            target,
+            action
+        )
+    }
+
+    /// Renders an INSERT with an `ON DUPLICATE KEY UPDATE` clause.
+    ///
+    /// Keeping the conflicting rows unchanged is rendered as assigning a column to itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values of the rows.
+    fn build_on_duplicate_key(&self, params: &mut QueryParams) -> String {
+        let update_columns = self.update_columns();
+        let assignments: Vec<String> = if update_columns.is_empty() {
+            self.conflict_columns
+                .iter()
+                .chain(&self.columns)
+                .take(1)
+                .map(|column| format!("{} = {}", column, column))
+                .collect()
+        } else {
+            update_columns
+                .iter()
+                .map(|column| format!("{} = VALUES({})", column, column))
+                .collect()
+        };
+
+        format!(
+            "{} ON DUPLICATE KEY UPDATE {}",
+            self.build_insert(params),
+            assignments.join(", ")
+        )
+    }
+
+    /// Renders a `MERGE` statement whose source are the rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list receiving the values of the rows.
+    fn build_merge(&self, params: &mut QueryParams) -> String {
+        let is_from_dual = self.dialect.upsert_style() == UpsertStyle::MergeFromDual;
+        let columns_str = self.columns.join(", ");
+
+        let source = if is_from_dual {
+            let selects: Vec<String> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    let values: Vec<String> = bind_row_values(&self.columns, row, params)
+                        .into_iter()
+                        .zip(&self.columns)
+                        .map(|(value, column)| format!("{} AS {}", value, column))
+                        .collect();
+                    format!("SELECT {} FROM DUAL", values.join(", "))
+                })
+                .collect();
+            format!("({}) source", selects.join(" UNION ALL "))
+        } else {
+            let values_str: Vec<String> = self
+                .rows
+                .iter()
+                .map(|row| {
+                    format!(
+                        "({})",
+                        bind_row_values(&self.columns, row, params).join(", ")
+                    )
+                })
+                .collect();
+            format!(
+                "(VALUES {}) AS source ({})",
+                values_str.join(", "),
+                columns_str
+            )
+        };
+
+        let condition: Vec<String> = self
+            .conflict_columns
+            .iter()
+            .map(|column| format!("target.{} = source.{}", column, column))
+            .collect();
+
+        let update_columns = self.update_columns();
+        let matched = if update_columns.is_empty() {
+            String::new()
+        } else {
+            let assignments: Vec<String> = update_columns
+                .iter()
+                .map(|column| format!("target.{} = source.{}", column, column))
+                .collect();
+            format!(" WHEN MATCHED THEN UPDATE SET {}", assignments.join(", "))
+        };
+
+        let source_columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| format!("source.{}", column))
+            .collect();
+
+        format!(
+            "MERGE INTO {}{} target USING {} ON ({}){} WHEN NOT MATCHED THEN INSERT ({}) VALUES ({}){}",
+            self.dialect.identifier(&self.table_name),
+            if is_from_dual { "" } else { " AS" },
+            source,
+            condition.join(" AND "),
+            matched,
+            columns_str,
+            source_columns.join(", "),
+            // MSSQL requires MERGE to be terminated, Oracle rejects the terminator
+            if is_from_dual { "" } else { ";" }
+        )
+    }
+}
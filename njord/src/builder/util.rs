@@ -0,0 +1,354 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    condition::Condition,
+    dialect::{Dialect, DmlLimitStyle},
+    query::QueryParams,
+};
+
+/// Generates an SQL WHERE clause string based on the provided condition.
+///
+/// If `condition` is Some, it constructs an SQL WHERE clause string with the specified condition.
+/// If `condition` is None, an empty string is returned.
+///
+/// # Arguments
+///
+/// * `condition` - An Option containing the condition.
+/// * `params` - The parameter list receiving the values bound by the condition.
+///
+/// # Returns
+///
+/// A String representing the generated SQL WHERE clause.
+pub fn generate_where_condition_str(
+    condition: Option<Condition>,
+    params: &mut QueryParams,
+) -> String {
+    if let Some(condition) = condition {
+        format!("WHERE {}", condition.build(params))
+    } else {
+        String::new()
+    }
+}
+
+/// Generates an SQL GROUP BY clause string based on the provided columns.
+///
+/// If `columns` is Some, it constructs an SQL GROUP BY clause string with the specified columns.
+/// If `columns` is None, an empty string is returned.
+///
+/// # Arguments
+///
+/// * `columns` - An Option containing a reference to a vector of column names.
+///
+/// # Returns
+///
+/// A String representing the generated SQL GROUP BY clause.
+pub fn generate_group_by_str(columns: &Option<Vec<String>>) -> String {
+    match columns {
+        Some(columns) => format!("GROUP BY {}", columns.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Generates an SQL ORDER BY clause string based on the provided `order_by` option.
+///
+/// If `order_by` is Some, it should contain pairs of column names and their sort order (ASC or
+/// DESC). This function constructs an SQL ORDER BY clause string from the pairs, in the order
+/// they were given. If there are no pairs, an empty string is returned.
+///
+/// # Arguments
+///
+/// * `order_by` - An Option containing pairs of column names and their sort order (ASC or DESC).
+///
+/// # Returns
+///
+/// A String representing the generated SQL ORDER BY clause.
+pub fn generate_order_by_str(order_by: &Option<Vec<(Vec<String>, String)>>) -> String {
+    let order_by_str = if let Some(order_by) = order_by.as_ref() {
+        let order_by_str: Vec<String> = order_by
+            .iter()
+            .map(|(columns, order)| format!("{} {}", columns.join(", "), order))
+            .collect();
+        if !order_by_str.is_empty() {
+            format!("ORDER BY {}", order_by_str.join(", "))
+        } else {
+            String::new()
+        }
+    } else {
+        String::new()
+    };
+
+    order_by_str
+}
+
+/// Generates an SQL HAVING clause string based on the provided group by flag and condition.
+///
+/// If `group_by` is true and `having_condition` is Some, it constructs an SQL HAVING clause string
+/// with the specified condition.
+/// If either `group_by` is false or `having_condition` is None, an empty string is returned.
+///
+/// # Arguments
+///
+/// * `group_by` - An Option indicating whether GROUP BY is present.
+/// * `having_condition` - An Option containing the condition for the HAVING clause.
+/// * `params` - The parameter list receiving the values bound by the condition.
+///
+/// # Returns
+///
+/// A String representing the generated SQL HAVING clause.
+pub fn generate_having_str(
+    group_by: bool,
+    having_condition: Option<&Condition>,
+    params: &mut QueryParams,
+) -> String {
+    match having_condition {
+        Some(condition) if group_by => format!("HAVING {}", condition.build(params)),
+        _ => String::new(),
+    }
+}
+
+/// The rows an UPDATE or DELETE statement applies to.
+pub struct DmlTarget {
+    /// A `WITH` clause to put in front of the statement, or an empty string.
+    pub with_clause: String,
+    /// The table (or common table expression) the statement modifies.
+    pub table: String,
+    /// The clauses following the statement's target, starting with WHERE.
+    pub tail: String,
+}
+
+/// Generates the target of an UPDATE or DELETE statement.
+///
+/// Without LIMIT and OFFSET the target is simply the table filtered by `condition`.
+/// Otherwise the rows are restricted the way the dialect supports it, see [`DmlLimitStyle`].
+///
+/// # Arguments
+///
+/// * `dialect` - The dialect the statement is rendered through.
+/// * `table_name` - The name of the modified table, as rendered in the statement.
+/// * `condition` - An Option containing the condition of the WHERE clause.
+/// * `order_by` - An Option containing pairs of column names and their sort order.
+/// * `limit` - The maximum number of rows to modify.
+/// * `offset` - The number of matching rows to skip.
+/// * `params` - The parameter list receiving the values bound by the condition.
+///
+/// # Returns
+///
+/// A `DmlTarget` describing the rows the statement applies to.
+pub fn generate_dml_target<D: Dialect + ?Sized>(
+    dialect: &D,
+    table_name: &str,
+    condition: Option<Condition>,
+    order_by: &Option<Vec<(Vec<String>, String)>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    params: &mut QueryParams,
+) -> DmlTarget {
+    let where_condition_str = generate_where_condition_str(condition, params);
+    let order_by_str = generate_order_by_str(order_by);
+    let style = dialect.dml_limit_style();
+
+    if limit.is_none() && offset.is_none() {
+        let tail = match style {
+            DmlLimitStyle::Inline => concat_clauses(&[where_condition_str, order_by_str]),
+            _ => where_condition_str,
+        };
+
+        return DmlTarget {
+            with_clause: String::new(),
+            table: table_name.to_string(),
+            tail,
+        };
+    }
+
+    match style {
+        DmlLimitStyle::Inline => {
+            // OFFSET is rendered as given, so that a database without support
+            // for it rejects the statement instead of modifying the wrong rows.
+            let limit_str = limit.map_or(String::new(), |count| format!("LIMIT {}", count));
+            let offset_str = offset
+                .filter(|offset| *offset > 0)
+                .map_or(String::new(), |offset| format!("OFFSET {}", offset));
+
+            DmlTarget {
+                with_clause: String::new(),
+                table: table_name.to_string(),
+                tail: concat_clauses(&[where_condition_str, order_by_str, limit_str, offset_str]),
+            }
+        }
+        DmlLimitStyle::RowId(row_id) => {
+            let pagination_str = dialect.pagination(limit, offset, !order_by_str.is_empty());
+            let rows = concat_clauses(&[
+                format!("SELECT {} FROM {}", row_id, table_name),
+                where_condition_str,
+                order_by_str,
+                pagination_str,
+            ]);
+
+            DmlTarget {
+                with_clause: String::new(),
+                table: table_name.to_string(),
+                tail: format!("WHERE {} IN ({})", row_id, rows),
+            }
+        }
+        DmlLimitStyle::CommonTableExpression => {
+            let pagination_str = dialect.pagination(limit, offset, !order_by_str.is_empty());
+            let rows = concat_clauses(&[
+                format!("SELECT * FROM {}", table_name),
+                where_condition_str,
+                order_by_str,
+                pagination_str,
+            ]);
+
+            DmlTarget {
+                with_clause: format!("WITH njord_rows AS ({})", rows),
+                table: "njord_rows".to_string(),
+                tail: String::new(),
+            }
+        }
+    }
+}
+
+/// Joins the clauses of a statement, skipping the ones that are empty.
+///
+/// # Arguments
+///
+/// * `clauses` - The clauses in the order they appear in the statement.
+///
+/// # Returns
+///
+/// A String with the non-empty clauses separated by single spaces.
+pub fn concat_clauses(clauses: &[String]) -> String {
+    clauses
+        .iter()
+        .filter(|clause| !clause.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Removes double quotes and backslashes from a given string.
+///
+/// # Arguments
+///
+/// * `input` - The input string from which double quotes and backslashes will be removed.
+///
+/// # Returns
+///
+/// A String with double quotes and backslashes removed.
+pub fn remove_quotes_and_backslashes(input: &str) -> String {
+    input.replace("\"", "").replace("\\", "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::condition::{Condition, Value};
+    use crate::query::{Param, PlaceholderStyle};
+
+    #[test]
+    fn test_generate_where_condition_str() {
+        // Test when condition is Some
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let condition = Condition::Eq("age".to_string(), Value::Int(30));
+        let result = generate_where_condition_str(Some(condition), &mut params);
+        assert_eq!(result, "WHERE age = ?");
+        assert_eq!(params.values(), &[Param::Int(30)]);
+
+        // Test when condition is None
+        let result = generate_where_condition_str(None, &mut params);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_generate_group_by_str() {
+        // Test when columns is Some
+        let columns = Some(vec!["name".to_string(), "age".to_string()]);
+        let result = generate_group_by_str(&columns);
+        assert_eq!(result, "GROUP BY name, age");
+
+        // Test when columns is None
+        let result = generate_group_by_str(&None);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_generate_order_by_str() {
+        // Test when order_by is Some
+        let order_by = vec![
+            (vec!["name".to_string()], "ASC".to_string()),
+            (vec!["age".to_string()], "DESC".to_string()),
+        ];
+        let result = generate_order_by_str(&Some(order_by));
+        assert_eq!(result, "ORDER BY name ASC, age DESC");
+
+        // Test when order_by is None
+        let result = generate_order_by_str(&None);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_generate_having_str() {
+        // Test when group_by is true and having_condition is Some
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let condition = Condition::Gt("COUNT(age)".to_string(), Value::Int(5));
+        let result = generate_having_str(true, Some(&condition), &mut params);
+        assert_eq!(result, "HAVING COUNT(age) > ?");
+        assert_eq!(params.values(), &[Param::Int(5)]);
+
+        // Test when group_by is false
+        let result = generate_having_str(false, Some(&condition), &mut params);
+        assert_eq!(result, "");
+
+        // Test when having_condition is None
+        let result = generate_having_str(true, None, &mut params);
+        assert_eq!(result, "");
+
+        // Test when both group_by is false and having_condition is None
+        let result = generate_having_str(false, None, &mut params);
+        assert_eq!(result, "");
+    }
+
+    #[test]
+    fn test_concat_clauses() {
+        let clauses = vec![
+            "SELECT * FROM users".to_string(),
+            String::new(),
+            "WHERE id = ?".to_string(),
+        ];
+        assert_eq!(concat_clauses(&clauses), "SELECT * FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn test_remove_quotes_and_backslashes() {
+        let input = r#""table_name\"""#;
+        let result = remove_quotes_and_backslashes(input);
+        assert_eq!(result, "table_name");
+    }
+}
@@ -1,4 +1,9 @@
-use crate::query::QueryBuilder;
+use std::marker::PhantomData;
+
+use crate::{
+    condition::{Condition, Value},
+    query::{PlaceholderStyle, QueryBuilder, QueryParams, ToParam},
+};
 
 /// Define the enum to represent a column as either a String or SelectQueryBuilder
 #[derive(Clone)]
@@ -12,27 +17,36 @@ pub enum Column<'a> {
 /// Implement the build method to convert the enum to a string
 impl<'a> Column<'a> {
     /// Helper function to convert the columns to a string
-    pub fn build(&self) -> String {
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list of the statement, receiving any values bound by a subquery.
+    pub fn build(&self, params: &mut QueryParams) -> String {
         match self {
             Column::Text(text) => text.clone(),
             Column::SubQuery(sub_query, alias) => {
-                "(".to_string() + &sub_query.to_sql() + ") AS " + alias
+                "(".to_string() + &sub_query.to_sql(params) + ") AS " + alias
             }
         }
     }
+
+    /// Renders the column with `?` placeholders, discarding any bound values.
+    fn to_text(&self) -> String {
+        self.build(&mut QueryParams::new(PlaceholderStyle::QuestionMark))
+    }
 }
 
 /// Implementation of fmt::Display for Column
 impl<'a> std::fmt::Display for Column<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.build())
+        write!(f, "{}", self.to_text())
     }
 }
 
 /// Implementation of PartialEq for Column
 impl<'a> PartialEq for Column<'a> {
     fn eq(&self, other: &Self) -> bool {
-        self.build() == other.build()
+        self.to_text() == other.to_text()
     }
 }
 
@@ -41,7 +55,9 @@ impl<'a> PartialEq<String> for Column<'a> {
     fn eq(&self, other: &String) -> bool {
         match self {
             Column::Text(text) => text == other,
-            Column::SubQuery(sub_query, _) => sub_query.to_sql() == *other,
+            Column::SubQuery(sub_query, _) => {
+                sub_query.to_sql(&mut QueryParams::new(PlaceholderStyle::QuestionMark)) == *other
+            }
         }
     }
 }
@@ -51,7 +67,242 @@ impl<'a> PartialEq<&str> for Column<'a> {
     fn eq(&self, other: &&str) -> bool {
         match self {
             Column::Text(text) => text == other,
-            Column::SubQuery(sub_query, _) => sub_query.to_sql() == *other,
+            Column::SubQuery(sub_query, _) => {
+                sub_query.to_sql(&mut QueryParams::new(PlaceholderStyle::QuestionMark)) == *other
+            }
         }
     }
 }
+
+/// A compile-time checked handle to a table column.
+///
+/// `#[derive(Table)]` generates one constant per field, named after the field in
+/// upper case, e.g. `User::USERNAME`. `T` is the table and `V` the field type, with
+/// `Option` and primary key wrappers removed; values passed to the condition
+/// builders must implement [`ColumnValue<V>`] and are bound with `ToParam`, so
+/// user types stored in a column can be compared with it as well.
+pub struct TypedColumn<T, V> {
+    name: &'static str,
+    marker: PhantomData<fn() -> (T, V)>,
+}
+
+impl<T, V> TypedColumn<T, V> {
+    /// Creates a handle for the column with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column in the database.
+    pub const fn new(name: &'static str) -> Self {
+        TypedColumn {
+            name,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the name of the column.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Builds a `column = value` condition.
+    pub fn eq<'a>(&self, value: impl ToParam + ColumnValue<V>) -> Condition<'a> {
+        Condition::Eq(self.name.to_string(), value.to_param().into())
+    }
+
+    /// Builds a `column <> value` condition.
+    pub fn ne<'a>(&self, value: impl ToParam + ColumnValue<V>) -> Condition<'a> {
+        Condition::Ne(self.name.to_string(), value.to_param().into())
+    }
+
+    /// Builds a `column < value` condition.
+    pub fn lt<'a>(&self, value: impl ToParam + ColumnValue<V>) -> Condition<'a> {
+        Condition::Lt(self.name.to_string(), value.to_param().into())
+    }
+
+    /// Builds a `column > value` condition.
+    pub fn gt<'a>(&self, value: impl ToParam + ColumnValue<V>) -> Condition<'a> {
+        Condition::Gt(self.name.to_string(), value.to_param().into())
+    }
+
+    /// Builds a `column <= value` condition.
+    pub fn le<'a>(&self, value: impl ToParam + ColumnValue<V>) -> Condition<'a> {
+        Condition::Le(self.name.to_string(), value.to_param().into())
+    }
+
+    /// Builds a `column >= value` condition.
+    pub fn ge<'a>(&self, value: impl ToParam + ColumnValue<V>) -> Condition<'a> {
+        Condition::Ge(self.name.to_string(), value.to_param().into())
+    }
+
+    /// Builds a `column IN (values)` condition.
+    pub fn in_<'a, I>(&self, values: I) -> Condition<'a>
+    where
+        I: IntoIterator,
+        I::Item: ToParam + ColumnValue<V>,
+    {
+        let values = values
+            .into_iter()
+            .map(|value| value.to_param().into())
+            .collect();
+        Condition::In(self.name.to_string(), values)
+    }
+
+    /// Builds a `column NOT IN (values)` condition.
+    pub fn not_in<'a, I>(&self, values: I) -> Condition<'a>
+    where
+        I: IntoIterator,
+        I::Item: ToParam + ColumnValue<V>,
+    {
+        let values = values
+            .into_iter()
+            .map(|value| value.to_param().into())
+            .collect();
+        Condition::NotIn(self.name.to_string(), values)
+    }
+
+    /// Builds a `column BETWEEN low AND high` condition.
+    pub fn between<'a, X>(&self, low: X, high: X) -> Condition<'a>
+    where
+        X: ToParam + ColumnValue<V>,
+    {
+        Condition::Between(
+            self.name.to_string(),
+            low.to_param().into(),
+            high.to_param().into(),
+        )
+    }
+
+    /// Builds a `column IS NULL` condition.
+    pub fn is_null<'a>(&self) -> Condition<'a> {
+        Condition::IsNull(self.name.to_string())
+    }
+
+    /// Builds a `column IS NOT NULL` condition.
+    pub fn is_not_null<'a>(&self) -> Condition<'a> {
+        Condition::IsNotNull(self.name.to_string())
+    }
+
+    /// Builds a `column LIKE pattern` condition. Only available for text columns.
+    pub fn like<'a>(&self, pattern: impl Into<String>) -> Condition<'a>
+    where
+        V: AsRef<str>,
+    {
+        Condition::Like(self.name.to_string(), Value::Text(pattern.into()), None)
+    }
+
+    /// Returns an ascending ORDER BY entry for this column.
+    pub fn asc(&self) -> (Vec<String>, String) {
+        (vec![self.name.to_string()], "ASC".to_string())
+    }
+
+    /// Returns a descending ORDER BY entry for this column.
+    pub fn desc(&self) -> (Vec<String>, String) {
+        (vec![self.name.to_string()], "DESC".to_string())
+    }
+}
+
+/// Marks the Rust types that may be compared with a column holding `V` values.
+///
+/// Every type is comparable with itself; on top of that text columns accept `&str`,
+/// integer columns accept any integer type and float columns accept floats and
+/// integers, so plain literals such as `User::ID.eq(5)` work.
+pub trait ColumnValue<V> {}
+
+impl<V> ColumnValue<V> for V {}
+
+impl ColumnValue<String> for &str {}
+impl ColumnValue<Vec<u8>> for &[u8] {}
+
+macro_rules! impl_column_value {
+    ($target:ty: $($source:ty),+) => {
+        $(impl ColumnValue<$target> for $source {})+
+    };
+}
+
+impl_column_value!(i8: i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_column_value!(i16: i8, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_column_value!(i32: i8, i16, i64, isize, u8, u16, u32, u64, usize);
+impl_column_value!(i64: i8, i16, i32, isize, u8, u16, u32, u64, usize);
+impl_column_value!(isize: i8, i16, i32, i64, u8, u16, u32, u64, usize);
+impl_column_value!(u8: i8, i16, i32, i64, isize, u16, u32, u64, usize);
+impl_column_value!(u16: i8, i16, i32, i64, isize, u8, u32, u64, usize);
+impl_column_value!(u32: i8, i16, i32, i64, isize, u8, u16, u64, usize);
+impl_column_value!(u64: i8, i16, i32, i64, isize, u8, u16, u32, usize);
+impl_column_value!(usize: i8, i16, i32, i64, isize, u8, u16, u32, u64);
+impl_column_value!(f32: f64, i8, i16, i32, i64, u8, u16, u32);
+impl_column_value!(f64: f32, i8, i16, i32, i64, u8, u16, u32);
+
+impl<T, V> Clone for TypedColumn<T, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, V> Copy for TypedColumn<T, V> {}
+
+impl<T, V> std::fmt::Debug for TypedColumn<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedColumn").field(&self.name).finish()
+    }
+}
+
+impl<T, V> std::fmt::Display for TypedColumn<T, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<T, V> From<TypedColumn<T, V>> for String {
+    fn from(column: TypedColumn<T, V>) -> Self {
+        column.name.to_string()
+    }
+}
+
+impl<'a, T, V> From<TypedColumn<T, V>> for Column<'a> {
+    fn from(column: TypedColumn<T, V>) -> Self {
+        Column::Text(column.name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::Param;
+
+    struct User;
+
+    const ID: TypedColumn<User, usize> = TypedColumn::new("id");
+    const USERNAME: TypedColumn<User, String> = TypedColumn::new("username");
+    const AGE: TypedColumn<User, i32> = TypedColumn::new("age");
+
+    #[test]
+    fn test_typed_column_conditions() {
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let condition = Condition::And(
+            Box::new(USERNAME.eq("mjovanc")),
+            Box::new(AGE.in_([30, 31])),
+        );
+
+        assert_eq!(
+            condition.build(&mut params),
+            "(username = ?) AND (age IN (?, ?))"
+        );
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("mjovanc".to_string()),
+                Param::Int(30),
+                Param::Int(31)
+            ]
+        );
+        assert_eq!(AGE.is_null().build(&mut params), "age IS NULL");
+        assert_eq!(USERNAME.like("mj%").build(&mut params), "username LIKE ?");
+    }
+
+    #[test]
+    fn test_typed_column_conversions() {
+        assert_eq!(ID.desc(), (vec!["id".to_string()], "DESC".to_string()));
+        assert_eq!(String::from(USERNAME), "username");
+        assert!(Column::from(AGE) == "age");
+    }
+}
@@ -27,14 +27,17 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::query::QueryBuilder;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::query::{Param, PlaceholderStyle, QueryBuilder, QueryParams};
 
 /// Represents a condition used in building SQL queries.
 #[derive(Clone)]
 pub enum Condition<'a> {
-    /// Equality condition: column = value.
+    /// Equality condition: column = value, or column IS NULL for a `NULL` value.
     Eq(String, Value<'a>),
-    /// Inequality condition: column <> value.
+    /// Inequality condition: column <> value, or column IS NOT NULL for a `NULL` value.
     Ne(String, Value<'a>),
     /// Less than condition: column < value.
     Lt(String, Value<'a>),
@@ -52,132 +55,572 @@ pub enum Condition<'a> {
     In(String, Vec<Value<'a>>),
     /// Not in condition: column NOT IN (value1, value2, ...).
     NotIn(String, Vec<Value<'a>>),
+    /// Null check: column IS NULL.
+    IsNull(String),
+    /// Not null check: column IS NOT NULL.
+    IsNotNull(String),
+    /// Pattern match: column LIKE pattern [ESCAPE char].
+    Like(String, Value<'a>, Option<char>),
+    /// Negated pattern match: column NOT LIKE pattern [ESCAPE char].
+    NotLike(String, Value<'a>, Option<char>),
+    /// Case-insensitive pattern match: LOWER(column) LIKE LOWER(pattern) [ESCAPE char].
+    ILike(String, Value<'a>, Option<char>),
+    /// Range condition: column BETWEEN low AND high.
+    Between(String, Value<'a>, Value<'a>),
+    /// Logical NOT condition.
+    Not(Box<Condition<'a>>),
+    /// Exists condition: EXISTS (subquery).
+    Exists(Box<dyn QueryBuilder<'a> + 'a>),
+    /// Not exists condition: NOT EXISTS (subquery).
+    NotExists(Box<dyn QueryBuilder<'a> + 'a>),
 }
 
-/// Required to implement support for subqueries and literals.
+/// A value on the right-hand side of a condition.
+///
+/// Scalar values are bound as parameters with their SQL type, so a `Text`
+/// value such as `"007"` stays text on every backend.
 #[derive(Clone)]
 pub enum Value<'a> {
-    /// A literal value, such as a string or number.
-    Literal(String),
-    /// A list of literals. ["foo", "bar", "baz"]
-    LiteralList(Vec<String>),
+    /// SQL `NULL`.
+    Null,
+    /// A boolean value.
+    Bool(bool),
+    /// A signed integer value.
+    Int(i64),
+    /// A floating point value.
+    Float(f64),
+    /// A text value.
+    Text(String),
+    /// A binary value.
+    Bytes(Vec<u8>),
+    /// A calendar date without time zone.
+    #[cfg(feature = "chrono")]
+    Date(NaiveDate),
+    /// A time of day without time zone.
+    #[cfg(feature = "chrono")]
+    Time(NaiveTime),
+    /// A date and time without time zone.
+    #[cfg(feature = "chrono")]
+    Timestamp(NaiveDateTime),
+    /// A date and time in UTC.
+    #[cfg(feature = "chrono")]
+    TimestampTz(DateTime<Utc>),
+    /// A reference to another column, e.g. `products.user_id` in a join condition.
+    Column(String),
+    /// A list of values. [1, 2, 3]
+    List(Vec<Value<'a>>),
     /// A subquery.
     Subquery(Box<dyn QueryBuilder<'a> + 'a>),
 }
 
 impl<'a> Value<'a> {
-    pub fn is_numeric(&self) -> bool {
+    /// Renders the value as SQL, binding scalar values to `params`.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list of the statement being built.
+    ///
+    /// # Returns
+    ///
+    /// A `String` with the placeholder(s), column or subquery standing in for the value.
+    pub fn build(&self, params: &mut QueryParams) -> String {
         match self {
-            Value::Literal(literal) => Condition::is_numeric(literal),
-            _ => false,
+            Value::Null => params.push(Param::Null),
+            Value::Bool(value) => params.push(Param::Bool(*value)),
+            Value::Int(value) => params.push(Param::Int(*value)),
+            Value::Float(value) => params.push(Param::Float(*value)),
+            Value::Text(value) => params.push(Param::Text(value.clone())),
+            Value::Bytes(value) => params.push(Param::Bytes(value.clone())),
+            #[cfg(feature = "chrono")]
+            Value::Date(value) => params.push(Param::Date(*value)),
+            #[cfg(feature = "chrono")]
+            Value::Time(value) => params.push(Param::Time(*value)),
+            #[cfg(feature = "chrono")]
+            Value::Timestamp(value) => params.push(Param::Timestamp(*value)),
+            #[cfg(feature = "chrono")]
+            Value::TimestampTz(value) => params.push(Param::TimestampTz(*value)),
+            Value::Column(column) => column.clone(),
+            Value::List(values) => format!("({})", Value::build_all(values, params)),
+            Value::Subquery(subquery) => format!("({})", subquery.to_sql(params)),
         }
     }
+
+    /// Renders a list of values separated by commas.
+    fn build_all(values: &[Value], params: &mut QueryParams) -> String {
+        values
+            .iter()
+            .map(|value| value.build(params))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
 }
 
 /// Implement Display for Value
 impl<'a> std::fmt::Display for Value<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Value::Literal(literal) => write!(f, "{}", literal),
-            Value::Subquery(subquery) => write!(f, "{}", subquery.to_sql()),
-            Value::LiteralList(literals) => {
-                let mut result = String::new();
-                for (i, literal) in literals.iter().enumerate() {
-                    if i > 0 {
-                        result.push_str(", ");
-                    }
-                    result.push_str(literal);
+            Value::Null => write!(f, "NULL"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Text(value) => write!(f, "'{}'", value.replace('\'', "''")),
+            Value::Bytes(value) => {
+                write!(f, "X'")?;
+                for byte in value {
+                    write!(f, "{:02X}", byte)?;
+                }
+                write!(f, "'")
+            }
+            #[cfg(feature = "chrono")]
+            Value::Date(value) => write!(f, "'{}'", value),
+            #[cfg(feature = "chrono")]
+            Value::Time(value) => write!(f, "'{}'", value),
+            #[cfg(feature = "chrono")]
+            Value::Timestamp(value) => write!(f, "'{}'", value),
+            #[cfg(feature = "chrono")]
+            Value::TimestampTz(value) => write!(f, "'{}'", value),
+            Value::Column(column) => write!(f, "{}", column),
+            Value::List(values) => {
+                let values = values
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<String>>();
+                write!(f, "({})", values.join(", "))
+            }
+            Value::Subquery(subquery) => {
+                let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+                let sql = subquery.to_sql(&mut params);
+                write!(f, "{}", inline_params(&sql, params.values()))
+            }
+        }
+    }
+}
+
+/// Replaces the `?` placeholders of `sql` with the values bound to them, written as literals.
+///
+/// Question marks inside of quoted text are left as they are.
+fn inline_params(sql: &str, values: &[Param]) -> String {
+    let mut values = values.iter();
+    let mut quoted = false;
+    let mut inlined = String::with_capacity(sql.len());
+
+    for c in sql.chars() {
+        match c {
+            '?' if !quoted => match values.next() {
+                Some(value) => inlined.push_str(&Value::from(value.clone()).to_string()),
+                None => inlined.push(c),
+            },
+            '\'' => {
+                quoted = !quoted;
+                inlined.push(c);
+            }
+            _ => inlined.push(c),
+        }
+    }
+
+    inlined
+}
+
+macro_rules! impl_from_for_value {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl<'a> From<$source> for Value<'a> {
+                fn from(value: $source) -> Self {
+                    Value::$variant(<$target>::from(value))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_for_value!(Bool, bool, bool);
+impl_from_for_value!(Int, i64, i8, i16, i32, i64, u8, u16, u32);
+impl_from_for_value!(Float, f64, f32, f64);
+impl_from_for_value!(Text, String, String, &str);
+impl_from_for_value!(Bytes, Vec<u8>, Vec<u8>, &[u8]);
+#[cfg(feature = "chrono")]
+impl_from_for_value!(Date, NaiveDate, NaiveDate);
+#[cfg(feature = "chrono")]
+impl_from_for_value!(Time, NaiveTime, NaiveTime);
+#[cfg(feature = "chrono")]
+impl_from_for_value!(Timestamp, NaiveDateTime, NaiveDateTime);
+#[cfg(feature = "chrono")]
+impl_from_for_value!(TimestampTz, DateTime<Utc>, DateTime<Utc>);
+
+#[cfg(feature = "uuid")]
+impl<'a> From<uuid::Uuid> for Value<'a> {
+    fn from(value: uuid::Uuid) -> Self {
+        Value::Text(value.hyphenated().to_string())
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl<'a> From<rust_decimal::Decimal> for Value<'a> {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a> From<serde_json::Value> for Value<'a> {
+    fn from(value: serde_json::Value) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+macro_rules! impl_from_unsigned_for_value {
+    ($($source:ty),+) => {
+        $(
+            /// Values beyond the range of `i64` are bound as text, keeping every digit.
+            impl<'a> From<$source> for Value<'a> {
+                fn from(value: $source) -> Self {
+                    i64::try_from(value).map_or_else(|_| Value::Text(value.to_string()), Value::Int)
                 }
-                write!(f, "({})", result)
             }
+        )+
+    };
+}
+
+impl_from_unsigned_for_value!(u64, usize);
+
+impl<'a> From<Param> for Value<'a> {
+    fn from(param: Param) -> Self {
+        match param {
+            Param::Null => Value::Null,
+            Param::Bool(value) => Value::Bool(value),
+            Param::Int(value) => Value::Int(value),
+            Param::Float(value) => Value::Float(value),
+            Param::Text(value) => Value::Text(value),
+            Param::Bytes(value) => Value::Bytes(value),
+            #[cfg(feature = "chrono")]
+            Param::Date(value) => Value::Date(value),
+            #[cfg(feature = "chrono")]
+            Param::Time(value) => Value::Time(value),
+            #[cfg(feature = "chrono")]
+            Param::Timestamp(value) => Value::Timestamp(value),
+            #[cfg(feature = "chrono")]
+            Param::TimestampTz(value) => Value::TimestampTz(value),
         }
     }
 }
 
+impl<'a, T: Into<Value<'a>>> From<Option<T>> for Value<'a> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
 impl<'a> Condition<'a> {
-    /// Checks if the given value is numeric.
-    ///
-    /// # Arguments
-    ///
-    /// * `value` - The value to check.
-    ///
-    /// # Returns
+    /// Renders a binary comparison, binding the right-hand value.
+    fn build_comparison(
+        column: &str,
+        operator: &str,
+        value: &Value,
+        params: &mut QueryParams,
+    ) -> String {
+        format!("{} {} {}", column, operator, value.build(params))
+    }
+
+    /// Renders the value list of an IN or NOT IN condition.
+    fn build_list(values: &[Value], params: &mut QueryParams) -> String {
+        match values {
+            [Value::Subquery(subquery)] => subquery.to_sql(params),
+            [Value::List(values)] => Value::build_all(values, params),
+            _ => Value::build_all(values, params),
+        }
+    }
+
+    /// Renders the optional ESCAPE clause of a LIKE condition.
     ///
-    /// Returns `true` if the value is numeric, otherwise `false`.
-    fn is_numeric(value: &str) -> bool {
-        value.parse::<f64>().is_ok() || value.parse::<i64>().is_ok()
+    /// The escape character is bound like any other value, which avoids the
+    /// backslash quoting differences between backends.
+    fn build_escape(escape: &Option<char>, params: &mut QueryParams) -> String {
+        match escape {
+            Some(escape) => format!(" ESCAPE {}", params.push(escape.to_string())),
+            None => String::new(),
+        }
     }
 
     /// Builds the SQL representation of the condition.
     ///
+    /// Scalar values are never written into the SQL text; they are bound to `params`
+    /// and replaced by placeholders.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameter list of the statement being built.
+    ///
     /// # Returns
     ///
     /// Returns a `String` representing the SQL condition.
-    pub fn build(&self) -> String {
+    pub fn build(&self, params: &mut QueryParams) -> String {
         match self {
-            Condition::Eq(column, value) => {
-                // If contains a dot, assume it's a table.column
-                if column.contains('.') {
-                    format!("{} = {}", column, value)
-                } else if value.is_numeric() {
-                    format!("{} = {}", column, value)
-                } else {
-                    format!("{} = '{}'", column, value)
-                }
-            }
+            // `column = NULL` is never true, so NULL is compared with IS [NOT] NULL
+            Condition::Eq(column, Value::Null) => format!("{} IS NULL", column),
+            Condition::Ne(column, Value::Null) => format!("{} IS NOT NULL", column),
+            Condition::Eq(column, value) => Condition::build_comparison(column, "=", value, params),
             Condition::Ne(column, value) => {
-                if value.is_numeric() {
-                    format!("{} <> {}", column, value)
-                } else {
-                    format!("{} <> '{}'", column, value)
-                }
-            }
-            Condition::Lt(column, value) => {
-                if value.is_numeric() {
-                    format!("{} < {}", column, value)
-                } else {
-                    format!("{} < '{}'", column, value)
-                }
-            }
-            Condition::Gt(column, value) => {
-                if value.is_numeric() {
-                    format!("{} > {}", column, value)
-                } else {
-                    format!("{} > '{}'", column, value)
-                }
+                Condition::build_comparison(column, "<>", value, params)
             }
+            Condition::Lt(column, value) => Condition::build_comparison(column, "<", value, params),
+            Condition::Gt(column, value) => Condition::build_comparison(column, ">", value, params),
             Condition::Le(column, value) => {
-                if value.is_numeric() {
-                    format!("{} <= {}", column, value)
-                } else {
-                    format!("{} <= '{}'", column, value)
-                }
+                Condition::build_comparison(column, "<=", value, params)
             }
             Condition::Ge(column, value) => {
-                if value.is_numeric() {
-                    format!("{} >= {}", column, value)
-                } else {
-                    format!("{} >= '{}'", column, value)
-                }
+                Condition::build_comparison(column, ">=", value, params)
+            }
+            Condition::And(left, right) => {
+                let left = left.build(params);
+                let right = right.build(params);
+                format!("({}) AND ({})", left, right)
+            }
+            Condition::Or(left, right) => {
+                let left = left.build(params);
+                let right = right.build(params);
+                format!("({}) OR ({})", left, right)
             }
-            Condition::And(left, right) => format!("({}) AND ({})", left.build(), right.build()),
-            Condition::Or(left, right) => format!("({}) OR ({})", left.build(), right.build()),
             Condition::In(column, values) => {
-                let values = values
-                    .iter()
-                    .map(|v| format!("'{}'", v))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                format!("{} IN ({})", column, values)
+                format!("{} IN ({})", column, Condition::build_list(values, params))
             }
             Condition::NotIn(column, values) => {
-                let values = values
-                    .iter()
-                    .map(|v| format!("'{}'", v))
-                    .collect::<Vec<String>>()
-                    .join(", ");
-                format!("{} NOT IN ({})", column, values)
+                format!(
+                    "{} NOT IN ({})",
+                    column,
+                    Condition::build_list(values, params)
+                )
+            }
+            Condition::IsNull(column) => format!("{} IS NULL", column),
+            Condition::IsNotNull(column) => format!("{} IS NOT NULL", column),
+            Condition::Like(column, pattern, escape) => {
+                let pattern = pattern.build(params);
+                let escape = Condition::build_escape(escape, params);
+                format!("{} LIKE {}{}", column, pattern, escape)
+            }
+            Condition::NotLike(column, pattern, escape) => {
+                let pattern = pattern.build(params);
+                let escape = Condition::build_escape(escape, params);
+                format!("{} NOT LIKE {}{}", column, pattern, escape)
+            }
+            Condition::ILike(column, pattern, escape) => {
+                let pattern = pattern.build(params);
+                let escape = Condition::build_escape(escape, params);
+                format!("LOWER({}) LIKE LOWER({}){}", column, pattern, escape)
+            }
+            Condition::Between(column, low, high) => {
+                let low = low.build(params);
+                let high = high.build(params);
+                format!("{} BETWEEN {} AND {}", column, low, high)
             }
+            Condition::Not(condition) => format!("NOT ({})", condition.build(params)),
+            Condition::Exists(subquery) => format!("EXISTS ({})", subquery.to_sql(params)),
+            Condition::NotExists(subquery) => {
+                format!("NOT EXISTS ({})", subquery.to_sql(params))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_binds_values() {
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let condition = Condition::And(
+            Box::new(Condition::Eq(
+                "code".to_string(),
+                Value::Text("007".to_string()),
+            )),
+            Box::new(Condition::Ne(
+                "name".to_string(),
+                Value::Text("O'Brien'; DROP TABLE users; --".to_string()),
+            )),
+        );
+
+        assert_eq!(condition.build(&mut params), "(code = ?) AND (name <> ?)");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("007".to_string()),
+                Param::Text("O'Brien'; DROP TABLE users; --".to_string())
+            ]
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_build_typed_values() {
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let date = NaiveDate::from_ymd_opt(2024, 5, 17).unwrap();
+        let condition = Condition::And(
+            Box::new(Condition::Eq("age".to_string(), 30.into())),
+            Box::new(Condition::In(
+                "created".to_string(),
+                vec![
+                    date.into(),
+                    Value::from(None::<i32>),
+                    vec![0xCA, 0xFE].into(),
+                ],
+            )),
+        );
+
+        assert_eq!(
+            condition.build(&mut params),
+            "(age = ?) AND (created IN (?, ?, ?))"
+        );
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Int(30),
+                Param::Date(date),
+                Param::Null,
+                Param::Bytes(vec![0xCA, 0xFE])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_numbers_placeholders() {
+        let mut params = QueryParams::new(PlaceholderStyle::AtP);
+        let condition = Condition::In(
+            "username".to_string(),
+            vec![
+                Value::Text("mjovanc".to_string()),
+                Value::Text("otheruser".to_string()),
+            ],
+        );
+
+        assert_eq!(condition.build(&mut params), "username IN (@P1, @P2)");
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_build_column_reference() {
+        let mut params = QueryParams::new(PlaceholderStyle::Colon);
+        let condition = Condition::Eq(
+            "users.id".to_string(),
+            Value::Column("products.user_id".to_string()),
+        );
+        assert_eq!(condition.build(&mut params), "users.id = products.user_id");
+        assert!(params.is_empty());
+
+        let condition = Condition::Eq(
+            "users.name".to_string(),
+            Value::Text("products.user_id".to_string()),
+        );
+        assert_eq!(condition.build(&mut params), "users.name = :1");
+    }
+
+    #[test]
+    fn test_build_null_checks() {
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let condition = Condition::Or(
+            Box::new(Condition::IsNull("email".to_string())),
+            Box::new(Condition::IsNotNull("address".to_string())),
+        );
+
+        assert_eq!(
+            condition.build(&mut params),
+            "(email IS NULL) OR (address IS NOT NULL)"
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_build_null_comparisons() {
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        let condition = Condition::And(
+            Box::new(Condition::Eq("email".to_string(), Value::Null)),
+            Box::new(Condition::Ne(
+                "address".to_string(),
+                Value::from(None::<String>),
+            )),
+        );
+
+        assert_eq!(
+            condition.build(&mut params),
+            "(email IS NULL) AND (address IS NOT NULL)"
+        );
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_build_like() {
+        let mut params = QueryParams::new(PlaceholderStyle::Colon);
+        let condition = Condition::And(
+            Box::new(Condition::Like(
+                "code".to_string(),
+                "100!%%".into(),
+                Some('!'),
+            )),
+            Box::new(Condition::NotLike(
+                "username".to_string(),
+                "test%".into(),
+                None,
+            )),
+        );
+
+        assert_eq!(
+            condition.build(&mut params),
+            "(code LIKE :1 ESCAPE :2) AND (username NOT LIKE :3)"
+        );
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("100!%%".to_string()),
+                Param::Text("!".to_string()),
+                Param::Text("test%".to_string())
+            ]
+        );
+
+        let mut params = QueryParams::new(PlaceholderStyle::AtP);
+        let condition = Condition::ILike("email".to_string(), "%@ICLOUD.COM".into(), None);
+        assert_eq!(condition.build(&mut params), "LOWER(email) LIKE LOWER(@P1)");
+    }
+
+    #[test]
+    fn test_build_between_and_not() {
+        let mut params = QueryParams::new(PlaceholderStyle::AtP);
+        let condition = Condition::Not(Box::new(Condition::Between(
+            "id".to_string(),
+            Value::Int(10),
+            Value::Int(20),
+        )));
+
+        assert_eq!(condition.build(&mut params), "NOT (id BETWEEN @P1 AND @P2)");
+        assert_eq!(params.values(), &[Param::Int(10), Param::Int(20)]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Value::Text("O'Brien".to_string()).to_string(), "'O''Brien'");
+        assert_eq!(Value::Bytes(vec![0xCA, 0xFE]).to_string(), "X'CAFE'");
+        assert_eq!(Value::from(None::<&str>).to_string(), "NULL");
+        assert_eq!(
+            Value::List(vec![1.into(), 2.5.into()]).to_string(),
+            "(1, 2.5)"
+        );
+    }
+
+    #[derive(Clone)]
+    struct CodeQuery;
+
+    impl<'a> QueryBuilder<'a> for CodeQuery {
+        fn to_sql(&self, params: &mut QueryParams) -> String {
+            format!(
+                "SELECT id FROM codes WHERE note <> 'why?' AND code = {}",
+                Value::Text("O'Brien".to_string()).build(params)
+            )
         }
     }
+
+    #[test]
+    fn test_display_subquery() {
+        assert_eq!(
+            Value::Subquery(Box::new(CodeQuery)).to_string(),
+            "SELECT id FROM codes WHERE note <> 'why?' AND code = 'O''Brien'"
+        );
+    }
 }
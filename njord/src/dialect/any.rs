@@ -0,0 +1,226 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{
+    AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, DuckDbDialect, MariaDbDialect,
+    MssqlDialect, MySqlDialect, OracleDialect, PostgresDialect, ReturningStyle, SqliteDialect,
+    UpsertStyle,
+};
+
+/// A dialect chosen at runtime.
+///
+/// Queries built for an [`AnyConnection`](crate::AnyConnection) carry this dialect and
+/// are re-rendered with the dialect of the connection they are executed on, so the
+/// variant only matters when such a query is rendered on its own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AnyDialect {
+    /// The SQLite dialect.
+    #[default]
+    Sqlite,
+    /// The MySQL dialect.
+    MySql,
+    /// The MariaDB dialect.
+    MariaDb,
+    /// The Oracle dialect.
+    Oracle,
+    /// The MSSQL dialect.
+    Mssql,
+    /// The PostgreSQL dialect.
+    Postgres,
+    /// The DuckDB dialect.
+    DuckDb,
+}
+
+impl AnyDialect {
+    /// Returns the concrete dialect this variant stands for.
+    fn inner(&self) -> &'static dyn Dialect {
+        match self {
+            AnyDialect::Sqlite => &SqliteDialect,
+            AnyDialect::MySql => &MySqlDialect,
+            AnyDialect::MariaDb => &MariaDbDialect,
+            AnyDialect::Oracle => &OracleDialect,
+            AnyDialect::Mssql => &MssqlDialect,
+            AnyDialect::Postgres => &PostgresDialect,
+            AnyDialect::DuckDb => &DuckDbDialect,
+        }
+    }
+}
+
+impl Dialect for AnyDialect {
+    fn name(&self) -> &'static str {
+        self.inner().name()
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        self.inner().placeholder_style()
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        self.inner().quote_identifier(identifier)
+    }
+
+    fn identifier(&self, identifier: &str) -> String {
+        self.inner().identifier(identifier)
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, ordered: bool) -> String {
+        self.inner().pagination(limit, offset, ordered)
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        self.inner().type_name(data_type)
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        self.inner().returning_style()
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        self.inner().dml_limit_style()
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        self.inner().upsert_style()
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        self.inner().auto_increment_style()
+    }
+
+    fn create_table_if_not_exists(&self, table: &str, definitions: &str) -> String {
+        self.inner().create_table_if_not_exists(table, definitions)
+    }
+
+    fn drop_table_if_exists(&self, table: &str) -> String {
+        self.inner().drop_table_if_exists(table)
+    }
+
+    fn add_column(&self, table: &str, definition: &str) -> String {
+        self.inner().add_column(table, definition)
+    }
+
+    fn rename_column(&self, table: &str, from: &str, to: &str) -> String {
+        self.inner().rename_column(table, from, to)
+    }
+
+    fn alter_column(
+        &self,
+        table: &str,
+        column: &str,
+        type_name: &str,
+        nullable: bool,
+    ) -> Option<Vec<String>> {
+        self.inner()
+            .alter_column(table, column, type_name, nullable)
+    }
+
+    fn supports_alter_constraints(&self) -> bool {
+        self.inner().supports_alter_constraints()
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        self.inner().supports_partial_indexes()
+    }
+
+    fn drop_index(&self, index: &str, table: &str) -> String {
+        self.inner().drop_index(index, table)
+    }
+
+    fn supports_multi_row_insert(&self) -> bool {
+        self.inner().supports_multi_row_insert()
+    }
+
+    fn max_bind_parameters(&self) -> usize {
+        self.inner().max_bind_parameters()
+    }
+
+    fn max_in_list(&self) -> Option<usize> {
+        self.inner().max_in_list()
+    }
+
+    fn max_insert_rows(&self) -> Option<usize> {
+        self.inner().max_insert_rows()
+    }
+
+    fn max_statement_bytes(&self) -> Option<usize> {
+        self.inner().max_statement_bytes()
+    }
+
+    fn begin_transaction(&self, isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        self.inner().begin_transaction(isolation_level)
+    }
+
+    fn commit_transaction(&self) -> String {
+        self.inner().commit_transaction()
+    }
+
+    fn rollback_transaction(&self) -> String {
+        self.inner().rollback_transaction()
+    }
+
+    fn reset_isolation_level(&self, isolation_level: IsolationLevel) -> Option<String> {
+        self.inner().reset_isolation_level(isolation_level)
+    }
+
+    fn savepoint(&self, name: &str) -> Option<String> {
+        self.inner().savepoint(name)
+    }
+
+    fn rollback_to_savepoint(&self, name: &str) -> String {
+        self.inner().rollback_to_savepoint(name)
+    }
+
+    fn release_savepoint(&self, name: &str) -> Option<String> {
+        self.inner().release_savepoint(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delegates_to_the_selected_dialect() {
+        assert_eq!(AnyDialect::default().name(), "sqlite");
+        assert_eq!(AnyDialect::MariaDb.name(), "mariadb");
+        assert_eq!(AnyDialect::Mssql.quote_identifier("users"), "[users]");
+        assert_eq!(AnyDialect::MySql.identifier("user name"), "`user name`");
+        assert_eq!(
+            AnyDialect::Oracle.pagination(Some(10), None, false),
+            "FETCH FIRST 10 ROWS ONLY"
+        );
+        assert!(!AnyDialect::Oracle.supports_multi_row_insert());
+        assert_eq!(AnyDialect::Postgres.name(), "postgres");
+        assert_eq!(AnyDialect::DuckDb.name(), "duckdb");
+        assert_eq!(AnyDialect::DuckDb.savepoint("sp"), None);
+        assert_eq!(AnyDialect::Mssql.commit_transaction(), "COMMIT TRANSACTION");
+    }
+}
@@ -0,0 +1,305 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, ReturningStyle, UpsertStyle};
+
+/// The DuckDB dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DuckDbDialect;
+
+impl Dialect for DuckDbDialect {
+    fn name(&self) -> &'static str {
+        "duckdb"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        PlaceholderStyle::QuestionMark
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, _ordered: bool) -> String {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => format!("LIMIT {} OFFSET {}", limit, offset),
+            (Some(limit), None) => format!("LIMIT {}", limit),
+            (None, Some(offset)) => format!("OFFSET {}", offset),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        match data_type {
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::SmallInt => "SMALLINT".to_string(),
+            DataType::Integer => "INTEGER".to_string(),
+            DataType::BigInt => "BIGINT".to_string(),
+            DataType::Float => "REAL".to_string(),
+            DataType::Double => "DOUBLE".to_string(),
+            DataType::Decimal { precision, scale } => {
+                format!("DECIMAL({}, {})", precision, scale)
+            }
+            DataType::Text => "VARCHAR".to_string(),
+            DataType::Varchar(length) => format!("VARCHAR({})", length),
+            DataType::Blob => "BLOB".to_string(),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => "TIME".to_string(),
+            DataType::Timestamp => "TIMESTAMP".to_string(),
+            DataType::TimestampTz => "TIMESTAMPTZ".to_string(),
+            DataType::Uuid => "UUID".to_string(),
+            DataType::Json => "JSON".to_string(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Returning
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        DmlLimitStyle::RowId("rowid")
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnConflict
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        AutoIncrementStyle::Sequence
+    }
+
+    fn supports_alter_constraints(&self) -> bool {
+        false
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        false
+    }
+
+    fn begin_transaction(&self, _isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        // DuckDB transactions always use snapshot isolation
+        vec!["BEGIN TRANSACTION".to_string()]
+    }
+
+    fn savepoint(&self, _name: &str) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder, SchemaError,
+    };
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, DuckDbDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = ? ORDER BY id DESC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, DuckDbDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(sql, "SELECT * FROM users OFFSET 5");
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, DuckDbDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .returning(["id"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?), (?, ?) RETURNING id"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, DuckDbDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = ? WHERE username = ?");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, DuckDbDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE rowid IN \
+             (SELECT rowid FROM users WHERE username = ? ORDER BY id ASC LIMIT 1)"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<DuckDbDialect>::from_table(&User::default()).render();
+        assert_eq!(
+            create,
+            vec![
+                "CREATE SEQUENCE users_id_seq",
+                "CREATE TABLE users (id BIGINT DEFAULT nextval('users_id_seq') NOT NULL, \
+                 username VARCHAR NOT NULL, email VARCHAR NOT NULL, PRIMARY KEY (id))"
+            ]
+        );
+
+        let drop = DropTableBuilder::<DuckDbDialect>::from_table(&User::default()).render();
+        assert_eq!(drop, vec!["DROP TABLE users", "DROP SEQUENCE users_id_seq"]);
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<DuckDbDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+            .drop_column("age")
+            .render()
+            .unwrap();
+        assert_eq!(
+            alter,
+            vec![
+                "ALTER TABLE users ADD COLUMN age INTEGER",
+                "ALTER TABLE users RENAME COLUMN username TO login",
+                "ALTER TABLE users ALTER COLUMN email TYPE VARCHAR(255)",
+                "ALTER TABLE users ALTER COLUMN email SET NOT NULL",
+                "ALTER TABLE users DROP COLUMN age",
+            ]
+        );
+
+        let index = CreateIndexBuilder::<DuckDbDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique();
+        assert_eq!(
+            index.render(),
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username)".to_string())
+        );
+        assert_eq!(
+            index.where_clause("email IS NOT NULL").render(),
+            Err(SchemaError::Unsupported {
+                dialect: "duckdb",
+                operation: "a partial index".to_string()
+            })
+        );
+
+        let drop = DropIndexBuilder::<DuckDbDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(DuckDbDialect.identifier("users"), "users");
+        assert_eq!(DuckDbDialect.identifier("order items"), "\"order items\"");
+        assert_eq!(DuckDbDialect.type_name(DataType::Boolean), "BOOLEAN");
+        assert_eq!(
+            DuckDbDialect.type_name(DataType::Decimal {
+                precision: 10,
+                scale: 2
+            }),
+            "DECIMAL(10, 2)"
+        );
+        assert_eq!(DuckDbDialect.type_name(DataType::Blob), "BLOB");
+    }
+
+    #[test]
+    fn transaction() {
+        assert_eq!(
+            DuckDbDialect.begin_transaction(Some(IsolationLevel::Serializable)),
+            vec!["BEGIN TRANSACTION"]
+        );
+        assert_eq!(DuckDbDialect.savepoint("sp"), None);
+    }
+}
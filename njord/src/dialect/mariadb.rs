@@ -0,0 +1,281 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{
+    AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, MySqlDialect, ReturningStyle, UpsertStyle,
+};
+
+/// The MariaDB dialect.
+///
+/// MariaDB speaks the MySQL dialect; it is a type of its own so that the
+/// `mariadb` and `mysql` backends can both be enabled at the same time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MariaDbDialect;
+
+impl Dialect for MariaDbDialect {
+    fn name(&self) -> &'static str {
+        "mariadb"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        MySqlDialect.placeholder_style()
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        MySqlDialect.quote_identifier(identifier)
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, ordered: bool) -> String {
+        MySqlDialect.pagination(limit, offset, ordered)
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        MySqlDialect.type_name(data_type)
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        MySqlDialect.returning_style()
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        MySqlDialect.dml_limit_style()
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        MySqlDialect.upsert_style()
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        MySqlDialect.auto_increment_style()
+    }
+
+    fn alter_column(
+        &self,
+        table: &str,
+        column: &str,
+        type_name: &str,
+        nullable: bool,
+    ) -> Option<Vec<String>> {
+        MySqlDialect.alter_column(table, column, type_name, nullable)
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        MySqlDialect.supports_partial_indexes()
+    }
+
+    fn drop_index(&self, index: &str, table: &str) -> String {
+        MySqlDialect.drop_index(index, table)
+    }
+
+    fn max_statement_bytes(&self) -> Option<usize> {
+        MySqlDialect.max_statement_bytes()
+    }
+
+    fn begin_transaction(&self, isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        MySqlDialect.begin_transaction(isolation_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder, SchemaError,
+    };
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, MariaDbDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = ? ORDER BY id DESC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, MariaDbDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(
+            sql,
+            "SELECT * FROM users LIMIT 18446744073709551615 OFFSET 5"
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, MariaDbDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?), (?, ?)"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, MariaDbDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = ? WHERE username = ?");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, MariaDbDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE username = ? ORDER BY id ASC LIMIT 1"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<MariaDbDialect>::from_table(&User::default()).render();
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE users (id BIGINT AUTO_INCREMENT NOT NULL, \
+                 username TEXT NOT NULL, email TEXT NOT NULL, PRIMARY KEY (id))"
+            ]
+        );
+
+        let drop = DropTableBuilder::<MariaDbDialect>::from_table(&User::default()).render();
+        assert_eq!(drop, vec!["DROP TABLE users"]);
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<MariaDbDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+            .drop_column("age")
+            .render()
+            .unwrap();
+        assert_eq!(
+            alter,
+            vec![
+                "ALTER TABLE users ADD COLUMN age INT",
+                "ALTER TABLE users RENAME COLUMN username TO login",
+                "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL",
+                "ALTER TABLE users DROP COLUMN age",
+            ]
+        );
+
+        let index = CreateIndexBuilder::<MariaDbDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique();
+        assert_eq!(
+            index.render(),
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username)".to_string())
+        );
+        assert_eq!(
+            index.where_clause("email IS NOT NULL").render(),
+            Err(SchemaError::Unsupported {
+                dialect: "mariadb",
+                operation: "a partial index".to_string()
+            })
+        );
+
+        let drop = DropIndexBuilder::<MariaDbDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email ON users");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(MariaDbDialect.identifier("users"), "users");
+        assert_eq!(MariaDbDialect.identifier("order items"), "`order items`");
+        assert_eq!(MariaDbDialect.type_name(DataType::Boolean), "BOOLEAN");
+        assert_eq!(
+            MariaDbDialect.type_name(DataType::Varchar(255)),
+            "VARCHAR(255)"
+        );
+    }
+}
@@ -0,0 +1,492 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+mod any;
+mod duckdb;
+mod mariadb;
+mod mssql;
+mod mysql;
+mod oracle;
+mod postgres;
+mod sqlite;
+
+pub use any::AnyDialect;
+pub use duckdb::DuckDbDialect;
+pub use mariadb::MariaDbDialect;
+pub use mssql::MssqlDialect;
+pub use mysql::MySqlDialect;
+pub use oracle::OracleDialect;
+pub use postgres::PostgresDialect;
+pub use sqlite::SqliteDialect;
+
+use crate::{
+    builder::util::remove_quotes_and_backslashes, query::PlaceholderStyle,
+    transaction::IsolationLevel,
+};
+
+/// A backend independent SQL data type.
+///
+/// Each [`Dialect`] renders it to the type name its database understands,
+/// e.g. `DataType::Boolean` becomes `INTEGER` on SQLite and `BIT` on MSSQL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataType {
+    /// A boolean value.
+    Boolean,
+    /// A 16-bit signed integer.
+    SmallInt,
+    /// A 32-bit signed integer.
+    Integer,
+    /// A 64-bit signed integer.
+    BigInt,
+    /// A single precision floating point number.
+    Float,
+    /// A double precision floating point number.
+    Double,
+    /// An exact number with `precision` digits, `scale` of them after the decimal point.
+    Decimal { precision: u8, scale: u8 },
+    /// Text without a length limit.
+    Text,
+    /// Text of at most the given number of characters.
+    Varchar(u32),
+    /// Binary data.
+    Blob,
+    /// A calendar date without time zone.
+    Date,
+    /// A time of day without time zone.
+    Time,
+    /// A date and time without time zone.
+    Timestamp,
+    /// A date and time with time zone.
+    TimestampTz,
+    /// A universally unique identifier.
+    Uuid,
+    /// A JSON document.
+    Json,
+}
+
+impl std::fmt::Display for DataType {
+    /// Formats the type with its standard SQL name, independent of any backend.
+    ///
+    /// All integers are written as `INTEGER` and all floating point numbers as `REAL`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataType::Boolean => write!(f, "BOOLEAN"),
+            DataType::SmallInt | DataType::Integer | DataType::BigInt => write!(f, "INTEGER"),
+            DataType::Float | DataType::Double => write!(f, "REAL"),
+            DataType::Decimal { precision, scale } => {
+                write!(f, "DECIMAL({}, {})", precision, scale)
+            }
+            DataType::Text => write!(f, "TEXT"),
+            DataType::Varchar(length) => write!(f, "VARCHAR({})", length),
+            DataType::Blob => write!(f, "BLOB"),
+            DataType::Date => write!(f, "DATE"),
+            DataType::Time => write!(f, "TIME"),
+            DataType::Timestamp => write!(f, "TIMESTAMP"),
+            DataType::TimestampTz => write!(f, "TIMESTAMPTZ"),
+            DataType::Uuid => write!(f, "UUID"),
+            DataType::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+/// How a dialect hands back values generated by an INSERT, such as auto-increment keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReturningStyle {
+    /// Generated values can't be returned by the statement itself and have to be
+    /// queried afterwards, e.g. with `LAST_INSERT_ID()`.
+    None,
+    /// A trailing `RETURNING col, ...` clause.
+    Returning,
+    /// An `OUTPUT INSERTED.col, ...` clause. The rows are inserted with a MERGE,
+    /// which can output the position of each row along with its values.
+    Output,
+    /// A trailing `RETURNING col, ... INTO :n, ...` clause filling out parameters.
+    ReturningInto,
+}
+
+/// How an UPDATE or DELETE restricted with LIMIT/OFFSET is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DmlLimitStyle {
+    /// `ORDER BY` and `LIMIT` directly on the statement. There is no OFFSET.
+    Inline,
+    /// The statement is restricted to the rows whose row id pseudo column (e.g. `rowid`)
+    /// is returned by a paginated subquery.
+    RowId(&'static str),
+    /// The statement runs against a paginated common table expression.
+    CommonTableExpression,
+}
+
+/// How an INSERT that updates conflicting rows instead is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpsertStyle {
+    /// A trailing `ON CONFLICT (col, ...) DO UPDATE SET ...` or `DO NOTHING` clause.
+    OnConflict,
+    /// A trailing `ON DUPLICATE KEY UPDATE ...` clause, matching any unique key.
+    OnDuplicateKey,
+    /// A `MERGE` statement whose source rows are a `VALUES` table.
+    Merge,
+    /// A `MERGE` statement whose source rows are selected from `DUAL`.
+    MergeFromDual,
+}
+
+/// How a column with generated auto-increment values is declared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoIncrementStyle {
+    /// `INTEGER PRIMARY KEY AUTOINCREMENT`, only allowed on a single-column key.
+    Autoincrement,
+    /// An `AUTO_INCREMENT` column attribute.
+    AutoIncrement,
+    /// An `IDENTITY(1, 1)` column attribute.
+    Identity,
+    /// A `GENERATED BY DEFAULT AS IDENTITY` column attribute.
+    GeneratedIdentity,
+    /// A default drawing the next value of a sequence created along with the table.
+    Sequence,
+}
+
+/// The SQL flavour of a database backend.
+///
+/// The query builders in [`crate::builder`] render all backend specific syntax
+/// through this trait, so a query only has to be written once.
+pub trait Dialect {
+    /// Returns the name of the dialect, e.g. `"sqlite"`.
+    fn name(&self) -> &'static str;
+
+    /// Returns the placeholder syntax used for bound parameters.
+    fn placeholder_style(&self) -> PlaceholderStyle;
+
+    /// Quotes an identifier, escaping any quote characters inside it.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The table or column name to quote.
+    ///
+    /// # Returns
+    ///
+    /// The quoted identifier, e.g. `"users"` or `[users]`.
+    fn quote_identifier(&self, identifier: &str) -> String;
+
+    /// Renders a table or column name as it should appear in a statement.
+    ///
+    /// Plain names (letters, digits, `_`, `$` and `.`) are kept as they are, so that
+    /// databases folding unquoted names to upper case (Oracle) still find the table.
+    /// Anything else is quoted with [`Dialect::quote_identifier`].
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The table or column name.
+    ///
+    /// # Returns
+    ///
+    /// The identifier, quoted if necessary.
+    fn identifier(&self, identifier: &str) -> String {
+        let identifier = remove_quotes_and_backslashes(identifier);
+        let is_plain = identifier
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && identifier
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '.'));
+
+        if is_plain {
+            identifier
+        } else {
+            self.quote_identifier(&identifier)
+        }
+    }
+
+    /// Renders the pagination clause of a SELECT.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of rows to return.
+    /// * `offset` - The number of rows to skip.
+    /// * `ordered` - Whether the query already has an ORDER BY clause.
+    ///
+    /// # Returns
+    ///
+    /// The clause to append after ORDER BY, or an empty string if neither
+    /// `limit` nor `offset` is set.
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, ordered: bool) -> String;
+
+    /// Returns the name of the column type used for `data_type`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - The backend independent data type.
+    fn type_name(&self, data_type: DataType) -> String;
+
+    /// Returns how generated values are handed back by an INSERT.
+    fn returning_style(&self) -> ReturningStyle;
+
+    /// Returns how an UPDATE or DELETE with LIMIT/OFFSET is rendered.
+    fn dml_limit_style(&self) -> DmlLimitStyle;
+
+    /// Returns how an INSERT updating conflicting rows is rendered.
+    fn upsert_style(&self) -> UpsertStyle;
+
+    /// Returns how a column with auto-increment values is declared.
+    fn auto_increment_style(&self) -> AutoIncrementStyle;
+
+    /// Renders a CREATE TABLE statement doing nothing if the table already exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The rendered name of the table.
+    /// * `definitions` - The column definitions and constraints, separated by commas.
+    fn create_table_if_not_exists(&self, table: &str, definitions: &str) -> String {
+        format!("CREATE TABLE IF NOT EXISTS {} ({})", table, definitions)
+    }
+
+    /// Renders a DROP TABLE statement doing nothing if the table does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The rendered name of the table.
+    fn drop_table_if_exists(&self, table: &str) -> String {
+        format!("DROP TABLE IF EXISTS {}", table)
+    }
+
+    /// Renders an ALTER TABLE statement adding a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The rendered name of the table.
+    /// * `definition` - The rendered definition of the column.
+    fn add_column(&self, table: &str, definition: &str) -> String {
+        format!("ALTER TABLE {} ADD COLUMN {}", table, definition)
+    }
+
+    /// Renders a statement renaming a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The rendered name of the table.
+    /// * `from` - The rendered current name of the column.
+    /// * `to` - The new name of the column, not yet rendered as an identifier.
+    fn rename_column(&self, table: &str, from: &str, to: &str) -> String {
+        format!(
+            "ALTER TABLE {} RENAME COLUMN {} TO {}",
+            table,
+            from,
+            self.identifier(to)
+        )
+    }
+
+    /// Renders the statements changing the type and nullability of a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The rendered name of the table.
+    /// * `column` - The rendered name of the column.
+    /// * `type_name` - The new type of the column.
+    /// * `nullable` - Whether the column accepts NULL afterwards.
+    ///
+    /// # Returns
+    ///
+    /// The statements to execute in order, or `None` if the dialect cannot change
+    /// the type of an existing column.
+    fn alter_column(
+        &self,
+        table: &str,
+        column: &str,
+        type_name: &str,
+        nullable: bool,
+    ) -> Option<Vec<String>> {
+        Some(vec![
+            format!(
+                "ALTER TABLE {} ALTER COLUMN {} TYPE {}",
+                table, column, type_name
+            ),
+            format!(
+                "ALTER TABLE {} ALTER COLUMN {} {} NOT NULL",
+                table,
+                column,
+                if nullable { "DROP" } else { "SET" }
+            ),
+        ])
+    }
+
+    /// Returns `true` if constraints can be added to or dropped from an existing table.
+    fn supports_alter_constraints(&self) -> bool {
+        true
+    }
+
+    /// Returns `true` if an index can cover only the rows matching a WHERE clause.
+    fn supports_partial_indexes(&self) -> bool {
+        true
+    }
+
+    /// Renders a DROP INDEX statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The rendered name of the index.
+    /// * `table` - The rendered name of the indexed table.
+    fn drop_index(&self, index: &str, _table: &str) -> String {
+        format!("DROP INDEX {}", index)
+    }
+
+    /// Returns `true` if one INSERT can take several rows as `VALUES (...), (...)`.
+    fn supports_multi_row_insert(&self) -> bool {
+        true
+    }
+
+    /// Returns the most values a single statement can bind.
+    fn max_bind_parameters(&self) -> usize {
+        65535
+    }
+
+    /// Returns the most values an `IN (...)` list can hold, or `None` if only the
+    /// number of bound values is limited.
+    fn max_in_list(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the most rows a single INSERT can take, or `None` if only the
+    /// number of bound values is limited.
+    fn max_insert_rows(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the most bytes of values a single statement can send, or `None`
+    /// if the size is not limited.
+    fn max_statement_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Renders the statements starting a transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `isolation_level` - The isolation level to run the transaction with, or
+    ///   `None` for the database default.
+    ///
+    /// # Returns
+    ///
+    /// The statements to execute in order.
+    fn begin_transaction(&self, isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        match isolation_level {
+            Some(level) => vec![format!(
+                "START TRANSACTION ISOLATION LEVEL {}",
+                standard_isolation_level(level).as_sql()
+            )],
+            None => vec!["START TRANSACTION".to_string()],
+        }
+    }
+
+    /// Returns the statement committing a transaction.
+    fn commit_transaction(&self) -> String {
+        "COMMIT".to_string()
+    }
+
+    /// Returns the statement rolling back a transaction.
+    fn rollback_transaction(&self) -> String {
+        "ROLLBACK".to_string()
+    }
+
+    /// Returns the statement setting the isolation level back to the database
+    /// default after a transaction, for databases where it outlives the transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `isolation_level` - The isolation level the transaction ran with.
+    fn reset_isolation_level(&self, _isolation_level: IsolationLevel) -> Option<String> {
+        None
+    }
+
+    /// Returns the statement creating a savepoint, or `None` if the database has no
+    /// savepoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the savepoint.
+    fn savepoint(&self, name: &str) -> Option<String> {
+        Some(format!("SAVEPOINT {}", name))
+    }
+
+    /// Returns the statement rolling back to a savepoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the savepoint.
+    fn rollback_to_savepoint(&self, name: &str) -> String {
+        format!("ROLLBACK TO SAVEPOINT {}", name)
+    }
+
+    /// Returns the statement releasing a savepoint, or `None` if savepoints are
+    /// released when the transaction ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the savepoint.
+    fn release_savepoint(&self, name: &str) -> Option<String> {
+        Some(format!("RELEASE SAVEPOINT {}", name))
+    }
+}
+
+/// Returns the isolation level to use on databases without snapshot isolation.
+///
+/// `Snapshot` becomes `Serializable`, the next stricter standard level.
+fn standard_isolation_level(isolation_level: IsolationLevel) -> IsolationLevel {
+    match isolation_level {
+        IsolationLevel::Snapshot => IsolationLevel::Serializable,
+        level => level,
+    }
+}
+
+/// Renders the standard `OFFSET n ROWS FETCH NEXT m ROWS ONLY` clause.
+///
+/// # Arguments
+///
+/// * `limit` - The maximum number of rows to return.
+/// * `offset` - The number of rows to skip.
+/// * `require_offset` - Whether OFFSET has to be present whenever FETCH is.
+fn offset_fetch(limit: Option<usize>, offset: Option<usize>, require_offset: bool) -> String {
+    let offset = match offset {
+        Some(offset) => Some(offset),
+        None if require_offset && limit.is_some() => Some(0),
+        None => None,
+    };
+
+    let mut clauses = Vec::new();
+    if let Some(offset) = offset {
+        clauses.push(format!("OFFSET {} ROWS", offset));
+    }
+    if let Some(limit) = limit {
+        if offset.is_some() {
+            clauses.push(format!("FETCH NEXT {} ROWS ONLY", limit));
+        } else {
+            clauses.push(format!("FETCH FIRST {} ROWS ONLY", limit));
+        }
+    }
+
+    clauses.join(" ")
+}
@@ -0,0 +1,459 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{
+    offset_fetch, AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, ReturningStyle, UpsertStyle,
+};
+
+/// The Microsoft SQL Server dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MssqlDialect;
+
+impl Dialect for MssqlDialect {
+    fn name(&self) -> &'static str {
+        "mssql"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        PlaceholderStyle::AtP
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("[{}]", identifier.replace(']', "]]"))
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, ordered: bool) -> String {
+        let pagination = offset_fetch(limit, offset, true);
+
+        // OFFSET ... FETCH is only allowed after an ORDER BY
+        if pagination.is_empty() || ordered {
+            pagination
+        } else {
+            format!("ORDER BY (SELECT NULL) {}", pagination)
+        }
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        match data_type {
+            DataType::Boolean => "BIT".to_string(),
+            DataType::SmallInt => "SMALLINT".to_string(),
+            DataType::Integer => "INT".to_string(),
+            DataType::BigInt => "BIGINT".to_string(),
+            DataType::Float => "REAL".to_string(),
+            DataType::Double => "FLOAT".to_string(),
+            DataType::Decimal { precision, scale } => format!("DECIMAL({}, {})", precision, scale),
+            DataType::Text | DataType::Json => "NVARCHAR(MAX)".to_string(),
+            DataType::Varchar(length) => format!("NVARCHAR({})", length),
+            DataType::Blob => "VARBINARY(MAX)".to_string(),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => "TIME".to_string(),
+            DataType::Timestamp => "DATETIME2".to_string(),
+            DataType::TimestampTz => "DATETIMEOFFSET".to_string(),
+            DataType::Uuid => "UNIQUEIDENTIFIER".to_string(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Output
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        DmlLimitStyle::CommonTableExpression
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::Merge
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        AutoIncrementStyle::Identity
+    }
+
+    fn create_table_if_not_exists(&self, table: &str, definitions: &str) -> String {
+        format!(
+            "IF OBJECT_ID(N'{}', N'U') IS NULL CREATE TABLE {} ({})",
+            table.replace('\'', "''"),
+            table,
+            definitions
+        )
+    }
+
+    fn add_column(&self, table: &str, definition: &str) -> String {
+        format!("ALTER TABLE {} ADD {}", table, definition)
+    }
+
+    fn rename_column(&self, table: &str, from: &str, to: &str) -> String {
+        format!(
+            "EXEC sp_rename N'{}.{}', N'{}', N'COLUMN'",
+            table.replace('\'', "''"),
+            from.replace('\'', "''"),
+            to.replace('\'', "''")
+        )
+    }
+
+    fn alter_column(
+        &self,
+        table: &str,
+        column: &str,
+        type_name: &str,
+        nullable: bool,
+    ) -> Option<Vec<String>> {
+        Some(vec![format!(
+            "ALTER TABLE {} ALTER COLUMN {} {} {}",
+            table,
+            column,
+            type_name,
+            if nullable { "NULL" } else { "NOT NULL" }
+        )])
+    }
+
+    fn drop_index(&self, index: &str, table: &str) -> String {
+        format!("DROP INDEX {} ON {}", index, table)
+    }
+
+    fn max_bind_parameters(&self) -> usize {
+        // A request takes 2100 parameters, two of which sp_executesql uses for
+        // the statement and the declaration of the others
+        2098
+    }
+
+    fn max_insert_rows(&self) -> Option<usize> {
+        // A table value constructor takes at most 1000 rows
+        Some(1000)
+    }
+
+    fn begin_transaction(&self, isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        let mut statements: Vec<String> = isolation_level
+            .map(|level| format!("SET TRANSACTION ISOLATION LEVEL {}", level.as_sql()))
+            .into_iter()
+            .collect();
+        statements.push("BEGIN TRANSACTION".to_string());
+        statements
+    }
+
+    fn commit_transaction(&self) -> String {
+        "COMMIT TRANSACTION".to_string()
+    }
+
+    fn rollback_transaction(&self) -> String {
+        "ROLLBACK TRANSACTION".to_string()
+    }
+
+    fn reset_isolation_level(&self, _isolation_level: IsolationLevel) -> Option<String> {
+        // The level is kept by the session until it is changed again
+        Some("SET TRANSACTION ISOLATION LEVEL READ COMMITTED".to_string())
+    }
+
+    fn savepoint(&self, name: &str) -> Option<String> {
+        Some(format!("SAVE TRANSACTION {}", name))
+    }
+
+    fn rollback_to_savepoint(&self, name: &str) -> String {
+        format!("ROLLBACK TRANSACTION {}", name)
+    }
+
+    fn release_savepoint(&self, _name: &str) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+        UpsertQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder,
+    };
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    #[derive(Table, Clone)]
+    #[table_name = "authors"]
+    #[has_many(Article)]
+    struct Author {
+        id: AutoIncrementPrimaryKey<usize>,
+        name: String,
+    }
+
+    #[derive(Table, Clone)]
+    #[table_name = "articles"]
+    struct Article {
+        id: AutoIncrementPrimaryKey<usize>,
+        author_id: usize,
+        title: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, MssqlDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = @P1 \
+             ORDER BY id DESC OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, MssqlDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(
+            sql,
+            "SELECT * FROM users ORDER BY (SELECT NULL) OFFSET 5 ROWS"
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, MssqlDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .returning(["id"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "MERGE INTO users USING (VALUES (0, @P1, @P2), (1, @P3, @P4)) \
+             AS source (njord_row, username, email) ON 1 = 0 \
+             WHEN NOT MATCHED THEN INSERT (username, email) VALUES (source.username, source.email) \
+             OUTPUT source.njord_row, INSERTED.id;"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn insert_without_returning() {
+        let (sql, _) = InsertQueryBuilder::<User, MssqlDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (@P1, @P2), (@P3, @P4)"
+        );
+    }
+
+    #[test]
+    fn eager_load_within_bind_parameters() {
+        let authors: Vec<Author> = (1..=2099)
+            .map(|id| Author {
+                id: AutoIncrementPrimaryKey::new(Some(id)),
+                name: format!("author{}", id),
+            })
+            .collect();
+
+        let queries =
+            SelectQueryBuilder::<Author, MssqlDialect>::new(vec![Column::Text("*".to_string())])
+                .from(Author::default())
+                .with(Article::default())
+                .related_queries(&authors)
+                .unwrap();
+        let params: Vec<usize> = queries.iter().map(|query| query.render().1.len()).collect();
+
+        assert_eq!(params, vec![2098, 1]);
+    }
+
+    #[test]
+    fn upsert() {
+        let (sql, params) = UpsertQueryBuilder::<User, MssqlDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .on_conflict(["username"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "MERGE INTO users AS target USING (VALUES (@P1, @P2), (@P3, @P4)) \
+             AS source (username, email) ON (target.username = source.username) \
+             WHEN MATCHED THEN UPDATE SET target.email = source.email \
+             WHEN NOT MATCHED THEN INSERT (username, email) \
+             VALUES (source.username, source.email);"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, MssqlDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = @P1 WHERE username = @P2");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, MssqlDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "WITH njord_rows AS (SELECT * FROM users WHERE username = @P1 \
+             ORDER BY id ASC OFFSET 0 ROWS FETCH NEXT 1 ROWS ONLY) DELETE FROM njord_rows"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<MssqlDialect>::from_table(&User::default())
+            .if_not_exists()
+            .render();
+        assert_eq!(
+            create,
+            vec![
+                "IF OBJECT_ID(N'users', N'U') IS NULL CREATE TABLE users \
+                 (id BIGINT IDENTITY(1, 1) NOT NULL, username NVARCHAR(MAX) NOT NULL, \
+                 email NVARCHAR(MAX) NOT NULL, PRIMARY KEY (id))"
+            ]
+        );
+
+        let drop = DropTableBuilder::<MssqlDialect>::from_table(&User::default()).render();
+        assert_eq!(drop, vec!["DROP TABLE users"]);
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<MssqlDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+            .drop_column("age")
+            .render()
+            .unwrap();
+        assert_eq!(
+            alter,
+            vec![
+                "ALTER TABLE users ADD age INT",
+                "EXEC sp_rename N'users.username', N'login', N'COLUMN'",
+                "ALTER TABLE users ALTER COLUMN email NVARCHAR(255) NOT NULL",
+                "ALTER TABLE users DROP COLUMN age",
+            ]
+        );
+
+        let index = CreateIndexBuilder::<MssqlDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique()
+            .where_clause("email IS NOT NULL")
+            .render();
+        assert_eq!(
+            index,
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username) WHERE email IS NOT NULL".to_string())
+        );
+
+        let drop = DropIndexBuilder::<MssqlDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email ON users");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(MssqlDialect.identifier("users"), "users");
+        assert_eq!(MssqlDialect.identifier("order items"), "[order items]");
+        assert_eq!(MssqlDialect.type_name(DataType::Boolean), "BIT");
+        assert_eq!(
+            MssqlDialect.type_name(DataType::Varchar(255)),
+            "NVARCHAR(255)"
+        );
+    }
+
+    #[test]
+    fn transaction() {
+        assert_eq!(
+            MssqlDialect.begin_transaction(Some(IsolationLevel::Snapshot)),
+            vec![
+                "SET TRANSACTION ISOLATION LEVEL SNAPSHOT",
+                "BEGIN TRANSACTION"
+            ]
+        );
+        assert_eq!(
+            MssqlDialect
+                .reset_isolation_level(IsolationLevel::Snapshot)
+                .as_deref(),
+            Some("SET TRANSACTION ISOLATION LEVEL READ COMMITTED")
+        );
+        assert_eq!(
+            MssqlDialect.savepoint("sp").as_deref(),
+            Some("SAVE TRANSACTION sp")
+        );
+        assert_eq!(
+            MssqlDialect.rollback_to_savepoint("sp"),
+            "ROLLBACK TRANSACTION sp"
+        );
+        assert_eq!(MssqlDialect.release_savepoint("sp"), None);
+    }
+}
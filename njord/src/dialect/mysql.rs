@@ -0,0 +1,372 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{
+    standard_isolation_level, AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, ReturningStyle,
+    UpsertStyle,
+};
+
+/// The MySQL dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MySqlDialect;
+
+impl Dialect for MySqlDialect {
+    fn name(&self) -> &'static str {
+        "mysql"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        PlaceholderStyle::QuestionMark
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("`{}`", identifier.replace('`', "``"))
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, _ordered: bool) -> String {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => format!("LIMIT {} OFFSET {}", limit, offset),
+            (Some(limit), None) => format!("LIMIT {}", limit),
+            // MySQL only accepts OFFSET after a LIMIT, this is the documented "no limit"
+            (None, Some(offset)) => format!("LIMIT 18446744073709551615 OFFSET {}", offset),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        match data_type {
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::SmallInt => "SMALLINT".to_string(),
+            DataType::Integer => "INT".to_string(),
+            DataType::BigInt => "BIGINT".to_string(),
+            DataType::Float => "FLOAT".to_string(),
+            DataType::Double => "DOUBLE".to_string(),
+            DataType::Decimal { precision, scale } => format!("DECIMAL({}, {})", precision, scale),
+            DataType::Text => "TEXT".to_string(),
+            DataType::Varchar(length) => format!("VARCHAR({})", length),
+            DataType::Blob => "LONGBLOB".to_string(),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => "TIME".to_string(),
+            DataType::Timestamp => "DATETIME".to_string(),
+            DataType::TimestampTz => "TIMESTAMP".to_string(),
+            DataType::Uuid => "CHAR(36)".to_string(),
+            DataType::Json => "JSON".to_string(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::None
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        DmlLimitStyle::Inline
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnDuplicateKey
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        AutoIncrementStyle::AutoIncrement
+    }
+
+    fn alter_column(
+        &self,
+        table: &str,
+        column: &str,
+        type_name: &str,
+        nullable: bool,
+    ) -> Option<Vec<String>> {
+        Some(vec![format!(
+            "ALTER TABLE {} MODIFY COLUMN {} {} {}",
+            table,
+            column,
+            type_name,
+            if nullable { "NULL" } else { "NOT NULL" }
+        )])
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        false
+    }
+
+    fn drop_index(&self, index: &str, table: &str) -> String {
+        format!("DROP INDEX {} ON {}", index, table)
+    }
+
+    fn max_statement_bytes(&self) -> Option<usize> {
+        // The smallest default of `max_allowed_packet` across MySQL versions
+        Some(4 * 1024 * 1024)
+    }
+
+    fn begin_transaction(&self, isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        // Without SESSION or GLOBAL the level only applies to the next transaction
+        let mut statements: Vec<String> = isolation_level
+            .map(|level| {
+                format!(
+                    "SET TRANSACTION ISOLATION LEVEL {}",
+                    standard_isolation_level(level).as_sql()
+                )
+            })
+            .into_iter()
+            .collect();
+        statements.push("START TRANSACTION".to_string());
+        statements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+        UpsertQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder, SchemaError,
+    };
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, MySqlDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = ? ORDER BY id DESC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, MySqlDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(
+            sql,
+            "SELECT * FROM users LIMIT 18446744073709551615 OFFSET 5"
+        );
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, MySqlDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?), (?, ?)"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn upsert() {
+        let (sql, params) = UpsertQueryBuilder::<User, MySqlDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .on_conflict(["username"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?), (?, ?) \
+             ON DUPLICATE KEY UPDATE email = VALUES(email)"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn upsert_do_nothing() {
+        let (sql, _) = UpsertQueryBuilder::<User, MySqlDialect>::new()
+            .values(vec![user("alice")])
+            .on_conflict(["username"])
+            .do_nothing()
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?) \
+             ON DUPLICATE KEY UPDATE username = username"
+        );
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, MySqlDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = ? WHERE username = ?");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, MySqlDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE username = ? ORDER BY id ASC LIMIT 1"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<MySqlDialect>::from_table(&User::default())
+            .if_not_exists()
+            .render();
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE IF NOT EXISTS users (id BIGINT AUTO_INCREMENT NOT NULL, \
+                 username TEXT NOT NULL, email TEXT NOT NULL, PRIMARY KEY (id))"
+            ]
+        );
+
+        let drop = DropTableBuilder::<MySqlDialect>::from_table(&User::default()).render();
+        assert_eq!(drop, vec!["DROP TABLE users"]);
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<MySqlDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+            .drop_column("age")
+            .render()
+            .unwrap();
+        assert_eq!(
+            alter,
+            vec![
+                "ALTER TABLE users ADD COLUMN age INT",
+                "ALTER TABLE users RENAME COLUMN username TO login",
+                "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL",
+                "ALTER TABLE users DROP COLUMN age",
+            ]
+        );
+
+        let index = CreateIndexBuilder::<MySqlDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique();
+        assert_eq!(
+            index.render(),
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username)".to_string())
+        );
+        assert_eq!(
+            index.where_clause("email IS NOT NULL").render(),
+            Err(SchemaError::Unsupported {
+                dialect: "mysql",
+                operation: "a partial index".to_string()
+            })
+        );
+
+        let drop = DropIndexBuilder::<MySqlDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email ON users");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(MySqlDialect.identifier("users"), "users");
+        assert_eq!(MySqlDialect.identifier("order items"), "`order items`");
+        assert_eq!(MySqlDialect.type_name(DataType::Boolean), "BOOLEAN");
+        assert_eq!(
+            MySqlDialect.type_name(DataType::Varchar(255)),
+            "VARCHAR(255)"
+        );
+    }
+
+    #[test]
+    fn transaction() {
+        assert_eq!(
+            MySqlDialect.begin_transaction(Some(IsolationLevel::Snapshot)),
+            vec![
+                "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+                "START TRANSACTION"
+            ]
+        );
+        assert_eq!(
+            MySqlDialect.begin_transaction(None),
+            vec!["START TRANSACTION"]
+        );
+        assert_eq!(
+            MySqlDialect.release_savepoint("sp").as_deref(),
+            Some("RELEASE SAVEPOINT sp")
+        );
+    }
+}
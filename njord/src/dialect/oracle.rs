@@ -0,0 +1,457 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{
+    offset_fetch, AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, ReturningStyle, UpsertStyle,
+};
+
+/// The Oracle dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OracleDialect;
+
+impl Dialect for OracleDialect {
+    fn name(&self) -> &'static str {
+        "oracle"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        PlaceholderStyle::Colon
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, _ordered: bool) -> String {
+        offset_fetch(limit, offset, false)
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        match data_type {
+            DataType::Boolean => "NUMBER(1)".to_string(),
+            DataType::SmallInt => "NUMBER(5)".to_string(),
+            DataType::Integer => "NUMBER(10)".to_string(),
+            DataType::BigInt => "NUMBER(19)".to_string(),
+            DataType::Float => "BINARY_FLOAT".to_string(),
+            DataType::Double => "BINARY_DOUBLE".to_string(),
+            DataType::Decimal { precision, scale } => format!("NUMBER({}, {})", precision, scale),
+            DataType::Text | DataType::Json => "CLOB".to_string(),
+            DataType::Varchar(length) => format!("VARCHAR2({})", length),
+            DataType::Blob => "BLOB".to_string(),
+            DataType::Date => "DATE".to_string(),
+            // Oracle has no time of day type, times are bound as text
+            DataType::Time => "VARCHAR2(18)".to_string(),
+            DataType::Timestamp => "TIMESTAMP".to_string(),
+            DataType::TimestampTz => "TIMESTAMP WITH TIME ZONE".to_string(),
+            DataType::Uuid => "VARCHAR2(36)".to_string(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::ReturningInto
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        DmlLimitStyle::RowId("ROWID")
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::MergeFromDual
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        AutoIncrementStyle::GeneratedIdentity
+    }
+
+    fn create_table_if_not_exists(&self, table: &str, definitions: &str) -> String {
+        // ORA-00955: name is already used by an existing object
+        ignore_error(&format!("CREATE TABLE {} ({})", table, definitions), -955)
+    }
+
+    fn drop_table_if_exists(&self, table: &str) -> String {
+        // ORA-00942: table or view does not exist
+        ignore_error(&format!("DROP TABLE {}", table), -942)
+    }
+
+    fn add_column(&self, table: &str, definition: &str) -> String {
+        format!("ALTER TABLE {} ADD ({})", table, definition)
+    }
+
+    fn alter_column(
+        &self,
+        table: &str,
+        column: &str,
+        type_name: &str,
+        nullable: bool,
+    ) -> Option<Vec<String>> {
+        // ORA-01442/ORA-01451: the column already is NOT NULL/NULL
+        let (constraint, code) = if nullable {
+            ("NULL", -1451)
+        } else {
+            ("NOT NULL", -1442)
+        };
+
+        Some(vec![
+            format!("ALTER TABLE {} MODIFY ({} {})", table, column, type_name),
+            ignore_error(
+                &format!("ALTER TABLE {} MODIFY ({} {})", table, column, constraint),
+                code,
+            ),
+        ])
+    }
+
+    fn supports_partial_indexes(&self) -> bool {
+        false
+    }
+
+    fn supports_multi_row_insert(&self) -> bool {
+        false
+    }
+
+    fn max_in_list(&self) -> Option<usize> {
+        // ORA-01795: maximum number of expressions in a list is 1000
+        Some(1000)
+    }
+
+    fn begin_transaction(&self, isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        // Transactions start implicitly, SET TRANSACTION has to be their first statement
+        let level = match isolation_level {
+            None => return Vec::new(),
+            Some(IsolationLevel::ReadUncommitted | IsolationLevel::ReadCommitted) => {
+                IsolationLevel::ReadCommitted
+            }
+            Some(_) => IsolationLevel::Serializable,
+        };
+
+        vec![format!(
+            "SET TRANSACTION ISOLATION LEVEL {}",
+            level.as_sql()
+        )]
+    }
+
+    fn release_savepoint(&self, _name: &str) -> Option<String> {
+        None
+    }
+}
+
+/// Wraps a DDL statement into a PL/SQL block ignoring one error code.
+///
+/// # Arguments
+///
+/// * `statement` - The statement to execute.
+/// * `code` - The `SQLCODE` to ignore, e.g. `-942`.
+fn ignore_error(statement: &str, code: i32) -> String {
+    format!(
+        "BEGIN EXECUTE IMMEDIATE '{}'; EXCEPTION WHEN OTHERS THEN IF SQLCODE != {} THEN RAISE; END IF; END;",
+        statement.replace('\'', "''"),
+        code
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+        UpsertQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder, SchemaError,
+    };
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    #[has_many(Post)]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    #[derive(Table, Clone)]
+    #[table_name = "posts"]
+    struct Post {
+        id: AutoIncrementPrimaryKey<usize>,
+        user_id: usize,
+        title: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, OracleDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = :1 \
+             ORDER BY id DESC OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_with_related_rows_in_chunks() {
+        let users: Vec<User> = (1..=2500)
+            .map(|id| {
+                let mut user = user("alice");
+                user.id = AutoIncrementPrimaryKey::new(Some(id));
+                user
+            })
+            .collect();
+
+        let query =
+            SelectQueryBuilder::<User, OracleDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .with(Post::default());
+        let queries = query.related_queries(&users).unwrap();
+
+        // Oracle takes at most 1000 expressions in an IN list
+        let sizes: Vec<usize> = queries.iter().map(|query| query.render().1.len()).collect();
+        assert_eq!(sizes, vec![1000, 1000, 500]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, OracleDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(sql, "SELECT * FROM users OFFSET 5 ROWS");
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, OracleDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT ALL INTO users (username, email) VALUES (:1, :2) \
+             INTO users (username, email) VALUES (:3, :4) SELECT 1 FROM DUAL"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn insert_returning() {
+        let (sql, params) = InsertQueryBuilder::<User, OracleDialect>::new()
+            .values(vec![user("alice")])
+            .returning(["id"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (:1, :2) RETURNING id INTO :3"
+        );
+        assert_eq!(params.len(), 3);
+    }
+
+    #[test]
+    fn upsert() {
+        let (sql, params) = UpsertQueryBuilder::<User, OracleDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .on_conflict(["username"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "MERGE INTO users target USING (SELECT :1 AS username, :2 AS email FROM DUAL \
+             UNION ALL SELECT :3 AS username, :4 AS email FROM DUAL) source \
+             ON (target.username = source.username) \
+             WHEN MATCHED THEN UPDATE SET target.email = source.email \
+             WHEN NOT MATCHED THEN INSERT (username, email) \
+             VALUES (source.username, source.email)"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn upsert_do_nothing() {
+        let (sql, _) = UpsertQueryBuilder::<User, OracleDialect>::new()
+            .values(vec![user("alice")])
+            .on_conflict(["username"])
+            .do_nothing()
+            .render();
+
+        assert_eq!(
+            sql,
+            "MERGE INTO users target USING (SELECT :1 AS username, :2 AS email FROM DUAL) source \
+             ON (target.username = source.username) \
+             WHEN NOT MATCHED THEN INSERT (username, email) \
+             VALUES (source.username, source.email)"
+        );
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, OracleDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = :1 WHERE username = :2");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, OracleDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE ROWID IN \
+             (SELECT ROWID FROM users WHERE username = :1 ORDER BY id ASC FETCH FIRST 1 ROWS ONLY)"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<OracleDialect>::from_table(&User::default()).render();
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE users (id NUMBER(19) GENERATED BY DEFAULT AS IDENTITY NOT NULL, \
+                 username CLOB NOT NULL, email CLOB NOT NULL, PRIMARY KEY (id))"
+            ]
+        );
+
+        let drop = DropTableBuilder::<OracleDialect>::from_table(&User::default())
+            .if_exists()
+            .render();
+        assert_eq!(
+            drop,
+            vec![
+                "BEGIN EXECUTE IMMEDIATE 'DROP TABLE users'; \
+                 EXCEPTION WHEN OTHERS THEN IF SQLCODE != -942 THEN RAISE; END IF; END;"
+            ]
+        );
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<OracleDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+            .drop_column("age")
+            .render()
+            .unwrap();
+        assert_eq!(
+            alter,
+            vec![
+                "ALTER TABLE users ADD (age NUMBER(10))",
+                "ALTER TABLE users RENAME COLUMN username TO login",
+                "ALTER TABLE users MODIFY (email VARCHAR2(255))",
+                "BEGIN EXECUTE IMMEDIATE 'ALTER TABLE users MODIFY (email NOT NULL)'; EXCEPTION WHEN OTHERS THEN IF SQLCODE != -1442 THEN RAISE; END IF; END;",
+                "ALTER TABLE users DROP COLUMN age",
+            ]
+        );
+
+        let index = CreateIndexBuilder::<OracleDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique();
+        assert_eq!(
+            index.render(),
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username)".to_string())
+        );
+        assert_eq!(
+            index.where_clause("email IS NOT NULL").render(),
+            Err(SchemaError::Unsupported {
+                dialect: "oracle",
+                operation: "a partial index".to_string()
+            })
+        );
+
+        let drop = DropIndexBuilder::<OracleDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(OracleDialect.identifier("users"), "users");
+        assert_eq!(OracleDialect.identifier("order items"), "\"order items\"");
+        assert_eq!(OracleDialect.type_name(DataType::Boolean), "NUMBER(1)");
+        assert_eq!(
+            OracleDialect.type_name(DataType::Varchar(255)),
+            "VARCHAR2(255)"
+        );
+    }
+
+    #[test]
+    fn transaction() {
+        assert!(OracleDialect.begin_transaction(None).is_empty());
+        assert_eq!(
+            OracleDialect.begin_transaction(Some(IsolationLevel::RepeatableRead)),
+            vec!["SET TRANSACTION ISOLATION LEVEL SERIALIZABLE"]
+        );
+        assert_eq!(
+            OracleDialect.begin_transaction(Some(IsolationLevel::ReadUncommitted)),
+            vec!["SET TRANSACTION ISOLATION LEVEL READ COMMITTED"]
+        );
+        assert_eq!(OracleDialect.release_savepoint("sp"), None);
+    }
+}
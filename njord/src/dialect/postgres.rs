@@ -0,0 +1,306 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::query::PlaceholderStyle;
+
+use super::{AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, ReturningStyle, UpsertStyle};
+
+/// The PostgreSQL dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PostgresDialect;
+
+impl Dialect for PostgresDialect {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        PlaceholderStyle::Dollar
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, _ordered: bool) -> String {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => format!("LIMIT {} OFFSET {}", limit, offset),
+            (Some(limit), None) => format!("LIMIT {}", limit),
+            (None, Some(offset)) => format!("OFFSET {}", offset),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        match data_type {
+            DataType::Boolean => "BOOLEAN".to_string(),
+            DataType::SmallInt => "SMALLINT".to_string(),
+            DataType::Integer => "INTEGER".to_string(),
+            DataType::BigInt => "BIGINT".to_string(),
+            DataType::Float => "REAL".to_string(),
+            DataType::Double => "DOUBLE PRECISION".to_string(),
+            DataType::Decimal { precision, scale } => {
+                format!("NUMERIC({}, {})", precision, scale)
+            }
+            DataType::Text => "TEXT".to_string(),
+            DataType::Varchar(length) => format!("VARCHAR({})", length),
+            DataType::Blob => "BYTEA".to_string(),
+            DataType::Date => "DATE".to_string(),
+            DataType::Time => "TIME".to_string(),
+            DataType::Timestamp => "TIMESTAMP".to_string(),
+            DataType::TimestampTz => "TIMESTAMPTZ".to_string(),
+            DataType::Uuid => "UUID".to_string(),
+            DataType::Json => "JSONB".to_string(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Returning
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        // UPDATE and DELETE have no LIMIT, rows are picked by their physical location
+        DmlLimitStyle::RowId("ctid")
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnConflict
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        AutoIncrementStyle::GeneratedIdentity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+        UpsertQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder,
+    };
+    use crate::table::Table;
+    use crate::transaction::IsolationLevel;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, PostgresDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = $1 ORDER BY id DESC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, PostgresDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(sql, "SELECT * FROM users OFFSET 5");
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, PostgresDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .returning(["id"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES ($1, $2), ($3, $4) RETURNING id"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn upsert() {
+        let (sql, params) = UpsertQueryBuilder::<User, PostgresDialect>::new()
+            .values(vec![user("alice")])
+            .on_conflict(["username"])
+            .update(["email"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES ($1, $2) \
+             ON CONFLICT (username) DO UPDATE SET email = excluded.email"
+        );
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, PostgresDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = $1 WHERE username = $2");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, PostgresDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE ctid IN \
+             (SELECT ctid FROM users WHERE username = $1 ORDER BY id ASC LIMIT 1)"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<PostgresDialect>::from_table(&User::default()).render();
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE users (id BIGINT GENERATED BY DEFAULT AS IDENTITY NOT NULL, \
+                 username TEXT NOT NULL, email TEXT NOT NULL, PRIMARY KEY (id))"
+            ]
+        );
+
+        let drop = DropTableBuilder::<PostgresDialect>::from_table(&User::default())
+            .if_exists()
+            .render();
+        assert_eq!(drop, vec!["DROP TABLE IF EXISTS users"]);
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<PostgresDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+            .drop_column("age")
+            .render()
+            .unwrap();
+        assert_eq!(
+            alter,
+            vec![
+                "ALTER TABLE users ADD COLUMN age INTEGER",
+                "ALTER TABLE users RENAME COLUMN username TO login",
+                "ALTER TABLE users ALTER COLUMN email TYPE VARCHAR(255)",
+                "ALTER TABLE users ALTER COLUMN email SET NOT NULL",
+                "ALTER TABLE users DROP COLUMN age",
+            ]
+        );
+
+        let index = CreateIndexBuilder::<PostgresDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique()
+            .where_clause("email IS NOT NULL")
+            .render();
+        assert_eq!(
+            index,
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username) WHERE email IS NOT NULL".to_string())
+        );
+
+        let drop = DropIndexBuilder::<PostgresDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(PostgresDialect.identifier("users"), "users");
+        assert_eq!(PostgresDialect.identifier("order items"), "\"order items\"");
+        assert_eq!(PostgresDialect.type_name(DataType::Boolean), "BOOLEAN");
+        assert_eq!(
+            PostgresDialect.type_name(DataType::Decimal {
+                precision: 10,
+                scale: 2
+            }),
+            "NUMERIC(10, 2)"
+        );
+        assert_eq!(PostgresDialect.type_name(DataType::Blob), "BYTEA");
+    }
+
+    #[test]
+    fn transaction() {
+        assert_eq!(
+            PostgresDialect.begin_transaction(Some(IsolationLevel::RepeatableRead)),
+            vec!["START TRANSACTION ISOLATION LEVEL REPEATABLE READ"]
+        );
+        assert_eq!(
+            PostgresDialect.rollback_to_savepoint("sp"),
+            "ROLLBACK TO SAVEPOINT sp"
+        );
+    }
+}
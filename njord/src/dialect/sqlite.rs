@@ -0,0 +1,524 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{query::PlaceholderStyle, transaction::IsolationLevel};
+
+use super::{AutoIncrementStyle, DataType, Dialect, DmlLimitStyle, ReturningStyle, UpsertStyle};
+
+/// The SQLite dialect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SqliteDialect;
+
+impl Dialect for SqliteDialect {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        PlaceholderStyle::QuestionMark
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn pagination(&self, limit: Option<usize>, offset: Option<usize>, _ordered: bool) -> String {
+        match (limit, offset) {
+            (Some(limit), Some(offset)) => format!("LIMIT {} OFFSET {}", limit, offset),
+            (Some(limit), None) => format!("LIMIT {}", limit),
+            // SQLite only accepts OFFSET after a LIMIT, a negative one means no limit
+            (None, Some(offset)) => format!("LIMIT -1 OFFSET {}", offset),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn type_name(&self, data_type: DataType) -> String {
+        match data_type {
+            DataType::Boolean | DataType::SmallInt | DataType::Integer | DataType::BigInt => {
+                "INTEGER".to_string()
+            }
+            DataType::Float | DataType::Double => "REAL".to_string(),
+            DataType::Decimal { .. } => "NUMERIC".to_string(),
+            DataType::Blob => "BLOB".to_string(),
+            DataType::Text
+            | DataType::Varchar(_)
+            | DataType::Date
+            | DataType::Time
+            | DataType::Timestamp
+            | DataType::TimestampTz
+            | DataType::Uuid
+            | DataType::Json => "TEXT".to_string(),
+        }
+    }
+
+    fn returning_style(&self) -> ReturningStyle {
+        ReturningStyle::Returning
+    }
+
+    fn dml_limit_style(&self) -> DmlLimitStyle {
+        DmlLimitStyle::RowId("rowid")
+    }
+
+    fn upsert_style(&self) -> UpsertStyle {
+        UpsertStyle::OnConflict
+    }
+
+    fn auto_increment_style(&self) -> AutoIncrementStyle {
+        AutoIncrementStyle::Autoincrement
+    }
+
+    fn alter_column(
+        &self,
+        _table: &str,
+        _column: &str,
+        _type_name: &str,
+        _nullable: bool,
+    ) -> Option<Vec<String>> {
+        // Only a rebuild of the whole table changes a column
+        None
+    }
+
+    fn supports_alter_constraints(&self) -> bool {
+        false
+    }
+
+    fn max_bind_parameters(&self) -> usize {
+        // SQLITE_MAX_VARIABLE_NUMBER of SQLite 3.32 and later
+        32766
+    }
+
+    fn begin_transaction(&self, _isolation_level: Option<IsolationLevel>) -> Vec<String> {
+        // SQLite transactions are always serializable
+        vec!["BEGIN".to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{
+        BuildError, DeleteQueryBuilder, InsertQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
+        UpsertQueryBuilder,
+    };
+    use crate::column::Column;
+    use crate::keys::AutoIncrementPrimaryKey;
+    use crate::query::Param;
+    use crate::row::DecodeError;
+    use crate::schema::{
+        AlterTableBuilder, ColumnDefinition, CreateIndexBuilder, CreateTableBuilder,
+        DropIndexBuilder, DropTableBuilder, SchemaError,
+    };
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    #[derive(Table, Clone)]
+    #[table_name = "users"]
+    #[has_many(Post)]
+    struct User {
+        id: AutoIncrementPrimaryKey<usize>,
+        username: String,
+        email: String,
+    }
+
+    #[derive(Table, Clone)]
+    #[table_name = "posts"]
+    struct Post {
+        id: AutoIncrementPrimaryKey<usize>,
+        user_id: usize,
+        title: String,
+    }
+
+    #[derive(Table, Clone)]
+    #[table_name = "memberships"]
+    struct Membership {
+        #[primary_key]
+        user_id: i64,
+        #[primary_key]
+        group_id: i64,
+        role: String,
+    }
+
+    fn user(username: &str) -> User {
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+        }
+    }
+
+    #[test]
+    fn select() {
+        let (sql, params) = SelectQueryBuilder::<User, SqliteDialect>::new(vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+        ])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .order_by([User::ID.desc()])
+        .limit(10)
+        .offset(20)
+        .render();
+
+        assert_eq!(
+            sql,
+            "SELECT id, username FROM users WHERE username = ? ORDER BY id DESC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(params.values(), &[Param::Text("mjovanc".to_string())]);
+    }
+
+    #[test]
+    fn select_by_key() {
+        let (sql, params) =
+            SelectQueryBuilder::<Membership, SqliteDialect>::new(vec![Column::Text(
+                "role".to_string(),
+            )])
+            .from(Membership::default())
+            .find((7, 3))
+            .unwrap()
+            .render();
+
+        assert_eq!(
+            sql,
+            "SELECT role FROM memberships WHERE (user_id = ?) AND (group_id = ?)"
+        );
+        assert_eq!(params.values(), &[Param::Int(7), Param::Int(3)]);
+
+        let (sql, _) =
+            SelectQueryBuilder::<User, SqliteDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .find(1usize)
+                .unwrap()
+                .render();
+
+        assert_eq!(sql, "SELECT * FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn select_with_related_rows() {
+        let mut alice = user("alice");
+        alice.id = AutoIncrementPrimaryKey::new(Some(1));
+        let mut bob = user("bob");
+        bob.id = AutoIncrementPrimaryKey::new(Some(2));
+
+        let query =
+            SelectQueryBuilder::<User, SqliteDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .with(Post::default());
+        let queries = query
+            .related_queries(&[alice, bob, user("carol")])
+            .unwrap();
+
+        assert_eq!(queries.len(), 1);
+        let (sql, params) = queries[0].render();
+        assert_eq!(
+            sql,
+            "SELECT posts.user_id, posts.id, posts.title, posts.user_id FROM posts \
+             WHERE posts.user_id IN (?, ?)"
+        );
+        assert_eq!(params.values(), &[Param::Int(1), Param::Int(2)]);
+    }
+
+    #[test]
+    fn group_related_rows_by_typed_key() {
+        let mut alice = user("alice");
+        alice.id = AutoIncrementPrimaryKey::new(Some(1));
+
+        let post = |title: &str| Post {
+            id: AutoIncrementPrimaryKey::default(),
+            user_id: 1,
+            title: title.to_string(),
+        };
+
+        let query =
+            SelectQueryBuilder::<User, SqliteDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .with(Post::default());
+        let grouped = query.group(
+            vec![alice],
+            vec![
+                (Param::Int(1), post("matching")),
+                (Param::Text("1".to_string()), post("text key")),
+            ],
+        );
+
+        let titles: Vec<&str> = grouped[0]
+            .1
+            .iter()
+            .map(|post| post.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["matching"]);
+    }
+
+    #[test]
+    fn select_offset_without_limit() {
+        let (sql, _) =
+            SelectQueryBuilder::<User, SqliteDialect>::new(vec![Column::Text("*".to_string())])
+                .from(User::default())
+                .offset(5)
+                .render();
+
+        assert_eq!(sql, "SELECT * FROM users LIMIT -1 OFFSET 5");
+    }
+
+    #[test]
+    fn insert() {
+        let (sql, params) = InsertQueryBuilder::<User, SqliteDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .returning(["id"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?), (?, ?) RETURNING id"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn insert_with_missing_key() {
+        let result = InsertQueryBuilder::<User, SqliteDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .into_rows_with_keys([Param::Int(1)]);
+
+        assert!(matches!(
+            result,
+            Err(DecodeError::KeyCount {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn upsert() {
+        let (sql, params) = UpsertQueryBuilder::<User, SqliteDialect>::new()
+            .values(vec![user("alice"), user("bob")])
+            .on_conflict(["username"])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?), (?, ?) \
+             ON CONFLICT (username) DO UPDATE SET email = excluded.email"
+        );
+        assert_eq!(params.len(), 4);
+    }
+
+    #[test]
+    fn upsert_on_composite_key() {
+        let membership = Membership {
+            user_id: 7,
+            group_id: 3,
+            role: "admin".to_string(),
+        };
+        let (sql, _) = UpsertQueryBuilder::<Membership, SqliteDialect>::new()
+            .values(vec![membership])
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO memberships (user_id, group_id, role) VALUES (?, ?, ?) \
+             ON CONFLICT (user_id, group_id) DO UPDATE SET role = excluded.role"
+        );
+    }
+
+    #[test]
+    fn upsert_do_nothing() {
+        let (sql, _) = UpsertQueryBuilder::<User, SqliteDialect>::new()
+            .values(vec![user("alice")])
+            .on_conflict(["username"])
+            .do_nothing()
+            .render();
+
+        assert_eq!(
+            sql,
+            "INSERT INTO users (username, email) VALUES (?, ?) \
+             ON CONFLICT (username) DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn update() {
+        let (sql, params) = UpdateQueryBuilder::<User, SqliteDialect>::new(user("alice"))
+            .set([User::EMAIL])
+            .where_clause(User::USERNAME.eq("alice"))
+            .render();
+
+        assert_eq!(sql, "UPDATE users SET email = ? WHERE username = ?");
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("alice@example.com".to_string()),
+                Param::Text("alice".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn update_and_delete_by_key() {
+        let membership = Membership {
+            user_id: 7,
+            group_id: 3,
+            role: "owner".to_string(),
+        };
+
+        let (sql, params) =
+            UpdateQueryBuilder::<Membership, SqliteDialect>::new(membership.clone())
+                .set([Membership::ROLE])
+                .by_key()
+                .unwrap()
+                .render();
+
+        assert_eq!(
+            sql,
+            "UPDATE memberships SET role = ? WHERE (user_id = ?) AND (group_id = ?)"
+        );
+        assert_eq!(
+            params.values(),
+            &[
+                Param::Text("owner".to_string()),
+                Param::Int(7),
+                Param::Int(3)
+            ]
+        );
+
+        let (sql, _) = DeleteQueryBuilder::<Membership, SqliteDialect>::new()
+            .from(membership)
+            .by_key()
+            .unwrap()
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM memberships WHERE (user_id = ?) AND (group_id = ?)"
+        );
+    }
+
+    #[test]
+    fn delete_by_key_without_table() {
+        let result = DeleteQueryBuilder::<Membership, SqliteDialect>::new().by_key();
+
+        assert!(matches!(result, Err(BuildError::MissingTable)));
+    }
+
+    #[test]
+    fn delete_with_limit() {
+        let (sql, _) = DeleteQueryBuilder::<User, SqliteDialect>::new()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("alice"))
+            .order_by([User::ID.asc()])
+            .limit(1)
+            .render();
+
+        assert_eq!(
+            sql,
+            "DELETE FROM users WHERE rowid IN \
+             (SELECT rowid FROM users WHERE username = ? ORDER BY id ASC LIMIT 1)"
+        );
+    }
+
+    #[test]
+    fn create_and_drop_table() {
+        let create = CreateTableBuilder::<SqliteDialect>::from_table(&User::default()).render();
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, \
+                 username TEXT NOT NULL, email TEXT NOT NULL)"
+            ]
+        );
+
+        let drop = DropTableBuilder::<SqliteDialect>::from_table(&User::default())
+            .if_exists()
+            .render();
+        assert_eq!(drop, vec!["DROP TABLE IF EXISTS users"]);
+    }
+
+    #[test]
+    fn alter_table_and_indexes() {
+        let alter = AlterTableBuilder::<SqliteDialect>::new("users")
+            .add_column(ColumnDefinition::new("age", DataType::Integer).nullable())
+            .rename_column("username", "login")
+            .drop_column("age");
+        assert_eq!(
+            alter.render(),
+            Ok(vec![
+                "ALTER TABLE users ADD COLUMN age INTEGER".to_string(),
+                "ALTER TABLE users RENAME COLUMN username TO login".to_string(),
+                "ALTER TABLE users DROP COLUMN age".to_string(),
+            ])
+        );
+        assert_eq!(
+            alter
+                .alter_column(ColumnDefinition::new("email", DataType::Varchar(255)))
+                .render(),
+            Err(SchemaError::Unsupported {
+                dialect: "sqlite",
+                operation: "changing a column".to_string()
+            })
+        );
+        assert!(AlterTableBuilder::<SqliteDialect>::new("users")
+            .add_check("email_not_empty", "email <> ''")
+            .render()
+            .is_err());
+
+        let index = CreateIndexBuilder::<SqliteDialect>::new("users_email", "users")
+            .columns(["email", "username"])
+            .unique()
+            .where_clause("email IS NOT NULL")
+            .render();
+        assert_eq!(
+            index,
+            Ok("CREATE UNIQUE INDEX users_email ON users (email, username) WHERE email IS NOT NULL".to_string())
+        );
+
+        let drop = DropIndexBuilder::<SqliteDialect>::new("users_email", "users").render();
+        assert_eq!(drop, "DROP INDEX users_email");
+    }
+
+    #[test]
+    fn identifiers_and_types() {
+        assert_eq!(SqliteDialect.identifier("users"), "users");
+        assert_eq!(SqliteDialect.identifier("order items"), "\"order items\"");
+        assert_eq!(SqliteDialect.type_name(DataType::Boolean), "INTEGER");
+        assert_eq!(SqliteDialect.type_name(DataType::Varchar(255)), "TEXT");
+    }
+
+    #[test]
+    fn transaction() {
+        assert_eq!(
+            SqliteDialect.begin_transaction(Some(IsolationLevel::ReadCommitted)),
+            vec!["BEGIN"]
+        );
+        assert_eq!(
+            SqliteDialect.savepoint("sp").as_deref(),
+            Some("SAVEPOINT sp")
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{builder, dialect::DuckDbDialect, table::Table};
+
+use duckdb::{params_from_iter, Connection};
+use log::debug;
+
+use super::{transaction, DuckDbError};
+
+/// Constructs a new DELETE query builder.
+///
+/// # Returns
+///
+/// A `DeleteQueryBuilder` instance.
+pub fn delete<T: Table + Default>() -> DeleteQueryBuilder<'static, T> {
+    DeleteQueryBuilder::new()
+}
+
+/// A builder for constructing DELETE queries on DuckDB.
+pub type DeleteQueryBuilder<'a, T> = builder::DeleteQueryBuilder<'a, T, DuckDbDialect>;
+
+impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
+    /// Builds and executes the DELETE query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of deleted rows if successful,
+    /// or a `DuckDbError` if the deletion fails or `expect_rows` is not met.
+    pub fn build(self, conn: &Connection) -> Result<u64, DuckDbError> {
+        let (query, params) = self.render();
+
+        debug!("{}", query);
+
+        transaction::expect_rows(conn, self.expected_rows(), |conn| {
+            conn.execute(&query, params_from_iter(params.values()))
+                .map(|rows| rows as u64)
+                .map_err(DuckDbError::DeleteError)
+        })
+    }
+}
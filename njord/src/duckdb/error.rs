@@ -0,0 +1,76 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use duckdb::Error as DuckDbLibError;
+
+use crate::{builder::BuildError, row::DecodeError};
+
+/// Represents errors that can occur during DuckDB operations.
+#[derive(Debug)]
+pub enum DuckDbError {
+    /// Error that occurs during a SELECT operation.
+    SelectError(DuckDbLibError),
+    /// Error that occurs during an INSERT operation.
+    InsertError(DuckDbLibError),
+    /// Error that occurs during an UPDATE operation.
+    UpdateError(DuckDbLibError),
+    /// Error that occurs during a DELETE operation.
+    DeleteError(DuckDbLibError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(DuckDbLibError),
+    /// A transaction was nested in another one, DuckDB has no savepoints to do so.
+    NestedTransactionError,
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
+}
+
+impl From<DuckDbLibError> for DuckDbError {
+    /// Converts a `duckdb::Error` into a `DuckDbError`.
+    fn from(error: DuckDbLibError) -> Self {
+        DuckDbError::InsertError(error)
+    }
+}
+
+impl From<DecodeError> for DuckDbError {
+    /// Converts a `DecodeError` into a `DuckDbError`.
+    fn from(error: DecodeError) -> Self {
+        DuckDbError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for DuckDbError {
+    /// Converts a `BuildError` into a `DuckDbError`.
+    fn from(error: BuildError) -> Self {
+        DuckDbError::BuildError(error)
+    }
+}
@@ -0,0 +1,195 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder::{self, BulkInsertProgress},
+    dialect::DuckDbDialect,
+    query::{Param, QueryBuilder},
+    table::Table,
+};
+
+use duckdb::{params_from_iter, Connection};
+use log::{debug, info};
+
+use super::{transaction, DuckDbError};
+
+/// Inserts rows into a DuckDB table.
+///
+/// This function takes a `Connection` and a vector of objects implementing
+/// the `Table` trait, which represents rows to be inserted into the table.
+///
+/// If the rows have an `AutoIncrementPrimaryKey` left to the database, they are
+/// inserted one at a time, in a transaction when none is open, and each generated
+/// key is read back with `RETURNING`, since the rows of a multi-row INSERT
+/// statement are returned in any order. Otherwise all rows are inserted with a
+/// single statement.
+///
+/// # Arguments
+///
+/// * `conn` - A `Connection` to the DuckDB database.
+/// * `table_rows` - A vector of objects implementing the `Table` trait representing
+///   the rows to be inserted into the database.
+///
+/// # Returns
+///
+/// A `Result` containing the inserted rows with their generated keys set
+/// if the insertion is successful, or a `DuckDbError` if an error occurs.
+pub fn insert<T: Table>(conn: &Connection, table_rows: Vec<T>) -> Result<Vec<T>, DuckDbError> {
+    if table_rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let builder = builder::InsertQueryBuilder::<T, DuckDbDialect>::new().values(table_rows);
+    builder.validate()?;
+
+    let Some(key) = builder.generated_key() else {
+        let (sql, params) = builder.render();
+        debug!("{}", sql);
+
+        conn.execute(&sql, params_from_iter(params.values()))
+            .map_err(DuckDbError::InsertError)?;
+
+        info!("Inserted into table, done.");
+
+        return Ok(builder.into_rows());
+    };
+
+    // A failing row undoes the ones before it, without ending an open transaction
+    transaction::guarded(conn, |conn| {
+        let mut rows = Vec::new();
+
+        for row in builder.into_rows() {
+            let row_builder = builder::InsertQueryBuilder::<T, DuckDbDialect>::new()
+                .values(vec![row])
+                .returning([key.as_str()]);
+            let (sql, params) = row_builder.render();
+            debug!("{}", sql);
+
+            let key = conn
+                .query_row(&sql, params_from_iter(params.values()), |row| {
+                    row.get::<_, Param>(0)
+                })
+                .map_err(DuckDbError::InsertError)?;
+            rows.extend(row_builder.into_rows_with_keys([key])?);
+        }
+
+        info!("Inserted into table, done.");
+
+        Ok(rows)
+    })
+}
+
+/// Inserts the rows selected by a subquery into a table.
+///
+/// # Arguments
+///
+/// * `conn` - A `Connection` to the DuckDB database.
+/// * `columns` - The columns the subquery's columns are inserted into.
+/// * `subquery` - The query selecting the rows to insert.
+///
+/// # Returns
+///
+/// A `Result` containing the number of inserted rows if successful,
+/// or a `DuckDbError` if an error occurs.
+pub fn into<'a, T: Table + Default>(
+    conn: &'a Connection,
+    columns: Vec<String>,
+    subquery: Box<dyn QueryBuilder<'a> + 'a>,
+) -> Result<u64, DuckDbError> {
+    let (sql, params) = builder::InsertQueryBuilder::<T, DuckDbDialect>::new()
+        .select(columns, subquery)
+        .render();
+
+    debug!("{}", sql);
+
+    let rows = conn
+        .execute(&sql, params_from_iter(params.values()))
+        .map_err(DuckDbError::InsertError)? as u64;
+
+    info!("Inserted into table, done.");
+
+    Ok(rows)
+}
+
+/// Constructs a new bulk insert builder.
+///
+/// # Arguments
+///
+/// * `rows` - The table rows to insert, e.g. an iterator reading them from a file.
+///
+/// # Returns
+///
+/// A `BulkInsertBuilder` instance.
+pub fn bulk_insert<'a, R>(rows: R) -> BulkInsertBuilder<'a, R::IntoIter>
+where
+    R: IntoIterator,
+    R::Item: Table,
+{
+    BulkInsertBuilder::new(rows)
+}
+
+/// A builder for inserting a large number of rows into DuckDB.
+pub type BulkInsertBuilder<'a, I> = builder::BulkInsertBuilder<'a, I, DuckDbDialect>;
+
+impl<'a, T: Table, I: Iterator<Item = T>> BulkInsertBuilder<'a, I> {
+    /// Builds and executes the INSERT statements of all chunks in one transaction,
+    /// or in the transaction that is already open. DuckDB has no savepoints, so
+    /// the rows of a failing bulk insert are only undone with that transaction then.
+    ///
+    /// Chunks with the same number of rows share one cached prepared statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `Connection` to the DuckDB database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the final progress, with the total number of rows
+    /// inserted, or a `DuckDbError` if an error occurs. Nothing is inserted then.
+    pub fn build(mut self, conn: &Connection) -> Result<BulkInsertProgress, DuckDbError> {
+        transaction::guarded(conn, |conn| {
+            while let Some(chunk) = self.next_chunk() {
+                let (sql, params) = chunk.render();
+
+                debug!("{}", sql);
+
+                let rows = conn
+                    .prepare_cached(&sql)
+                    .and_then(|mut stmt| stmt.execute(params_from_iter(params.values())))
+                    .map_err(DuckDbError::InsertError)?;
+
+                self.record_chunk(rows);
+            }
+
+            info!("Inserted into table, done.");
+
+            Ok(self.progress())
+        })
+    }
+}
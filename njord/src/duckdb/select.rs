@@ -0,0 +1,271 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder,
+    column::Column,
+    dialect::DuckDbDialect,
+    query::{Param, PlaceholderStyle, QueryParams},
+    relation::Related,
+    row::{FromRow, Row as ResultRow},
+    table::Table,
+};
+use std::marker::PhantomData;
+
+use duckdb::{params_from_iter, Connection, Row, Rows, Statement};
+use ouroboros::self_referencing;
+
+use super::DuckDbError;
+
+/// Constructs a new SELECT query builder.
+///
+/// # Arguments
+///
+/// * `columns` - A vector of strings representing the columns to be selected.
+///
+/// # Returns
+///
+/// A `SelectQueryBuilder` instance.
+pub fn select<'a, T: Table + Default>(columns: Vec<Column<'a>>) -> SelectQueryBuilder<'a, T> {
+    SelectQueryBuilder::new(columns)
+}
+
+/// A builder for constructing SELECT queries on DuckDB.
+pub type SelectQueryBuilder<'a, T> = builder::SelectQueryBuilder<'a, T, DuckDbDialect>;
+
+impl<'a, T: Table + FromRow + Default> SelectQueryBuilder<'a, T> {
+    /// Builds and executes the SELECT query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of selected table rows if successful,
+    /// or a `DuckDbError` if the query fails or a row cannot be decoded.
+    pub fn build(self, conn: &Connection) -> Result<Vec<T>, DuckDbError> {
+        self.build_as(conn)
+    }
+
+    /// Builds and executes the SELECT query, decoding the rows one at a time.
+    ///
+    /// Unlike `build`, the rows are not collected; DuckDB hands them over in
+    /// chunks, which are decoded as the iterator reaches them.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing an iterator over the selected table rows if successful,
+    /// or a `DuckDbError` if the query could not be executed.
+    pub fn build_iter(self, conn: &Connection) -> Result<SelectIter<'_, T>, DuckDbError> {
+        let (final_query, params) = self.render();
+
+        let cursor = CursorTryBuilder {
+            statement: conn
+                .prepare(&final_query)
+                .map_err(DuckDbError::SelectError)?,
+            rows_builder: |statement: &mut Statement<'_>| {
+                statement.query(params_from_iter(params.values()))
+            },
+        }
+        .try_build()
+        .map_err(DuckDbError::SelectError)?;
+        let columns = cursor.with_rows(column_names);
+
+        Ok(SelectIter {
+            cursor,
+            columns,
+            marker: PhantomData,
+        })
+    }
+
+    /// Builds and executes the SELECT query, decoding the rows into `R` instead of `T`.
+    ///
+    /// `R` is decoded by column name or alias, so it can be a struct deriving `FromRow`
+    /// with only some of the columns, a tuple of single values, or a tuple of structs
+    /// for the tables of a join, e.g. `(User, Option<Address>)` for a LEFT JOIN.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of the decoded rows if successful,
+    /// or a `DuckDbError` if the query fails or a row cannot be decoded.
+    pub fn build_as<R: FromRow>(self, conn: &Connection) -> Result<Vec<R>, DuckDbError> {
+        let (final_query, params) = self.render();
+
+        execute_query(&final_query, &params, conn)
+    }
+}
+
+/// A builder for SELECT queries on DuckDB loading related rows, created by `with`.
+pub type EagerQueryBuilder<'a, T, R> = builder::EagerQueryBuilder<'a, T, R, DuckDbDialect>;
+
+impl<T, R> EagerQueryBuilder<'_, T, R>
+where
+    T: Related<R> + FromRow + Default,
+    R: Table + FromRow + Default + Clone,
+{
+    /// Builds and executes the SELECT query, then loads the related rows with one
+    /// batched query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the selected table rows, each paired with its related rows,
+    /// or a `DuckDbError` if a query fails or a row cannot be decoded.
+    pub fn build(self, conn: &Connection) -> Result<Vec<(T, Vec<R>)>, DuckDbError> {
+        let (final_query, params) = self.select().render();
+        let parents: Vec<T> = execute_query(&final_query, &params, conn)?;
+
+        let mut related = Vec::new();
+        for query in self.related_queries(&parents)? {
+            let (final_query, params) = query.render();
+            related.extend(execute_query(&final_query, &params, conn)?);
+        }
+
+        Ok(self.group(parents, related))
+    }
+}
+
+/// An iterator over the rows of a SELECT query, created by `build_iter`.
+///
+/// The iterator owns the prepared statement, so it may outlive the query builder
+/// but not the connection.
+pub struct SelectIter<'conn, T> {
+    cursor: Cursor<'conn>,
+    columns: Vec<String>,
+    marker: PhantomData<T>,
+}
+
+impl<T: FromRow> Iterator for SelectIter<'_, T> {
+    type Item = Result<T, DuckDbError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor
+            .with_rows_mut(|rows| rows.next().transpose())
+            .map(|row| {
+                row.map_err(DuckDbError::SelectError)
+                    .and_then(|row| decode_row(row, &self.columns))
+            })
+    }
+}
+
+/// A prepared statement together with the rows it is reading.
+#[self_referencing]
+struct Cursor<'conn> {
+    statement: Statement<'conn>,
+    #[borrows(mut statement)]
+    #[covariant]
+    rows: Rows<'this>,
+}
+
+/// Executes a raw SQL query and returns the decoded rows.
+///
+/// # Arguments
+///
+/// * `sql` - The SQL query to execute.
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `DuckDbError` if the query fails or a row cannot be decoded.
+pub fn raw_execute<R: FromRow>(sql: &str, conn: &Connection) -> Result<Vec<R>, DuckDbError> {
+    execute_query(sql, &QueryParams::new(PlaceholderStyle::QuestionMark), conn)
+}
+
+/// Executes a SQL query with its bound parameters and returns the decoded rows.
+///
+/// Columns are matched to the fields of `R` by name, so the query may select
+/// them in any order.
+///
+/// # Arguments
+///
+/// * `sql` - The SQL query to execute.
+/// * `params` - The values bound to the placeholders in `sql`.
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `DuckDbError` if the query fails or a row cannot be decoded.
+fn execute_query<R: FromRow>(
+    sql: &str,
+    params: &QueryParams,
+    conn: &Connection,
+) -> Result<Vec<R>, DuckDbError> {
+    let mut statement = conn.prepare(sql).map_err(DuckDbError::SelectError)?;
+    let mut rows = statement
+        .query(params_from_iter(params.values()))
+        .map_err(DuckDbError::SelectError)?;
+    let columns = column_names(&rows);
+
+    let mut results = Vec::new();
+    while let Some(row) = rows.next().map_err(DuckDbError::SelectError)? {
+        results.push(decode_row(row, &columns)?);
+    }
+
+    Ok(results)
+}
+
+/// Returns the names of the columns a query returns.
+///
+/// # Arguments
+///
+/// * `rows` - The rows of the executed query.
+fn column_names(rows: &Rows<'_>) -> Vec<String> {
+    rows.as_ref()
+        .map(|statement| statement.column_names())
+        .unwrap_or_default()
+}
+
+/// Decodes a result row, reading each column with its DuckDB type.
+///
+/// # Arguments
+///
+/// * `row` - The row read from the database.
+/// * `columns` - The names of the columns of the row.
+fn decode_row<R: FromRow>(row: &Row<'_>, columns: &[String]) -> Result<R, DuckDbError> {
+    let values = (0..columns.len())
+        .map(|index| row.get::<usize, Param>(index))
+        .collect::<duckdb::Result<Vec<_>>>()
+        .map_err(DuckDbError::SelectError)?;
+
+    Ok(R::from_row(&ResultRow::new(columns, &values))?)
+}
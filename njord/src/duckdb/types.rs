@@ -0,0 +1,218 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use chrono::{DateTime, Duration, NaiveTime};
+use duckdb::types::Value;
+
+/// Renders a value read from DuckDB as text, for values `Param` has no variant for.
+///
+/// `NULL` becomes an empty string, as it does on SQLite. Nested values are
+/// rendered the way the DuckDB shell prints them.
+///
+/// # Arguments
+///
+/// * `value` - The value to render.
+pub(crate) fn value_to_string(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Boolean(val) => val.to_string(),
+        Value::TinyInt(val) => val.to_string(),
+        Value::SmallInt(val) => val.to_string(),
+        Value::Int(val) => val.to_string(),
+        Value::BigInt(val) => val.to_string(),
+        Value::HugeInt(val) => val.to_string(),
+        Value::UTinyInt(val) => val.to_string(),
+        Value::USmallInt(val) => val.to_string(),
+        Value::UInt(val) => val.to_string(),
+        Value::UBigInt(val) => val.to_string(),
+        Value::Float(val) => val.to_string(),
+        Value::Double(val) => val.to_string(),
+        Value::Decimal(val) => val.to_string(),
+        Value::Text(val) | Value::Enum(val) => val,
+        Value::Blob(val) => String::from_utf8_lossy(&val).to_string(),
+        Value::Date32(days) => DateTime::UNIX_EPOCH
+            .date_naive()
+            .checked_add_signed(Duration::days(days.into()))
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| infinity(days.into())),
+        Value::Time64(unit, val) => (NaiveTime::MIN + Duration::microseconds(unit.to_micros(val)))
+            .format("%H:%M:%S%.f")
+            .to_string(),
+        Value::Timestamp(unit, val) => DateTime::UNIX_EPOCH
+            .naive_utc()
+            .checked_add_signed(Duration::microseconds(unit.to_micros(val)))
+            .map(|timestamp| timestamp.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+            .unwrap_or_else(|| infinity(val)),
+        Value::Interval {
+            months,
+            days,
+            nanos,
+        } => format_interval(months, days, nanos),
+        Value::List(values) | Value::Array(values) => format!(
+            "[{}]",
+            values
+                .into_iter()
+                .map(nested_value_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Struct(fields) => format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|(name, value)| format!(
+                    "'{}': {}",
+                    name,
+                    nested_value_to_string(value.clone())
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Map(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(key, value)| format!(
+                    "{}={}",
+                    nested_value_to_string(key.clone()),
+                    nested_value_to_string(value.clone())
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Union(value) => value_to_string(*value),
+    }
+}
+
+/// Renders a value nested in a list, struct or map, where `NULL` is spelled out.
+fn nested_value_to_string(value: Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        value => value_to_string(value),
+    }
+}
+
+/// Renders a date or timestamp outside of the range chrono supports,
+/// which DuckDB uses for its infinite values.
+fn infinity(value: i64) -> String {
+    if value < 0 {
+        "-infinity".to_string()
+    } else {
+        "infinity".to_string()
+    }
+}
+
+/// Renders an interval like DuckDB does, e.g. `1 year 2 months 3 days 04:05:06`.
+fn format_interval(months: i32, days: i32, nanos: i64) -> String {
+    let mut parts = Vec::new();
+
+    let unit = |count: i32, name: &str| {
+        let plural = if count.abs() == 1 { "" } else { "s" };
+        format!("{} {}{}", count, name, plural)
+    };
+
+    if months / 12 != 0 {
+        parts.push(unit(months / 12, "year"));
+    }
+    if months % 12 != 0 {
+        parts.push(unit(months % 12, "month"));
+    }
+    if days != 0 {
+        parts.push(unit(days, "day"));
+    }
+    if nanos != 0 || parts.is_empty() {
+        let sign = if nanos < 0 { "-" } else { "" };
+        let micros = (nanos / 1_000).unsigned_abs();
+        let seconds = micros / 1_000_000;
+        let mut time = format!(
+            "{}{:02}:{:02}:{:02}",
+            sign,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+        if micros % 1_000_000 != 0 {
+            time.push_str(&format!(".{:06}", micros % 1_000_000));
+        }
+        parts.push(time);
+    }
+
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::types::TimeUnit;
+
+    #[test]
+    fn temporal_values() {
+        assert_eq!(value_to_string(Value::Date32(19_723)), "2024-01-01");
+        assert_eq!(
+            value_to_string(Value::Time64(TimeUnit::Microsecond, 3_723_500_000)),
+            "01:02:03.500"
+        );
+        assert_eq!(
+            value_to_string(Value::Timestamp(TimeUnit::Second, 1_704_067_200)),
+            "2024-01-01 00:00:00"
+        );
+        assert_eq!(
+            value_to_string(Value::Timestamp(TimeUnit::Microsecond, i64::MAX)),
+            "infinity"
+        );
+    }
+
+    #[test]
+    fn scalar_and_nested_values() {
+        assert_eq!(value_to_string(Value::Null), "");
+        assert_eq!(value_to_string(Value::Boolean(true)), "true");
+        assert_eq!(value_to_string(Value::HugeInt(-42)), "-42");
+        assert_eq!(
+            value_to_string(Value::List(vec![Value::Int(1), Value::Null])),
+            "[1, NULL]"
+        );
+        assert_eq!(
+            value_to_string(Value::Struct(
+                vec![("a".to_string(), Value::Text("x".to_string()))].into()
+            )),
+            "{'a': x}"
+        );
+    }
+
+    #[test]
+    fn intervals() {
+        assert_eq!(
+            format_interval(14, 3, 3_723_000_000_000),
+            "1 year 2 months 3 days 01:02:03"
+        );
+        assert_eq!(format_interval(0, 1, 0), "1 day");
+        assert_eq!(format_interval(0, 0, 0), "00:00:00");
+        assert_eq!(format_interval(0, 0, -1_500_000_000), "-00:00:01.500000");
+    }
+}
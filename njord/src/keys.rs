@@ -35,6 +35,14 @@ use std::{
 
 use serde::{Deserialize, Deserializer};
 
+use crate::{
+    builder::BuildError,
+    condition::Condition,
+    query::{Param, ToParam},
+    row::FromValue,
+    table::Table,
+};
+
 /// A simple primary key wrapper.
 ///
 /// The `PrimaryKey` struct wraps a value that acts as a primary key in a database.
@@ -118,6 +126,20 @@ where
     }
 }
 
+impl<T: FromValue> FromValue for PrimaryKey<T> {
+    /// Decodes the inner value of the `PrimaryKey`.
+    fn from_value(value: &Param) -> Result<Self, String> {
+        T::from_value(value).map(PrimaryKey)
+    }
+}
+
+impl<T: ToParam> ToParam for PrimaryKey<T> {
+    /// Binds the inner value of the `PrimaryKey`.
+    fn to_param(&self) -> Param {
+        self.0.to_param()
+    }
+}
+
 /// A wrapper for auto-incremented primary keys.
 ///
 /// The `AutoIncrementPrimaryKey` struct wraps an optional value that can act as an auto-incremented
@@ -209,6 +231,25 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for AutoIncrementPrimaryKey<T> {
     }
 }
 
+impl<T: FromValue> FromValue for AutoIncrementPrimaryKey<T> {
+    /// Decodes the inner value of the `AutoIncrementPrimaryKey`.
+    fn from_value(value: &Param) -> Result<Self, String> {
+        T::from_value(value).map(|value| AutoIncrementPrimaryKey(Some(value)))
+    }
+
+    /// Decodes `NULL` as a key without a value.
+    fn from_null() -> Option<Self> {
+        Some(AutoIncrementPrimaryKey(None))
+    }
+}
+
+impl<T: ToParam> ToParam for AutoIncrementPrimaryKey<T> {
+    /// Binds the inner value, or `NULL` for a key without a value.
+    fn to_param(&self) -> Param {
+        self.0.to_param()
+    }
+}
+
 impl<T: PartialEq> PartialEq for AutoIncrementPrimaryKey<T> {
     /// Implements the `PartialEq` trait for `AutoIncrementPrimaryKey`.
     ///
@@ -217,3 +258,156 @@ impl<T: PartialEq> PartialEq for AutoIncrementPrimaryKey<T> {
         self.0 == other.0
     }
 }
+
+/// The value of a primary key, used to look up a row by its key.
+///
+/// Single values are the keys of tables with a one-column key. Tuples hold the
+/// values of a composite key, in the order of `Table::primary_key`.
+pub trait KeyValues {
+    /// Returns the values of the key columns.
+    fn key_values(&self) -> Vec<Param>;
+}
+
+impl<T: ToParam> KeyValues for T {
+    fn key_values(&self) -> Vec<Param> {
+        vec![self.to_param()]
+    }
+}
+
+macro_rules! tuple_key_values {
+    ($($name:ident),+) => {
+        impl<$($name: ToParam),+> KeyValues for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn key_values(&self) -> Vec<Param> {
+                let ($($name,)+) = self;
+                vec![$($name.to_param()),+]
+            }
+        }
+    };
+}
+
+tuple_key_values!(A);
+tuple_key_values!(A, B);
+tuple_key_values!(A, B, C);
+tuple_key_values!(A, B, C, D);
+tuple_key_values!(A, B, C, D, E);
+tuple_key_values!(A, B, C, D, E, F);
+
+/// Builds the condition matching the row with the given key.
+///
+/// # Arguments
+///
+/// * `table` - The table whose primary key is matched, see `Table::primary_key`.
+/// * `values` - The values of the key columns, in the same order.
+///
+/// # Returns
+///
+/// A `Result` containing a `Condition` comparing every key column with its value,
+/// joined with AND, or a `BuildError` if the table has no primary key or the number
+/// of values differs from the number of key columns.
+pub fn key_condition<'a>(
+    table: &dyn Table,
+    values: Vec<Param>,
+) -> Result<Condition<'a>, BuildError> {
+    let columns = table.primary_key();
+
+    if columns.is_empty() {
+        return Err(BuildError::MissingPrimaryKey(table.get_name().to_string()));
+    }
+    if columns.len() != values.len() {
+        return Err(BuildError::KeyValueCount {
+            expected: columns.len(),
+            actual: values.len(),
+        });
+    }
+
+    Ok(columns
+        .into_iter()
+        .zip(values)
+        .map(|(column, value)| Condition::Eq(column, value.into()))
+        .reduce(|left, right| Condition::And(Box::new(left), Box::new(right)))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::query::{PlaceholderStyle, QueryParams};
+
+    struct Membership {
+        primary_key: Vec<String>,
+    }
+
+    impl Table for Membership {
+        fn get_name(&self) -> &str {
+            "memberships"
+        }
+
+        fn get_columns(&self) -> HashMap<String, String> {
+            HashMap::new()
+        }
+
+        fn get_column_fields(&self) -> Vec<String> {
+            Vec::new()
+        }
+
+        fn get_column_values(&self) -> Vec<Param> {
+            Vec::new()
+        }
+
+        fn primary_key(&self) -> Vec<String> {
+            self.primary_key.clone()
+        }
+    }
+
+    fn membership(primary_key: &[&str]) -> Membership {
+        Membership {
+            primary_key: primary_key
+                .iter()
+                .map(|column| column.to_string())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn composite_key_condition() {
+        let table = membership(&["user_id", "group_id"]);
+        let mut params = QueryParams::new(PlaceholderStyle::Dollar);
+
+        let condition = key_condition(&table, (7, "admins").key_values()).unwrap();
+
+        assert_eq!(
+            condition.build(&mut params),
+            "(user_id = $1) AND (group_id = $2)"
+        );
+        assert_eq!(
+            params.values(),
+            &[Param::Int(7), Param::Text("admins".to_string())]
+        );
+    }
+
+    #[test]
+    fn key_condition_with_missing_values() {
+        let table = membership(&["user_id", "group_id"]);
+
+        assert_eq!(
+            key_condition(&table, 7.key_values()).err(),
+            Some(BuildError::KeyValueCount {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn key_condition_without_primary_key() {
+        let table = membership(&[]);
+
+        assert_eq!(
+            key_condition(&table, 7.key_values()).err(),
+            Some(BuildError::MissingPrimaryKey("memberships".to_string()))
+        );
+    }
+}
@@ -27,11 +27,21 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+// Lets code generated by `njord_derive` refer to `::njord` inside this crate as well.
+extern crate self as njord;
+
+pub mod builder;
 pub mod column;
 pub mod condition;
+pub mod dialect;
 pub mod keys;
 pub mod query;
+pub mod relation;
+pub mod row;
+pub mod schema;
 pub mod table;
+pub mod transaction;
+pub mod types;
 pub mod util;
 
 #[cfg(feature = "sqlite")]
@@ -48,3 +58,31 @@ pub mod mssql;
 
 #[cfg(feature = "mariadb")]
 pub mod mariadb;
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
+#[cfg(feature = "duckdb")]
+pub mod duckdb;
+
+#[cfg(any(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mariadb",
+    feature = "oracle",
+    feature = "mssql",
+    feature = "postgres",
+    feature = "duckdb"
+))]
+pub mod any;
+
+#[cfg(any(
+    feature = "sqlite",
+    feature = "mysql",
+    feature = "mariadb",
+    feature = "oracle",
+    feature = "mssql",
+    feature = "postgres",
+    feature = "duckdb"
+))]
+pub use any::{connect, AnyConnection};
@@ -31,6 +31,8 @@
 
 use mysql::Error as MariaDBLibError;
 
+use crate::{builder::BuildError, row::DecodeError};
+
 /// Represents errors that can occur during SQLite operations.
 #[derive(Debug)]
 pub enum MariaDBError {
@@ -42,6 +44,14 @@ pub enum MariaDBError {
     UpdateError(MariaDBLibError),
     /// Error that occurs during a DELETE operation.
     DeleteError(MariaDBLibError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(MariaDBLibError),
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
 }
 
 impl From<MariaDBLibError> for MariaDBError {
@@ -50,3 +60,17 @@ impl From<MariaDBLibError> for MariaDBError {
         MariaDBError::InsertError(error)
     }
 }
+
+impl From<DecodeError> for MariaDBError {
+    /// Converts a `DecodeError` into a `MariaDBError`.
+    fn from(error: DecodeError) -> Self {
+        MariaDBError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for MariaDBError {
+    /// Converts a `BuildError` into a `MariaDBError`.
+    fn from(error: BuildError) -> Self {
+        MariaDBError::BuildError(error)
+    }
+}
@@ -29,20 +29,23 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use mysql::{Error, Pool, PooledConn};
+use mysql::{prelude::Queryable, Error, Pool, PooledConn};
 
 pub mod delete;
 pub mod error;
 pub mod insert;
 pub mod select;
+pub mod transaction;
 pub mod update;
-mod util;
+pub mod upsert;
 
 pub use delete::delete;
 pub use error::MariaDBError;
-pub use insert::insert;
+pub use insert::{bulk_insert, insert};
 pub use select::select;
+pub use transaction::{transaction, transaction_with, Transaction};
 pub use update::update;
+pub use upsert::upsert;
 
 /// Open a database connection.
 ///
@@ -62,3 +65,21 @@ pub fn open(url: &str) -> Result<PooledConn, Error> {
 
     Ok(conn)
 }
+
+/// Executes a raw SQL statement, e.g. a schema change, without reading any rows.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `sql` - The SQL statement to execute.
+///
+/// # Returns
+///
+/// A `Result` indicating success, or a `MariaDBError` if an error occurs during
+/// the execution.
+pub fn raw_execute(conn: &mut PooledConn, sql: &str) -> Result<(), MariaDBError> {
+    match conn.query_drop(sql) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
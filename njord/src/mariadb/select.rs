@@ -29,23 +29,24 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{
-    column::Column,
-    condition::Condition,
-    mariadb::util::{
-        generate_group_by_str, generate_having_str, generate_limit_str, generate_offset_str,
-        generate_order_by_str, generate_where_condition_str,
-    },
-    query::QueryBuilder,
-};
-use std::{collections::HashMap, sync::Arc};
+use crate::{builder, column::Column, dialect::MariaDbDialect};
 
-use log::{debug, info};
+use log::debug;
+#[cfg(feature = "chrono")]
+use mysql::consts::ColumnType;
 use mysql::prelude::*;
-use mysql::{Error, PooledConn, Value};
+use mysql::{Binary, Params, PooledConn, QueryResult, Row};
 
-use crate::table::Table;
-use crate::util::{Join, JoinType};
+use std::marker::PhantomData;
+
+use crate::{
+    query::Param,
+    relation::Related,
+    row::{FromRow, Row as ResultRow},
+    table::Table,
+};
+
+use super::MariaDBError;
 
 /// Constructs a new SELECT query builder.
 ///
@@ -60,325 +61,150 @@ pub fn select<T: Table + Default>(columns: Vec<Column>) -> SelectQueryBuilder<T>
     SelectQueryBuilder::new(columns)
 }
 
-/// A builder for constructing SELECT queries.
-#[derive(Clone)]
-pub struct SelectQueryBuilder<'a, T: Table + Default> {
-    table: Option<T>,
-    columns: Vec<Column<'a>>,
-    where_condition: Option<Condition<'a>>,
-    distinct: bool,
-    group_by: Option<Vec<String>>,
-    order_by: Option<HashMap<Vec<String>, String>>,
-    limit: Option<usize>,
-    offset: Option<usize>,
-    having_condition: Option<Condition<'a>>,
-    except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
-    union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
-    joins: Option<Vec<Join<'a>>>,
-}
+/// A builder for constructing SELECT queries on MariaDB.
+pub type SelectQueryBuilder<'a, T> = builder::SelectQueryBuilder<'a, T, MariaDbDialect>;
 
-impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
-    /// Creates a new `SelectQueryBuilder` instance.
+impl<'a, T: Table + FromRow + Default> SelectQueryBuilder<'a, T> {
+    /// Builds and executes the SELECT query.
     ///
     /// # Arguments
     ///
-    /// * `columns` - A vector of strings representing the columns to be selected.
-    pub fn new(columns: Vec<Column<'a>>) -> Self {
-        SelectQueryBuilder {
-            table: None,
-            columns,
-            where_condition: None,
-            distinct: false,
-            group_by: None,
-            order_by: None,
-            limit: None,
-            offset: None,
-            having_condition: None,
-            except_clauses: None,
-            union_clauses: None,
-            joins: None,
-        }
-    }
-
-    /// Sets the columns to be selected.
+    /// * `conn` - A mutable reference to the database connection.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `columns` - A vector of strings representing the columns to be selected.
-    pub fn select(mut self, columns: Vec<Column<'a>>) -> Self {
-        self.columns = columns;
-        self
-    }
+    /// A `Result` containing a vector of selected table rows if successful,
+    /// or a `MariaDBError` if the query fails or a row cannot be decoded.
+    pub fn build(&mut self, conn: &mut PooledConn) -> Result<Vec<T>, MariaDBError> {
+        let (final_query, params) = self.render();
 
-    /// Sets the DISTINCT keyword for the query.
-    pub fn distinct(mut self) -> Self {
-        self.distinct = true;
-        self
-    }
+        debug!("{}", final_query);
 
-    /// Sets the table from which to select data.
-    ///
-    /// # Arguments
-    ///
-    /// * `table` - The table from which to select data.
-    pub fn from(mut self, table: T) -> Self {
-        self.table = Some(table);
-        self
-    }
+        let query_set = conn
+            .exec_iter(&final_query, Params::from(params.values().to_vec()))
+            .map_err(MariaDBError::SelectError)?;
 
-    /// Sets the WHERE clause condition.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
-        self
+        collect_rows(query_set)
     }
 
-    /// Sets the GROUP BY clause columns.
+    /// Builds and executes the SELECT query, decoding the rows one at a time.
     ///
-    /// # Arguments
-    ///
-    /// * `columns` - A vector of strings representing the columns to be grouped by.
-    pub fn group_by(mut self, columns: Vec<String>) -> Self {
-        self.group_by = Some(columns);
-        self
-    }
-
-    /// Sets the ORDER BY clause columns and order direction.
-    ///
-    /// # Arguments
-    ///
-    /// * `col_and_order` - A HashMap containing column names as keys and order direction as values.
-    pub fn order_by(mut self, col_and_order: HashMap<Vec<String>, String>) -> Self {
-        self.order_by = Some(col_and_order);
-        self
-    }
-
-    /// Sets the LIMIT clause for the query.
+    /// Unlike `build`, the rows are not collected; each one is read from the
+    /// connection when the iterator reaches it. The connection can't be used for
+    /// other queries until the iterator is dropped.
     ///
     /// # Arguments
     ///
-    /// * `count` - The number of rows to limit the result to.
-    pub fn limit(mut self, count: usize) -> Self {
-        self.limit = Some(count);
-        self
-    }
-
-    /// Sets the OFFSET clause for the query.
+    /// * `conn` - A mutable reference to the database connection.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `offset` - The number of rows to skip.
-    pub fn offset(mut self, offset: usize) -> Self {
-        self.offset = Some(offset);
-        self
-    }
+    /// A `Result` containing an iterator over the selected table rows if successful,
+    /// or a `MariaDBError` if the query could not be executed.
+    pub fn build_iter(self, conn: &mut PooledConn) -> Result<SelectIter<'_, T>, MariaDBError> {
+        let (final_query, params) = self.render();
 
-    /// Sets the HAVING clause condition.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
-        self
-    }
+        debug!("{}", final_query);
 
-    /// Adds an EXCEPT clause to the query, allowing you to exclude results from another query.
-    ///
-    /// This method modifies the current query builder to exclude the results of the specified
-    /// `other_query`. If there are already existing EXCEPT clauses, the new clause will be added
-    /// to the list. If no EXCEPT clauses exist, a new list will be created with the provided
-    /// query.
-    ///
-    /// # Arguments
-    ///
-    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
-    ///   should be excluded from the current query.
-    ///
-    /// # Returns
-    ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new EXCEPT clause added.
-    pub fn except(mut self, other_query: SelectQueryBuilder<'a, T>) -> Self {
-        match self.except_clauses {
-            Some(ref mut clauses) => clauses.push(other_query),
-            None => self.except_clauses = Some(vec![other_query]),
-        }
-        self
-    }
+        let rows = conn
+            .exec_iter(&final_query, Params::from(params.values().to_vec()))
+            .map_err(MariaDBError::SelectError)?;
 
-    /// Adds a UNION clause to the query, allowing you to combine results from another query.
-    ///
-    /// This method modifies the current query builder to include the results of the specified
-    /// `other_query`. If there are already existing UNION clauses, the new clause will be added
-    /// to the list. If no UNION clauses exist, a new list will be created with the provided
-    /// query.
-    ///
-    /// # Arguments
-    ///
-    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
-    ///   should be combined with the current query.
-    ///
-    /// # Returns
-    ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new UNION clause added.
-    pub fn union(mut self, other_query: SelectQueryBuilder<'a, T>) -> Self {
-        match self.union_clauses {
-            Some(ref mut clauses) => clauses.push(other_query),
-            None => self.union_clauses = Some(vec![other_query]),
-        }
-        self
+        Ok(SelectIter {
+            rows,
+            marker: PhantomData,
+        })
     }
 
-    /// Adds a JOIN clause to the query, allowing you to combine rows from two or more tables based on a related column.
+    /// Builds and executes the SELECT query, decoding the rows into `R` instead of `T`.
     ///
-    /// This method modifies the current query builder to include a join clause with the specified join type,
-    /// target table, and condition for the join. If there are already existing JOIN clauses, the new clause
-    /// will be added to the list. If no JOIN clauses exist, a new list will be created with the provided
-    /// join information.
+    /// `R` is decoded by column name or alias, so it can be a struct deriving `FromRow`
+    /// with only some of the columns, a tuple of single values, or a tuple of structs
+    /// for the tables of a join, e.g. `(User, Option<Address>)` for a LEFT JOIN.
     ///
     /// # Arguments
     ///
-    /// * `join_type` - The type of join to perform (e.g., INNER, LEFT, RIGHT, FULL).
-    /// * `table` - The table to join with the current table.
-    /// * `on_condition` - The condition that specifies how the tables are related (the ON clause).
+    /// * `conn` - A mutable reference to the database connection.
     ///
     /// # Returns
     ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new JOIN clause added.
-    pub fn join(
-        mut self,
-        join_type: JoinType,
-        table: Arc<dyn Table>,
-        on_condition: Condition<'a>,
-    ) -> Self {
-        match self.joins {
-            Some(ref mut joins) => joins.push(Join::new(join_type, table, on_condition)),
-            None => self.joins = Some(vec![Join::new(join_type, table, on_condition)]),
-        }
-        self
-    }
+    /// A `Result` containing a vector of the decoded rows if successful,
+    /// or a `MariaDBError` if the query fails or a row cannot be decoded.
+    pub fn build_as<R: FromRow>(self, conn: &mut PooledConn) -> Result<Vec<R>, MariaDBError> {
+        let (final_query, params) = self.render();
 
-    /// Builds the query string, this function should be used internally.
-    pub fn build_query(&self) -> String {
-        let columns_str = self
-            .columns
-            .iter()
-            .map(|c| c.build())
-            .collect::<Vec<String>>()
-            .join(", ");
-
-        let table_name = self
-            .table
-            .as_ref()
-            .map(|t| t.get_name().to_string())
-            .unwrap_or("".to_string());
-
-        // Generate JOIN clauses, if any
-        let join_clauses: Vec<String> = match &self.joins {
-            Some(joins) => joins
-                .iter()
-                .map(|join| {
-                    let join_type_str = match join.join_type {
-                        JoinType::Inner => "INNER JOIN",
-                        JoinType::Left => "LEFT JOIN",
-                        JoinType::Right => "RIGHT JOIN",
-                        JoinType::Full => "FULL OUTER JOIN",
-                    };
-                    format!(
-                        "{} {} ON {}",
-                        join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
-                    )
-                })
-                .collect(),
-            None => Vec::new(),
-        };
-
-        let distinct_str = if self.distinct { "DISTINCT " } else { "" };
-        let where_condition_str = generate_where_condition_str(self.where_condition.clone());
-        let group_by_str = generate_group_by_str(&self.group_by);
-        let order_by_str = generate_order_by_str(&self.order_by);
-        let limit_str = generate_limit_str(self.limit);
-        let offset_str = generate_offset_str(self.offset);
-        let having_str =
-            generate_having_str(self.group_by.is_some(), self.having_condition.as_ref());
-
-        // Create the JOIN clause or an empty string
-        let join_clause = if !join_clauses.is_empty() {
-            join_clauses.join(" ")
-        } else {
-            String::new()
-        };
-
-        let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {} {}",
-            distinct_str,
-            columns_str,
-            table_name,
-            join_clause,
-            where_condition_str,
-            group_by_str,
-            having_str,
-            order_by_str,
-            format!("{} {}", limit_str, offset_str),
-        );
-
-        // Handle EXCEPT clauses
-        if let Some(except_clauses) = &self.except_clauses {
-            for except_query in except_clauses {
-                let except_sql = except_query.build_query();
-                query = format!("{} EXCEPT {}", query, except_sql);
-            }
-        }
+        debug!("{}", final_query);
 
-        // Handle UNION clauses
-        if let Some(union_clauses) = &self.union_clauses {
-            for union_query in union_clauses {
-                let union_sql = union_query.build_query();
-                query = format!("{} UNION {}", query, union_sql);
-            }
-        }
+        let query_set = conn
+            .exec_iter(&final_query, Params::from(params.values().to_vec()))
+            .map_err(MariaDBError::SelectError)?;
 
-        query
+        collect_rows(query_set)
     }
+}
 
-    /// Builds and executes the SELECT query.
+/// A builder for SELECT queries on MariaDB loading related rows, created by `with`.
+pub type EagerQueryBuilder<'a, T, R> = builder::EagerQueryBuilder<'a, T, R, MariaDbDialect>;
+
+impl<T, R> EagerQueryBuilder<'_, T, R>
+where
+    T: Related<R> + FromRow + Default,
+    R: Table + FromRow + Default + Clone,
+{
+    /// Builds and executes the SELECT query, then loads the related rows with one
+    /// batched query.
     ///
     /// # Arguments
     ///
-    /// * `conn` - A mutable reference to the database connection.
+    /// * `conn` - A mutable reference to the pooled connection.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a vector of selected table rows if successful,
-    /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub fn build(&mut self, conn: &mut PooledConn) -> Result<Vec<T>, Error> {
-        let final_query = self.build_query();
+    /// A `Result` containing the selected table rows, each paired with its related rows,
+    /// or a `MariaDBError` if a query fails or a row cannot be decoded.
+    pub fn build(self, conn: &mut PooledConn) -> Result<Vec<(T, Vec<R>)>, MariaDBError> {
+        let (final_query, params) = self.select().render();
 
         debug!("{}", final_query);
 
-        raw_execute(&final_query, conn)
+        let parents: Vec<T> = collect_rows(
+            conn.exec_iter(&final_query, Params::from(params.values().to_vec()))
+                .map_err(MariaDBError::SelectError)?,
+        )?;
+
+        let mut related = Vec::new();
+        for query in self.related_queries(&parents)? {
+            let (final_query, params) = query.render();
+
+            debug!("{}", final_query);
+
+            related.extend(collect_rows(
+                conn.exec_iter(&final_query, Params::from(params.values().to_vec()))
+                    .map_err(MariaDBError::SelectError)?,
+            )?);
+        }
+
+        Ok(self.group(parents, related))
     }
 }
 
-/// Implement `QueryBuilder` for `SelectQueryBuilder`
-///
-/// The where statement ensures the T is long lived
-impl<'a, T> QueryBuilder<'a> for SelectQueryBuilder<'a, T>
-where
-    T: Table + Default + Clone + 'a, // Added 'a bound here
-{
-    fn to_sql(&self) -> String {
-        self.build_query()
+/// An iterator over the rows of a SELECT query, created by `build_iter`.
+pub struct SelectIter<'conn, T> {
+    rows: QueryResult<'conn, 'conn, 'conn, Binary>,
+    marker: PhantomData<T>,
+}
+
+impl<T: FromRow> Iterator for SelectIter<'_, T> {
+    type Item = Result<T, MariaDBError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows
+            .next()
+            .map(|row| row.map_err(MariaDBError::SelectError).and_then(decode_row))
     }
 }
 
-/// Executes a raw SQL query and returns a vector of table rows.
+/// Executes a raw SQL query and returns the decoded rows.
 ///
 /// # Arguments
 ///
@@ -387,47 +213,70 @@ where
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of table rows if successful,
-/// or a `rusqlite::Error` if an error occurs during the execution.
-pub fn raw_execute<T: Table + Default>(sql: &str, conn: &mut PooledConn) -> Result<Vec<T>, Error> {
-    let query_set = conn.query_iter(sql).unwrap();
-
-    let mut results: Vec<T> = Vec::new();
-
-    for row_result in query_set {
-        let row = row_result.unwrap(); // Unwrap the row result
-        let mut instance = T::default();
-
-        for column in row.columns_ref() {
-            // Cells in a row can be indexed by numeric index or by column name
-            let column_value = &row[column.name_str().as_ref()];
-
-            let column_value_str = match column_value {
-                Value::NULL => "NULL".to_string(),
-                Value::Bytes(bytes) => String::from_utf8_lossy(bytes).to_string(),
-                Value::Int(i) => i.to_string(),
-                Value::UInt(u) => u.to_string(),
-                Value::Float(f) => f.to_string(),
-                Value::Double(d) => d.to_string(),
-                Value::Date(year, month, day, hour, min, sec, micro) => format!(
-                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
-                    year, month, day, hour, min, sec, micro
-                ),
-                Value::Time(neg, days, hours, minutes, seconds, micros) => format!(
-                    "{}{:02}:{:02}:{:02}.{:06}",
-                    if *neg { "-" } else { "" },
-                    days * 24 + u32::from(*hours),
-                    minutes,
-                    seconds,
-                    micros
-                ),
-            };
-
-            instance.set_column_value(column.name_str().as_ref(), &column_value_str);
-        }
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `MariaDBError` if the query fails or a row cannot be decoded.
+pub fn raw_execute<R: FromRow>(sql: &str, conn: &mut PooledConn) -> Result<Vec<R>, MariaDBError> {
+    let query_set = conn.query_iter(sql).map_err(MariaDBError::SelectError)?;
 
-        results.push(instance);
-    }
+    collect_rows(query_set)
+}
+
+/// Decodes the rows of a result set.
+///
+/// # Arguments
+///
+/// * `query_set` - The result set returned by the text or the binary protocol.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `MariaDBError` if reading or decoding a row fails.
+fn collect_rows<R: FromRow, P: Protocol>(
+    query_set: QueryResult<'_, '_, '_, P>,
+) -> Result<Vec<R>, MariaDBError> {
+    query_set
+        .map(|row| row.map_err(MariaDBError::SelectError).and_then(decode_row))
+        .collect()
+}
 
-    Ok(results)
+/// Decodes a result row from the values sent by the server.
+///
+/// # Arguments
+///
+/// * `row` - The row read from the database.
+fn decode_row<R: FromRow>(row: Row) -> Result<R, MariaDBError> {
+    let (columns, values) = row_values(row);
+
+    Ok(R::from_row(&ResultRow::new(&columns, &values))?)
+}
+
+/// Splits a result row into the names and the values of its columns.
+///
+/// # Arguments
+///
+/// * `row` - The row read from the database.
+fn row_values(row: Row) -> (Vec<String>, Vec<Param>) {
+    let columns = row.columns();
+    let values = row
+        .unwrap()
+        .into_iter()
+        .zip(columns.iter())
+        .map(
+            |(value, column)| match (Param::from(value), column.column_type()) {
+                // DATE and DATETIME values look the same on the wire
+                #[cfg(feature = "chrono")]
+                (Param::Timestamp(timestamp), ColumnType::MYSQL_TYPE_DATE) => {
+                    Param::Date(timestamp.date())
+                }
+                (param, _) => param,
+            },
+        )
+        .collect();
+
+    let names = columns
+        .iter()
+        .map(|column| column.name_str().into_owned())
+        .collect();
+
+    (names, values)
 }
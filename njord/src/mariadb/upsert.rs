@@ -0,0 +1,77 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{builder, dialect::MariaDbDialect, table::Table};
+
+use log::debug;
+use mysql::{prelude::Queryable, Params, PooledConn};
+
+use super::MariaDBError;
+
+/// Constructs a new upsert builder.
+///
+/// # Arguments
+///
+/// * `rows` - The table rows to insert, or to update the conflicting rows with.
+///
+/// # Returns
+///
+/// An `UpsertQueryBuilder` instance.
+pub fn upsert<T: Table>(rows: Vec<T>) -> UpsertQueryBuilder<T> {
+    UpsertQueryBuilder::new().values(rows)
+}
+
+/// A builder for constructing upserts on MariaDB.
+pub type UpsertQueryBuilder<T> = builder::UpsertQueryBuilder<T, MariaDbDialect>;
+
+impl<T: Table> UpsertQueryBuilder<T> {
+    /// Builds and executes the upsert.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `PooledConn` to the MariaDB database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of inserted or updated rows if successful,
+    /// or a `MariaDBError` if an error occurs.
+    /// MariaDB counts an inserted row once and an updated row twice.
+    pub fn build(self, conn: &mut PooledConn) -> Result<u64, MariaDBError> {
+        self.validate()?;
+
+        let (query, params) = self.render();
+
+        debug!("{}", query);
+
+        conn.exec_drop(&query, Params::from(params.values().to_vec()))
+            .map_err(MariaDBError::InsertError)?;
+
+        Ok(conn.affected_rows())
+    }
+}
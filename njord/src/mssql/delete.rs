@@ -29,16 +29,12 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{
-    condition::Condition,
-    mssql::util::{generate_where_condition_str, remove_quotes_and_backslashes},
-};
+use crate::{builder, dialect::MssqlDialect, table::Table};
 
-use log::{debug, info};
+use log::debug;
+use tiberius::ToSql;
 
-use crate::table::Table;
-
-use super::Connection;
+use super::{transaction, Connection, MSSQLError};
 
 /// Constructs a new DELETE query builder.
 ///
@@ -49,73 +45,34 @@ pub fn delete<T: Table + Default>() -> DeleteQueryBuilder<'static, T> {
     DeleteQueryBuilder::new()
 }
 
-/// A builder for constructing DELETE queries.
-pub struct DeleteQueryBuilder<'a, T: Table + Default> {
-    table: Option<T>,
-    where_condition: Option<Condition<'a>>,
-}
+/// A builder for constructing DELETE queries on MSSQL.
+pub type DeleteQueryBuilder<'a, T> = builder::DeleteQueryBuilder<'a, T, MssqlDialect>;
 
 impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
-    /// Creates a new `DeleteQueryBuilder` instance.
-    ///
-    /// # Returns
-    ///
-    /// A `DeleteQueryBuilder` instance.
-    pub fn new() -> Self {
-        DeleteQueryBuilder {
-            table: None,
-            where_condition: None,
-        }
-    }
-
-    /// Sets the table from which to delete data.
-    ///
-    /// # Arguments
-    ///
-    /// * `table` - An instance of the table from which to delete data.
-    pub fn from(mut self, table: T) -> Self {
-        self.table = Some(table);
-        self
-    }
-
-    /// Sets the WHERE clause condition.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
-        self
-    }
-
     /// Builds and executes the DELETE query.
-    /// 
+    ///
     /// # Arguments
     ///
     /// * `conn` - A mutable reference to the database connection.
     ///
     /// # Returns
     ///
-    /// A `Result` indicating success or failure of the deletion operation.
-    pub async fn build(self, conn: &mut Connection) -> Result<(), String> {
-        let table_name = self
-            .table
-            .as_ref()
-            .map(|t| t.get_name().to_string())
-            .unwrap_or("".to_string());
-
-        // Sanitize table name from unwanted quotations or backslashes
-        let table_name_str = remove_quotes_and_backslashes(&table_name);
-        let where_condition_str = generate_where_condition_str(self.where_condition);
-
-        // Construct the query based on defined variables above
-        let query = format!("DELETE FROM {} {}", table_name_str, where_condition_str,);
+    /// A `Result` containing the number of deleted rows if successful,
+    /// or a `MSSQLError` if the deletion fails or `expect_rows` is not met.
+    pub async fn build(self, conn: &mut Connection) -> Result<u64, MSSQLError> {
+        let (query, params) = self.render();
 
         debug!("{}", query);
 
-        match conn.client.execute(&query, &[]).await {
-            Ok(_) => Ok(()),
-            Err(err) => Err(err.to_string()),
-        }
+        let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
+
+        transaction::expect_rows(
+            conn,
+            &query,
+            &binds,
+            self.expected_rows(),
+            MSSQLError::DeleteError,
+        )
+        .await
     }
 }
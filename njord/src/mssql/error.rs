@@ -31,6 +31,8 @@
 
 use tiberius::error::Error as MSSQLLibError;
 
+use crate::{builder::BuildError, row::DecodeError};
+
 /// Represents errors that can occur during MSSQL operations.
 #[derive(Debug)]
 pub enum MSSQLError {
@@ -42,7 +44,18 @@ pub enum MSSQLError {
     UpdateError(MSSQLLibError),
     /// Error that occurs during a DELETE operation.
     DeleteError(MSSQLLibError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(MSSQLLibError),
     InvalidQuery,
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount {
+        expected: u64,
+        actual: u64,
+    },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
 }
 
 impl From<MSSQLLibError> for MSSQLError {
@@ -51,3 +64,17 @@ impl From<MSSQLLibError> for MSSQLError {
         MSSQLError::InsertError(error)
     }
 }
+
+impl From<DecodeError> for MSSQLError {
+    /// Converts a `DecodeError` into a `MSSQLError`.
+    fn from(error: DecodeError) -> Self {
+        MSSQLError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for MSSQLError {
+    /// Converts a `BuildError` into a `MSSQLError`.
+    fn from(error: BuildError) -> Self {
+        MSSQLError::BuildError(error)
+    }
+}
@@ -29,171 +29,204 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{mssql::MSSQLError, query::QueryBuilder, table::Table};
+use crate::{
+    builder::{self, BulkInsertProgress},
+    dialect::MssqlDialect,
+    mssql::MSSQLError,
+    query::{Param, QueryBuilder},
+    table::Table,
+    transaction::TransactionStatements,
+};
 
 use log::{debug, info};
-use std::fmt::Error;
 
-use super::Connection;
+use tiberius::ToSql;
+
+use super::{select::decode_row, transaction, Connection};
 
 /// Inserts rows into a MSSQL table.
 ///
 /// This function takes a `Connection` and a vector of objects implementing
 /// the `Table` trait, which represents rows to be inserted into the table.
-/// It generates SQL INSERT statements for each row and executes them within
-/// a transaction.
+/// All rows are inserted with a single statement. If the database generates their
+/// `AutoIncrementPrimaryKey`, it is a MERGE reading the keys back with `OUTPUT`,
+/// each one together with the position of its row.
 ///
 /// # Arguments
 ///
 /// * `conn` - A `Connection` to the MSSQL database.
 /// * `table_rows` - A vector of objects implementing the `Table` trait representing
-///                  the rows to be inserted into the database.
+///   the rows to be inserted into the database.
 ///
 /// # Returns
 ///
-/// A `Result` containing a `String` representing the joined SQL statements
-/// if the insertion is successful, or a `RusqliteError` if an error occurs.
+/// A `Result` containing the inserted rows with their generated keys set
+/// if the insertion is successful, or a `MSSQLError` if an error occurs.
 pub async fn insert<T: Table>(
     conn: &mut Connection,
     table_rows: Vec<T>,
-) -> Result<String, MSSQLError> {
-    let mut statements: Vec<String> = Vec::new();
-    for (index, table_row) in table_rows.iter().enumerate() {
-        match generate_statement(table_row, index == 0) {
-            Ok(statement) => statements.push(statement),
-            Err(_) => return Err(MSSQLError::InvalidQuery),
-        }
+) -> Result<Vec<T>, MSSQLError> {
+    if table_rows.is_empty() {
+        return Ok(Vec::new());
     }
 
-    let joined_statements = statements.join(", ");
+    let builder = builder::InsertQueryBuilder::<T, MssqlDialect>::new().values(table_rows);
+    builder.validate()?;
 
-    debug!("{}", joined_statements);
+    let key = builder.generated_key();
+    let builder = match &key {
+        Some(key) => builder.returning([key.as_str()]),
+        None => builder,
+    };
 
-    match conn.client.query(&joined_statements, &[]).await {
-        Ok(_) => Ok("Inserted into table, done.".to_string()),
-        Err(err) => {
-            eprintln!("Error: {}", err);
-            Err(MSSQLError::InvalidQuery)
-        }
+    let (sql, params) = builder.render();
+
+    debug!("{}", sql);
+
+    let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
+
+    let rows = conn
+        .client
+        .query(&sql, &binds)
+        .await
+        .map_err(MSSQLError::InsertError)?
+        .into_first_result()
+        .await
+        .map_err(MSSQLError::InsertError)?;
+
+    info!("Inserted into table, done.");
+
+    if key.is_none() {
+        return Ok(builder.into_rows());
     }
+
+    // The OUTPUT rows come in any order, each one starts with the position of its row
+    let mut keys = rows
+        .iter()
+        .map(decode_row::<(usize, Param)>)
+        .collect::<Result<Vec<_>, _>>()?;
+    keys.sort_by_key(|(row, _)| *row);
+
+    Ok(builder.into_rows_with_keys(keys.into_iter().map(|(_, key)| key))?)
 }
 
-/// Generates an SQL INSERT INTO statement for a given table row.
+/// Inserts the rows selected by a subquery into a table.
 ///
 /// # Arguments
 ///
-/// * `table_row` - A reference to an object implementing the `Table` trait.
+/// * `conn` - A `Connection` to the MSSQL database.
+/// * `columns` - The columns the subquery's columns are inserted into.
+/// * `subquery` - The query selecting the rows to insert.
 ///
 /// # Returns
 ///
-/// A `Result` containing a `String` representing the generated SQL statement
-/// if the operation is successful, or a `RusqliteError` if an error occurs.
-pub fn into<'a, T: Table + Default>(
+/// A `Result` containing the number of inserted rows if successful,
+/// or a `MSSQLError` if an error occurs.
+pub async fn into<'a, T: Table + Default>(
     conn: &'a mut Connection,
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
-) -> Result<String, MSSQLError> {
-    let statement = generate_insert_into_statement::<T>(columns, subquery);
-    let sql = statement.unwrap();
+) -> Result<u64, MSSQLError> {
+    let (sql, params) = builder::InsertQueryBuilder::<T, MssqlDialect>::new()
+        .select(columns, subquery)
+        .render();
 
-    // FIXME: Convert to transaction
-    let _ = conn.client.execute(&sql, &[]);
+    debug!("{}", sql);
+
+    let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
+
+    let rows = conn
+        .client
+        .execute(&sql, &binds)
+        .await
+        .map_err(MSSQLError::InsertError)?
+        .total();
 
     info!("Inserted into table, done.");
 
-    // FIXME: Return the number of rows affected
-    Ok(sql)
+    Ok(rows)
 }
 
-/// Generates an SQL INSERT INTO statement for a given subquery.
+/// Constructs a new bulk insert builder.
 ///
 /// # Arguments
 ///
-/// * `columns` - A `Vec` of column names.
-/// * `subquery` - A `QueryBuilder` object representing the subquery.
+/// * `rows` - The table rows to insert, e.g. an iterator reading them from a file.
 ///
 /// # Returns
 ///
-/// A `Result` containing a `String` representing the generated SQL statement
-/// if the operation is successful, or a `RusqliteError` if an error occurs.
-fn generate_insert_into_statement<'a, T: Table + Default>(
-    columns: Vec<String>,
-    subquery: Box<dyn QueryBuilder<'a> + 'a>,
-) -> Result<String, MSSQLError> {
-    let columns_str = columns.join(", ");
-    let subquery_str = subquery.to_sql();
-    let table_row = T::default();
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
-
-    let sql = format!(
-        "INSERT INTO {} ({}) {}",
-        table_name, columns_str, subquery_str
-    );
-
-    Ok(sql)
+/// A `BulkInsertBuilder` instance.
+pub fn bulk_insert<'a, R>(rows: R) -> BulkInsertBuilder<'a, R::IntoIter>
+where
+    R: IntoIterator,
+    R::Item: Table,
+{
+    BulkInsertBuilder::new(rows)
 }
 
-/// Generates an SQL INSERT statement for a given table row.
-///
-/// This function takes an object implementing the `Table` trait, representing
-/// a single row of data to be inserted into the database. It generates an SQL
-/// INSERT statement based on the column names and values of the table row.
-///
-/// # Arguments
-///
-/// * `table_row` - An object implementing the `Table` trait representing
-///                 a single row of data to be inserted.
-/// * `first_statement` - A boolean flag indicating whether this is the first
-///                       statement to be generated.
-///
-/// # Returns
-///
-/// A `Result` containing a `String` representing the generated SQL statement
-/// if successful, or a `Error` if an error occurs during the generation process.
-fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<String, Error> {
-    // Generate strings for columns and values
-    let mut columns_str = String::new();
-    let mut values_str = String::new();
-
-    // Iterate over the fields to generate columns and values
-    let column_fields = table_row.get_column_fields();
-    let column_values = table_row.get_column_values();
-
-    for (column_name, value) in column_fields.iter().zip(column_values.iter()) {
-        // Check if the field is an AutoIncrementPrimaryKey
-        if table_row.is_auto_increment_primary_key(value) {
-            debug!("Skipping AutoIncrementPrimaryKey field in SQL statement generation.");
-            continue;
-        }
+/// A builder for inserting a large number of rows into MSSQL.
+pub type BulkInsertBuilder<'a, I> = builder::BulkInsertBuilder<'a, I, MssqlDialect>;
+
+impl<'a, T: Table, I: Iterator<Item = T>> BulkInsertBuilder<'a, I> {
+    /// Builds and executes the INSERT statements of all chunks in one transaction,
+    /// or in a savepoint when a transaction is already open.
+    ///
+    /// The statements are run through `sp_executesql`, so chunks with the same
+    /// number of rows share one cached query plan.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `Connection` to the MSSQL database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the final progress, with the total number of rows
+    /// inserted, or a `MSSQLError` if an error occurs. Nothing is inserted then.
+    pub async fn build(mut self, conn: &mut Connection) -> Result<BulkInsertProgress, MSSQLError> {
+        let in_transaction = transaction::in_transaction(conn).await?;
+        let statements = TransactionStatements::guard(&MssqlDialect, in_transaction)
+            .expect("MSSQL supports savepoints");
+
+        // The transaction is run by hand, a `transaction` closure can't borrow the builder
+        transaction::execute(conn, &statements.begin).await?;
+
+        let result = self.insert_chunks(conn).await;
+
+        let end = match result {
+            Ok(_) => &statements.commit,
+            Err(_) => &statements.rollback,
+        };
+        transaction::execute(conn, end).await?;
+
+        result
+    }
 
-        // Escape single quotes in the value
-        let escaped_value = value.replace("'", "''");
+    /// Inserts the chunks one after the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `Connection` to the MSSQL database.
+    async fn insert_chunks(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<BulkInsertProgress, MSSQLError> {
+        while let Some(chunk) = self.next_chunk() {
+            let (sql, params) = chunk.render();
 
-        columns_str.push_str(&format!("{}, ", column_name));
-        values_str.push_str(&format!("'{}', ", escaped_value)); // Surround values with single quotes
-    }
+            debug!("{}", sql);
 
-    // Sanitize table name from unwanted quotations or backslashes
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+            let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
 
-    // Remove the trailing comma and space
-    if !columns_str.is_empty() {
-        columns_str.pop();
-        columns_str.pop();
-    }
-    if !values_str.is_empty() {
-        values_str.pop();
-        values_str.pop();
-    }
+            conn.client
+                .execute(sql.as_str(), &binds)
+                .await
+                .map_err(MSSQLError::InsertError)?;
 
-    let sql = if first_statement {
-        format!(
-            "INSERT INTO {} ({}) VALUES ({})",
-            table_name, columns_str, values_str
-        )
-    } else {
-        format!("({})", values_str)
-    };
+            self.record_chunk(chunk.rows().len());
+        }
 
-    Ok(sql)
+        info!("Inserted into table, done.");
+
+        Ok(self.progress())
+    }
 }
@@ -33,8 +33,9 @@ pub mod delete;
 pub mod error;
 pub mod insert;
 pub mod select;
+pub mod transaction;
 pub mod update;
-mod util;
+pub mod upsert;
 
 use tiberius::{error::Error, Client, Config};
 use tokio::net::TcpStream;
@@ -42,9 +43,11 @@ use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
 pub use delete::delete;
 pub use error::MSSQLError;
-pub use insert::insert;
+pub use insert::{bulk_insert, insert};
 pub use select::select;
+pub use transaction::{transaction, transaction_with, Transaction};
 pub use update::update;
+pub use upsert::upsert;
 
 /// Wrapping client inside of connection so the entire type doesn't have to be used every time
 #[derive(Debug)]
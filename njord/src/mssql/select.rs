@@ -30,23 +30,21 @@
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::{
+    builder,
     column::Column,
-    condition::Condition,
-    mssql::util::{
-        generate_group_by_str, generate_having_str, generate_order_by_str,
-        generate_where_condition_str,
-    },
-    query::QueryBuilder,
+    dialect::MssqlDialect,
+    query::{Param, PlaceholderStyle, QueryParams},
+    relation::Related,
+    row::{FromRow, Row as ResultRow},
 };
-use std::{collections::HashMap, sync::Arc};
 
+use futures_util::{Stream, StreamExt};
 use log::info;
-use tiberius::{error::Error, ColumnData};
+use tiberius::{error::Error, FromSql, Row, ToSql};
 
 use crate::table::Table;
-use crate::util::{Join, JoinType};
 
-use super::Connection;
+use super::{Connection, MSSQLError};
 
 /// Constructs a new SELECT query builder.
 ///
@@ -62,360 +60,185 @@ pub fn select<T: Table + Default>(columns: Vec<Column>) -> SelectQueryBuilder<T>
     SelectQueryBuilder::new(columns)
 }
 
-/// A builder for constructing SELECT queries.
-#[derive(Clone)]
-pub struct SelectQueryBuilder<'a, T: Table + Default> {
-    table: Option<T>,
-    columns: Vec<Column<'a>>,
-    where_condition: Option<Condition<'a>>,
-    distinct: bool,
-    group_by: Option<Vec<String>>,
-    order_by: Option<HashMap<Vec<String>, String>>,
-    having_condition: Option<Condition<'a>>,
-    except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
-    union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
-    joins: Option<Vec<Join<'a>>>,
-}
+/// A builder for constructing SELECT queries on MSSQL.
+pub type SelectQueryBuilder<'a, T> = builder::SelectQueryBuilder<'a, T, MssqlDialect>;
 
-impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
-    /// Creates a new `SelectQueryBuilder` instance.
+impl<'a, T: Table + FromRow + Default> SelectQueryBuilder<'a, T> {
+    /// Builds and executes the SELECT query.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `conn` - A `Connection` to the MSSQL database.
-    /// * `columns` - A vector of strings representing the columns to be selected.
-    pub fn new(columns: Vec<Column<'a>>) -> Self {
-        SelectQueryBuilder {
-            table: None,
-            columns,
-            where_condition: None,
-            distinct: false,
-            group_by: None,
-            order_by: None,
-            having_condition: None,
-            except_clauses: None,
-            union_clauses: None,
-            joins: None,
-        }
-    }
+    /// A `Result` containing a vector of selected table rows if successful,
+    /// or a `MSSQLError` if the query fails or a row cannot be decoded.
+    pub async fn build(&mut self, conn: &mut Connection) -> Result<Vec<T>, MSSQLError> {
+        let (final_query, params) = self.render();
 
-    /// Sets the columns to be selected.
-    ///
-    /// # Arguments
-    ///
-    /// * `columns` - A vector of strings representing the columns to be selected.
-    pub fn select(mut self, columns: Vec<Column<'a>>) -> Self {
-        self.columns = columns;
-        self
-    }
+        info!("{}", final_query);
 
-    /// Sets the DISTINCT keyword for the query.
-    pub fn distinct(mut self) -> Self {
-        self.distinct = true;
-        self
+        execute_query(&final_query, &params, conn).await
     }
 
-    /// Sets the table from which to select data.
-    ///
-    /// # Arguments
+    /// Builds and executes the SELECT query, decoding the rows one at a time.
     ///
-    /// * `table` - The table from which to select data.
-    pub fn from(mut self, table: T) -> Self {
-        self.table = Some(table);
-        self
-    }
-
-    /// Sets the WHERE clause condition.
+    /// Unlike `build`, the result set is not buffered; each row is read from the
+    /// connection when the stream reaches it. The connection can't be used for
+    /// other queries until the stream is dropped.
     ///
     /// # Arguments
     ///
-    /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
-        self
-    }
-
-    /// Sets the GROUP BY clause columns.
+    /// * `conn` - A mutable reference to the database connection.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `columns` - A vector of strings representing the columns to be grouped by.
-    pub fn group_by(mut self, columns: Vec<String>) -> Self {
-        self.group_by = Some(columns);
-        self
-    }
+    /// A `Result` containing a stream of the selected table rows if successful,
+    /// or a `MSSQLError` if the query could not be executed.
+    pub async fn build_stream(
+        self,
+        conn: &mut Connection,
+    ) -> Result<impl Stream<Item = Result<T, MSSQLError>> + '_, MSSQLError> {
+        let (final_query, params) = self.render();
 
-    /// Sets the ORDER BY clause columns and order direction.
-    ///
-    /// # Arguments
-    ///
-    /// * `col_and_order` - A HashMap containing column names as keys and order direction as values.
-    pub fn order_by(mut self, col_and_order: HashMap<Vec<String>, String>) -> Self {
-        self.order_by = Some(col_and_order);
-        self
-    }
+        info!("{}", final_query);
 
-    /// Sets the HAVING clause condition.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
-        self
+        let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
+        let stream = conn
+            .client
+            .query(final_query, &binds)
+            .await
+            .map_err(MSSQLError::SelectError)?;
+
+        Ok(stream.into_row_stream().map(|row| {
+            row.map_err(MSSQLError::SelectError)
+                .and_then(|row| decode_row(&row))
+        }))
     }
 
-    /// Adds an EXCEPT clause to the query, allowing you to exclude results from another query.
+    /// Builds and executes the SELECT query, decoding the rows into `R` instead of `T`.
     ///
-    /// This method modifies the current query builder to exclude the results of the specified
-    /// `other_query`. If there are already existing EXCEPT clauses, the new clause will be added
-    /// to the list. If no EXCEPT clauses exist, a new list will be created with the provided
-    /// query.
+    /// `R` is decoded by column name or alias, so it can be a struct deriving `FromRow`
+    /// with only some of the columns, a tuple of single values, or a tuple of structs
+    /// for the tables of a join, e.g. `(User, Option<Address>)` for a LEFT JOIN.
     ///
     /// # Arguments
     ///
-    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
-    ///   should be excluded from the current query.
+    /// * `conn` - A mutable reference to the database connection.
     ///
     /// # Returns
     ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new EXCEPT clause added.
-    pub fn except(mut self, other_query: SelectQueryBuilder<'a, T>) -> Self {
-        match self.except_clauses {
-            Some(ref mut clauses) => clauses.push(other_query),
-            None => self.except_clauses = Some(vec![other_query]),
-        }
-        self
-    }
+    /// A `Result` containing a vector of the decoded rows if successful,
+    /// or a `MSSQLError` if the query fails or a row cannot be decoded.
+    pub async fn build_as<R: FromRow>(self, conn: &mut Connection) -> Result<Vec<R>, MSSQLError> {
+        let (final_query, params) = self.render();
 
-    /// Adds a UNION clause to the query, allowing you to combine results from another query.
-    ///
-    /// This method modifies the current query builder to include the results of the specified
-    /// `other_query`. If there are already existing UNION clauses, the new clause will be added
-    /// to the list. If no UNION clauses exist, a new list will be created with the provided
-    /// query.
-    ///
-    /// # Arguments
-    ///
-    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
-    ///   should be combined with the current query.
-    ///
-    /// # Returns
-    ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new UNION clause added.
-    pub fn union(mut self, other_query: SelectQueryBuilder<'a, T>) -> Self {
-        match self.union_clauses {
-            Some(ref mut clauses) => clauses.push(other_query),
-            None => self.union_clauses = Some(vec![other_query]),
-        }
-        self
+        info!("{}", final_query);
+
+        execute_query(&final_query, &params, conn).await
     }
+}
 
-    /// Adds a JOIN clause to the query, allowing you to combine rows from two or more tables based on a related column.
-    ///
-    /// This method modifies the current query builder to include a join clause with the specified join type,
-    /// target table, and condition for the join. If there are already existing JOIN clauses, the new clause
-    /// will be added to the list. If no JOIN clauses exist, a new list will be created with the provided
-    /// join information.
+/// A builder for SELECT queries on MSSQL loading related rows, created by `with`.
+pub type EagerQueryBuilder<'a, T, R> = builder::EagerQueryBuilder<'a, T, R, MssqlDialect>;
+
+impl<T, R> EagerQueryBuilder<'_, T, R>
+where
+    T: Related<R> + FromRow + Default,
+    R: Table + FromRow + Default + Clone,
+{
+    /// Builds and executes the SELECT query, then loads the related rows with one
+    /// batched query.
     ///
     /// # Arguments
     ///
-    /// * `join_type` - The type of join to perform (e.g., INNER, LEFT, RIGHT, FULL).
-    /// * `table` - The table to join with the current table.
-    /// * `on_condition` - The condition that specifies how the tables are related (the ON clause).
+    /// * `conn` - A mutable reference to the database connection.
     ///
     /// # Returns
     ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new JOIN clause added.
-    pub fn join(
-        mut self,
-        join_type: JoinType,
-        table: Arc<dyn Table>,
-        on_condition: Condition<'a>,
-    ) -> Self {
-        match self.joins {
-            Some(ref mut joins) => joins.push(Join::new(join_type, table, on_condition)),
-            None => self.joins = Some(vec![Join::new(join_type, table, on_condition)]),
-        }
-        self
-    }
-
-    /// Builds the query string, this function should be used internally.
-    pub fn build_query(&self) -> String {
-        let columns_str = self
-            .columns
-            .iter()
-            .map(|c| c.build())
-            .collect::<Vec<String>>()
-            .join(", ");
-
-        let table_name = self
-            .table
-            .as_ref()
-            .map(|t| t.get_name().to_string())
-            .unwrap_or("".to_string());
+    /// A `Result` containing the selected table rows, each paired with its related rows,
+    /// or a `MSSQLError` if a query fails or a row cannot be decoded.
+    pub async fn build(self, conn: &mut Connection) -> Result<Vec<(T, Vec<R>)>, MSSQLError> {
+        let (final_query, params) = self.select().render();
 
-        // Generate JOIN clauses, if any
-        let join_clauses: Vec<String> = match &self.joins {
-            Some(joins) => joins
-                .iter()
-                .map(|join| {
-                    let join_type_str = match join.join_type {
-                        JoinType::Inner => "INNER JOIN",
-                        JoinType::Left => "LEFT JOIN",
-                        JoinType::Right => "RIGHT JOIN",
-                        JoinType::Full => "FULL OUTER JOIN",
-                    };
-                    format!(
-                        "{} {} ON {}",
-                        join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
-                    )
-                })
-                .collect(),
-            None => Vec::new(),
-        };
-
-        let distinct_str = if self.distinct { "DISTINCT " } else { "" };
-        let where_condition_str = generate_where_condition_str(self.where_condition.clone());
-        let group_by_str = generate_group_by_str(&self.group_by);
-        let order_by_str = generate_order_by_str(&self.order_by);
-        let having_str =
-            generate_having_str(self.group_by.is_some(), self.having_condition.as_ref());
+        info!("{}", final_query);
 
-        // Create the JOIN clause or an empty string
-        let join_clause = if !join_clauses.is_empty() {
-            join_clauses.join(" ")
-        } else {
-            String::new()
-        };
+        let parents: Vec<T> = execute_query(&final_query, &params, conn).await?;
 
-        let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {}",
-            distinct_str,
-            columns_str,
-            table_name,
-            join_clause,
-            where_condition_str,
-            group_by_str,
-            having_str,
-            order_by_str,
-        );
+        let mut related = Vec::new();
+        for query in self.related_queries(&parents)? {
+            let (final_query, params) = query.render();
 
-        // Handle EXCEPT clauses
-        if let Some(except_clauses) = &self.except_clauses {
-            for except_query in except_clauses {
-                let except_sql = except_query.build_query();
-                query = format!("{} EXCEPT {}", query, except_sql);
-            }
-        }
+            info!("{}", final_query);
 
-        // Handle UNION clauses
-        if let Some(union_clauses) = &self.union_clauses {
-            for union_query in union_clauses {
-                let union_sql = union_query.build_query();
-                query = format!("{} UNION {}", query, union_sql);
-            }
+            related.extend(execute_query(&final_query, &params, conn).await?);
         }
 
-        query
-    }
-
-    /// Builds and executes the SELECT query.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing a vector of selected table rows if successful,
-    /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub async fn build(&mut self, conn: &mut Connection) -> Result<Vec<T>, Error> {
-        let final_query = self.build_query();
-
-        info!("{}", final_query);
-
-        raw_execute(&final_query, conn).await
+        Ok(self.group(parents, related))
     }
 }
 
-/// Implement `QueryBuilder` for `SelectQueryBuilder`
+/// Executes a raw SQL query and returns the decoded rows.
 ///
-/// The where statement ensures the T is long lived
-impl<'a, T> QueryBuilder<'a> for SelectQueryBuilder<'a, T>
-where
-    T: Table + Default + Clone + 'a, // Added 'a bound here
-{
-    fn to_sql(&self) -> String {
-        self.build_query()
-    }
+/// # Arguments
+///
+/// * `sql` - The SQL query to execute.
+/// * `conn` - A mutable reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `MSSQLError` if the query fails or a row cannot be decoded.
+pub async fn raw_execute<R: FromRow>(
+    sql: &str,
+    conn: &mut Connection,
+) -> Result<Vec<R>, MSSQLError> {
+    execute_query(sql, &QueryParams::new(PlaceholderStyle::AtP), conn).await
 }
 
-/// Executes a raw SQL query and returns the results as a vector of table rows.
+/// Executes a SQL query with its bound parameters and returns the decoded rows.
 ///
 /// # Arguments
 ///
 /// * `sql` - The SQL query to execute.
+/// * `params` - The values bound to the placeholders in `sql`.
 /// * `conn` - A mutable reference to the database connection.
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of table rows if successful,
-/// or a `rusqlite::Error` if an error occurs during the execution.
-pub async fn raw_execute<T: Table + Default>(
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `MSSQLError` if the query fails or a row cannot be decoded.
+async fn execute_query<R: FromRow>(
     sql: &str,
+    params: &QueryParams,
     conn: &mut Connection,
-) -> Result<Vec<T>, Error> {
-    let mut stream = conn.client.query(sql, &[]).await?;
+) -> Result<Vec<R>, MSSQLError> {
+    let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
+    let rows = conn
+        .client
+        .query(sql, &binds)
+        .await
+        .map_err(MSSQLError::SelectError)?
+        .into_first_result()
+        .await
+        .map_err(MSSQLError::SelectError)?;
+
+    rows.iter().map(decode_row).collect()
+}
 
-    // Clone the column names so we can use the stream later
-    let columns: Vec<String> = stream
+/// Decodes a result row from the values sent by the server.
+///
+/// # Arguments
+///
+/// * `row` - The row read from the database.
+pub(super) fn decode_row<R: FromRow>(row: &Row) -> Result<R, MSSQLError> {
+    let columns: Vec<String> = row
         .columns()
-        .await?
-        .unwrap()
         .iter()
-        .map(|col| col.name().to_lowercase().to_string())
+        .map(|column| column.name().to_lowercase())
         .collect();
+    let values = row
+        .cells()
+        .map(|(_, data)| Param::from_sql(data).map(|param| param.unwrap_or(Param::Null)))
+        .collect::<Result<Vec<_>, Error>>()
+        .map_err(MSSQLError::SelectError)?;
 
-    let rows = stream.into_first_result().await?;
-
-    let mut results: Vec<T> = Vec::new();
-
-    for row_result in rows {
-        let mut instance = T::default();
-
-        for (idx, column_data) in row_result.into_iter().enumerate() {
-            let col_name = &columns[idx];
-            // let column_value_str = format!("{:?}", column_data);
-            let column_value_str = match column_data {
-                ColumnData::U8(Some(val)) => val.to_string(),
-                ColumnData::I16(Some(val)) => val.to_string(),
-                ColumnData::I32(Some(val)) => val.to_string(),
-                ColumnData::I64(Some(val)) => val.to_string(),
-                ColumnData::F32(Some(val)) => val.to_string(),
-                ColumnData::F64(Some(val)) => val.to_string(),
-                ColumnData::Bit(Some(val)) => val.to_string(),
-                ColumnData::String(Some(val)) => val.to_string(),
-                ColumnData::Guid(Some(val)) => val.to_string(),
-                ColumnData::Binary(Some(val)) => format!("{:?}", val), // Handle binary data with debug format.
-                ColumnData::Numeric(Some(val)) => val.to_string(),
-                ColumnData::Xml(Some(val)) => format!("{:?}", val), // XML might need custom formatting.
-                // ColumnData::DateTime(Some(val)) => {
-                //     // Format Days since 1st of January, 1900
-                //     format!("{}:00", val.days())
-                // }
-                // ColumnData::SmallDateTime(Some(val)) => format!("{}:00", val.days()),
-                // ColumnData::Time(Some(val)) => val.to_string(),
-                // ColumnData::Date(Some(val)) => val.to_string(),
-                // ColumnData::DateTime2(Some(val)) => val.to_string(),
-                // ColumnData::DateTimeOffset(Some(val)) => val.to_string(),
-                _ => "NULL".to_string(), // Handle the None cases.
-            };
-
-            instance.set_column_value(col_name, &column_value_str);
-        }
-
-        results.push(instance);
-    }
-
-    Ok(results)
+    Ok(R::from_row(&ResultRow::new(&columns, &values))?)
 }
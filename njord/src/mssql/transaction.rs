@@ -0,0 +1,286 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::ops::{Deref, DerefMut};
+
+use tiberius::{error::Error as MSSQLLibError, ToSql};
+
+use log::error;
+
+use crate::{
+    dialect::MssqlDialect,
+    transaction::{BoxFuture, IsolationLevel, TransactionStatements},
+};
+
+use super::{error::MSSQLError, Connection};
+
+/// A transaction running on an MSSQL connection.
+///
+/// The transaction dereferences to its connection, so every query builder can be
+/// run inside it. A transaction whose future is dropped before it finished stays
+/// open, and the connection should not be used any further.
+pub struct Transaction<'c> {
+    conn: &'c mut Connection,
+    depth: usize,
+    statements: TransactionStatements,
+}
+
+impl<'c> Transaction<'c> {
+    async fn begin(
+        conn: &'c mut Connection,
+        depth: usize,
+        isolation_level: Option<IsolationLevel>,
+    ) -> Result<Self, MSSQLError> {
+        let statements = TransactionStatements::new(&MssqlDialect, depth, isolation_level)
+            .expect("MSSQL supports savepoints");
+
+        execute(conn, &statements.begin).await?;
+
+        Ok(Transaction {
+            conn,
+            depth,
+            statements,
+        })
+    }
+
+    /// Runs a closure inside a transaction nested in this one.
+    ///
+    /// The nested transaction is a savepoint (`SAVE TRANSACTION`). Rolling it back keeps
+    /// the changes made by the outer transaction before it started.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run. It returns its future boxed, e.g. with `Box::pin`.
+    ///
+    /// # Returns
+    ///
+    /// The value returned by the closure, or the error the closure or the database
+    /// returned.
+    pub async fn transaction<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        E: From<MSSQLError>,
+        F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+    {
+        run(
+            Transaction::begin(self.conn, self.depth + 1, None).await?,
+            f,
+        )
+        .await
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl DerefMut for Transaction<'_> {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+    }
+}
+
+/// Runs a closure inside a transaction.
+///
+/// The transaction is committed if the closure returns `Ok`, and rolled back if it
+/// returns `Err`.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `f` - The closure to run. It gets the transaction to run its queries on and
+///   returns its future boxed, e.g. with `Box::pin`.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub async fn transaction<T, E, F>(conn: &mut Connection, f: F) -> Result<T, E>
+where
+    E: From<MSSQLError>,
+    F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+{
+    run(Transaction::begin(conn, 0, None).await?, f).await
+}
+
+/// Runs a closure inside a transaction with the given isolation level.
+///
+/// `Snapshot` requires `ALLOW_SNAPSHOT_ISOLATION` to be enabled on the database. The
+/// session is set back to `ReadCommitted` when the transaction ends.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `isolation_level` - The isolation level to run the transaction with.
+/// * `f` - The closure to run. It gets the transaction to run its queries on and
+///   returns its future boxed, e.g. with `Box::pin`.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub async fn transaction_with<T, E, F>(
+    conn: &mut Connection,
+    isolation_level: IsolationLevel,
+    f: F,
+) -> Result<T, E>
+where
+    E: From<MSSQLError>,
+    F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+{
+    run(Transaction::begin(conn, 0, Some(isolation_level)).await?, f).await
+}
+
+async fn run<T, E, F>(mut tx: Transaction<'_>, f: F) -> Result<T, E>
+where
+    E: From<MSSQLError>,
+    F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<T, E>>,
+{
+    let result = f(&mut tx).await;
+
+    let ended = match result {
+        Ok(_) => execute(tx.conn, &tx.statements.commit).await,
+        Err(_) => {
+            roll_back(tx.conn, &tx.statements).await;
+            Ok(())
+        }
+    };
+
+    // A failed commit leaves the transaction open
+    if ended.is_err() {
+        roll_back(tx.conn, &tx.statements).await;
+    }
+    ended?;
+
+    result
+}
+
+/// Rolls a transaction back, only logging a failure so the error that caused it is kept.
+async fn roll_back(conn: &mut Connection, statements: &TransactionStatements) {
+    if let Err(err) = execute(conn, &statements.rollback).await {
+        error!("Failed to roll back the transaction: {:?}", err);
+    }
+}
+
+/// Executes a write checked with `expect_rows`, undoing it when the check fails.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `query` - The statement to execute.
+/// * `binds` - The values bound to the placeholders of the statement.
+/// * `expected_rows` - The number of rows the write is expected to affect, if set.
+/// * `map_err` - Converts an error executing the statement.
+///
+/// # Returns
+///
+/// The number of affected rows, or an error executing the statement or an
+/// `UnexpectedRowCount` error.
+pub(crate) async fn expect_rows(
+    conn: &mut Connection,
+    query: &str,
+    binds: &[&dyn ToSql],
+    expected_rows: Option<u64>,
+    map_err: fn(MSSQLLibError) -> MSSQLError,
+) -> Result<u64, MSSQLError> {
+    let Some(expected) = expected_rows else {
+        return execute_write(conn, query, binds, map_err).await;
+    };
+
+    let statements = TransactionStatements::guard(&MssqlDialect, in_transaction(conn).await?)
+        .expect("MSSQL supports savepoints");
+
+    execute(conn, &statements.begin).await?;
+
+    let result = match execute_write(conn, query, binds, map_err).await {
+        Ok(rows) if rows != expected => Err(MSSQLError::UnexpectedRowCount {
+            expected,
+            actual: rows,
+        }),
+        result => result,
+    };
+
+    let end = match result {
+        Ok(_) => &statements.commit,
+        Err(_) => &statements.rollback,
+    };
+    execute(conn, end).await?;
+
+    result
+}
+
+async fn execute_write(
+    conn: &mut Connection,
+    query: &str,
+    binds: &[&dyn ToSql],
+    map_err: fn(MSSQLLibError) -> MSSQLError,
+) -> Result<u64, MSSQLError> {
+    conn.client
+        .execute(query, binds)
+        .await
+        .map(|result| result.total())
+        .map_err(map_err)
+}
+
+/// Checks whether a transaction is open on the connection.
+pub(crate) async fn in_transaction(conn: &mut Connection) -> Result<bool, MSSQLError> {
+    let row = conn
+        .client
+        .simple_query("SELECT @@TRANCOUNT")
+        .await
+        .map_err(MSSQLError::TransactionError)?
+        .into_row()
+        .await
+        .map_err(MSSQLError::TransactionError)?;
+
+    Ok(row.and_then(|row| row.get::<i32, _>(0)).unwrap_or(0) > 0)
+}
+
+/// Executes transaction control statements one after the other.
+pub(crate) async fn execute(
+    conn: &mut Connection,
+    statements: &[String],
+) -> Result<(), MSSQLError> {
+    // Sent as plain batches, SET statements run through sp_executesql would only last
+    // for that call
+    for statement in statements {
+        conn.client
+            .simple_query(statement.as_str())
+            .await
+            .map_err(MSSQLError::TransactionError)?
+            .into_results()
+            .await
+            .map_err(MSSQLError::TransactionError)?;
+    }
+
+    Ok(())
+}
@@ -31,16 +31,13 @@
 
 use std::collections::HashMap;
 
-use crate::{
-    condition::Condition,
-    mssql::util::{generate_where_condition_str, remove_quotes_and_backslashes},
-};
+use crate::{builder, condition::Condition, dialect::MssqlDialect, table::Table};
 
-use log::{debug, info};
+use log::debug;
 
-use crate::table::Table;
+use tiberius::ToSql;
 
-use super::{select::SelectQueryBuilder, Connection};
+use super::{select::SelectQueryBuilder, transaction, Connection, MSSQLError};
 
 /// Constructs a new UPDATE query builder.
 ///
@@ -52,17 +49,16 @@ use super::{select::SelectQueryBuilder, Connection};
 /// # Returns
 ///
 /// An `UpdateQueryBuilder` instance.
-pub fn update<T: Table + Default>(conn: &mut Connection, table: T) -> UpdateQueryBuilder<T> {
+pub fn update<T: Table + Default>(conn: &mut Connection, table: T) -> UpdateQueryBuilder<'_, T> {
     UpdateQueryBuilder::new(conn, table)
 }
 
-/// A builder for constructing UPDATE queries.
+/// A builder for constructing UPDATE queries on MSSQL.
+///
+/// Unlike the other backends it holds on to the connection it is executed on.
 pub struct UpdateQueryBuilder<'a, T: Table + Default> {
     conn: &'a mut Connection,
-    table: Option<T>,
-    columns: Vec<String>,
-    sub_queries: HashMap<String, SelectQueryBuilder<'a, T>>,
-    where_condition: Option<Condition<'a>>,
+    query: builder::UpdateQueryBuilder<'a, T, MssqlDialect>,
 }
 
 impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
@@ -75,10 +71,7 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     pub fn new(conn: &'a mut Connection, table: T) -> Self {
         UpdateQueryBuilder {
             conn,
-            table: Some(table),
-            columns: Vec::new(),
-            sub_queries: HashMap::new(),
-            where_condition: None,
+            query: builder::UpdateQueryBuilder::new(table),
         }
     }
 
@@ -86,19 +79,23 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     ///
     /// # Arguments
     ///
-    /// * `columns` - A vector of strings representing the columns to be updated.
-    pub fn set(mut self, columns: Vec<String>) -> Self {
-        self.columns = columns;
+    /// * `columns` - The columns to be updated, as strings or typed column handles.
+    pub fn set<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.query = self.query.set(columns);
         self
     }
 
     /// Sets the columns and values (as subquery) to be updated.
     ///
     /// # Arguments
-    ///     
+    ///
     /// * `columns` - A hashmap representing the columns and their subqueries.
     pub fn set_subqueries(mut self, columns: HashMap<String, SelectQueryBuilder<'a, T>>) -> Self {
-        self.sub_queries = columns;
+        self.query = self.query.set_subqueries(columns);
         self
     }
 
@@ -108,7 +105,54 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
     pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+        self.query = self.query.where_clause(condition);
+        self
+    }
+
+    /// Sets the ORDER BY clause columns and order direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_and_order` - Pairs of columns and their order direction, e.g. a `HashMap` or
+    ///   `[User::ID.desc()]`.
+    pub fn order_by<I>(mut self, col_and_order: I) -> Self
+    where
+        I: IntoIterator<Item = (Vec<String>, String)>,
+    {
+        self.query = self.query.order_by(col_and_order);
+        self
+    }
+
+    /// Sets the LIMIT clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The maximum number of rows to be updated.
+    pub fn limit(mut self, count: usize) -> Self {
+        self.query = self.query.limit(count);
+        self
+    }
+
+    /// Sets the OFFSET clause for the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The offset from which to start updating rows.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.query = self.query.offset(offset);
+        self
+    }
+
+    /// Makes the query fail unless exactly `count` rows are updated.
+    ///
+    /// The statement runs in a transaction of its own, or in a savepoint inside of
+    /// a transaction, which is rolled back when the check fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows the query is expected to affect.
+    pub fn expect_rows(mut self, count: u64) -> Self {
+        self.query = self.query.expect_rows(count);
         self
     }
 
@@ -116,65 +160,42 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     ///
     /// # Returns
     ///
-    /// A `Result` indicating success or failure of the update operation.
-    pub async fn build(self) -> Result<String, String> {
-        let table_name = self
-            .table
-            .as_ref()
-            .map(|t| t.get_name().to_string())
-            .unwrap_or("".to_string());
-
-        // Sanitize table name from unwanted quotations or backslashes
-        let table_name_str = remove_quotes_and_backslashes(&table_name);
-
-        // Generate SET clause
-        let set = if let Some(table) = &self.table {
-            let mut set_fields = Vec::new();
-            let fields = table.get_column_fields();
-            let values = table.get_column_values();
-
-            for column in &self.columns {
-                // Check if column exists in the table's fields
-                if let Some(index) = fields.iter().position(|c| column == c) {
-                    let value = values.get(index).cloned().unwrap_or_default();
-                    let formatted_value = if value.is_empty() {
-                        "NULL".to_string()
-                    } else if value.parse::<f64>().is_ok() {
-                        value
-                    } else {
-                        format!("'{}'", value)
-                    };
-                    set_fields.push(format!("{} = {}", column, formatted_value));
-                } else {
-                    // Handle the case when the column doesn't exist in the table
-                    eprintln!("Column '{}' does not exist in the table", column);
-                }
-            }
-
-            // Generate subqueries
-            for (column_name, sub_query) in &self.sub_queries {
-                let formatted_value = format!("({})", sub_query.build_query());
-                set_fields.push(format!("{} = {}", column_name, formatted_value));
-            }
-
-            set_fields.join(", ")
-        } else {
-            String::new()
-        };
-
-        let where_condition_str = generate_where_condition_str(self.where_condition);
-
-        // Construct the query based on defined variables above
-        let query = format!(
-            "UPDATE {} SET {} {}",
-            table_name_str, set, where_condition_str,
-        );
-
-        debug!("{}", query);
-
-        match self.conn.client.execute(query.as_str(), &[]).await {
-            Ok(_) => Ok("Success!".to_string()),
-            Err(_) => Err("Could not execute...".to_string()),
-        }
+    /// A `Result` containing the number of updated rows if successful,
+    /// or a `MSSQLError` if the update fails or `expect_rows` is not met.
+    pub async fn build(self) -> Result<u64, MSSQLError> {
+        execute(self.query, self.conn).await
     }
 }
+
+/// Executes an UPDATE query on a MSSQL connection.
+///
+/// # Arguments
+///
+/// * `query` - The UPDATE query to execute.
+/// * `conn` - A `Connection` to the MSSQL database.
+///
+/// # Returns
+///
+/// A `Result` containing the number of updated rows if successful,
+/// or a `MSSQLError` if the update fails or `expect_rows` is not met.
+pub(crate) async fn execute<T: Table + Default>(
+    query: builder::UpdateQueryBuilder<'_, T, MssqlDialect>,
+    conn: &mut Connection,
+) -> Result<u64, MSSQLError> {
+    query.validate()?;
+
+    let (sql, params) = query.render();
+
+    debug!("{}", sql);
+
+    let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
+
+    transaction::expect_rows(
+        conn,
+        &sql,
+        &binds,
+        query.expected_rows(),
+        MSSQLError::UpdateError,
+    )
+    .await
+}
@@ -29,20 +29,12 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::collections::HashMap;
+use crate::{builder, dialect::MySqlDialect, table::Table};
 
-use crate::{
-    condition::Condition,
-    mysql::util::{
-        generate_limit_str, generate_offset_str, generate_order_by_str,
-        generate_where_condition_str, remove_quotes_and_backslashes,
-    },
-};
+use log::debug;
+use mysql::{prelude::Queryable, Params, PooledConn};
 
-use log::{debug, info};
-use mysql::{prelude::Queryable, PooledConn};
-
-use crate::table::Table;
+use super::{transaction, MySqlError};
 
 /// Constructs a new DELETE query builder.
 ///
@@ -53,113 +45,29 @@ pub fn delete<T: Table + Default>() -> DeleteQueryBuilder<'static, T> {
     DeleteQueryBuilder::new()
 }
 
-/// A builder for constructing DELETE queries.
-pub struct DeleteQueryBuilder<'a, T: Table + Default> {
-    table: Option<T>,
-    where_condition: Option<Condition<'a>>,
-    order_by: Option<HashMap<Vec<String>, String>>,
-    limit: Option<usize>,
-    offset: Option<usize>,
-}
+/// A builder for constructing DELETE queries on MySQL.
+pub type DeleteQueryBuilder<'a, T> = builder::DeleteQueryBuilder<'a, T, MySqlDialect>;
 
 impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
-    /// Creates a new `DeleteQueryBuilder` instance.
-    pub fn new() -> Self {
-        DeleteQueryBuilder {
-            table: None,
-            where_condition: None,
-            order_by: None,
-            limit: None,
-            offset: None,
-        }
-    }
-
-    /// Sets the table from which to delete data.
-    ///
-    /// # Arguments
-    ///
-    /// * `table` - An instance of the table from which to delete data.
-    pub fn from(mut self, table: T) -> Self {
-        self.table = Some(table);
-        self
-    }
-
-    /// Sets the WHERE clause condition.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
-        self
-    }
-
-    /// Sets the ORDER BY clause columns and order direction.
-    ///
-    /// # Arguments
-    ///
-    /// * `col_and_order` - A hashmap representing the columns and their order directions.
-    pub fn order_by(mut self, col_and_order: HashMap<Vec<String>, String>) -> Self {
-        self.order_by = Some(col_and_order);
-        self
-    }
-
-    /// Sets the LIMIT clause for the query.
-    ///
-    /// # Arguments
-    ///
-    /// * `count` - The maximum number of rows to be deleted.
-    pub fn limit(mut self, count: usize) -> Self {
-        self.limit = Some(count);
-        self
-    }
-
-    /// Sets the OFFSET clause for the query.
-    ///
-    /// # Arguments
-    ///
-    /// * `offset` - The offset from which to start deleting rows.
-    pub fn offset(mut self, offset: usize) -> Self {
-        self.offset = Some(offset);
-        self
-    }
-
     /// Builds and executes the DELETE query.
     ///
     /// # Arguments
     ///
     /// * `conn` - A `PooledConn` to the MySql database.
-    /// 
+    ///
     /// # Returns
     ///
-    /// A `Result` indicating success or failure of the deletion operation.
-    pub fn build(self, conn: &mut PooledConn) -> Result<(), String> {
-        let table_name = self
-            .table
-            .as_ref()
-            .map(|t| t.get_name().to_string())
-            .unwrap_or("".to_string());
-
-        // Sanitize table name from unwanted quotations or backslashes
-        let table_name_str = remove_quotes_and_backslashes(&table_name);
-        let where_condition_str = generate_where_condition_str(self.where_condition);
-        let order_by_str = generate_order_by_str(&self.order_by);
-        let limit_str = generate_limit_str(self.limit);
-        let offset_str = generate_offset_str(self.offset);
-
-        // Construct the query based on defined variables above
-        let query = format!(
-            "DELETE FROM {} {} {} {}",
-            table_name_str,
-            where_condition_str,
-            order_by_str,
-            format!("{} {}", limit_str, offset_str),
-        );
+    /// A `Result` containing the number of deleted rows if successful,
+    /// or a `MySqlError` if the deletion fails or `expect_rows` is not met.
+    pub fn build(self, conn: &mut PooledConn) -> Result<u64, MySqlError> {
+        let (query, params) = self.render();
 
         debug!("{}", query);
 
-        let _ = conn.query_drop(&query.to_string());
-
-        Ok(())
+        transaction::expect_rows(conn, self.expected_rows(), |conn| {
+            conn.exec_drop(&query, Params::from(params.values().to_vec()))
+                .map(|_| conn.affected_rows())
+                .map_err(MySqlError::DeleteError)
+        })
     }
 }
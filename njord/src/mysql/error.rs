@@ -31,6 +31,8 @@
 
 use mysql::Error as MySqlLibError;
 
+use crate::{builder::BuildError, row::DecodeError};
+
 /// Represents errors that can occur during SQLite operations.
 #[derive(Debug)]
 pub enum MySqlError {
@@ -42,6 +44,14 @@ pub enum MySqlError {
     UpdateError(MySqlLibError),
     /// Error that occurs during a DELETE operation.
     DeleteError(MySqlLibError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(MySqlLibError),
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
 }
 
 impl From<MySqlLibError> for MySqlError {
@@ -50,3 +60,17 @@ impl From<MySqlLibError> for MySqlError {
         MySqlError::InsertError(error)
     }
 }
+
+impl From<DecodeError> for MySqlError {
+    /// Converts a `DecodeError` into a `MySqlError`.
+    fn from(error: DecodeError) -> Self {
+        MySqlError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for MySqlError {
+    /// Converts a `BuildError` into a `MySqlError`.
+    fn from(error: BuildError) -> Self {
+        MySqlError::BuildError(error)
+    }
+}
@@ -0,0 +1,305 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::ops::{Deref, DerefMut};
+
+use mysql::{prelude::Queryable, PooledConn};
+
+use log::error;
+
+use crate::{
+    dialect::MySqlDialect,
+    transaction::{IsolationLevel, TransactionStatements},
+};
+
+use super::MySqlError;
+
+/// The user variable set while a transaction started by [`transaction`] is open.
+const IN_TRANSACTION: &str = "@njord_in_transaction";
+
+/// A transaction running on a MySQL connection.
+///
+/// The transaction dereferences to its connection, so every query builder can be
+/// run inside it. It is rolled back if it is dropped without being finished, e.g.
+/// when the closure passed to [`transaction`] panics.
+pub struct Transaction<'c> {
+    conn: &'c mut PooledConn,
+    depth: usize,
+    statements: TransactionStatements,
+    finished: bool,
+}
+
+impl<'c> Transaction<'c> {
+    fn begin(
+        conn: &'c mut PooledConn,
+        depth: usize,
+        isolation_level: Option<IsolationLevel>,
+    ) -> Result<Self, MySqlError> {
+        let mut statements = TransactionStatements::new(&MySqlDialect, depth, isolation_level)
+            .expect("MySQL supports savepoints");
+
+        // MySQL can't tell whether a transaction is open, see `in_transaction`
+        if depth == 0 {
+            statements.begin.push(format!("SET {} = 1", IN_TRANSACTION));
+            statements
+                .commit
+                .push(format!("SET {} = NULL", IN_TRANSACTION));
+            statements
+                .rollback
+                .push(format!("SET {} = NULL", IN_TRANSACTION));
+        }
+
+        execute(&mut *conn, &statements.begin)?;
+
+        Ok(Transaction {
+            conn,
+            depth,
+            statements,
+            finished: false,
+        })
+    }
+
+    /// Runs a closure inside a transaction nested in this one.
+    ///
+    /// The nested transaction is a savepoint. Rolling it back keeps the changes made
+    /// by the outer transaction before it started.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure to run.
+    ///
+    /// # Returns
+    ///
+    /// The value returned by the closure, or the error the closure or the database
+    /// returned.
+    pub fn transaction<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        E: From<MySqlError>,
+        F: FnOnce(&mut Transaction) -> Result<T, E>,
+    {
+        run(Transaction::begin(self.conn, self.depth + 1, None)?, f)
+    }
+
+    fn finish(mut self, statements: &[String]) -> Result<(), MySqlError> {
+        // Left unfinished on failure, so that dropping it rolls the transaction back
+        execute(self.conn, statements)?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl Deref for Transaction<'_> {
+    type Target = PooledConn;
+
+    fn deref(&self) -> &PooledConn {
+        &*self.conn
+    }
+}
+
+impl DerefMut for Transaction<'_> {
+    fn deref_mut(&mut self) -> &mut PooledConn {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = execute(self.conn, &self.statements.rollback);
+        }
+    }
+}
+
+/// Runs a closure inside a transaction.
+///
+/// The transaction is committed if the closure returns `Ok`, and rolled back if it
+/// returns `Err`.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `f` - The closure to run. It gets the transaction to run its queries on.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub fn transaction<T, E, F>(conn: &mut PooledConn, f: F) -> Result<T, E>
+where
+    E: From<MySqlError>,
+    F: FnOnce(&mut Transaction) -> Result<T, E>,
+{
+    run(Transaction::begin(conn, 0, None)?, f)
+}
+
+/// Runs a closure inside a transaction with the given isolation level.
+///
+/// MySQL has no snapshot isolation level, `Snapshot` runs the transaction
+/// serializable.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `isolation_level` - The isolation level to run the transaction with.
+/// * `f` - The closure to run. It gets the transaction to run its queries on.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub fn transaction_with<T, E, F>(
+    conn: &mut PooledConn,
+    isolation_level: IsolationLevel,
+    f: F,
+) -> Result<T, E>
+where
+    E: From<MySqlError>,
+    F: FnOnce(&mut Transaction) -> Result<T, E>,
+{
+    run(Transaction::begin(conn, 0, Some(isolation_level))?, f)
+}
+
+fn run<T, E, F>(mut tx: Transaction, f: F) -> Result<T, E>
+where
+    E: From<MySqlError>,
+    F: FnOnce(&mut Transaction) -> Result<T, E>,
+{
+    match f(&mut tx) {
+        Ok(value) => {
+            let commit = tx.statements.commit.clone();
+            tx.finish(&commit)?;
+            Ok(value)
+        }
+        Err(err) => {
+            let rollback = tx.statements.rollback.clone();
+            if let Err(rollback_err) = tx.finish(&rollback) {
+                error!("Failed to roll back the transaction: {:?}", rollback_err);
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Runs a write that is undone as a whole when it fails.
+///
+/// Outside of transactions the write runs in a transaction of its own, inside of
+/// one in a savepoint, so the transaction is neither committed nor rolled back.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `f` - The closure executing the write.
+///
+/// # Returns
+///
+/// The value returned by the closure, or the error the closure or the database
+/// returned.
+pub(crate) fn guarded<T, F>(conn: &mut PooledConn, f: F) -> Result<T, MySqlError>
+where
+    F: FnOnce(&mut PooledConn) -> Result<T, MySqlError>,
+{
+    let statements = TransactionStatements::guard(&MySqlDialect, in_transaction(conn)?)
+        .expect("MySQL supports savepoints");
+
+    execute(conn, &statements.begin)?;
+
+    let result = f(conn);
+
+    let ended = match result {
+        Ok(_) => execute(conn, &statements.commit),
+        Err(_) => {
+            roll_back(conn, &statements);
+            Ok(())
+        }
+    };
+
+    // A failed commit leaves the transaction open
+    if ended.is_err() {
+        roll_back(conn, &statements);
+    }
+    ended?;
+
+    result
+}
+
+/// Rolls a write back, only logging a failure so the error that caused it is kept.
+fn roll_back(conn: &mut PooledConn, statements: &TransactionStatements) {
+    if let Err(err) = execute(conn, &statements.rollback) {
+        error!("Failed to roll back the write: {:?}", err);
+    }
+}
+
+/// Runs a write checked with `expect_rows`, undoing it when the check fails.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the database connection.
+/// * `expected_rows` - The number of rows the write is expected to affect, if set.
+/// * `f` - The closure executing the write. It returns the number of affected rows.
+///
+/// # Returns
+///
+/// The number of affected rows, or the error the closure returned or an
+/// `UnexpectedRowCount` error.
+pub(crate) fn expect_rows<F>(
+    conn: &mut PooledConn,
+    expected_rows: Option<u64>,
+    f: F,
+) -> Result<u64, MySqlError>
+where
+    F: FnOnce(&mut PooledConn) -> Result<u64, MySqlError>,
+{
+    let Some(expected) = expected_rows else {
+        return f(conn);
+    };
+
+    guarded(conn, |conn| {
+        f(conn).and_then(|rows| match rows {
+            rows if rows == expected => Ok(rows),
+            actual => Err(MySqlError::UnexpectedRowCount { expected, actual }),
+        })
+    })
+}
+
+/// Checks whether a transaction started by [`transaction`] is open on the connection.
+fn in_transaction(conn: &mut PooledConn) -> Result<bool, MySqlError> {
+    conn.query_first::<Option<u8>, _>(format!("SELECT {}", IN_TRANSACTION))
+        .map(|marker| marker.flatten().is_some())
+        .map_err(MySqlError::TransactionError)
+}
+
+/// Executes transaction control statements one after the other.
+pub(crate) fn execute(conn: &mut PooledConn, statements: &[String]) -> Result<(), MySqlError> {
+    for statement in statements {
+        conn.query_drop(statement)
+            .map_err(MySqlError::TransactionError)?;
+    }
+
+    Ok(())
+}
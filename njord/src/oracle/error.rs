@@ -31,6 +31,8 @@
 
 use oracle::Error as OracleLibError;
 
+use crate::{builder::BuildError, row::DecodeError};
+
 /// Represents errors that can occur during SQLite operations.
 #[derive(Debug)]
 pub enum OracleError {
@@ -42,6 +44,14 @@ pub enum OracleError {
     UpdateError(OracleLibError),
     /// Error that occurs during a DELETE operation.
     DeleteError(OracleLibError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(OracleLibError),
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
 }
 
 impl From<OracleLibError> for OracleError {
@@ -50,3 +60,17 @@ impl From<OracleLibError> for OracleError {
         OracleError::InsertError(error)
     }
 }
+
+impl From<DecodeError> for OracleError {
+    /// Converts a `DecodeError` into a `OracleError`.
+    fn from(error: DecodeError) -> Self {
+        OracleError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for OracleError {
+    /// Converts a `BuildError` into a `OracleError`.
+    fn from(error: BuildError) -> Self {
+        OracleError::BuildError(error)
+    }
+}
@@ -29,178 +29,188 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{query::QueryBuilder, table::Table};
-
-use oracle::Connection;
-use rusqlite::Error as RusqliteError;
+use crate::{
+    builder::{self, BulkInsertProgress},
+    dialect::OracleDialect,
+    query::{Param, QueryBuilder},
+    table::Table,
+};
+
+use oracle::{
+    sql_type::{OracleType, ToSql},
+    Connection,
+};
 
 use log::{debug, info};
-use std::fmt::Error;
+
+use super::{transaction, OracleError};
 
 /// Inserts rows into a Oracle table.
 ///
 /// This function takes a `Connection` and a vector of objects implementing
 /// the `Table` trait, which represents rows to be inserted into the table.
-/// It generates SQL INSERT statements for each row and executes them within
-/// a transaction.
+///
+/// If the rows have an `AutoIncrementPrimaryKey` left to the database, they are
+/// inserted one at a time, in a transaction or in a savepoint of an open one, and
+/// the generated keys are read back with `RETURNING ... INTO`, since `INSERT ALL`
+/// can't return values. Otherwise all rows are inserted with a single statement.
 ///
 /// # Arguments
 ///
 /// * `conn` - A `Connection` to the Oracle database.
 /// * `table_rows` - A vector of objects implementing the `Table` trait representing
-///                  the rows to be inserted into the database.
+///   the rows to be inserted into the database.
 ///
 /// # Returns
 ///
-/// A `Result` containing a `String` representing the joined SQL statements
-/// if the insertion is successful, or a `RusqliteError` if an error occurs.
-pub fn insert<T: Table>(
-    conn: &mut Connection,
-    table_rows: Vec<T>,
-) -> Result<String, RusqliteError> {
-    let mut statements: Vec<String> = Vec::new();
-    for (index, table_row) in table_rows.iter().enumerate() {
-        match generate_statement(table_row, index == 0) {
-            Ok(statement) => statements.push(statement),
-            Err(_) => return Err(RusqliteError::InvalidQuery),
-        }
+/// A `Result` containing the inserted rows with their generated keys set
+/// if the insertion is successful, or a `OracleError` if an error occurs.
+pub fn insert<T: Table>(conn: &mut Connection, table_rows: Vec<T>) -> Result<Vec<T>, OracleError> {
+    if table_rows.is_empty() {
+        return Ok(Vec::new());
     }
 
-    let joined_statements = statements.join(", ");
+    let builder = builder::InsertQueryBuilder::<T, OracleDialect>::new().values(table_rows);
+    builder.validate()?;
+
+    let Some(key) = builder.generated_key() else {
+        let (sql, params) = builder.render();
+        debug!("{}", sql);
 
-    debug!("{}", joined_statements);
+        let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
 
-    let _ = match conn.execute(&joined_statements, &[]) {
-        Ok(_) => info!("Inserted into table, done."),
-        Err(err) => eprintln!("Error: {}", err),
+        conn.execute(&sql, &binds)
+            .map_err(OracleError::InsertError)?;
+
+        info!("Inserted into table, done.");
+
+        return Ok(builder.into_rows());
     };
 
-    let _ = conn.commit();
+    // A failing row undoes the ones before it, without ending an open transaction
+    transaction::guarded(conn, |conn| {
+        let mut rows = Vec::new();
+
+        for row in builder.into_rows() {
+            let row_builder = builder::InsertQueryBuilder::<T, OracleDialect>::new()
+                .values(vec![row])
+                .returning([key.as_str()]);
+            let (sql, params) = row_builder.render();
+            debug!("{}", sql);
+
+            // The last placeholder receives the generated key
+            let mut binds: Vec<&dyn ToSql> =
+                params.values().iter().map(|v| v as &dyn ToSql).collect();
+            binds.pop();
+            binds.push(&OracleType::Int64);
+
+            let stmt = conn
+                .execute(&sql, &binds)
+                .map_err(OracleError::InsertError)?;
+            let keys = stmt
+                .returned_values::<usize, i64>(params.len())
+                .map_err(OracleError::InsertError)?;
+
+            rows.extend(row_builder.into_rows_with_keys(keys.into_iter().map(Param::Int))?);
+        }
 
-    info!("Inserted into table, done.");
+        info!("Inserted into table, done.");
 
-    // FIXME: Return the number of rows affected
-    Ok(joined_statements)
+        Ok(rows)
+    })
 }
 
-/// Generates an SQL INSERT INTO statement for a given table row.
+/// Inserts the rows selected by a subquery into a table.
 ///
 /// # Arguments
 ///
-/// * `table_row` - A reference to an object implementing the `Table` trait.
+/// * `conn` - A `Connection` to the Oracle database.
+/// * `columns` - The columns the subquery's columns are inserted into.
+/// * `subquery` - The query selecting the rows to insert.
 ///
 /// # Returns
 ///
-/// A `Result` containing a `String` representing the generated SQL statement
-/// if the operation is successful, or a `RusqliteError` if an error occurs.
+/// A `Result` containing the number of inserted rows if successful,
+/// or a `OracleError` if an error occurs.
 pub fn into<'a, T: Table + Default>(
     conn: &'a mut Connection,
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
-) -> Result<String, RusqliteError> {
-    let statement = generate_insert_into_statement::<T>(columns, subquery);
-    let sql = statement.unwrap();
+) -> Result<u64, OracleError> {
+    let (sql, params) = builder::InsertQueryBuilder::<T, OracleDialect>::new()
+        .select(columns, subquery)
+        .render();
+
+    debug!("{}", sql);
+
+    let binds: Vec<&dyn ToSql> = params.values().iter().map(|v| v as &dyn ToSql).collect();
 
-    // FIXME: Convert to transaction
-    let _ = conn.execute(&sql, &[]);
+    let rows = conn
+        .execute(&sql, &binds)
+        .and_then(|stmt| stmt.row_count())
+        .map_err(OracleError::InsertError)?;
 
     info!("Inserted into table, done.");
 
-    // FIXME: Return the number of rows affected
-    Ok(sql)
+    Ok(rows)
 }
 
-/// Generates an SQL INSERT INTO statement for a given subquery.
+/// Constructs a new bulk insert builder.
 ///
 /// # Arguments
 ///
-/// * `columns` - A `Vec` of column names.
-/// * `subquery` - A `QueryBuilder` object representing the subquery.
+/// * `rows` - The table rows to insert, e.g. an iterator reading them from a file.
 ///
 /// # Returns
 ///
-/// A `Result` containing a `String` representing the generated SQL statement
-/// if the operation is successful, or a `RusqliteError` if an error occurs.
-fn generate_insert_into_statement<'a, T: Table + Default>(
-    columns: Vec<String>,
-    subquery: Box<dyn QueryBuilder<'a> + 'a>,
-) -> Result<String, RusqliteError> {
-    let columns_str = columns.join(", ");
-    let subquery_str = subquery.to_sql();
-    let table_row = T::default();
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
-
-    let sql = format!(
-        "INSERT INTO {} ({}) {}",
-        table_name, columns_str, subquery_str
-    );
-
-    Ok(sql)
+/// A `BulkInsertBuilder` instance.
+pub fn bulk_insert<'a, R>(rows: R) -> BulkInsertBuilder<'a, R::IntoIter>
+where
+    R: IntoIterator,
+    R::Item: Table,
+{
+    BulkInsertBuilder::new(rows)
 }
 
-/// Generates an SQL INSERT statement for a given table row.
-///
-/// This function takes an object implementing the `Table` trait, representing
-/// a single row of data to be inserted into the database. It generates an SQL
-/// INSERT statement based on the column names and values of the table row.
-///
-/// # Arguments
-///
-/// * `table_row` - An object implementing the `Table` trait representing
-///                 a single row of data to be inserted.
-/// * `first_statement` - A boolean flag indicating whether this is the first
-///                       statement to be generated.
-///
-/// # Returns
-///
-/// A `Result` containing a `String` representing the generated SQL statement
-/// if successful, or a `Error` if an error occurs during the generation process.
-fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<String, Error> {
-    // Generate strings for columns and values
-    let mut columns_str = String::new();
-    let mut values_str = String::new();
-
-    // Iterate over the fields to generate columns and values
-    let column_fields = table_row.get_column_fields();
-    let column_values = table_row.get_column_values();
-
-    for (column_name, value) in column_fields.iter().zip(column_values.iter()) {
-        // Check if the field is an AutoIncrementPrimaryKey
-        if table_row.is_auto_increment_primary_key(value) {
-            debug!("Skipping AutoIncrementPrimaryKey field in SQL statement generation.");
-            continue;
-        }
-
-        // Escape single quotes in the value
-        let escaped_value = value.replace("'", "''");
-
-        columns_str.push_str(&format!("{}, ", column_name));
-        values_str.push_str(&format!("'{}', ", escaped_value)); // Surround values with single quotes
+/// A builder for inserting a large number of rows into Oracle.
+pub type BulkInsertBuilder<'a, I> = builder::BulkInsertBuilder<'a, I, OracleDialect>;
+
+impl<'a, T: Table, I: Iterator<Item = T>> BulkInsertBuilder<'a, I> {
+    /// Builds and executes the INSERT statements of all chunks in one transaction,
+    /// or in a savepoint when a transaction is already open.
+    ///
+    /// Chunks with the same number of rows share one prepared statement, taken
+    /// from the statement cache of the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `Connection` to the Oracle database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the final progress, with the total number of rows
+    /// inserted, or a `OracleError` if an error occurs. Nothing is inserted then.
+    pub fn build(mut self, conn: &mut Connection) -> Result<BulkInsertProgress, OracleError> {
+        transaction::guarded(conn, |conn| {
+            while let Some(chunk) = self.next_chunk() {
+                let (sql, params) = chunk.render();
+
+                debug!("{}", sql);
+
+                let binds: Vec<&dyn ToSql> =
+                    params.values().iter().map(|v| v as &dyn ToSql).collect();
+
+                conn.statement(&sql)
+                    .build()
+                    .and_then(|mut stmt| stmt.execute(&binds))
+                    .map_err(OracleError::InsertError)?;
+
+                self.record_chunk(chunk.rows().len());
+            }
+
+            info!("Inserted into table, done.");
+
+            Ok(self.progress())
+        })
     }
-
-    // Sanitize table name from unwanted quotations or backslashes
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
-
-    // Remove the trailing comma and space
-    if !columns_str.is_empty() {
-        columns_str.pop();
-        columns_str.pop();
-    }
-    if !values_str.is_empty() {
-        values_str.pop();
-        values_str.pop();
-    }
-
-    let sql = if first_statement {
-        format!(
-            "INSERT INTO {} ({}) VALUES ({})",
-            table_name, columns_str, values_str
-        )
-    } else {
-        format!("({})", values_str)
-    };
-
-    debug!("{}", sql);
-
-    Ok(sql)
 }
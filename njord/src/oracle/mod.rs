@@ -35,14 +35,17 @@ pub mod delete;
 pub mod error;
 pub mod insert;
 pub mod select;
+pub mod transaction;
 pub mod update;
-mod util;
+pub mod upsert;
 
 pub use delete::delete;
 pub use error::OracleError;
-pub use insert::insert;
+pub use insert::{bulk_insert, insert};
 pub use select::select;
+pub use transaction::{transaction, transaction_with, Transaction};
 pub use update::update;
+pub use upsert::upsert;
 
 /// Open a database connection.
 ///
@@ -61,9 +64,12 @@ pub fn open(username: &str, password: &str, connect_string: &str) -> Result<Conn
     let conn = Connection::connect(username, password, connect_string);
 
     match conn {
-        Ok(conn) => {
+        Ok(mut conn) => {
             println!("Successfully connected to Oracle database");
 
+            // Statements outside of a transaction are committed right away
+            conn.set_autocommit(true);
+
             Ok(conn)
         }
         Err(err) => {
@@ -0,0 +1,80 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{builder, dialect::PostgresDialect, table::Table};
+
+use log::debug;
+use tokio_postgres::types::ToSql;
+
+use super::{transaction, Connection, PostgresError};
+
+/// Constructs a new DELETE query builder.
+///
+/// # Returns
+///
+/// A `DeleteQueryBuilder` instance.
+pub fn delete<T: Table + Default>() -> DeleteQueryBuilder<'static, T> {
+    DeleteQueryBuilder::new()
+}
+
+/// A builder for constructing DELETE queries on PostgreSQL.
+pub type DeleteQueryBuilder<'a, T> = builder::DeleteQueryBuilder<'a, T, PostgresDialect>;
+
+impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
+    /// Builds and executes the DELETE query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A mutable reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of deleted rows if successful,
+    /// or a `PostgresError` if the deletion fails or `expect_rows` is not met.
+    pub async fn build(self, conn: &mut Connection) -> Result<u64, PostgresError> {
+        let (query, params) = self.render();
+
+        debug!("{}", query);
+
+        let binds: Vec<&(dyn ToSql + Sync)> = params
+            .values()
+            .iter()
+            .map(|v| v as &(dyn ToSql + Sync))
+            .collect();
+
+        transaction::expect_rows(
+            conn,
+            &query,
+            &binds,
+            self.expected_rows(),
+            PostgresError::DeleteError,
+        )
+        .await
+    }
+}
@@ -0,0 +1,74 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tokio_postgres::Error as PostgresLibError;
+
+use crate::{builder::BuildError, row::DecodeError};
+
+/// Represents errors that can occur during PostgreSQL operations.
+#[derive(Debug)]
+pub enum PostgresError {
+    /// Error that occurs during a SELECT operation.
+    SelectError(PostgresLibError),
+    /// Error that occurs during an INSERT operation.
+    InsertError(PostgresLibError),
+    /// Error that occurs during an UPDATE operation.
+    UpdateError(PostgresLibError),
+    /// Error that occurs during a DELETE operation.
+    DeleteError(PostgresLibError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(PostgresLibError),
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
+}
+
+impl From<PostgresLibError> for PostgresError {
+    /// Converts a `tokio_postgres::Error` into a `PostgresError`.
+    fn from(error: PostgresLibError) -> Self {
+        PostgresError::InsertError(error)
+    }
+}
+
+impl From<DecodeError> for PostgresError {
+    /// Converts a `DecodeError` into a `PostgresError`.
+    fn from(error: DecodeError) -> Self {
+        PostgresError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for PostgresError {
+    /// Converts a `BuildError` into a `PostgresError`.
+    fn from(error: BuildError) -> Self {
+        PostgresError::BuildError(error)
+    }
+}
@@ -0,0 +1,297 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder::{self, BulkInsertProgress},
+    dialect::PostgresDialect,
+    query::{Param, QueryBuilder},
+    table::Table,
+    transaction::TransactionStatements,
+};
+
+use log::{debug, info};
+use tokio_postgres::{types::ToSql, Statement};
+
+use super::{transaction, Connection, PostgresError};
+
+/// Inserts rows into a PostgreSQL table.
+///
+/// This function takes a `Connection` and a vector of objects implementing
+/// the `Table` trait, which represents rows to be inserted into the table.
+///
+/// If the rows have an `AutoIncrementPrimaryKey` left to the database, they are
+/// inserted one at a time, in a transaction or in a savepoint of an open one, and
+/// each generated key is read back with `RETURNING`, since the rows of a multi-row
+/// INSERT statement are returned in any order. Otherwise all rows are inserted
+/// with a single statement.
+///
+/// # Arguments
+///
+/// * `conn` - A `Connection` to the PostgreSQL database.
+/// * `table_rows` - A vector of objects implementing the `Table` trait representing
+///   the rows to be inserted into the database.
+///
+/// # Returns
+///
+/// A `Result` containing the inserted rows with their generated keys set
+/// if the insertion is successful, or a `PostgresError` if an error occurs.
+pub async fn insert<T: Table>(
+    conn: &mut Connection,
+    table_rows: Vec<T>,
+) -> Result<Vec<T>, PostgresError> {
+    if table_rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let builder = builder::InsertQueryBuilder::<T, PostgresDialect>::new().values(table_rows);
+    builder.validate()?;
+
+    let Some(key) = builder.generated_key() else {
+        let (sql, params) = builder.render();
+
+        debug!("{}", sql);
+
+        let binds: Vec<&(dyn ToSql + Sync)> = params
+            .values()
+            .iter()
+            .map(|v| v as &(dyn ToSql + Sync))
+            .collect();
+
+        conn.client
+            .execute(&sql, &binds)
+            .await
+            .map_err(PostgresError::InsertError)?;
+
+        info!("Inserted into table, done.");
+
+        return Ok(builder.into_rows());
+    };
+
+    let statements = TransactionStatements::guard(&PostgresDialect, conn.in_transaction)
+        .expect("PostgreSQL supports savepoints");
+
+    // A failing row undoes the ones before it, without ending an open transaction
+    transaction::execute(conn, &statements.begin).await?;
+
+    let result = insert_with_keys(conn, builder.into_rows(), &key).await;
+
+    let end = match result {
+        Ok(_) => &statements.commit,
+        Err(_) => &statements.rollback,
+    };
+    transaction::execute(conn, end).await?;
+
+    result
+}
+
+/// Inserts rows one at a time, reading back the key generated for each one.
+///
+/// # Arguments
+///
+/// * `conn` - A `Connection` to the PostgreSQL database.
+/// * `table_rows` - The rows to insert.
+/// * `key` - The column whose values the database generates.
+async fn insert_with_keys<T: Table>(
+    conn: &mut Connection,
+    table_rows: Vec<T>,
+    key: &str,
+) -> Result<Vec<T>, PostgresError> {
+    let mut rows = Vec::new();
+
+    for row in table_rows {
+        let row_builder = builder::InsertQueryBuilder::<T, PostgresDialect>::new()
+            .values(vec![row])
+            .returning([key]);
+        let (sql, params) = row_builder.render();
+
+        debug!("{}", sql);
+
+        let binds: Vec<&(dyn ToSql + Sync)> = params
+            .values()
+            .iter()
+            .map(|v| v as &(dyn ToSql + Sync))
+            .collect();
+
+        let key = conn
+            .client
+            .query_one(&sql, &binds)
+            .await
+            .and_then(|row| row.try_get::<_, Param>(0))
+            .map_err(PostgresError::InsertError)?;
+        rows.extend(row_builder.into_rows_with_keys([key])?);
+    }
+
+    info!("Inserted into table, done.");
+
+    Ok(rows)
+}
+
+/// Inserts the rows selected by a subquery into a table.
+///
+/// # Arguments
+///
+/// * `conn` - A `Connection` to the PostgreSQL database.
+/// * `columns` - The columns the subquery's columns are inserted into.
+/// * `subquery` - The query selecting the rows to insert.
+///
+/// # Returns
+///
+/// A `Result` containing the number of inserted rows if successful,
+/// or a `PostgresError` if an error occurs.
+pub async fn into<'a, T: Table + Default>(
+    conn: &'a mut Connection,
+    columns: Vec<String>,
+    subquery: Box<dyn QueryBuilder<'a> + 'a>,
+) -> Result<u64, PostgresError> {
+    let (sql, params) = builder::InsertQueryBuilder::<T, PostgresDialect>::new()
+        .select(columns, subquery)
+        .render();
+
+    debug!("{}", sql);
+
+    let binds: Vec<&(dyn ToSql + Sync)> = params
+        .values()
+        .iter()
+        .map(|v| v as &(dyn ToSql + Sync))
+        .collect();
+
+    let rows = conn
+        .client
+        .execute(&sql, &binds)
+        .await
+        .map_err(PostgresError::InsertError)?;
+
+    info!("Inserted into table, done.");
+
+    Ok(rows)
+}
+
+/// Constructs a new bulk insert builder.
+///
+/// # Arguments
+///
+/// * `rows` - The table rows to insert, e.g. an iterator reading them from a file.
+///
+/// # Returns
+///
+/// A `BulkInsertBuilder` instance.
+pub fn bulk_insert<'a, R>(rows: R) -> BulkInsertBuilder<'a, R::IntoIter>
+where
+    R: IntoIterator,
+    R::Item: Table,
+{
+    BulkInsertBuilder::new(rows)
+}
+
+/// A builder for inserting a large number of rows into PostgreSQL.
+pub type BulkInsertBuilder<'a, I> = builder::BulkInsertBuilder<'a, I, PostgresDialect>;
+
+impl<'a, T: Table, I: Iterator<Item = T>> BulkInsertBuilder<'a, I> {
+    /// Builds and executes the INSERT statements of all chunks in one transaction,
+    /// or in a savepoint when a transaction is already open.
+    ///
+    /// Chunks with the same number of rows share one prepared statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `Connection` to the PostgreSQL database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the final progress, with the total number of rows
+    /// inserted, or a `PostgresError` if an error occurs. Nothing is inserted then.
+    pub async fn build(
+        mut self,
+        conn: &mut Connection,
+    ) -> Result<BulkInsertProgress, PostgresError> {
+        let statements = TransactionStatements::guard(&PostgresDialect, conn.in_transaction)
+            .expect("PostgreSQL supports savepoints");
+
+        // The transaction is run by hand, a `transaction` closure can't borrow the builder
+        transaction::execute(conn, &statements.begin).await?;
+
+        let result = self.insert_chunks(conn).await;
+
+        let end = match result {
+            Ok(_) => &statements.commit,
+            Err(_) => &statements.rollback,
+        };
+        transaction::execute(conn, end).await?;
+
+        result
+    }
+
+    /// Inserts the chunks one after the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `Connection` to the PostgreSQL database.
+    async fn insert_chunks(
+        &mut self,
+        conn: &mut Connection,
+    ) -> Result<BulkInsertProgress, PostgresError> {
+        let mut prepared: Option<(String, Statement)> = None;
+
+        while let Some(chunk) = self.next_chunk() {
+            let (sql, params) = chunk.render();
+
+            debug!("{}", sql);
+
+            let stmt = match prepared {
+                Some((ref prepared_sql, ref stmt)) if *prepared_sql == sql => stmt.clone(),
+                _ => {
+                    let stmt = conn
+                        .client
+                        .prepare(&sql)
+                        .await
+                        .map_err(PostgresError::InsertError)?;
+                    prepared = Some((sql, stmt.clone()));
+                    stmt
+                }
+            };
+
+            let binds: Vec<&(dyn ToSql + Sync)> = params
+                .values()
+                .iter()
+                .map(|v| v as &(dyn ToSql + Sync))
+                .collect();
+
+            conn.client
+                .execute(&stmt, &binds)
+                .await
+                .map_err(PostgresError::InsertError)?;
+
+            self.record_chunk(chunk.rows().len());
+        }
+
+        info!("Inserted into table, done.");
+
+        Ok(self.progress())
+    }
+}
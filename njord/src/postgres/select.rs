@@ -0,0 +1,242 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    builder,
+    column::Column,
+    dialect::PostgresDialect,
+    query::{Param, PlaceholderStyle, QueryParams},
+    relation::Related,
+    row::{FromRow, Row as ResultRow},
+    table::Table,
+};
+
+use futures_util::{Stream, StreamExt};
+use log::info;
+use tokio_postgres::{types::ToSql, Error, Row};
+
+use super::{Connection, PostgresError};
+
+/// Constructs a new SELECT query builder.
+///
+/// # Arguments
+///
+/// * `columns` - A vector of strings representing the columns to be selected.
+///
+/// # Returns
+///
+/// A `SelectQueryBuilder` instance.
+pub fn select<T: Table + Default>(columns: Vec<Column>) -> SelectQueryBuilder<T> {
+    SelectQueryBuilder::new(columns)
+}
+
+/// A builder for constructing SELECT queries on PostgreSQL.
+pub type SelectQueryBuilder<'a, T> = builder::SelectQueryBuilder<'a, T, PostgresDialect>;
+
+impl<'a, T: Table + FromRow + Default> SelectQueryBuilder<'a, T> {
+    /// Builds and executes the SELECT query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A mutable reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of selected table rows if successful,
+    /// or a `PostgresError` if the query fails or a row cannot be decoded.
+    pub async fn build(self, conn: &mut Connection) -> Result<Vec<T>, PostgresError> {
+        self.build_as(conn).await
+    }
+
+    /// Builds and executes the SELECT query, decoding the rows one at a time.
+    ///
+    /// Unlike `build`, the rows are not collected; each one is read from the
+    /// connection when the stream reaches it.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A mutable reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a stream of the selected table rows if successful,
+    /// or a `PostgresError` if the query could not be executed.
+    pub async fn build_stream(
+        self,
+        conn: &mut Connection,
+    ) -> Result<impl Stream<Item = Result<T, PostgresError>>, PostgresError> {
+        let (final_query, params) = self.render();
+
+        info!("{}", final_query);
+
+        let binds = params.values().iter().map(|v| v as &(dyn ToSql + Sync));
+        let stream = conn
+            .client
+            .query_raw(&final_query, binds)
+            .await
+            .map_err(PostgresError::SelectError)?;
+
+        Ok(stream.map(|row| {
+            row.map_err(PostgresError::SelectError)
+                .and_then(|row| decode_row(&row))
+        }))
+    }
+
+    /// Builds and executes the SELECT query, decoding the rows into `R` instead of `T`.
+    ///
+    /// `R` is decoded by column name or alias, so it can be a struct deriving `FromRow`
+    /// with only some of the columns, a tuple of single values, or a tuple of structs
+    /// for the tables of a join, e.g. `(User, Option<Address>)` for a LEFT JOIN.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A mutable reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of the decoded rows if successful,
+    /// or a `PostgresError` if the query fails or a row cannot be decoded.
+    pub async fn build_as<R: FromRow>(
+        self,
+        conn: &mut Connection,
+    ) -> Result<Vec<R>, PostgresError> {
+        let (final_query, params) = self.render();
+
+        info!("{}", final_query);
+
+        execute_query(&final_query, &params, conn).await
+    }
+}
+
+/// A builder for SELECT queries on PostgreSQL loading related rows, created by `with`.
+pub type EagerQueryBuilder<'a, T, R> = builder::EagerQueryBuilder<'a, T, R, PostgresDialect>;
+
+impl<T, R> EagerQueryBuilder<'_, T, R>
+where
+    T: Related<R> + FromRow + Default,
+    R: Table + FromRow + Default + Clone,
+{
+    /// Builds and executes the SELECT query, then loads the related rows with one
+    /// batched query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A mutable reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the selected table rows, each paired with its related rows,
+    /// or a `PostgresError` if a query fails or a row cannot be decoded.
+    pub async fn build(self, conn: &mut Connection) -> Result<Vec<(T, Vec<R>)>, PostgresError> {
+        let (final_query, params) = self.select().render();
+
+        info!("{}", final_query);
+
+        let parents: Vec<T> = execute_query(&final_query, &params, conn).await?;
+
+        let mut related = Vec::new();
+        for query in self.related_queries(&parents)? {
+            let (final_query, params) = query.render();
+
+            info!("{}", final_query);
+
+            related.extend(execute_query(&final_query, &params, conn).await?);
+        }
+
+        Ok(self.group(parents, related))
+    }
+}
+
+/// Executes a raw SQL query and returns the decoded rows.
+///
+/// # Arguments
+///
+/// * `sql` - The SQL query to execute.
+/// * `conn` - A mutable reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `PostgresError` if the query fails or a row cannot be decoded.
+pub async fn raw_execute<R: FromRow>(
+    sql: &str,
+    conn: &mut Connection,
+) -> Result<Vec<R>, PostgresError> {
+    execute_query(sql, &QueryParams::new(PlaceholderStyle::Dollar), conn).await
+}
+
+/// Executes a SQL query with its bound parameters and returns the decoded rows.
+///
+/// # Arguments
+///
+/// * `sql` - The SQL query to execute.
+/// * `params` - The values bound to the placeholders in `sql`.
+/// * `conn` - A mutable reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `PostgresError` if the query fails or a row cannot be decoded.
+async fn execute_query<R: FromRow>(
+    sql: &str,
+    params: &QueryParams,
+    conn: &mut Connection,
+) -> Result<Vec<R>, PostgresError> {
+    let binds: Vec<&(dyn ToSql + Sync)> = params
+        .values()
+        .iter()
+        .map(|v| v as &(dyn ToSql + Sync))
+        .collect();
+    let rows = conn
+        .client
+        .query(sql, &binds)
+        .await
+        .map_err(PostgresError::SelectError)?;
+
+    rows.iter().map(decode_row).collect()
+}
+
+/// Decodes a result row from the values sent by the server.
+///
+/// # Arguments
+///
+/// * `row` - The row read from the database.
+fn decode_row<R: FromRow>(row: &Row) -> Result<R, PostgresError> {
+    let columns: Vec<String> = row
+        .columns()
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect();
+    let values = (0..row.len())
+        .map(|index| row.try_get::<usize, Param>(index))
+        .collect::<Result<Vec<_>, Error>>()
+        .map_err(PostgresError::SelectError)?;
+
+    Ok(R::from_row(&ResultRow::new(&columns, &values))?)
+}
@@ -0,0 +1,393 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Conversions between njord's text based row values and PostgreSQL's binary wire format.
+//!
+//! Column values travel through njord as text, while PostgreSQL binds parameters in
+//! their binary form and strictly checks them against the column type. These helpers
+//! parse text into the type the server expects and render received values as text.
+
+use std::error::Error;
+use std::fmt::Write;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use tokio_postgres::types::{FromSql, IsNull, ToSql, Type};
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+const NUMERIC_POSITIVE: u16 = 0x0000;
+const NUMERIC_NEGATIVE: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+
+/// Binds a text value to a parameter of type `ty`, parsing it if `ty` is not textual.
+///
+/// # Arguments
+///
+/// * `value` - The text value to bind.
+/// * `ty` - The type the server expects for the parameter.
+/// * `out` - The buffer receiving the binary value.
+pub(crate) fn text_to_sql(value: &str, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+    match *ty {
+        Type::BOOL => parse_bool(value)?.to_sql(ty, out),
+        Type::INT2 => value.trim().parse::<i16>()?.to_sql(ty, out),
+        Type::INT4 => value.trim().parse::<i32>()?.to_sql(ty, out),
+        Type::INT8 => value.trim().parse::<i64>()?.to_sql(ty, out),
+        Type::OID => value.trim().parse::<u32>()?.to_sql(ty, out),
+        Type::FLOAT4 => value.trim().parse::<f32>()?.to_sql(ty, out),
+        Type::FLOAT8 => value.trim().parse::<f64>()?.to_sql(ty, out),
+        Type::NUMERIC => encode_numeric(value, out),
+        Type::DATE => NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")?.to_sql(ty, out),
+        Type::TIME => NaiveTime::parse_from_str(value.trim(), "%H:%M:%S%.f")?.to_sql(ty, out),
+        Type::TIMESTAMP => parse_timestamp(value)?.to_sql(ty, out),
+        Type::TIMESTAMPTZ => value
+            .trim()
+            .parse::<DateTime<FixedOffset>>()?
+            .with_timezone(&Utc)
+            .to_sql(ty, out),
+        Type::BYTEA => value.as_bytes().to_sql(ty, out),
+        Type::UUID => {
+            out.put_slice(&parse_uuid(value)?);
+            Ok(IsNull::No)
+        }
+        Type::JSON => {
+            out.put_slice(value.as_bytes());
+            Ok(IsNull::No)
+        }
+        Type::JSONB => {
+            out.put_u8(1);
+            out.put_slice(value.as_bytes());
+            Ok(IsNull::No)
+        }
+        _ => value.to_sql_checked(ty, out),
+    }
+}
+
+/// Parses the usual spellings of a boolean.
+fn parse_bool(value: &str) -> Result<bool, BoxError> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "t" | "1" | "yes" | "y" | "on" => Ok(true),
+        "false" | "f" | "0" | "no" | "n" | "off" => Ok(false),
+        _ => Err(format!("invalid boolean value '{}'", value).into()),
+    }
+}
+
+/// Parses a timestamp separated by either a space or a `T`.
+fn parse_timestamp(value: &str) -> Result<NaiveDateTime, BoxError> {
+    let value = value.trim();
+
+    match NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f") {
+        Ok(timestamp) => Ok(timestamp),
+        Err(_) => Ok(value.parse::<NaiveDateTime>()?),
+    }
+}
+
+/// Parses a UUID in its hyphenated or simple form into its 16 bytes.
+fn parse_uuid(value: &str) -> Result<[u8; 16], BoxError> {
+    let invalid = || -> BoxError { format!("invalid uuid '{}'", value).into() };
+
+    let hex: Vec<u8> = value.trim().bytes().filter(|b| *b != b'-').collect();
+    if hex.len() != 32 {
+        return Err(invalid());
+    }
+
+    let mut bytes = [0u8; 16];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+    }
+
+    Ok(bytes)
+}
+
+/// Encodes a decimal number in PostgreSQL's binary NUMERIC format.
+///
+/// The digits are stored in base 10000 groups aligned on the decimal point, preceded
+/// by the group count, the weight of the first group, the sign and the display scale.
+///
+/// # Arguments
+///
+/// * `value` - The decimal number, e.g. `-12.50`.
+/// * `out` - The buffer receiving the binary value.
+pub(crate) fn encode_numeric(value: &str, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+    let value = value.trim();
+
+    if value.eq_ignore_ascii_case("nan") {
+        out.put_i16(0);
+        out.put_i16(0);
+        out.put_u16(NUMERIC_NAN);
+        out.put_u16(0);
+        return Ok(IsNull::No);
+    }
+
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(format!("invalid numeric value '{}'", value).into());
+    }
+
+    let integer = integer.trim_start_matches('0');
+    let integer_padding = (4 - integer.len() % 4) % 4;
+    let fraction_padding = (4 - fraction.len() % 4) % 4;
+    let padded = format!(
+        "{}{}{}{}",
+        "0".repeat(integer_padding),
+        integer,
+        fraction,
+        "0".repeat(fraction_padding)
+    );
+
+    let mut groups: Vec<i16> = padded
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0, |acc, digit| acc * 10 + i16::from(digit - b'0'))
+        })
+        .collect();
+    let mut weight = ((integer_padding + integer.len()) / 4) as i16 - 1;
+
+    let leading_zeros = groups.iter().take_while(|group| **group == 0).count();
+    groups.drain(..leading_zeros);
+    weight -= leading_zeros as i16;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+
+    out.put_i16(i16::try_from(groups.len())?);
+    out.put_i16(weight);
+    out.put_u16(if negative && !groups.is_empty() {
+        NUMERIC_NEGATIVE
+    } else {
+        NUMERIC_POSITIVE
+    });
+    out.put_u16(u16::try_from(fraction.len())?);
+    for group in groups {
+        out.put_i16(group);
+    }
+
+    Ok(IsNull::No)
+}
+
+/// Decodes a value in PostgreSQL's binary NUMERIC format into its decimal text.
+///
+/// # Arguments
+///
+/// * `raw` - The binary value.
+pub(crate) fn decode_numeric(raw: &[u8]) -> Result<String, BoxError> {
+    let read = |index: usize| -> Result<u16, BoxError> {
+        match raw.get(index * 2..index * 2 + 2) {
+            Some(bytes) => Ok(u16::from_be_bytes([bytes[0], bytes[1]])),
+            None => Err("truncated numeric value".into()),
+        }
+    };
+
+    let count = usize::from(read(0)?);
+    let weight = read(1)? as i16;
+    let sign = read(2)?;
+    let scale = usize::from(read(3)?);
+    let groups = (0..count)
+        .map(|index| read(4 + index))
+        .collect::<Result<Vec<u16>, BoxError>>()?;
+
+    if sign == NUMERIC_NAN {
+        return Ok("NaN".to_string());
+    }
+
+    let group = |index: isize| -> u16 {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| groups.get(index).copied())
+            .unwrap_or(0)
+    };
+
+    let mut result = String::new();
+    if sign == NUMERIC_NEGATIVE {
+        result.push('-');
+    }
+
+    if weight < 0 {
+        result.push('0');
+    } else {
+        write!(result, "{}", group(0))?;
+        for index in 1..=weight as isize {
+            write!(result, "{:04}", group(index))?;
+        }
+    }
+
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut index = weight as isize + 1;
+        while fraction.len() < scale {
+            write!(fraction, "{:04}", group(index))?;
+            index += 1;
+        }
+        fraction.truncate(scale);
+
+        result.push('.');
+        result.push_str(&fraction);
+    }
+
+    Ok(result)
+}
+
+/// A column value of any type, rendered as text.
+pub(crate) struct TextValue(pub(crate) String);
+
+impl<'a> FromSql<'a> for TextValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        let value = match *ty {
+            Type::BOOL => bool::from_sql(ty, raw)?.to_string(),
+            Type::INT2 => i16::from_sql(ty, raw)?.to_string(),
+            Type::INT4 => i32::from_sql(ty, raw)?.to_string(),
+            Type::INT8 => i64::from_sql(ty, raw)?.to_string(),
+            Type::OID => u32::from_sql(ty, raw)?.to_string(),
+            Type::FLOAT4 => f32::from_sql(ty, raw)?.to_string(),
+            Type::FLOAT8 => f64::from_sql(ty, raw)?.to_string(),
+            Type::NUMERIC => decode_numeric(raw)?,
+            Type::DATE => NaiveDate::from_sql(ty, raw)?.to_string(),
+            Type::TIME => NaiveTime::from_sql(ty, raw)?.to_string(),
+            Type::TIMESTAMP => NaiveDateTime::from_sql(ty, raw)?.to_string(),
+            Type::TIMESTAMPTZ => DateTime::<Utc>::from_sql(ty, raw)?
+                .format("%Y-%m-%d %H:%M:%S%.f%:z")
+                .to_string(),
+            Type::BYTEA => String::from_utf8_lossy(raw).to_string(),
+            Type::UUID => {
+                let hex: String = raw.iter().map(|byte| format!("{:02x}", byte)).collect();
+                if hex.len() != 32 {
+                    return Err("invalid uuid value".into());
+                }
+                format!(
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                )
+            }
+            Type::JSON => String::from_utf8(raw.to_vec())?,
+            Type::JSONB => String::from_utf8(raw.get(1..).unwrap_or_default().to_vec())?,
+            _ if <&str as FromSql>::accepts(ty) => <&str>::from_sql(ty, raw)?.to_string(),
+            _ => return Err(format!("unsupported column type '{}'", ty).into()),
+        };
+
+        Ok(TextValue(value))
+    }
+
+    fn from_sql_null(_ty: &Type) -> Result<Self, BoxError> {
+        Ok(TextValue("NULL".to_string()))
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric_roundtrip(value: &str) -> String {
+        let mut out = BytesMut::new();
+        encode_numeric(value, &mut out).unwrap();
+        decode_numeric(&out).unwrap()
+    }
+
+    #[test]
+    fn numeric_encoding() {
+        let mut out = BytesMut::new();
+        encode_numeric("12345.6", &mut out).unwrap();
+
+        // 3 groups, weight 1, positive, scale 1: 1 2345 . 6000
+        assert_eq!(
+            out.as_ref(),
+            &[0, 3, 0, 1, 0, 0, 0, 1, 0, 1, 0x09, 0x29, 0x17, 0x70]
+        );
+    }
+
+    #[test]
+    fn numeric_roundtrips() {
+        assert_eq!(numeric_roundtrip("12345.6"), "12345.6");
+        assert_eq!(numeric_roundtrip("10.00"), "10.00");
+        assert_eq!(numeric_roundtrip("0.05"), "0.05");
+        assert_eq!(numeric_roundtrip("-0.00001"), "-0.00001");
+        assert_eq!(numeric_roundtrip("100000000"), "100000000");
+        assert_eq!(numeric_roundtrip("-0"), "0");
+        assert_eq!(numeric_roundtrip(".5"), "0.5");
+        assert_eq!(numeric_roundtrip("NaN"), "NaN");
+    }
+
+    #[test]
+    fn numeric_rejects_invalid_values() {
+        let mut out = BytesMut::new();
+
+        assert!(encode_numeric("", &mut out).is_err());
+        assert!(encode_numeric("1e5", &mut out).is_err());
+        assert!(encode_numeric("12.3.4", &mut out).is_err());
+    }
+
+    #[test]
+    fn text_is_parsed_into_the_column_type() {
+        let mut out = BytesMut::new();
+        text_to_sql("42", &Type::INT4, &mut out).unwrap();
+        assert_eq!(out.as_ref(), &42i32.to_be_bytes());
+
+        let mut out = BytesMut::new();
+        text_to_sql("t", &Type::BOOL, &mut out).unwrap();
+        assert_eq!(out.as_ref(), &[1]);
+
+        let mut out = BytesMut::new();
+        assert!(text_to_sql("forty-two", &Type::INT8, &mut out).is_err());
+    }
+
+    #[test]
+    fn uuid_roundtrips() {
+        let uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+        let mut out = BytesMut::new();
+        text_to_sql(uuid, &Type::UUID, &mut out).unwrap();
+
+        let value = TextValue::from_sql(&Type::UUID, &out).unwrap();
+        assert_eq!(value.0, uuid);
+    }
+}
@@ -29,11 +29,733 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+/// The placeholder syntax a backend uses for bound parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// Positional question marks: `?` (SQLite, MySQL, MariaDB).
+    QuestionMark,
+    /// Numbered colon placeholders: `:1`, `:2`, ... (Oracle).
+    Colon,
+    /// Numbered `@P` placeholders: `@P1`, `@P2`, ... (MSSQL).
+    AtP,
+    /// Numbered dollar placeholders: `$1`, `$2`, ... (PostgreSQL).
+    Dollar,
+}
+
+/// A single value bound to a statement parameter or read from a result row.
+///
+/// Each backend binds these with its driver's native type, so a `Text` value
+/// such as `"007"` is never mistaken for a number.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Param {
+    /// SQL `NULL`.
+    Null,
+    /// A boolean value.
+    Bool(bool),
+    /// A signed integer value.
+    Int(i64),
+    /// A floating point value.
+    Float(f64),
+    /// A text value.
+    Text(String),
+    /// A binary value.
+    Bytes(Vec<u8>),
+    /// A calendar date without time zone.
+    #[cfg(feature = "chrono")]
+    Date(NaiveDate),
+    /// A time of day without time zone.
+    #[cfg(feature = "chrono")]
+    Time(NaiveTime),
+    /// A date and time without time zone.
+    #[cfg(feature = "chrono")]
+    Timestamp(NaiveDateTime),
+    /// A date and time in UTC.
+    #[cfg(feature = "chrono")]
+    TimestampTz(DateTime<Utc>),
+}
+
+impl From<String> for Param {
+    fn from(value: String) -> Self {
+        Param::Text(value)
+    }
+}
+
+impl From<&str> for Param {
+    fn from(value: &str) -> Self {
+        Param::Text(value.to_string())
+    }
+}
+
+impl std::fmt::Display for Param {
+    /// Formats the value the way it reads in a log line or a table's `Display`.
+    ///
+    /// Text is written without quotes and binary values as hex digits.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Param::Null => write!(f, "NULL"),
+            Param::Bool(value) => write!(f, "{}", value),
+            Param::Int(value) => write!(f, "{}", value),
+            Param::Float(value) => write!(f, "{}", value),
+            Param::Text(value) => write!(f, "{}", value),
+            Param::Bytes(value) => value.iter().try_for_each(|byte| write!(f, "{:02X}", byte)),
+            #[cfg(feature = "chrono")]
+            Param::Date(value) => write!(f, "{}", value.format("%Y-%m-%d")),
+            #[cfg(feature = "chrono")]
+            Param::Time(value) => write!(f, "{}", value.format("%H:%M:%S%.f")),
+            #[cfg(feature = "chrono")]
+            Param::Timestamp(value) => write!(f, "{}", value.format("%Y-%m-%d %H:%M:%S%.f")),
+            #[cfg(feature = "chrono")]
+            Param::TimestampTz(value) => write!(f, "{}", value.to_rfc3339()),
+        }
+    }
+}
+
+/// A type whose values can be bound to a statement parameter.
+///
+/// `#[derive(Table)]` uses it to bind the fields of a row when it is inserted or
+/// updated, so each field reaches the driver with its native type. It is the
+/// counterpart of `FromValue`, which decodes the fields again.
+pub trait ToParam {
+    /// Converts the value into a parameter.
+    fn to_param(&self) -> Param;
+}
+
+impl<T: ToParam + ?Sized> ToParam for &T {
+    fn to_param(&self) -> Param {
+        (**self).to_param()
+    }
+}
+
+impl<T: ToParam> ToParam for Option<T> {
+    /// Binds `None` as `NULL`.
+    fn to_param(&self) -> Param {
+        self.as_ref().map_or(Param::Null, ToParam::to_param)
+    }
+}
+
+impl ToParam for Param {
+    fn to_param(&self) -> Param {
+        self.clone()
+    }
+}
+
+macro_rules! impl_to_param {
+    ($variant:ident, $target:ty: $($source:ty),+) => {
+        $(
+            impl ToParam for $source {
+                fn to_param(&self) -> Param {
+                    Param::$variant(<$target>::from(self.clone()))
+                }
+            }
+        )+
+    };
+}
+
+impl_to_param!(Bool, bool: bool);
+impl_to_param!(Int, i64: i8, i16, i32, i64, u8, u16, u32);
+impl_to_param!(Float, f64: f32, f64);
+impl_to_param!(Text, String: String);
+impl_to_param!(Bytes, Vec<u8>: Vec<u8>);
+#[cfg(feature = "chrono")]
+impl_to_param!(Date, NaiveDate: NaiveDate);
+#[cfg(feature = "chrono")]
+impl_to_param!(Time, NaiveTime: NaiveTime);
+#[cfg(feature = "chrono")]
+impl_to_param!(Timestamp, NaiveDateTime: NaiveDateTime);
+#[cfg(feature = "chrono")]
+impl_to_param!(TimestampTz, DateTime<Utc>: DateTime<Utc>);
+
+macro_rules! impl_to_param_wide {
+    ($($source:ty),+) => {
+        $(
+            /// Values beyond the range of `i64` are bound as text, keeping every digit.
+            impl ToParam for $source {
+                fn to_param(&self) -> Param {
+                    i64::try_from(*self).map_or_else(|_| Param::Text(self.to_string()), Param::Int)
+                }
+            }
+        )+
+    };
+}
+
+impl_to_param_wide!(i128, isize, u64, u128, usize);
+
+impl ToParam for str {
+    fn to_param(&self) -> Param {
+        Param::Text(self.to_string())
+    }
+}
+
+impl ToParam for [u8] {
+    fn to_param(&self) -> Param {
+        Param::Bytes(self.to_vec())
+    }
+}
+
+/// UUIDs are bound as their hyphenated text, which every backend can store and
+/// PostgreSQL converts into its `uuid` type.
+#[cfg(feature = "uuid")]
+impl ToParam for uuid::Uuid {
+    fn to_param(&self) -> Param {
+        Param::Text(self.hyphenated().to_string())
+    }
+}
+
+/// Decimals are bound as text so no digit is lost to a float.
+#[cfg(feature = "decimal")]
+impl ToParam for rust_decimal::Decimal {
+    fn to_param(&self) -> Param {
+        Param::Text(self.to_string())
+    }
+}
+
+/// JSON values are bound as their serialized text.
+#[cfg(feature = "json")]
+impl ToParam for serde_json::Value {
+    fn to_param(&self) -> Param {
+        Param::Text(self.to_string())
+    }
+}
+
+/// Collects the values bound to a statement while its SQL is being generated.
+///
+/// Every value pushed returns the placeholder to put into the SQL text in its
+/// place, so the values never end up inside the query string itself.
+#[derive(Clone, Debug)]
+pub struct QueryParams {
+    style: PlaceholderStyle,
+    values: Vec<Param>,
+}
+
+impl QueryParams {
+    /// Creates an empty parameter list for the given placeholder style.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The placeholder syntax of the backend the query is built for.
+    pub fn new(style: PlaceholderStyle) -> Self {
+        QueryParams {
+            style,
+            values: Vec::new(),
+        }
+    }
+
+    /// Binds a value and returns the placeholder that refers to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to bind.
+    ///
+    /// # Returns
+    ///
+    /// The placeholder (`?`, `:n`, `@Pn` or `$n`) to use in the SQL text.
+    pub fn push(&mut self, value: impl Into<Param>) -> String {
+        self.values.push(value.into());
+
+        match self.style {
+            PlaceholderStyle::QuestionMark => "?".to_string(),
+            PlaceholderStyle::Colon => format!(":{}", self.values.len()),
+            PlaceholderStyle::AtP => format!("@P{}", self.values.len()),
+            PlaceholderStyle::Dollar => format!("${}", self.values.len()),
+        }
+    }
+
+    /// Returns the placeholder style of this parameter list.
+    pub fn style(&self) -> PlaceholderStyle {
+        self.style
+    }
+
+    /// Returns the bound values in placeholder order.
+    pub fn values(&self) -> &[Param] {
+        &self.values
+    }
+
+    /// Returns the number of bound values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no values have been bound.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Formats a date or time parameter as text for backends without a native type for it.
+#[cfg(all(feature = "chrono", any(feature = "sqlite", feature = "oracle")))]
+fn format_temporal(param: &Param) -> Option<String> {
+    match param {
+        Param::Date(date) => Some(date.format("%Y-%m-%d").to_string()),
+        Param::Time(time) => Some(time.format("%H:%M:%S%.f").to_string()),
+        Param::Timestamp(timestamp) => Some(timestamp.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        Param::TimestampTz(timestamp) => {
+            Some(timestamp.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl rusqlite::ToSql for Param {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        use rusqlite::types::{ToSqlOutput, Value, ValueRef};
+
+        let output = match self {
+            Param::Null => ToSqlOutput::Owned(Value::Null),
+            Param::Bool(value) => ToSqlOutput::Owned(Value::Integer(i64::from(*value))),
+            Param::Int(value) => ToSqlOutput::Owned(Value::Integer(*value)),
+            Param::Float(value) => ToSqlOutput::Owned(Value::Real(*value)),
+            Param::Text(value) => ToSqlOutput::Borrowed(ValueRef::Text(value.as_bytes())),
+            Param::Bytes(value) => ToSqlOutput::Borrowed(ValueRef::Blob(value)),
+            #[cfg(feature = "chrono")]
+            Param::Date(_) | Param::Time(_) | Param::Timestamp(_) | Param::TimestampTz(_) => {
+                ToSqlOutput::Owned(Value::Text(format_temporal(self).unwrap_or_default()))
+            }
+        };
+
+        Ok(output)
+    }
+}
+
+#[cfg(feature = "duckdb")]
+impl duckdb::ToSql for Param {
+    fn to_sql(&self) -> duckdb::Result<duckdb::types::ToSqlOutput<'_>> {
+        use chrono::{DateTime, Timelike};
+        use duckdb::types::{TimeUnit, ToSqlOutput, Value, ValueRef};
+
+        let output = match self {
+            Param::Null => ToSqlOutput::Owned(Value::Null),
+            Param::Bool(value) => ToSqlOutput::Owned(Value::Boolean(*value)),
+            Param::Int(value) => ToSqlOutput::Owned(Value::BigInt(*value)),
+            Param::Float(value) => ToSqlOutput::Owned(Value::Double(*value)),
+            Param::Text(value) => ToSqlOutput::Borrowed(ValueRef::Text(value.as_bytes())),
+            Param::Bytes(value) => ToSqlOutput::Borrowed(ValueRef::Blob(value)),
+            Param::Date(value) => ToSqlOutput::Owned(Value::Date32(
+                (*value - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32,
+            )),
+            Param::Time(value) => ToSqlOutput::Owned(Value::Time64(
+                TimeUnit::Microsecond,
+                i64::from(value.num_seconds_from_midnight()) * 1_000_000
+                    + i64::from(value.nanosecond() / 1_000),
+            )),
+            Param::Timestamp(value) => ToSqlOutput::Owned(Value::Timestamp(
+                TimeUnit::Microsecond,
+                value.and_utc().timestamp_micros(),
+            )),
+            Param::TimestampTz(value) => ToSqlOutput::Owned(Value::Timestamp(
+                TimeUnit::Microsecond,
+                value.timestamp_micros(),
+            )),
+        };
+
+        Ok(output)
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "mariadb"))]
+impl From<Param> for mysql::Value {
+    fn from(param: Param) -> Self {
+        #[cfg(feature = "chrono")]
+        use chrono::{Datelike, Timelike};
+
+        #[cfg(feature = "chrono")]
+        let datetime = |timestamp: NaiveDateTime| {
+            mysql::Value::Date(
+                timestamp.year() as u16,
+                timestamp.month() as u8,
+                timestamp.day() as u8,
+                timestamp.hour() as u8,
+                timestamp.minute() as u8,
+                timestamp.second() as u8,
+                timestamp.nanosecond() / 1_000,
+            )
+        };
+
+        match param {
+            Param::Null => mysql::Value::NULL,
+            Param::Bool(value) => mysql::Value::Int(i64::from(value)),
+            Param::Int(value) => mysql::Value::Int(value),
+            Param::Float(value) => mysql::Value::Double(value),
+            Param::Text(value) => mysql::Value::Bytes(value.into_bytes()),
+            Param::Bytes(value) => mysql::Value::Bytes(value),
+            #[cfg(feature = "chrono")]
+            Param::Date(date) => datetime(date.and_time(NaiveTime::MIN)),
+            #[cfg(feature = "chrono")]
+            Param::Time(time) => mysql::Value::Time(
+                false,
+                0,
+                time.hour() as u8,
+                time.minute() as u8,
+                time.second() as u8,
+                time.nanosecond() / 1_000,
+            ),
+            #[cfg(feature = "chrono")]
+            Param::Timestamp(timestamp) => datetime(timestamp),
+            #[cfg(feature = "chrono")]
+            Param::TimestampTz(timestamp) => datetime(timestamp.naive_utc()),
+        }
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl oracle::sql_type::ToSql for Param {
+    fn oratype(&self, conn: &oracle::Connection) -> oracle::Result<oracle::sql_type::OracleType> {
+        match self {
+            Param::Null => None::<String>.oratype(conn),
+            Param::Bool(value) => i64::from(*value).oratype(conn),
+            Param::Int(value) => value.oratype(conn),
+            Param::Float(value) => value.oratype(conn),
+            Param::Text(value) => value.oratype(conn),
+            Param::Bytes(value) => value.oratype(conn),
+            Param::Date(value) => value.oratype(conn),
+            Param::Time(_) => format_temporal(self).unwrap_or_default().oratype(conn),
+            Param::Timestamp(value) => value.oratype(conn),
+            Param::TimestampTz(value) => value.oratype(conn),
+        }
+    }
+
+    fn to_sql(&self, val: &mut oracle::SqlValue) -> oracle::Result<()> {
+        match self {
+            Param::Null => None::<String>.to_sql(val),
+            Param::Bool(value) => i64::from(*value).to_sql(val),
+            Param::Int(value) => value.to_sql(val),
+            Param::Float(value) => value.to_sql(val),
+            Param::Text(value) => value.to_sql(val),
+            Param::Bytes(value) => value.to_sql(val),
+            Param::Date(value) => value.to_sql(val),
+            Param::Time(_) => format_temporal(self).unwrap_or_default().to_sql(val),
+            Param::Timestamp(value) => value.to_sql(val),
+            Param::TimestampTz(value) => value.to_sql(val),
+        }
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl tiberius::ToSql for Param {
+    fn to_sql(&self) -> tiberius::ColumnData<'_> {
+        match self {
+            Param::Null => tiberius::ColumnData::String(None),
+            Param::Bool(value) => value.to_sql(),
+            Param::Int(value) => value.to_sql(),
+            Param::Float(value) => value.to_sql(),
+            Param::Text(value) => value.to_sql(),
+            Param::Bytes(value) => value.to_sql(),
+            Param::Date(value) => value.to_sql(),
+            Param::Time(value) => value.to_sql(),
+            Param::Timestamp(value) => value.to_sql(),
+            Param::TimestampTz(value) => value.to_sql(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl tokio_postgres::types::ToSql for Param {
+    fn to_sql(
+        &self,
+        ty: &tokio_postgres::types::Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<tokio_postgres::types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        use crate::postgres::types::{encode_numeric, text_to_sql};
+        use tokio_postgres::types::{IsNull, Type};
+
+        // PostgreSQL checks parameters strictly against the column type, so values
+        // are converted to whatever type the server asks for.
+        match self {
+            Param::Null => Ok(IsNull::Yes),
+            Param::Bool(value) => match *ty {
+                Type::BOOL => value.to_sql(ty, out),
+                _ => Param::Int(i64::from(*value)).to_sql(ty, out),
+            },
+            Param::Int(value) => match *ty {
+                Type::BOOL => (*value != 0).to_sql(ty, out),
+                Type::INT2 => i16::try_from(*value)?.to_sql(ty, out),
+                Type::INT4 => i32::try_from(*value)?.to_sql(ty, out),
+                Type::INT8 => value.to_sql(ty, out),
+                Type::FLOAT4 => (*value as f32).to_sql(ty, out),
+                Type::FLOAT8 => (*value as f64).to_sql(ty, out),
+                _ => text_to_sql(&value.to_string(), ty, out),
+            },
+            Param::Float(value) => match *ty {
+                Type::FLOAT4 => (*value as f32).to_sql(ty, out),
+                Type::FLOAT8 => value.to_sql(ty, out),
+                Type::NUMERIC => encode_numeric(&value.to_string(), out),
+                _ => text_to_sql(&value.to_string(), ty, out),
+            },
+            Param::Text(value) => text_to_sql(value, ty, out),
+            Param::Bytes(value) => value.to_sql_checked(ty, out),
+            Param::Date(value) => value.to_sql_checked(ty, out),
+            Param::Time(value) => value.to_sql_checked(ty, out),
+            Param::Timestamp(value) => match *ty {
+                Type::TIMESTAMPTZ => value.and_utc().to_sql(ty, out),
+                _ => value.to_sql_checked(ty, out),
+            },
+            Param::TimestampTz(value) => match *ty {
+                Type::TIMESTAMP => value.naive_utc().to_sql(ty, out),
+                _ => value.to_sql_checked(ty, out),
+            },
+        }
+    }
+
+    fn accepts(_ty: &tokio_postgres::types::Type) -> bool {
+        true
+    }
+
+    tokio_postgres::types::to_sql_checked!();
+}
+
+#[cfg(feature = "sqlite")]
+impl rusqlite::types::FromSql for Param {
+    fn column_result(value: rusqlite::types::ValueRef<'_>) -> rusqlite::types::FromSqlResult<Self> {
+        use rusqlite::types::ValueRef;
+
+        let param = match value {
+            ValueRef::Null => Param::Null,
+            ValueRef::Integer(value) => Param::Int(value),
+            ValueRef::Real(value) => Param::Float(value),
+            ValueRef::Text(value) => match std::str::from_utf8(value) {
+                Ok(text) => Param::Text(text.to_string()),
+                Err(_) => Param::Bytes(value.to_vec()),
+            },
+            ValueRef::Blob(value) => Param::Bytes(value.to_vec()),
+        };
+
+        Ok(param)
+    }
+}
+
+#[cfg(feature = "duckdb")]
+impl duckdb::types::FromSql for Param {
+    fn column_result(value: duckdb::types::ValueRef<'_>) -> duckdb::types::FromSqlResult<Self> {
+        use crate::duckdb::types::value_to_string;
+        use chrono::{DateTime, Duration};
+        use duckdb::types::Value;
+
+        let param = match Value::from(value) {
+            Value::Null => Param::Null,
+            Value::Boolean(value) => Param::Bool(value),
+            Value::TinyInt(value) => Param::Int(value.into()),
+            Value::SmallInt(value) => Param::Int(value.into()),
+            Value::Int(value) => Param::Int(value.into()),
+            Value::BigInt(value) => Param::Int(value),
+            Value::UTinyInt(value) => Param::Int(value.into()),
+            Value::USmallInt(value) => Param::Int(value.into()),
+            Value::UInt(value) => Param::Int(value.into()),
+            Value::UBigInt(value) => i64::try_from(value)
+                .map(Param::Int)
+                .unwrap_or_else(|_| Param::Text(value.to_string())),
+            Value::HugeInt(value) => i64::try_from(value)
+                .map(Param::Int)
+                .unwrap_or_else(|_| Param::Text(value.to_string())),
+            Value::Float(value) => Param::Float(value.into()),
+            Value::Double(value) => Param::Float(value),
+            Value::Text(value) | Value::Enum(value) => Param::Text(value),
+            Value::Blob(value) => Param::Bytes(value),
+            Value::Date32(days) => DateTime::UNIX_EPOCH
+                .date_naive()
+                .checked_add_signed(Duration::days(days.into()))
+                .map(Param::Date)
+                .unwrap_or_else(|| Param::Text(value_to_string(Value::Date32(days)))),
+            Value::Time64(unit, value) => {
+                Param::Time(NaiveTime::MIN + Duration::microseconds(unit.to_micros(value)))
+            }
+            Value::Timestamp(unit, value) => DateTime::UNIX_EPOCH
+                .naive_utc()
+                .checked_add_signed(Duration::microseconds(unit.to_micros(value)))
+                .map(Param::Timestamp)
+                .unwrap_or_else(|| Param::Text(value_to_string(Value::Timestamp(unit, value)))),
+            value => Param::Text(value_to_string(value)),
+        };
+
+        Ok(param)
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "mariadb"))]
+impl From<mysql::Value> for Param {
+    /// Converts a value read with MySQL's binary protocol.
+    ///
+    /// Dates are converted to a `Timestamp`, as the protocol sends `DATE` and
+    /// `DATETIME` values alike, and are read as text without the `chrono` feature.
+    /// Text and binary columns are told apart by whether their bytes are valid UTF-8.
+    fn from(value: mysql::Value) -> Self {
+        #[cfg(feature = "chrono")]
+        use chrono::NaiveDate;
+
+        match value {
+            mysql::Value::NULL => Param::Null,
+            mysql::Value::Bytes(bytes) => match String::from_utf8(bytes) {
+                Ok(text) => Param::Text(text),
+                Err(error) => Param::Bytes(error.into_bytes()),
+            },
+            mysql::Value::Int(value) => Param::Int(value),
+            mysql::Value::UInt(value) => i64::try_from(value)
+                .map(Param::Int)
+                .unwrap_or_else(|_| Param::Text(value.to_string())),
+            mysql::Value::Float(value) => Param::Float(value.into()),
+            mysql::Value::Double(value) => Param::Float(value),
+            mysql::Value::Date(year, month, day, hour, minute, second, micros) => {
+                #[cfg(feature = "chrono")]
+                if let Some(timestamp) = NaiveDate::from_ymd_opt(
+                    year.into(),
+                    month.into(),
+                    day.into(),
+                )
+                .and_then(|date| {
+                    date.and_hms_micro_opt(hour.into(), minute.into(), second.into(), micros)
+                }) {
+                    return Param::Timestamp(timestamp);
+                }
+
+                let mut text = format!(
+                    "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                    year, month, day, hour, minute, second
+                );
+                if micros > 0 {
+                    text.push_str(&format!(".{:06}", micros));
+                }
+                Param::Text(text)
+            }
+            mysql::Value::Time(negative, days, hours, minutes, seconds, micros) => {
+                #[cfg(feature = "chrono")]
+                if let Some(time) = NaiveTime::from_hms_micro_opt(
+                    hours.into(),
+                    minutes.into(),
+                    seconds.into(),
+                    micros,
+                )
+                .filter(|_| !negative && days == 0)
+                {
+                    return Param::Time(time);
+                }
+
+                Param::Text(format!(
+                    "{}{:02}:{:02}:{:02}.{:06}",
+                    if negative { "-" } else { "" },
+                    days * 24 + u32::from(hours),
+                    minutes,
+                    seconds,
+                    micros
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl oracle::sql_type::FromSql for Param {
+    fn from_sql(val: &oracle::SqlValue) -> oracle::Result<Self> {
+        use oracle::sql_type::OracleType;
+
+        if val.is_null()? {
+            return Ok(Param::Null);
+        }
+
+        let param = match val.oracle_type()? {
+            OracleType::Number(_, _) | OracleType::Float(_) | OracleType::Int64 => {
+                // Decimals keep their exact text, as NUMBER may not fit a float.
+                let number: String = val.get()?;
+                number
+                    .parse::<i64>()
+                    .map(Param::Int)
+                    .unwrap_or(Param::Text(number))
+            }
+            OracleType::BinaryFloat | OracleType::BinaryDouble => Param::Float(val.get()?),
+            OracleType::Boolean => Param::Bool(val.get()?),
+            OracleType::Date | OracleType::Timestamp(_) => Param::Timestamp(val.get()?),
+            OracleType::TimestampTZ(_) | OracleType::TimestampLTZ(_) => {
+                Param::TimestampTz(val.get()?)
+            }
+            OracleType::Raw(_) | OracleType::BLOB | OracleType::LongRaw => Param::Bytes(val.get()?),
+            _ => Param::Text(val.get()?),
+        };
+
+        Ok(param)
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl<'a> tiberius::FromSql<'a> for Param {
+    fn from_sql(value: &'a tiberius::ColumnData<'static>) -> tiberius::Result<Option<Self>> {
+        use tiberius::ColumnData;
+
+        let param = match value {
+            ColumnData::U8(value) => value.map(|value| Param::Int(value.into())),
+            ColumnData::I16(value) => value.map(|value| Param::Int(value.into())),
+            ColumnData::I32(value) => value.map(|value| Param::Int(value.into())),
+            ColumnData::I64(value) => value.map(Param::Int),
+            ColumnData::F32(value) => value.map(|value| Param::Float(value.into())),
+            ColumnData::F64(value) => value.map(Param::Float),
+            ColumnData::Bit(value) => value.map(Param::Bool),
+            ColumnData::String(value) => value.as_ref().map(|value| Param::Text(value.to_string())),
+            ColumnData::Guid(value) => value.map(|value| Param::Text(value.to_string())),
+            ColumnData::Binary(value) => value.as_ref().map(|value| Param::Bytes(value.to_vec())),
+            ColumnData::Numeric(value) => value.map(|value| Param::Text(value.to_string())),
+            ColumnData::Xml(value) => value.as_ref().map(|value| Param::Text(value.to_string())),
+            ColumnData::Date(_) => NaiveDate::from_sql(value)?.map(Param::Date),
+            ColumnData::Time(_) => NaiveTime::from_sql(value)?.map(Param::Time),
+            ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => {
+                NaiveDateTime::from_sql(value)?.map(Param::Timestamp)
+            }
+            ColumnData::DateTimeOffset(_) => {
+                DateTime::<Utc>::from_sql(value)?.map(Param::TimestampTz)
+            }
+        };
+
+        Ok(param)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> tokio_postgres::types::FromSql<'a> for Param {
+    fn from_sql(
+        ty: &tokio_postgres::types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        use crate::postgres::types::TextValue;
+        use tokio_postgres::types::Type;
+
+        let param = match *ty {
+            Type::BOOL => Param::Bool(bool::from_sql(ty, raw)?),
+            Type::INT2 => Param::Int(i16::from_sql(ty, raw)?.into()),
+            Type::INT4 => Param::Int(i32::from_sql(ty, raw)?.into()),
+            Type::INT8 => Param::Int(i64::from_sql(ty, raw)?),
+            Type::OID => Param::Int(u32::from_sql(ty, raw)?.into()),
+            Type::FLOAT4 => Param::Float(f32::from_sql(ty, raw)?.into()),
+            Type::FLOAT8 => Param::Float(f64::from_sql(ty, raw)?),
+            Type::BYTEA => Param::Bytes(Vec::<u8>::from_sql(ty, raw)?),
+            Type::DATE => Param::Date(NaiveDate::from_sql(ty, raw)?),
+            Type::TIME => Param::Time(NaiveTime::from_sql(ty, raw)?),
+            Type::TIMESTAMP => Param::Timestamp(NaiveDateTime::from_sql(ty, raw)?),
+            Type::TIMESTAMPTZ => Param::TimestampTz(DateTime::<Utc>::from_sql(ty, raw)?),
+            _ => Param::Text(TextValue::from_sql(ty, raw)?.0),
+        };
+
+        Ok(param)
+    }
+
+    fn from_sql_null(
+        _ty: &tokio_postgres::types::Type,
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(Param::Null)
+    }
+
+    fn accepts(_ty: &tokio_postgres::types::Type) -> bool {
+        true
+    }
+}
+
 /// The `QueryBuilder` trait.
 ///
 /// Primarily used for subqueries within conditions.
 pub trait QueryBuilder<'a>: QueryBuilderClone<'a> {
-    fn to_sql(&self) -> String;
+    /// Renders the query, binding its values to `params`.
+    ///
+    /// The same `params` must be used for the whole statement so that numbered
+    /// placeholders stay in sequence across subqueries.
+    fn to_sql(&self, params: &mut QueryParams) -> String;
 }
 
 /// A helper trait to enable cloning of `Box<dyn QueryBuilder>`
@@ -55,3 +777,31 @@ impl<'a> Clone for Box<dyn QueryBuilder<'a> + 'a> {
         self.clone_box()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_params_placeholders() {
+        let mut params = QueryParams::new(PlaceholderStyle::QuestionMark);
+        assert_eq!(params.push("a"), "?");
+        assert_eq!(params.push("b"), "?");
+
+        let mut params = QueryParams::new(PlaceholderStyle::Colon);
+        assert_eq!(params.push("a"), ":1");
+        assert_eq!(params.push("b"), ":2");
+
+        let mut params = QueryParams::new(PlaceholderStyle::AtP);
+        assert_eq!(params.push("a"), "@P1");
+        assert_eq!(params.push("007"), "@P2");
+        assert_eq!(
+            params.values(),
+            &[Param::Text("a".to_string()), Param::Text("007".to_string())]
+        );
+
+        let mut params = QueryParams::new(PlaceholderStyle::Dollar);
+        assert_eq!(params.push("a"), "$1");
+        assert_eq!(params.push("b"), "$2");
+    }
+}
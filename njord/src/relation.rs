@@ -0,0 +1,81 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Relationships between tables.
+//!
+//! `#[derive(Table)]` implements [`Related`] for every relationship declared on a
+//! struct:
+//!
+//! - `#[belongs_to(User, fk = "user_id")]` - the table holds a foreign key to `User`.
+//! - `#[has_many(Post, fk = "user_id")]` - `Post` holds a foreign key to the table.
+//! - `#[many_to_many(Tag, through = "post_tags", fk = "post_id", related_fk = "tag_id")]` -
+//!   the join table `post_tags` links the table to `Tag`.
+//!
+//! The foreign keys default to the snake case name of the referenced struct followed
+//! by `_id`, so `#[has_many(Post)]` on `User` looks for `posts.user_id`. Related rows
+//! are then loaded together with the rows of a query with `select(...).with(...)`.
+
+use crate::{query::Param, table::Table};
+
+/// How the rows of a related table are found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// The table holds a foreign key referencing the primary key of the related table.
+    BelongsTo {
+        /// The column of the table holding the foreign key.
+        foreign_key: String,
+    },
+    /// The related table holds a foreign key referencing the primary key of the table.
+    HasMany {
+        /// The column of the related table holding the foreign key.
+        foreign_key: String,
+    },
+    /// A join table holds foreign keys referencing the primary keys of both tables.
+    ManyToMany {
+        /// The name of the join table.
+        through: String,
+        /// The column of the join table referencing the table.
+        foreign_key: String,
+        /// The column of the join table referencing the related table.
+        related_key: String,
+    },
+}
+
+/// A table with rows related to the rows of `R`.
+///
+/// Implemented by `#[derive(Table)]` for each `belongs_to`, `has_many` and
+/// `many_to_many` attribute of a struct.
+pub trait Related<R: Table>: Table {
+    /// Returns how the rows of `R` are found.
+    fn relation() -> Relation;
+
+    /// Returns the value the rows of `R` are matched against: the foreign key of a
+    /// `BelongsTo` relation, the primary key otherwise.
+    fn relation_key(&self) -> Param;
+}
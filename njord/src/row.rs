@@ -0,0 +1,777 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Decoding of result rows into Rust types.
+//!
+//! Backends read every row into a [`Row`] of driver independent [`Param`] values,
+//! which [`FromRow`] turns into the result type of a query. Besides the types
+//! implementing it with `#[derive(FromRow)]`, it is implemented for single values
+//! and for tuples, whose elements each read the next columns of the row:
+//!
+//! ```rust
+//! use njord::row::{FromRow, Row};
+//! use njord::query::Param;
+//!
+//! let columns = vec!["name".to_string(), "amount".to_string(), "street".to_string()];
+//! let values = vec![Param::Text("Anna".to_string()), Param::Int(3), Param::Null];
+//!
+//! let row = Row::new(&columns, &values);
+//! let (name, amount, street) = <(String, i64, Option<String>)>::from_row(&row).unwrap();
+//!
+//! assert_eq!((name.as_str(), amount, street), ("Anna", 3, None));
+//! ```
+
+use std::{fmt, ops::Range};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::query::Param;
+
+/// Represents errors that can occur while decoding a result row.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    /// The row has no column with the given name.
+    ColumnNotFound(String),
+    /// The row has fewer columns than the result type reads.
+    ColumnCount { expected: usize, actual: usize },
+    /// The column is `NULL`, but the result type cannot hold `NULL`.
+    UnexpectedNull(String),
+    /// The value of the column cannot be converted into the result type.
+    InvalidValue { column: String, message: String },
+    /// The database returned another number of generated keys than rows were inserted.
+    KeyCount { expected: usize, actual: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::ColumnNotFound(column) => write!(f, "no column named '{}'", column),
+            DecodeError::ColumnCount { expected, actual } => write!(
+                f,
+                "expected at least {} columns, but the row has {}",
+                expected, actual
+            ),
+            DecodeError::UnexpectedNull(column) => {
+                write!(f, "unexpected NULL in column '{}'", column)
+            }
+            DecodeError::InvalidValue { column, message } => {
+                write!(f, "invalid value in column '{}': {}", column, message)
+            }
+            DecodeError::KeyCount { expected, actual } => write!(
+                f,
+                "expected {} generated keys, but the database returned {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// A row read from the database.
+///
+/// The row borrows its column names and values from the backend, and can be
+/// narrowed down to some of its columns with `slice`.
+#[derive(Clone, Copy, Debug)]
+pub struct Row<'r> {
+    columns: &'r [String],
+    values: &'r [Param],
+}
+
+impl<'r> Row<'r> {
+    /// Creates a row from its column names and values.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The names of the columns, or their aliases.
+    /// * `values` - The values of the columns, in the same order.
+    pub fn new(columns: &'r [String], values: &'r [Param]) -> Self {
+        debug_assert_eq!(columns.len(), values.len());
+
+        Row { columns, values }
+    }
+
+    /// Returns the names of the columns.
+    pub fn columns(&self) -> &'r [String] {
+        self.columns
+    }
+
+    /// Returns the values of the columns.
+    pub fn values(&self) -> &'r [Param] {
+        self.values
+    }
+
+    /// Returns the number of columns.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the index of a column.
+    ///
+    /// Column names are compared case-insensitively if no column matches exactly,
+    /// since databases differ in the case they report unquoted names in.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name or alias of the column.
+    pub fn index_of(&self, column: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|name| name == column)
+            .or_else(|| {
+                self.columns
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(column))
+            })
+    }
+
+    /// Decodes the value of a column by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name or alias of the column.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded value, or a `DecodeError` if there is no
+    /// such column or its value cannot be converted.
+    pub fn get<T: FromValue>(&self, column: &str) -> Result<T, DecodeError> {
+        let index = self
+            .index_of(column)
+            .ok_or_else(|| DecodeError::ColumnNotFound(column.to_string()))?;
+
+        self.get_at(index)
+    }
+
+    /// Decodes the value of a column by its name, or returns the default value
+    /// if the row has no such column.
+    ///
+    /// Tables use this so a SELECT may pick only some of their columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name or alias of the column.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded or the default value, or a `DecodeError`
+    /// if the value of the column cannot be converted.
+    pub fn get_or_default<T: FromValue + Default>(&self, column: &str) -> Result<T, DecodeError> {
+        match self.index_of(column) {
+            Some(index) => self.get_at(index),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Decodes the value of a column by its position.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the column, starting at 0.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded value, or a `DecodeError` if there is no
+    /// such column or its value cannot be converted.
+    pub fn get_at<T: FromValue>(&self, index: usize) -> Result<T, DecodeError> {
+        let value = self.values.get(index).ok_or(DecodeError::ColumnCount {
+            expected: index + 1,
+            actual: self.len(),
+        })?;
+
+        decode_value(&self.columns[index], value)
+    }
+
+    /// Narrows the row down to some of its columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The positions of the columns to keep. It is cut off at the end of the row.
+    pub fn slice(&self, range: Range<usize>) -> Row<'r> {
+        let end = range.end.min(self.len());
+        let start = range.start.min(end);
+
+        Row {
+            columns: &self.columns[start..end],
+            values: &self.values[start..end],
+        }
+    }
+}
+
+/// A type that can be decoded from a single column value.
+pub trait FromValue: Sized {
+    /// Converts a value that is not `NULL`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value read from the database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the converted value, or a message saying why the
+    /// value cannot be converted.
+    fn from_value(value: &Param) -> Result<Self, String>;
+
+    /// Returns the value `NULL` is decoded to, or `None` if the type cannot hold `NULL`.
+    fn from_null() -> Option<Self> {
+        None
+    }
+}
+
+/// Decodes a single column value.
+///
+/// # Arguments
+///
+/// * `column` - The name of the column, reported when the value cannot be converted.
+/// * `value` - The value read from the database.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded value, or a `DecodeError` if the value
+/// cannot be converted.
+pub fn decode_value<T: FromValue>(column: &str, value: &Param) -> Result<T, DecodeError> {
+    match value {
+        Param::Null => {
+            T::from_null().ok_or_else(|| DecodeError::UnexpectedNull(column.to_string()))
+        }
+        value => T::from_value(value).map_err(|message| DecodeError::InvalidValue {
+            column: column.to_string(),
+            message,
+        }),
+    }
+}
+
+/// A type that can be decoded from a result row.
+///
+/// It is derived with `#[derive(FromRow)]` for structs, which decode their fields
+/// from the columns with the same name, whatever their position in the row.
+pub trait FromRow: Sized {
+    /// Returns the number of columns the type reads.
+    ///
+    /// Tuples use it to hand each of their elements the next columns of the row, so
+    /// `(User, Option<Address>)` reads the columns of `User` first and gives the
+    /// remaining ones to `Address`.
+    fn column_count() -> usize;
+
+    /// Decodes a row.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row read from the database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the decoded value, or a `DecodeError` if a column is
+    /// missing or holds a value that cannot be converted.
+    fn from_row(row: &Row<'_>) -> Result<Self, DecodeError>;
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        T::from_value(value).map(Some)
+    }
+
+    fn from_null() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<T: FromRow> FromRow for Option<T> {
+    fn column_count() -> usize {
+        T::column_count()
+    }
+
+    /// Decodes the row as `None` if all of its columns are `NULL`, as they are for
+    /// the missing side of an outer join.
+    fn from_row(row: &Row<'_>) -> Result<Self, DecodeError> {
+        if row.values().iter().all(|value| *value == Param::Null) {
+            Ok(None)
+        } else {
+            T::from_row(row).map(Some)
+        }
+    }
+}
+
+impl FromValue for Param {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        Ok(value.clone())
+    }
+
+    fn from_null() -> Option<Self> {
+        Some(Param::Null)
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Text(value) => Ok(value.clone()),
+            Param::Bytes(value) => String::from_utf8(value.clone()).map_err(|e| e.to_string()),
+            Param::Bool(value) => Ok(value.to_string()),
+            Param::Int(value) => Ok(value.to_string()),
+            Param::Float(value) => Ok(value.to_string()),
+            #[cfg(feature = "chrono")]
+            Param::Date(value) => Ok(value.format("%Y-%m-%d").to_string()),
+            #[cfg(feature = "chrono")]
+            Param::Time(value) => Ok(value.format("%H:%M:%S%.f").to_string()),
+            #[cfg(feature = "chrono")]
+            Param::Timestamp(value) => Ok(value.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+            #[cfg(feature = "chrono")]
+            Param::TimestampTz(value) => Ok(value.to_rfc3339()),
+            Param::Null => Err(unexpected(value, "text")),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Bool(value) => Ok(*value),
+            Param::Int(value) => Ok(*value != 0),
+            Param::Text(text) => match text.trim().to_ascii_lowercase().as_str() {
+                "true" | "t" | "1" | "yes" | "y" | "on" => Ok(true),
+                "false" | "f" | "0" | "no" | "n" | "off" => Ok(false),
+                _ => Err(unexpected(value, "a boolean")),
+            },
+            _ => Err(unexpected(value, "a boolean")),
+        }
+    }
+}
+
+macro_rules! integer_from_value {
+    ($($ty:ty),*) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: &Param) -> Result<Self, String> {
+                    match value {
+                        Param::Int(int) => <$ty>::try_from(*int).map_err(|e| e.to_string()),
+                        Param::Bool(value) => Ok(<$ty>::from(*value)),
+                        Param::Float(float) if float.fract() == 0.0 => {
+                            <$ty>::try_from(*float as i128).map_err(|e| e.to_string())
+                        }
+                        Param::Text(text) => text
+                            .trim()
+                            .parse()
+                            .map_err(|_| unexpected(value, "an integer")),
+                        _ => Err(unexpected(value, "an integer")),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer_from_value!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float_from_value {
+    ($($ty:ty),*) => {
+        $(
+            impl FromValue for $ty {
+                fn from_value(value: &Param) -> Result<Self, String> {
+                    match value {
+                        Param::Float(float) => Ok(*float as $ty),
+                        Param::Int(int) => Ok(*int as $ty),
+                        Param::Text(text) => text
+                            .trim()
+                            .parse()
+                            .map_err(|_| unexpected(value, "a number")),
+                        _ => Err(unexpected(value, "a number")),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_from_value!(f32, f64);
+
+impl FromValue for Vec<u8> {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Bytes(value) => Ok(value.clone()),
+            Param::Text(value) => Ok(value.clone().into_bytes()),
+            _ => Err(unexpected(value, "binary data")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromValue for NaiveDate {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Date(value) => Ok(*value),
+            Param::Timestamp(value) => Ok(value.date()),
+            Param::Text(text) => NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+                .or_else(|_| parse_timestamp(text).map(|timestamp| timestamp.date()))
+                .map_err(|_| unexpected(value, "a date")),
+            _ => Err(unexpected(value, "a date")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromValue for NaiveTime {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Time(value) => Ok(*value),
+            Param::Text(text) => NaiveTime::parse_from_str(text.trim(), "%H:%M:%S%.f")
+                .map_err(|_| unexpected(value, "a time")),
+            _ => Err(unexpected(value, "a time")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromValue for NaiveDateTime {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Timestamp(value) => Ok(*value),
+            Param::TimestampTz(value) => Ok(value.naive_utc()),
+            Param::Date(value) => Ok(value.and_time(NaiveTime::MIN)),
+            Param::Text(text) => {
+                parse_timestamp(text).map_err(|_| unexpected(value, "a timestamp"))
+            }
+            _ => Err(unexpected(value, "a timestamp")),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromValue for DateTime<Utc> {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::TimestampTz(value) => Ok(*value),
+            Param::Timestamp(value) => Ok(value.and_utc()),
+            Param::Text(text) => text
+                .trim()
+                .parse::<DateTime<FixedOffset>>()
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .or_else(|_| parse_timestamp(text).map(|timestamp| timestamp.and_utc()))
+                .map_err(|_| unexpected(value, "a timestamp")),
+            _ => Err(unexpected(value, "a timestamp")),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl FromValue for uuid::Uuid {
+    /// Decodes a UUID from its text, or from its 16 bytes on backends storing it as binary.
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Text(text) => {
+                uuid::Uuid::parse_str(text.trim()).map_err(|_| unexpected(value, "a uuid"))
+            }
+            Param::Bytes(bytes) => {
+                uuid::Uuid::from_slice(bytes).map_err(|_| unexpected(value, "a uuid"))
+            }
+            _ => Err(unexpected(value, "a uuid")),
+        }
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl FromValue for rust_decimal::Decimal {
+    fn from_value(value: &Param) -> Result<Self, String> {
+        use rust_decimal::Decimal;
+
+        match value {
+            Param::Int(int) => Ok(Decimal::from(*int)),
+            Param::Float(float) => {
+                Decimal::try_from(*float).map_err(|_| unexpected(value, "a decimal"))
+            }
+            Param::Text(text) => text
+                .trim()
+                .parse::<Decimal>()
+                .or_else(|_| Decimal::from_scientific(text.trim()))
+                .map_err(|_| unexpected(value, "a decimal")),
+            _ => Err(unexpected(value, "a decimal")),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl FromValue for serde_json::Value {
+    /// Decodes a JSON document from its text. Numbers and booleans, e.g. the results of
+    /// SQLite's JSON functions, become the matching JSON values.
+    fn from_value(value: &Param) -> Result<Self, String> {
+        match value {
+            Param::Text(text) => serde_json::from_str(text).map_err(|e| e.to_string()),
+            Param::Bytes(bytes) => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            Param::Bool(value) => Ok(serde_json::Value::Bool(*value)),
+            Param::Int(value) => Ok(serde_json::Value::from(*value)),
+            Param::Float(value) => Ok(serde_json::Value::from(*value)),
+            _ => Err(unexpected(value, "a JSON document")),
+        }
+    }
+}
+
+/// Parses a timestamp separated by either a space or a `T`.
+#[cfg(feature = "chrono")]
+fn parse_timestamp(text: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+    let text = text.trim();
+
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| text.parse::<NaiveDateTime>())
+}
+
+/// Describes a value that cannot be converted into the expected type.
+fn unexpected(value: &Param, expected: &str) -> String {
+    format!("expected {}, found {:?}", expected, value)
+}
+
+macro_rules! scalar_from_row {
+    ($($ty:ty),*) => {
+        $(
+            impl FromRow for $ty {
+                fn column_count() -> usize {
+                    1
+                }
+
+                fn from_row(row: &Row<'_>) -> Result<Self, DecodeError> {
+                    row.get_at(0)
+                }
+            }
+        )*
+    };
+}
+
+scalar_from_row!(
+    Param,
+    String,
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    Vec<u8>
+);
+
+#[cfg(feature = "chrono")]
+scalar_from_row!(NaiveDate, NaiveTime, NaiveDateTime, DateTime<Utc>);
+
+#[cfg(feature = "uuid")]
+scalar_from_row!(uuid::Uuid);
+
+#[cfg(feature = "decimal")]
+scalar_from_row!(rust_decimal::Decimal);
+
+#[cfg(feature = "json")]
+scalar_from_row!(serde_json::Value);
+
+macro_rules! tuple_from_row {
+    ($($name:ident),+) => {
+        impl<$($name: FromRow),+> FromRow for ($($name,)+) {
+            fn column_count() -> usize {
+                0 $(+ $name::column_count())+
+            }
+
+            #[allow(unused_assignments)]
+            fn from_row(row: &Row<'_>) -> Result<Self, DecodeError> {
+                if row.len() < Self::column_count() {
+                    return Err(DecodeError::ColumnCount {
+                        expected: Self::column_count(),
+                        actual: row.len(),
+                    });
+                }
+
+                let mut start = 0;
+
+                Ok(($({
+                    let end = start + $name::column_count();
+                    let value = $name::from_row(&row.slice(start..end))?;
+                    start = end;
+                    value
+                },)+))
+            }
+        }
+    };
+}
+
+tuple_from_row!(A);
+tuple_from_row!(A, B);
+tuple_from_row!(A, B, C);
+tuple_from_row!(A, B, C, D);
+tuple_from_row!(A, B, C, D, E);
+tuple_from_row!(A, B, C, D, E, F);
+tuple_from_row!(A, B, C, D, E, F, G);
+tuple_from_row!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn get_by_name() {
+        let columns = columns(&["ID", "name", "score"]);
+        let values = vec![Param::Int(7), Param::Text("Anna".to_string()), Param::Null];
+        let row = Row::new(&columns, &values);
+
+        assert_eq!(row.get::<u32>("id"), Ok(7));
+        assert_eq!(row.get::<String>("name"), Ok("Anna".to_string()));
+        assert_eq!(row.get::<Option<f64>>("score"), Ok(None));
+        assert_eq!(
+            row.get::<f64>("score"),
+            Err(DecodeError::UnexpectedNull("score".to_string()))
+        );
+        assert_eq!(
+            row.get::<String>("email"),
+            Err(DecodeError::ColumnNotFound("email".to_string()))
+        );
+        assert!(matches!(
+            row.get::<i64>("name"),
+            Err(DecodeError::InvalidValue { column, .. }) if column == "name"
+        ));
+    }
+
+    #[test]
+    fn convert_values() {
+        assert!(u8::from_value(&Param::Int(300)).is_err());
+        assert_eq!(i64::from_value(&Param::Text(" 42 ".to_string())), Ok(42));
+        assert_eq!(f64::from_value(&Param::Int(2)), Ok(2.0));
+        assert_eq!(bool::from_value(&Param::Int(1)), Ok(true));
+        assert_eq!(
+            Vec::<u8>::from_value(&Param::Bytes(vec![0, 159, 146, 150])),
+            Ok(vec![0, 159, 146, 150])
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn convert_timestamp() {
+        assert_eq!(
+            NaiveDateTime::from_value(&Param::Text("2024-01-02T03:04:05".to_string())),
+            Ok(NaiveDate::from_ymd_opt(2024, 1, 2)
+                .unwrap()
+                .and_hms_opt(3, 4, 5)
+                .unwrap())
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn convert_timestamp_with_offset() {
+        let expected = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(1, 4, 5)
+            .unwrap()
+            .and_utc();
+
+        assert_eq!(
+            DateTime::<Utc>::from_value(&Param::Text("2024-01-02 03:04:05+02:00".to_string())),
+            Ok(expected)
+        );
+        assert_eq!(
+            DateTime::<Utc>::from_value(&Param::Timestamp(expected.naive_utc())),
+            Ok(expected)
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn convert_uuid() {
+        let id = uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+
+        assert_eq!(uuid::Uuid::from_value(&Param::Text(id.to_string())), Ok(id));
+        assert_eq!(
+            uuid::Uuid::from_value(&Param::Bytes(id.as_bytes().to_vec())),
+            Ok(id)
+        );
+        assert!(uuid::Uuid::from_value(&Param::Int(1)).is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn convert_decimal() {
+        use rust_decimal::Decimal;
+
+        assert_eq!(
+            Decimal::from_value(&Param::Text("12345678901234567890.25".to_string())),
+            Ok("12345678901234567890.25".parse().unwrap())
+        );
+        assert_eq!(Decimal::from_value(&Param::Int(7)), Ok(Decimal::from(7)));
+        assert!(Decimal::from_value(&Param::Text("abc".to_string())).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn convert_json() {
+        assert_eq!(
+            serde_json::Value::from_value(&Param::Text(r#"{"tags":["a","b"]}"#.to_string())),
+            Ok(serde_json::json!({ "tags": ["a", "b"] }))
+        );
+        assert_eq!(
+            serde_json::Value::from_value(&Param::Int(3)),
+            Ok(serde_json::json!(3))
+        );
+    }
+
+    #[test]
+    fn tuples() {
+        let columns = columns(&["count", "name", "street", "city"]);
+        let values = vec![
+            Param::Int(2),
+            Param::Text("Anna".to_string()),
+            Param::Null,
+            Param::Null,
+        ];
+        let row = Row::new(&columns, &values);
+
+        let (count, name, address) =
+            <(i64, String, Option<(String, String)>)>::from_row(&row).unwrap();
+        assert_eq!((count, name.as_str(), address), (2, "Anna", None));
+
+        assert_eq!(
+            <(i64, String, String, String, String)>::from_row(&row).err(),
+            Some(DecodeError::ColumnCount {
+                expected: 5,
+                actual: 4
+            })
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    dialect::{AutoIncrementStyle, Dialect},
+    schema::{
+        column::sequence_name,
+        error::{unsupported, SchemaError},
+        Check, ColumnDefinition, ForeignKey,
+    },
+};
+
+/// A change made by an ALTER TABLE statement.
+enum AlterOperation {
+    AddColumn(ColumnDefinition),
+    DropColumn(String),
+    RenameColumn(String, String),
+    AlterColumn(ColumnDefinition),
+    AddForeignKey(ForeignKey),
+    AddCheck(Check),
+    DropConstraint(String),
+}
+
+/// A builder for ALTER TABLE statements.
+///
+/// Every change is rendered as a statement of its own, in the order the changes
+/// were added, since not every dialect can make several changes at once.
+pub struct AlterTableBuilder<D: Dialect> {
+    dialect: D,
+    name: String,
+    operations: Vec<AlterOperation>,
+}
+
+impl<D: Dialect + Default> AlterTableBuilder<D> {
+    /// Creates a new `AlterTableBuilder` instance without any changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_dialect(D::default(), name)
+    }
+}
+
+impl<D: Dialect> AlterTableBuilder<D> {
+    /// Creates a new `AlterTableBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `name` - The name of the table.
+    pub fn with_dialect(dialect: D, name: impl Into<String>) -> Self {
+        AlterTableBuilder {
+            dialect,
+            name: name.into(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Adds a column to the table.
+    ///
+    /// The column does not become part of the primary key. A NOT NULL column
+    /// needs a default if the table already has rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The definition of the column.
+    pub fn add_column(mut self, column: ColumnDefinition) -> Self {
+        self.operations.push(AlterOperation::AddColumn(column));
+        self
+    }
+
+    /// Drops a column from the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column.
+    pub fn drop_column(mut self, name: impl Into<String>) -> Self {
+        self.operations
+            .push(AlterOperation::DropColumn(name.into()));
+        self
+    }
+
+    /// Renames a column of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The current name of the column.
+    /// * `to` - The new name of the column.
+    pub fn rename_column(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.operations
+            .push(AlterOperation::RenameColumn(from.into(), to.into()));
+        self
+    }
+
+    /// Changes the type and nullability of a column to those of `column`.
+    ///
+    /// Defaults and auto-increment values of the column are left as they are.
+    /// SQLite cannot change an existing column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The new definition of the column.
+    pub fn alter_column(mut self, column: ColumnDefinition) -> Self {
+        self.operations.push(AlterOperation::AlterColumn(column));
+        self
+    }
+
+    /// Adds a foreign key to the table.
+    ///
+    /// SQLite and DuckDB only declare foreign keys in CREATE TABLE.
+    ///
+    /// # Arguments
+    ///
+    /// * `foreign_key` - The foreign key constraint.
+    pub fn add_foreign_key(mut self, foreign_key: ForeignKey) -> Self {
+        self.operations
+            .push(AlterOperation::AddForeignKey(foreign_key));
+        self
+    }
+
+    /// Adds a check constraint to the table.
+    ///
+    /// SQLite and DuckDB only declare check constraints in CREATE TABLE.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the constraint.
+    /// * `expression` - An SQL expression every row has to satisfy, e.g. `price >= 0`.
+    pub fn add_check(mut self, name: impl Into<String>, expression: impl Into<String>) -> Self {
+        self.operations
+            .push(AlterOperation::AddCheck(Check::new(name, expression)));
+        self
+    }
+
+    /// Drops a named constraint from the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the constraint.
+    pub fn drop_constraint(mut self, name: impl Into<String>) -> Self {
+        self.operations
+            .push(AlterOperation::DropConstraint(name.into()));
+        self
+    }
+
+    /// Renders the statements altering the table.
+    ///
+    /// # Returns
+    ///
+    /// The statements to execute in order, or a `SchemaError` if the dialect
+    /// cannot make one of the changes.
+    pub fn render(&self) -> Result<Vec<String>, SchemaError> {
+        let dialect: &dyn Dialect = &self.dialect;
+        let table = dialect.identifier(&self.name);
+        let mut statements = Vec::new();
+
+        for operation in &self.operations {
+            match operation {
+                AlterOperation::AddColumn(column) => {
+                    if column.auto_increment
+                        && dialect.auto_increment_style() == AutoIncrementStyle::Sequence
+                    {
+                        statements.push(format!(
+                            "CREATE SEQUENCE {}",
+                            dialect.identifier(&sequence_name(&self.name, &column.name))
+                        ));
+                    }
+                    statements
+                        .push(dialect.add_column(&table, &column.render(dialect, &self.name)));
+                }
+                AlterOperation::DropColumn(column) => statements.push(format!(
+                    "ALTER TABLE {} DROP COLUMN {}",
+                    table,
+                    dialect.identifier(column)
+                )),
+                AlterOperation::RenameColumn(from, to) => {
+                    statements.push(dialect.rename_column(&table, &dialect.identifier(from), to))
+                }
+                AlterOperation::AlterColumn(column) => statements.extend(
+                    dialect
+                        .alter_column(
+                            &table,
+                            &dialect.identifier(&column.name),
+                            &column.type_name(dialect),
+                            column.nullable && !column.primary_key,
+                        )
+                        .ok_or_else(|| unsupported(dialect, "changing a column"))?,
+                ),
+                AlterOperation::AddForeignKey(foreign_key) => {
+                    statements.push(self.add_constraint(&table, foreign_key.render(dialect))?)
+                }
+                AlterOperation::AddCheck(check) => {
+                    statements.push(self.add_constraint(&table, check.render(dialect))?)
+                }
+                AlterOperation::DropConstraint(name) => {
+                    if !dialect.supports_alter_constraints() {
+                        return Err(unsupported(dialect, "dropping a constraint"));
+                    }
+                    statements.push(format!(
+                        "ALTER TABLE {} DROP CONSTRAINT {}",
+                        table,
+                        dialect.identifier(name)
+                    ));
+                }
+            }
+        }
+
+        Ok(statements)
+    }
+
+    /// Renders the statement adding a constraint to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The rendered name of the table.
+    /// * `constraint` - The rendered constraint.
+    fn add_constraint(&self, table: &str, constraint: String) -> Result<String, SchemaError> {
+        if !self.dialect.supports_alter_constraints() {
+            return Err(unsupported(&self.dialect, "adding a constraint"));
+        }
+
+        Ok(format!("ALTER TABLE {} ADD {}", table, constraint))
+    }
+}
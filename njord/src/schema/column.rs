@@ -0,0 +1,193 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    dialect::{AutoIncrementStyle, DataType, Dialect},
+    types::SqlType,
+};
+
+/// The definition of a table column.
+///
+/// `#[derive(Table)]` returns one for every stored field from
+/// `Table::column_definitions`.
+#[derive(Clone, Debug)]
+pub struct ColumnDefinition {
+    /// The name of the column.
+    pub name: String,
+    /// The type of the column.
+    pub data_type: DataType,
+    /// Whether the column accepts NULL.
+    pub nullable: bool,
+    /// Whether the column is part of the primary key.
+    pub primary_key: bool,
+    /// Whether the database generates increasing values for the column.
+    pub auto_increment: bool,
+    /// The SQL expression the database fills in when no value is written.
+    pub default: Option<String>,
+    type_name: Option<fn(&dyn Dialect) -> String>,
+}
+
+impl ColumnDefinition {
+    /// Creates the definition of a NOT NULL column.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column.
+    /// * `data_type` - The type of the column.
+    pub fn new(name: impl Into<String>, data_type: DataType) -> Self {
+        ColumnDefinition {
+            name: name.into(),
+            data_type,
+            nullable: false,
+            primary_key: false,
+            auto_increment: false,
+            default: None,
+            type_name: None,
+        }
+    }
+
+    /// Creates the definition of a column storing values of `T`.
+    ///
+    /// The column is nullable if `T` is, and its type name comes from
+    /// `SqlType::type_name`, so a custom type name of `T` is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column.
+    pub fn of_type<T: SqlType>(name: impl Into<String>) -> Self {
+        ColumnDefinition {
+            nullable: T::nullable(),
+            type_name: Some(T::type_name),
+            ..Self::new(name, T::data_type())
+        }
+    }
+
+    /// Sets the type of the column, replacing the type name of the Rust type.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_type` - The type of the column.
+    pub fn data_type(mut self, data_type: DataType) -> Self {
+        self.data_type = data_type;
+        self.type_name = None;
+        self
+    }
+
+    /// Makes the column accept NULL.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
+    /// Makes the column part of the primary key.
+    pub fn primary_key(mut self) -> Self {
+        self.primary_key = true;
+        self
+    }
+
+    /// Lets the database generate increasing values for the column.
+    pub fn auto_increment(mut self) -> Self {
+        self.auto_increment = true;
+        self
+    }
+
+    /// Sets the value the database fills in when none is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - An SQL expression, e.g. `'active'` or `CURRENT_TIMESTAMP`.
+    pub fn default(mut self, expression: impl Into<String>) -> Self {
+        self.default = Some(expression.into());
+        self
+    }
+
+    /// Returns the name of the column type in `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the type for.
+    pub fn type_name(&self, dialect: &dyn Dialect) -> String {
+        match self.type_name {
+            Some(type_name) => type_name(dialect),
+            None => dialect.type_name(self.data_type),
+        }
+    }
+
+    /// Renders the column as it is declared in CREATE TABLE or ALTER TABLE.
+    ///
+    /// On dialects drawing auto-increment values from a sequence, the column
+    /// defaults to the next value of [`sequence_name`]; the sequence itself is not created.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the column for.
+    /// * `table` - The name of the table the column belongs to.
+    pub(crate) fn render(&self, dialect: &dyn Dialect, table: &str) -> String {
+        let mut definition = format!(
+            "{} {}",
+            dialect.identifier(&self.name),
+            self.type_name(dialect)
+        );
+        let mut default = self.default.clone();
+
+        if self.auto_increment {
+            match dialect.auto_increment_style() {
+                // Only a single INTEGER PRIMARY KEY can be declared AUTOINCREMENT
+                AutoIncrementStyle::Autoincrement => {}
+                AutoIncrementStyle::AutoIncrement => definition.push_str(" AUTO_INCREMENT"),
+                AutoIncrementStyle::Identity => definition.push_str(" IDENTITY(1, 1)"),
+                AutoIncrementStyle::GeneratedIdentity => {
+                    definition.push_str(" GENERATED BY DEFAULT AS IDENTITY")
+                }
+                AutoIncrementStyle::Sequence => {
+                    default = Some(format!("nextval('{}')", sequence_name(table, &self.name)));
+                }
+            }
+        }
+
+        if let Some(default) = default {
+            definition.push_str(&format!(" DEFAULT {}", default));
+        }
+        if !self.nullable || self.primary_key {
+            definition.push_str(" NOT NULL");
+        }
+
+        definition
+    }
+}
+
+/// Returns the name of the sequence generating the values of an auto-increment column.
+///
+/// # Arguments
+///
+/// * `table` - The name of the table.
+/// * `column` - The name of the column.
+pub(crate) fn sequence_name(table: &str, column: &str) -> String {
+    format!("{}_{}_seq", table, column)
+}
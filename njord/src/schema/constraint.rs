@@ -0,0 +1,210 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+
+use crate::dialect::Dialect;
+
+/// What the database does with referencing rows when the referenced row is
+/// deleted or its key is updated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferentialAction {
+    /// Fails the statement if referencing rows remain at its end.
+    NoAction,
+    /// Fails the statement right away if there are referencing rows.
+    Restrict,
+    /// Deletes or updates the referencing rows along with the referenced row.
+    Cascade,
+    /// Sets the referencing columns to NULL.
+    SetNull,
+    /// Sets the referencing columns to their default values.
+    SetDefault,
+}
+
+impl fmt::Display for ReferentialAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferentialAction::NoAction => write!(f, "NO ACTION"),
+            ReferentialAction::Restrict => write!(f, "RESTRICT"),
+            ReferentialAction::Cascade => write!(f, "CASCADE"),
+            ReferentialAction::SetNull => write!(f, "SET NULL"),
+            ReferentialAction::SetDefault => write!(f, "SET DEFAULT"),
+        }
+    }
+}
+
+/// A FOREIGN KEY constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForeignKey {
+    /// The name of the constraint, generated by the database if not set.
+    pub name: Option<String>,
+    /// The referencing columns.
+    pub columns: Vec<String>,
+    /// The name of the referenced table.
+    pub referenced_table: String,
+    /// The referenced columns, in the order of `columns`.
+    pub referenced_columns: Vec<String>,
+    /// What happens when the referenced row is deleted.
+    pub on_delete: Option<ReferentialAction>,
+    /// What happens when the key of the referenced row is updated.
+    pub on_update: Option<ReferentialAction>,
+}
+
+impl ForeignKey {
+    /// Creates a foreign key from `columns` to `referenced_columns` of another table.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The referencing columns.
+    /// * `referenced_table` - The name of the referenced table.
+    /// * `referenced_columns` - The referenced columns, usually its primary key.
+    pub fn new<I, J>(columns: I, referenced_table: impl Into<String>, referenced_columns: J) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        J: IntoIterator,
+        J::Item: Into<String>,
+    {
+        ForeignKey {
+            name: None,
+            columns: columns.into_iter().map(Into::into).collect(),
+            referenced_table: referenced_table.into(),
+            referenced_columns: referenced_columns.into_iter().map(Into::into).collect(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    /// Sets the name of the constraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the constraint, needed to drop it later on.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets what happens when the referenced row is deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action applied to the referencing rows.
+    pub fn on_delete(mut self, action: ReferentialAction) -> Self {
+        self.on_delete = Some(action);
+        self
+    }
+
+    /// Sets what happens when the key of the referenced row is updated.
+    ///
+    /// Oracle has no ON UPDATE clause.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action applied to the referencing rows.
+    pub fn on_update(mut self, action: ReferentialAction) -> Self {
+        self.on_update = Some(action);
+        self
+    }
+
+    /// Renders the constraint as it is declared in CREATE TABLE or ALTER TABLE.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the constraint for.
+    pub(crate) fn render(&self, dialect: &dyn Dialect) -> String {
+        let identifiers = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| dialect.identifier(name))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let mut constraint = format!(
+            "{}FOREIGN KEY ({}) REFERENCES {} ({})",
+            constraint_name(dialect, self.name.as_deref()),
+            identifiers(&self.columns),
+            dialect.identifier(&self.referenced_table),
+            identifiers(&self.referenced_columns)
+        );
+
+        if let Some(action) = self.on_delete {
+            constraint.push_str(&format!(" ON DELETE {}", action));
+        }
+        if let Some(action) = self.on_update {
+            constraint.push_str(&format!(" ON UPDATE {}", action));
+        }
+
+        constraint
+    }
+}
+
+/// A CHECK constraint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    /// The name of the constraint.
+    pub name: String,
+    /// The SQL expression every row has to satisfy.
+    pub expression: String,
+}
+
+impl Check {
+    /// Creates a check constraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the constraint.
+    /// * `expression` - An SQL expression, e.g. `price >= 0`.
+    pub fn new(name: impl Into<String>, expression: impl Into<String>) -> Self {
+        Check {
+            name: name.into(),
+            expression: expression.into(),
+        }
+    }
+
+    /// Renders the constraint as it is declared in CREATE TABLE or ALTER TABLE.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the constraint for.
+    pub(crate) fn render(&self, dialect: &dyn Dialect) -> String {
+        format!(
+            "{}CHECK ({})",
+            constraint_name(dialect, Some(&self.name)),
+            self.expression
+        )
+    }
+}
+
+/// Renders the `CONSTRAINT name ` prefix of a named constraint.
+fn constraint_name(dialect: &dyn Dialect, name: Option<&str>) -> String {
+    name.map(|name| format!("CONSTRAINT {} ", dialect.identifier(name)))
+        .unwrap_or_default()
+}
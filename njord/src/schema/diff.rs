@@ -0,0 +1,504 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    dialect::{DataType, Dialect},
+    schema::{AlterTableBuilder, ColumnDefinition, CreateTableBuilder, DropTableBuilder},
+    table::Table,
+};
+
+/// The name and columns of a table, as declared by a model or found in a database.
+#[derive(Clone, Debug)]
+pub struct TableSchema {
+    /// The name of the table.
+    pub name: String,
+    /// The columns of the table, in table order.
+    pub columns: Vec<ColumnDefinition>,
+}
+
+impl TableSchema {
+    /// Creates the schema of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table.
+    /// * `columns` - The definitions of the columns, in table order.
+    pub fn new(name: impl Into<String>, columns: Vec<ColumnDefinition>) -> Self {
+        TableSchema {
+            name: name.into(),
+            columns,
+        }
+    }
+
+    /// Creates the schema declared by a table model.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table.
+    pub fn from_table<T: Table>(table: &T) -> Self {
+        Self::new(table.get_name(), table.column_definitions())
+    }
+
+    /// Returns the column with the given name, if the table has one.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column.
+    pub fn column(&self, name: &str) -> Option<&ColumnDefinition> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+/// The statements migrating a schema to another one and back.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Migration {
+    /// The statements applying the changes, in order.
+    pub up: Vec<String>,
+    /// The statements reverting the changes, in order.
+    pub down: Vec<String>,
+    /// Descriptions of the changes that can lose data or fail on existing rows.
+    pub warnings: Vec<String>,
+}
+
+impl Migration {
+    /// Returns `true` if the schemas are the same.
+    pub fn is_empty(&self) -> bool {
+        self.up.is_empty()
+    }
+}
+
+/// What `diff` does with the tables of the current schema missing from the target one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownTables {
+    /// Leaves the tables alone, e.g. tables of the database not managed by models.
+    #[default]
+    Keep,
+    /// Drops the tables.
+    Drop,
+}
+
+/// A change of a single column.
+enum ColumnChange<'a> {
+    Add(&'a ColumnDefinition),
+    Drop(&'a ColumnDefinition),
+    Alter(&'a ColumnDefinition, &'a ColumnDefinition),
+}
+
+/// Compares two schemas and renders the statements turning `current` into `target`.
+///
+/// Tables and columns are matched by name, so a renamed table or column shows up
+/// as dropped and added again. Columns are compared by their type in `dialect` and
+/// whether they accept NULL; changes of defaults and primary keys are not detected.
+///
+/// Where `dialect` cannot change a column in place, as on SQLite, the table is
+/// rebuilt: its rows are copied into a new table replacing the old one.
+///
+/// # Arguments
+///
+/// * `dialect` - The SQL dialect of the target database.
+/// * `current` - The tables as they are now, e.g. read from the database.
+/// * `target` - The tables as they should be, e.g. declared by the models.
+/// * `unknown_tables` - Whether the tables of `current` missing from `target` are kept
+///   or dropped.
+pub fn diff<D: Dialect + Clone>(
+    dialect: D,
+    current: &[TableSchema],
+    target: &[TableSchema],
+    unknown_tables: UnknownTables,
+) -> Migration {
+    let mut migration = Migration::default();
+    let mut down_steps = Vec::new();
+
+    for table in target {
+        match current.iter().find(|existing| existing.name == table.name) {
+            Some(existing) => {
+                let (up, down) = diff_table(&dialect, existing, table, &mut migration.warnings);
+                migration.up.extend(up);
+                down_steps.push(down);
+            }
+            None => {
+                migration.up.extend(create_table(&dialect, table));
+                down_steps.push(drop_table(&dialect, table));
+            }
+        }
+    }
+
+    for table in current {
+        if unknown_tables == UnknownTables::Drop
+            && target.iter().all(|wanted| wanted.name != table.name)
+        {
+            migration.warnings.push(format!(
+                "dropping table {} deletes all of its rows",
+                table.name
+            ));
+            migration.up.extend(drop_table(&dialect, table));
+            down_steps.push(create_table(&dialect, table));
+        }
+    }
+
+    migration.down = down_steps.into_iter().rev().flatten().collect();
+    migration
+}
+
+/// Renders the statements turning one version of a table into another and back.
+///
+/// # Arguments
+///
+/// * `dialect` - The SQL dialect of the target database.
+/// * `from` - The table as it is now.
+/// * `to` - The table as it should be.
+/// * `warnings` - The warnings receiving the changes that can lose data.
+fn diff_table<D: Dialect + Clone>(
+    dialect: &D,
+    from: &TableSchema,
+    to: &TableSchema,
+    warnings: &mut Vec<String>,
+) -> (Vec<String>, Vec<String>) {
+    let mut changes = Vec::new();
+
+    for column in &to.columns {
+        match from.column(&column.name) {
+            Some(existing) if is_changed(dialect, existing, column) => {
+                changes.push(ColumnChange::Alter(existing, column))
+            }
+            Some(_) => {}
+            None => changes.push(ColumnChange::Add(column)),
+        }
+    }
+    for column in &from.columns {
+        if to.column(&column.name).is_none() {
+            changes.push(ColumnChange::Drop(column));
+        }
+    }
+
+    if changes.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    for change in &changes {
+        if let Some(warning) = warning(dialect, &to.name, change) {
+            warnings.push(warning);
+        }
+    }
+
+    let mut up = AlterTableBuilder::with_dialect(dialect.clone(), &to.name);
+    for change in &changes {
+        up = match change {
+            ColumnChange::Add(column) => up.add_column((*column).clone()),
+            ColumnChange::Drop(column) => up.drop_column(&column.name),
+            ColumnChange::Alter(_, column) => up.alter_column((*column).clone()),
+        };
+    }
+
+    let mut down = AlterTableBuilder::with_dialect(dialect.clone(), &to.name);
+    for change in changes.iter().rev() {
+        down = match change {
+            ColumnChange::Add(column) => down.drop_column(&column.name),
+            ColumnChange::Drop(column) => down.add_column((*column).clone()),
+            ColumnChange::Alter(column, _) => down.alter_column((*column).clone()),
+        };
+    }
+
+    match (up.render(), down.render()) {
+        (Ok(up), Ok(down)) => (up, down),
+        _ => {
+            warnings.push(format!(
+                "table {} is rebuilt to change its columns, its indexes and triggers are dropped",
+                to.name
+            ));
+            (
+                rebuild_table(dialect, from, to),
+                rebuild_table(dialect, to, from),
+            )
+        }
+    }
+}
+
+/// Renders the statements replacing a table with a new one, keeping the values
+/// of the columns both versions have.
+///
+/// # Arguments
+///
+/// * `dialect` - The SQL dialect of the target database.
+/// * `from` - The table as it is now.
+/// * `to` - The table as it should be.
+fn rebuild_table<D: Dialect + Clone>(
+    dialect: &D,
+    from: &TableSchema,
+    to: &TableSchema,
+) -> Vec<String> {
+    let temporary = format!("_njord_new_{}", to.name);
+    let columns: Vec<String> = to
+        .columns
+        .iter()
+        .filter(|column| from.column(&column.name).is_some())
+        .map(|column| dialect.identifier(&column.name))
+        .collect();
+
+    let mut statements = CreateTableBuilder::with_dialect(dialect.clone(), &temporary)
+        .columns(to.columns.clone())
+        .render();
+    statements.push(format!(
+        "INSERT INTO {} ({}) SELECT {} FROM {}",
+        dialect.identifier(&temporary),
+        columns.join(", "),
+        columns.join(", "),
+        dialect.identifier(&from.name)
+    ));
+    statements.extend(DropTableBuilder::with_dialect(dialect.clone(), &from.name).render());
+    statements.push(format!(
+        "ALTER TABLE {} RENAME TO {}",
+        dialect.identifier(&temporary),
+        dialect.identifier(&to.name)
+    ));
+
+    statements
+}
+
+/// Renders the statements creating a table.
+fn create_table<D: Dialect + Clone>(dialect: &D, table: &TableSchema) -> Vec<String> {
+    CreateTableBuilder::with_dialect(dialect.clone(), &table.name)
+        .columns(table.columns.clone())
+        .render()
+}
+
+/// Renders the statements dropping a table.
+fn drop_table<D: Dialect + Clone>(dialect: &D, table: &TableSchema) -> Vec<String> {
+    DropTableBuilder::with_dialect(dialect.clone(), &table.name)
+        .columns(table.columns.clone())
+        .render()
+}
+
+/// Returns `true` if the type or nullability of a column differs between two versions.
+fn is_changed(dialect: &dyn Dialect, from: &ColumnDefinition, to: &ColumnDefinition) -> bool {
+    from.type_name(dialect) != to.type_name(dialect) || is_nullable(from) != is_nullable(to)
+}
+
+/// Returns `true` if a column accepts NULL; primary key columns never do.
+fn is_nullable(column: &ColumnDefinition) -> bool {
+    column.nullable && !column.primary_key
+}
+
+/// Describes the data a column change can lose, or the rows it can fail on.
+fn warning(dialect: &dyn Dialect, table: &str, change: &ColumnChange) -> Option<String> {
+    match change {
+        ColumnChange::Add(column)
+            if !is_nullable(column) && column.default.is_none() && !column.auto_increment =>
+        {
+            Some(format!(
+                "adding {}.{} as NOT NULL without a default fails if the table has rows",
+                table, column.name
+            ))
+        }
+        ColumnChange::Add(_) => None,
+        ColumnChange::Drop(column) => Some(format!(
+            "dropping column {}.{} deletes its values",
+            table, column.name
+        )),
+        ColumnChange::Alter(from, to) => {
+            let (from_type, to_type) = (from.type_name(dialect), to.type_name(dialect));
+
+            if from_type != to_type && !is_widening(from.data_type, to.data_type) {
+                Some(format!(
+                    "changing {}.{} from {} to {} can truncate or reject its values",
+                    table, to.name, from_type, to_type
+                ))
+            } else if is_nullable(from) && !is_nullable(to) {
+                Some(format!(
+                    "making {}.{} NOT NULL fails if it holds NULL values",
+                    table, to.name
+                ))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Returns `true` if every value of type `from` can be stored as type `to`.
+fn is_widening(from: DataType, to: DataType) -> bool {
+    let integer_rank = |data_type| match data_type {
+        DataType::Boolean => Some(0),
+        DataType::SmallInt => Some(1),
+        DataType::Integer => Some(2),
+        DataType::BigInt => Some(3),
+        _ => None,
+    };
+
+    match (from, to) {
+        _ if from == to => true,
+        (DataType::Float, DataType::Double) => true,
+        (DataType::SmallInt | DataType::Integer, DataType::Double) => true,
+        (DataType::Varchar(from), DataType::Varchar(to)) => to >= from,
+        (DataType::Varchar(_), DataType::Text) => true,
+        (
+            DataType::Decimal { precision, scale },
+            DataType::Decimal {
+                precision: to_precision,
+                scale: to_scale,
+            },
+        ) => {
+            to_scale >= scale
+                && i16::from(to_precision) - i16::from(to_scale)
+                    >= i16::from(precision) - i16::from(scale)
+        }
+        _ => matches!(
+            (integer_rank(from), integer_rank(to)),
+            (Some(from), Some(to)) if to >= from
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{PostgresDialect, SqliteDialect};
+
+    fn users(columns: Vec<ColumnDefinition>) -> TableSchema {
+        let mut all = vec![ColumnDefinition::new("id", DataType::BigInt)
+            .primary_key()
+            .auto_increment()];
+        all.extend(columns);
+        TableSchema::new("users", all)
+    }
+
+    #[test]
+    fn diff_tables_and_columns() {
+        let current = vec![
+            users(vec![
+                ColumnDefinition::new("name", DataType::Text),
+                ColumnDefinition::new("age", DataType::Integer).nullable(),
+            ]),
+            TableSchema::new(
+                "sessions",
+                vec![ColumnDefinition::new("token", DataType::Text)],
+            ),
+        ];
+        let target = vec![
+            users(vec![
+                ColumnDefinition::new("name", DataType::Varchar(40)),
+                ColumnDefinition::new("email", DataType::Text),
+            ]),
+            TableSchema::new(
+                "posts",
+                vec![ColumnDefinition::new("title", DataType::Text).nullable()],
+            ),
+        ];
+
+        let migration = diff(PostgresDialect, &current, &target, UnknownTables::Drop);
+
+        assert_eq!(
+            migration.up,
+            vec![
+                "ALTER TABLE users ALTER COLUMN name TYPE VARCHAR(40)",
+                "ALTER TABLE users ALTER COLUMN name SET NOT NULL",
+                "ALTER TABLE users ADD COLUMN email TEXT NOT NULL",
+                "ALTER TABLE users DROP COLUMN age",
+                "CREATE TABLE posts (title TEXT)",
+                "DROP TABLE sessions",
+            ]
+        );
+        assert_eq!(
+            migration.down,
+            vec![
+                "CREATE TABLE sessions (token TEXT NOT NULL)",
+                "DROP TABLE posts",
+                "ALTER TABLE users ADD COLUMN age INTEGER",
+                "ALTER TABLE users DROP COLUMN email",
+                "ALTER TABLE users ALTER COLUMN name TYPE TEXT",
+                "ALTER TABLE users ALTER COLUMN name SET NOT NULL",
+            ]
+        );
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "changing users.name from TEXT to VARCHAR(40) can truncate or reject its values",
+                "adding users.email as NOT NULL without a default fails if the table has rows",
+                "dropping column users.age deletes its values",
+                "dropping table sessions deletes all of its rows",
+            ]
+        );
+
+        assert!(diff(PostgresDialect, &target, &target, UnknownTables::Drop).is_empty());
+    }
+
+    #[test]
+    fn keep_unknown_tables() {
+        let current = vec![
+            users(Vec::new()),
+            TableSchema::new(
+                "sessions",
+                vec![ColumnDefinition::new("token", DataType::Text)],
+            ),
+        ];
+        let target = vec![users(Vec::new())];
+
+        assert!(diff(PostgresDialect, &current, &target, UnknownTables::Keep).is_empty());
+    }
+
+    #[test]
+    fn rebuild_table_without_alter_column() {
+        let current = vec![users(vec![
+            ColumnDefinition::new("name", DataType::Text).nullable()
+        ])];
+        let target = vec![users(vec![
+            ColumnDefinition::new("name", DataType::Text),
+            ColumnDefinition::new("active", DataType::Boolean).default("1"),
+        ])];
+
+        let migration = diff(SqliteDialect, &current, &target, UnknownTables::Keep);
+
+        assert_eq!(
+            migration.up,
+            vec![
+                "CREATE TABLE _njord_new_users (id INTEGER PRIMARY KEY AUTOINCREMENT, \
+                 name TEXT NOT NULL, active INTEGER DEFAULT 1 NOT NULL)",
+                "INSERT INTO _njord_new_users (id, name) SELECT id, name FROM users",
+                "DROP TABLE users",
+                "ALTER TABLE _njord_new_users RENAME TO users",
+            ]
+        );
+        assert_eq!(
+            migration.down,
+            vec![
+                "CREATE TABLE _njord_new_users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)",
+                "INSERT INTO _njord_new_users (id, name) SELECT id, name FROM users",
+                "DROP TABLE users",
+                "ALTER TABLE _njord_new_users RENAME TO users",
+            ]
+        );
+        assert_eq!(
+            migration.warnings,
+            vec![
+                "making users.name NOT NULL fails if it holds NULL values",
+                "table users is rebuilt to change its columns, its indexes and triggers are dropped",
+            ]
+        );
+    }
+}
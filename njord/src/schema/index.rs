@@ -0,0 +1,182 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    dialect::Dialect,
+    schema::error::{unsupported, SchemaError},
+};
+
+/// A builder for CREATE INDEX statements.
+pub struct CreateIndexBuilder<D: Dialect> {
+    dialect: D,
+    name: String,
+    table: String,
+    columns: Vec<String>,
+    unique: bool,
+    condition: Option<String>,
+}
+
+impl<D: Dialect + Default> CreateIndexBuilder<D> {
+    /// Creates a new `CreateIndexBuilder` instance for an index without columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the index.
+    /// * `table` - The name of the indexed table.
+    pub fn new(name: impl Into<String>, table: impl Into<String>) -> Self {
+        Self::with_dialect(D::default(), name, table)
+    }
+}
+
+impl<D: Dialect> CreateIndexBuilder<D> {
+    /// Creates a new `CreateIndexBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `name` - The name of the index.
+    /// * `table` - The name of the indexed table.
+    pub fn with_dialect(dialect: D, name: impl Into<String>, table: impl Into<String>) -> Self {
+        CreateIndexBuilder {
+            dialect,
+            name: name.into(),
+            table: table.into(),
+            columns: Vec::new(),
+            unique: false,
+            condition: None,
+        }
+    }
+
+    /// Sets the indexed columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to be indexed, most significant first.
+    pub fn columns<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Makes the index reject rows with the same values in all indexed columns.
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    /// Restricts the index to the rows matching a condition.
+    ///
+    /// Partial indexes are supported by SQLite, PostgreSQL and MSSQL.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - An SQL expression, e.g. `deleted_at IS NULL`.
+    pub fn where_clause(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
+    /// Renders the statement creating the index.
+    ///
+    /// # Returns
+    ///
+    /// The CREATE INDEX statement, or a `SchemaError` if the index is partial and
+    /// the dialect does not support partial indexes.
+    pub fn render(&self) -> Result<String, SchemaError> {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|column| self.dialect.identifier(column))
+            .collect();
+
+        let mut statement = format!(
+            "CREATE {}INDEX {} ON {} ({})",
+            if self.unique { "UNIQUE " } else { "" },
+            self.dialect.identifier(&self.name),
+            self.dialect.identifier(&self.table),
+            columns.join(", ")
+        );
+
+        if let Some(condition) = &self.condition {
+            if !self.dialect.supports_partial_indexes() {
+                return Err(unsupported(&self.dialect, "a partial index"));
+            }
+            statement.push_str(&format!(" WHERE {}", condition));
+        }
+
+        Ok(statement)
+    }
+}
+
+/// A builder for DROP INDEX statements.
+pub struct DropIndexBuilder<D: Dialect> {
+    dialect: D,
+    name: String,
+    table: String,
+}
+
+impl<D: Dialect + Default> DropIndexBuilder<D> {
+    /// Creates a new `DropIndexBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the index.
+    /// * `table` - The name of the indexed table, needed by MySQL, MariaDB and MSSQL.
+    pub fn new(name: impl Into<String>, table: impl Into<String>) -> Self {
+        Self::with_dialect(D::default(), name, table)
+    }
+}
+
+impl<D: Dialect> DropIndexBuilder<D> {
+    /// Creates a new `DropIndexBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `name` - The name of the index.
+    /// * `table` - The name of the indexed table, needed by MySQL, MariaDB and MSSQL.
+    pub fn with_dialect(dialect: D, name: impl Into<String>, table: impl Into<String>) -> Self {
+        DropIndexBuilder {
+            dialect,
+            name: name.into(),
+            table: table.into(),
+        }
+    }
+
+    /// Renders the statement dropping the index.
+    pub fn render(&self) -> String {
+        self.dialect.drop_index(
+            &self.dialect.identifier(&self.name),
+            &self.dialect.identifier(&self.table),
+        )
+    }
+}
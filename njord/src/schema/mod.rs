@@ -0,0 +1,183 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Rendering of DDL statements.
+//!
+//! The builders render the statements through a [`Dialect`](crate::dialect::Dialect),
+//! so the same schema is created with the column types and syntax of each backend.
+//! They only render SQL; execute the statements with the `raw_execute` function of
+//! the backend:
+//!
+//! ```rust
+//! use njord::dialect::{MssqlDialect, OracleDialect};
+//! use njord::keys::AutoIncrementPrimaryKey;
+//! use njord::schema::CreateTableBuilder;
+//! use njord::table::Table;
+//! use njord_derive::Table;
+//!
+//! #[derive(Table)]
+//! #[table_name = "users"]
+//! struct User {
+//!     id: AutoIncrementPrimaryKey<i64>,
+//!     #[column(length = 64)]
+//!     name: String,
+//!     active: bool,
+//! }
+//!
+//! let oracle = CreateTableBuilder::<OracleDialect>::from_table(&User::default()).render();
+//! assert_eq!(
+//!     oracle,
+//!     vec![
+//!         "CREATE TABLE users (id NUMBER(19) GENERATED BY DEFAULT AS IDENTITY NOT NULL, \
+//!          name VARCHAR2(64) NOT NULL, active NUMBER(1) NOT NULL, PRIMARY KEY (id))"
+//!     ]
+//! );
+//!
+//! let mssql = CreateTableBuilder::<MssqlDialect>::from_table(&User::default()).render();
+//! assert_eq!(
+//!     mssql,
+//!     vec![
+//!         "CREATE TABLE users (id BIGINT IDENTITY(1, 1) NOT NULL, \
+//!          name NVARCHAR(64) NOT NULL, active BIT NOT NULL, PRIMARY KEY (id))"
+//!     ]
+//! );
+//! ```
+//!
+//! Changes to an existing schema are written once as well. Builders taking an
+//! [`AnyDialect`](crate::dialect::AnyDialect) pick the backend at runtime, e.g. to
+//! run the same migration on a SQLite development database and on MySQL:
+//!
+//! ```rust
+//! use njord::dialect::{AnyDialect, DataType};
+//! use njord::schema::{AlterTableBuilder, ColumnDefinition, CreateIndexBuilder};
+//!
+//! let migration = |dialect: AnyDialect| {
+//!     let mut statements = AlterTableBuilder::with_dialect(dialect, "users")
+//!         .add_column(ColumnDefinition::new("nickname", DataType::Varchar(32)).nullable())
+//!         .rename_column("name", "full_name")
+//!         .render()
+//!         .unwrap();
+//!     statements.push(
+//!         CreateIndexBuilder::with_dialect(dialect, "users_nickname", "users")
+//!             .columns(["nickname"])
+//!             .unique()
+//!             .render()
+//!             .unwrap(),
+//!     );
+//!     statements
+//! };
+//!
+//! assert_eq!(
+//!     migration(AnyDialect::Sqlite),
+//!     vec![
+//!         "ALTER TABLE users ADD COLUMN nickname TEXT",
+//!         "ALTER TABLE users RENAME COLUMN name TO full_name",
+//!         "CREATE UNIQUE INDEX users_nickname ON users (nickname)",
+//!     ]
+//! );
+//! assert_eq!(
+//!     migration(AnyDialect::MySql),
+//!     vec![
+//!         "ALTER TABLE users ADD COLUMN nickname VARCHAR(32)",
+//!         "ALTER TABLE users RENAME COLUMN name TO full_name",
+//!         "CREATE UNIQUE INDEX users_nickname ON users (nickname)",
+//!     ]
+//! );
+//! ```
+
+pub mod alter;
+pub mod column;
+pub mod constraint;
+pub mod diff;
+pub mod error;
+pub mod index;
+pub mod table;
+
+pub use alter::AlterTableBuilder;
+pub use column::ColumnDefinition;
+pub use constraint::{Check, ForeignKey, ReferentialAction};
+pub use diff::{diff, Migration, TableSchema, UnknownTables};
+pub use error::SchemaError;
+pub use index::{CreateIndexBuilder, DropIndexBuilder};
+pub use table::{CreateTableBuilder, DropTableBuilder};
+
+use crate::dialect::Dialect;
+
+/// Creates a new `CreateTableBuilder` instance for a table without columns.
+///
+/// # Arguments
+///
+/// * `name` - The name of the table.
+pub fn create_table<D: Dialect + Default>(name: impl Into<String>) -> CreateTableBuilder<D> {
+    CreateTableBuilder::new(name)
+}
+
+/// Creates a new `AlterTableBuilder` instance without any changes.
+///
+/// # Arguments
+///
+/// * `name` - The name of the table.
+pub fn alter_table<D: Dialect + Default>(name: impl Into<String>) -> AlterTableBuilder<D> {
+    AlterTableBuilder::new(name)
+}
+
+/// Creates a new `DropTableBuilder` instance.
+///
+/// # Arguments
+///
+/// * `name` - The name of the table.
+pub fn drop_table<D: Dialect + Default>(name: impl Into<String>) -> DropTableBuilder<D> {
+    DropTableBuilder::new(name)
+}
+
+/// Creates a new `CreateIndexBuilder` instance for an index without columns.
+///
+/// # Arguments
+///
+/// * `name` - The name of the index.
+/// * `table` - The name of the indexed table.
+pub fn create_index<D: Dialect + Default>(
+    name: impl Into<String>,
+    table: impl Into<String>,
+) -> CreateIndexBuilder<D> {
+    CreateIndexBuilder::new(name, table)
+}
+
+/// Creates a new `DropIndexBuilder` instance.
+///
+/// # Arguments
+///
+/// * `name` - The name of the index.
+/// * `table` - The name of the indexed table.
+pub fn drop_index<D: Dialect + Default>(
+    name: impl Into<String>,
+    table: impl Into<String>,
+) -> DropIndexBuilder<D> {
+    DropIndexBuilder::new(name, table)
+}
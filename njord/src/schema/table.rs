@@ -0,0 +1,377 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    dialect::{AutoIncrementStyle, Dialect},
+    schema::{column::sequence_name, Check, ColumnDefinition, ForeignKey},
+    table::Table,
+};
+
+/// A builder for CREATE TABLE statements.
+pub struct CreateTableBuilder<D: Dialect> {
+    dialect: D,
+    name: String,
+    columns: Vec<ColumnDefinition>,
+    foreign_keys: Vec<ForeignKey>,
+    checks: Vec<Check>,
+    if_not_exists: bool,
+}
+
+impl<D: Dialect + Default> CreateTableBuilder<D> {
+    /// Creates a new `CreateTableBuilder` instance for a table without columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_dialect(D::default(), name)
+    }
+
+    /// Creates a new `CreateTableBuilder` instance with the name and columns of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table to be created.
+    pub fn from_table<T: Table>(table: &T) -> Self {
+        Self::new(table.get_name()).columns(table.column_definitions())
+    }
+}
+
+impl<D: Dialect> CreateTableBuilder<D> {
+    /// Creates a new `CreateTableBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `name` - The name of the table.
+    pub fn with_dialect(dialect: D, name: impl Into<String>) -> Self {
+        CreateTableBuilder {
+            dialect,
+            name: name.into(),
+            columns: Vec::new(),
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            if_not_exists: false,
+        }
+    }
+
+    /// Adds a column to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The definition of the column.
+    pub fn column(mut self, column: ColumnDefinition) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Adds several columns to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The definitions of the columns, in table order.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = ColumnDefinition>) -> Self {
+        self.columns.extend(columns);
+        self
+    }
+
+    /// Adds a foreign key to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `foreign_key` - The foreign key constraint.
+    pub fn foreign_key(mut self, foreign_key: ForeignKey) -> Self {
+        self.foreign_keys.push(foreign_key);
+        self
+    }
+
+    /// Adds a check constraint to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the constraint.
+    /// * `expression` - An SQL expression every row has to satisfy, e.g. `price >= 0`.
+    pub fn check(mut self, name: impl Into<String>, expression: impl Into<String>) -> Self {
+        self.checks.push(Check::new(name, expression));
+        self
+    }
+
+    /// Makes the statement do nothing if the table already exists.
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// Renders the statements creating the table.
+    ///
+    /// # Returns
+    ///
+    /// The statements to execute in order: the CREATE TABLE statement, preceded by
+    /// the sequences of its auto-increment columns on dialects that need them.
+    pub fn render(&self) -> Vec<String> {
+        let dialect: &dyn Dialect = &self.dialect;
+        let table = dialect.identifier(&self.name);
+        let key: Vec<&ColumnDefinition> = self.columns.iter().filter(|c| c.primary_key).collect();
+        let inline_key = matches!(key.as_slice(), [column] if column.auto_increment)
+            && dialect.auto_increment_style() == AutoIncrementStyle::Autoincrement;
+
+        let mut statements = Vec::new();
+        let mut definitions = Vec::new();
+
+        for column in &self.columns {
+            let name = dialect.identifier(&column.name);
+
+            if inline_key && column.primary_key {
+                definitions.push(format!("{} INTEGER PRIMARY KEY AUTOINCREMENT", name));
+                continue;
+            }
+
+            if column.auto_increment
+                && dialect.auto_increment_style() == AutoIncrementStyle::Sequence
+            {
+                statements.push(format!(
+                    "CREATE SEQUENCE {}{}",
+                    if self.if_not_exists {
+                        "IF NOT EXISTS "
+                    } else {
+                        ""
+                    },
+                    dialect.identifier(&sequence_name(&self.name, &column.name))
+                ));
+            }
+
+            definitions.push(column.render(dialect, &self.name));
+        }
+
+        if !key.is_empty() && !inline_key {
+            let key: Vec<String> = key
+                .iter()
+                .map(|column| dialect.identifier(&column.name))
+                .collect();
+            definitions.push(format!("PRIMARY KEY ({})", key.join(", ")));
+        }
+        for foreign_key in &self.foreign_keys {
+            definitions.push(foreign_key.render(dialect));
+        }
+        for check in &self.checks {
+            definitions.push(check.render(dialect));
+        }
+
+        let definitions = definitions.join(", ");
+        statements.push(if self.if_not_exists {
+            dialect.create_table_if_not_exists(&table, &definitions)
+        } else {
+            format!("CREATE TABLE {} ({})", table, definitions)
+        });
+
+        statements
+    }
+}
+
+/// A builder for DROP TABLE statements.
+pub struct DropTableBuilder<D: Dialect> {
+    dialect: D,
+    name: String,
+    auto_increment_columns: Vec<String>,
+    if_exists: bool,
+}
+
+impl<D: Dialect + Default> DropTableBuilder<D> {
+    /// Creates a new `DropTableBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the table.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_dialect(D::default(), name)
+    }
+
+    /// Creates a new `DropTableBuilder` instance for a table, dropping the sequences
+    /// created along with it as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table to be dropped.
+    pub fn from_table<T: Table>(table: &T) -> Self {
+        Self::new(table.get_name()).columns(table.column_definitions())
+    }
+}
+
+impl<D: Dialect> DropTableBuilder<D> {
+    /// Creates a new `DropTableBuilder` instance rendering through `dialect`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The SQL dialect of the target database.
+    /// * `name` - The name of the table.
+    pub fn with_dialect(dialect: D, name: impl Into<String>) -> Self {
+        DropTableBuilder {
+            dialect,
+            name: name.into(),
+            auto_increment_columns: Vec::new(),
+            if_exists: false,
+        }
+    }
+
+    /// Sets the columns of the table, so the sequences created along with its
+    /// auto-increment columns are dropped as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The definitions of the columns.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = ColumnDefinition>) -> Self {
+        self.auto_increment_columns = columns
+            .into_iter()
+            .filter(|column| column.auto_increment)
+            .map(|column| column.name)
+            .collect();
+        self
+    }
+
+    /// Makes the statement do nothing if the table does not exist.
+    pub fn if_exists(mut self) -> Self {
+        self.if_exists = true;
+        self
+    }
+
+    /// Renders the statements dropping the table.
+    ///
+    /// # Returns
+    ///
+    /// The statements to execute in order: the DROP TABLE statement, followed by
+    /// the sequences of its auto-increment columns on dialects that need them.
+    pub fn render(&self) -> Vec<String> {
+        let table = self.dialect.identifier(&self.name);
+        let mut statements = vec![if self.if_exists {
+            self.dialect.drop_table_if_exists(&table)
+        } else {
+            format!("DROP TABLE {}", table)
+        }];
+
+        if self.dialect.auto_increment_style() == AutoIncrementStyle::Sequence {
+            for column in &self.auto_increment_columns {
+                statements.push(format!(
+                    "DROP SEQUENCE {}{}",
+                    if self.if_exists { "IF EXISTS " } else { "" },
+                    self.dialect.identifier(&sequence_name(&self.name, column))
+                ));
+            }
+        }
+
+        statements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{DataType, MssqlDialect, PostgresDialect, SqliteDialect};
+    use crate::schema::ReferentialAction;
+    use crate::table::Table;
+    use njord_derive::Table;
+
+    // `status` is only ever written by the database
+    #[allow(dead_code)]
+    #[derive(Table)]
+    #[table_name = "order_lines"]
+    struct OrderLine {
+        #[primary_key]
+        order_id: i64,
+        #[primary_key]
+        line: i32,
+        #[column(length = 40)]
+        sku: String,
+        #[column(precision = 10, scale = 2)]
+        price: f64,
+        note: Option<String>,
+        #[default = "'open'"]
+        status: String,
+    }
+
+    #[test]
+    fn create_table_from_model() {
+        let create = CreateTableBuilder::<PostgresDialect>::from_table(&OrderLine::default())
+            .if_not_exists()
+            .render();
+
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE IF NOT EXISTS order_lines (order_id BIGINT NOT NULL, \
+                 line INTEGER NOT NULL, sku VARCHAR(40) NOT NULL, price NUMERIC(10, 2) NOT NULL, \
+                 note TEXT, status TEXT DEFAULT 'open' NOT NULL, PRIMARY KEY (order_id, line))"
+            ]
+        );
+    }
+
+    #[test]
+    fn create_table_from_columns() {
+        let create = CreateTableBuilder::<SqliteDialect>::new("audit log")
+            .column(
+                ColumnDefinition::new("id", DataType::BigInt)
+                    .primary_key()
+                    .auto_increment(),
+            )
+            .column(ColumnDefinition::new("message", DataType::Text).nullable())
+            .column(ColumnDefinition::new("at", DataType::Timestamp).default("CURRENT_TIMESTAMP"))
+            .render();
+
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE \"audit log\" (id INTEGER PRIMARY KEY AUTOINCREMENT, \
+                 message TEXT, at TEXT DEFAULT CURRENT_TIMESTAMP NOT NULL)"
+            ]
+        );
+    }
+
+    #[test]
+    fn create_table_with_constraints() {
+        let create = CreateTableBuilder::<MssqlDialect>::new("order_lines")
+            .columns(OrderLine::default().column_definitions())
+            .foreign_key(
+                ForeignKey::new(["order_id"], "orders", ["id"])
+                    .name("order_lines_order")
+                    .on_delete(ReferentialAction::Cascade),
+            )
+            .check("order_lines_price", "price >= 0")
+            .render();
+
+        assert_eq!(
+            create,
+            vec![
+                "CREATE TABLE order_lines (order_id BIGINT NOT NULL, line INT NOT NULL, \
+                 sku NVARCHAR(40) NOT NULL, price DECIMAL(10, 2) NOT NULL, note NVARCHAR(MAX), \
+                 status NVARCHAR(MAX) DEFAULT 'open' NOT NULL, PRIMARY KEY (order_id, line), \
+                 CONSTRAINT order_lines_order FOREIGN KEY (order_id) REFERENCES orders (id) \
+                 ON DELETE CASCADE, CONSTRAINT order_lines_price CHECK (price >= 0))"
+            ]
+        );
+    }
+}
@@ -29,6 +29,8 @@
 
 use rusqlite::Error as RusqliteError;
 
+use crate::{builder::BuildError, row::DecodeError};
+
 /// Represents errors that can occur during SQLite operations.
 #[derive(Debug)]
 pub enum SqliteError {
@@ -40,6 +42,14 @@ pub enum SqliteError {
     UpdateError(RusqliteError),
     /// Error that occurs during a DELETE operation.
     DeleteError(RusqliteError),
+    /// Error that occurs while starting or ending a transaction.
+    TransactionError(RusqliteError),
+    /// The query affected a different number of rows than expected with `expect_rows`.
+    UnexpectedRowCount { expected: u64, actual: u64 },
+    /// A result row could not be decoded into the requested type.
+    DecodeError(DecodeError),
+    /// The query could not be built from the given rows or columns.
+    BuildError(BuildError),
 }
 
 impl From<RusqliteError> for SqliteError {
@@ -48,3 +58,17 @@ impl From<RusqliteError> for SqliteError {
         SqliteError::InsertError(error)
     }
 }
+
+impl From<DecodeError> for SqliteError {
+    /// Converts a `DecodeError` into a `SqliteError`.
+    fn from(error: DecodeError) -> Self {
+        SqliteError::DecodeError(error)
+    }
+}
+
+impl From<BuildError> for SqliteError {
+    /// Converts a `BuildError` into a `SqliteError`.
+    fn from(error: BuildError) -> Self {
+        SqliteError::BuildError(error)
+    }
+}
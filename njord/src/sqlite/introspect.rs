@@ -0,0 +1,181 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use rusqlite::Connection;
+
+use crate::{
+    dialect::{DataType, Dialect, SqliteDialect},
+    schema::{ColumnDefinition, TableSchema},
+};
+
+use super::SqliteError;
+
+/// Reads the tables of a database.
+///
+/// The column types are read back from the declared type names, so a column declared
+/// `VARCHAR(40)` becomes `DataType::Varchar(40)`, and unknown names are mapped by the
+/// SQLite type affinity rules. The internal `sqlite_` tables are left out.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing the tables ordered by name, or a `SqliteError` if the
+/// schema could not be read.
+pub fn introspect(conn: &Connection) -> Result<Vec<TableSchema>, SqliteError> {
+    let mut statement = conn
+        .prepare(
+            "SELECT name, sql FROM sqlite_master \
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .map_err(SqliteError::SelectError)?;
+    let tables = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(SqliteError::SelectError)?;
+
+    tables
+        .into_iter()
+        .map(|(name, sql)| {
+            let columns = read_columns(conn, &name, &sql)?;
+            Ok(TableSchema::new(name, columns))
+        })
+        .collect()
+}
+
+/// Reads the columns of a table with `PRAGMA table_info`.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `table` - The name of the table.
+/// * `sql` - The CREATE TABLE statement of the table, telling whether its key is AUTOINCREMENT.
+fn read_columns(
+    conn: &Connection,
+    table: &str,
+    sql: &str,
+) -> Result<Vec<ColumnDefinition>, SqliteError> {
+    let mut statement = conn
+        .prepare(&format!(
+            "PRAGMA table_info({})",
+            SqliteDialect.quote_identifier(table)
+        ))
+        .map_err(SqliteError::SelectError)?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>("name")?,
+                row.get::<_, String>("type")?,
+                row.get::<_, bool>("notnull")?,
+                row.get::<_, Option<String>>("dflt_value")?,
+                row.get::<_, i64>("pk")? > 0,
+            ))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(SqliteError::SelectError)?;
+
+    let key_columns = rows.iter().filter(|(.., primary_key)| *primary_key).count();
+    let autoincrement = sql.to_ascii_uppercase().contains("AUTOINCREMENT");
+
+    Ok(rows
+        .into_iter()
+        .map(|(name, declared_type, not_null, default, primary_key)| {
+            let mut column = ColumnDefinition::new(name, data_type(&declared_type));
+            column.nullable = !not_null && !primary_key;
+            column.primary_key = primary_key;
+            // Only a single INTEGER PRIMARY KEY can be declared AUTOINCREMENT
+            column.auto_increment = primary_key
+                && key_columns == 1
+                && autoincrement
+                && declared_type.eq_ignore_ascii_case("INTEGER");
+            column.default = default;
+            column
+        })
+        .collect())
+}
+
+/// Returns the data type of a column declared with the given type name.
+///
+/// # Arguments
+///
+/// * `declared_type` - The type name in the CREATE TABLE statement, e.g. `VARCHAR(40)`.
+fn data_type(declared_type: &str) -> DataType {
+    let declared_type = declared_type.trim().to_ascii_uppercase();
+    let (name, arguments) = match declared_type.split_once('(') {
+        Some((name, arguments)) => (
+            name.trim(),
+            arguments
+                .trim_end_matches(')')
+                .split(',')
+                .filter_map(|argument| argument.trim().parse::<u32>().ok())
+                .collect(),
+        ),
+        None => (declared_type.as_str(), Vec::new()),
+    };
+
+    match (name, arguments.as_slice()) {
+        ("BOOLEAN" | "BOOL", _) => DataType::Boolean,
+        ("SMALLINT" | "TINYINT", _) => DataType::SmallInt,
+        ("INT" | "INTEGER" | "MEDIUMINT", _) => DataType::Integer,
+        ("BIGINT", _) => DataType::BigInt,
+        ("FLOAT", _) => DataType::Float,
+        ("REAL" | "DOUBLE" | "DOUBLE PRECISION", _) => DataType::Double,
+        ("DECIMAL" | "NUMERIC", [precision, scale]) => DataType::Decimal {
+            precision: *precision as u8,
+            scale: *scale as u8,
+        },
+        ("DECIMAL" | "NUMERIC", [precision]) => DataType::Decimal {
+            precision: *precision as u8,
+            scale: 0,
+        },
+        ("VARCHAR" | "NVARCHAR" | "CHARACTER VARYING", [length]) => DataType::Varchar(*length),
+        ("DATE", _) => DataType::Date,
+        ("TIME", _) => DataType::Time,
+        ("TIMESTAMP" | "DATETIME", _) => DataType::Timestamp,
+        ("TIMESTAMPTZ", _) => DataType::TimestampTz,
+        ("UUID", _) => DataType::Uuid,
+        ("JSON", _) => DataType::Json,
+        ("BLOB" | "", _) => DataType::Blob,
+        (name, _) if name.contains("INT") => DataType::BigInt,
+        (name, _) if ["CHAR", "CLOB", "TEXT"].iter().any(|t| name.contains(t)) => DataType::Text,
+        (name, _) if ["REAL", "FLOA", "DOUB"].iter().any(|t| name.contains(t)) => DataType::Double,
+        // Columns with NUMERIC affinity, read like `rust_decimal::Decimal`
+        _ => DataType::Decimal {
+            precision: 28,
+            scale: 10,
+        },
+    }
+}
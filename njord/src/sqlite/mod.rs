@@ -34,15 +34,20 @@ use rusqlite::{Connection, Error};
 pub mod delete;
 pub mod error;
 pub mod insert;
+pub mod introspect;
 pub mod select;
+pub mod transaction;
 pub mod update;
-mod util;
+pub mod upsert;
 
 pub use delete::delete;
 pub use error::SqliteError;
-pub use insert::insert;
+pub use insert::{bulk_insert, insert};
+pub use introspect::introspect;
 pub use select::select;
+pub use transaction::{transaction, transaction_with, Transaction};
 pub use update::update;
+pub use upsert::upsert;
 
 /// Open a database connection.
 ///
@@ -28,21 +28,20 @@
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::{
+    builder,
     column::Column,
-    condition::Condition,
-    query::QueryBuilder,
-    sqlite::util::{
-        generate_group_by_str, generate_having_str, generate_limit_str, generate_offset_str,
-        generate_order_by_str, generate_where_condition_str,
-    },
+    dialect::SqliteDialect,
+    query::{Param, PlaceholderStyle, QueryParams},
+    relation::Related,
+    row::{FromRow, Row as ResultRow},
+    table::Table,
 };
-use rusqlite::{Connection, Result};
-use std::{collections::HashMap, sync::Arc};
+use std::marker::PhantomData;
 
-use rusqlite::types::Value;
+use ouroboros::self_referencing;
+use rusqlite::{params_from_iter, Connection, Row, Rows, Statement};
 
-use crate::table::Table;
-use crate::util::{Join, JoinType};
+use super::SqliteError;
 
 /// Constructs a new SELECT query builder.
 ///
@@ -57,359 +56,219 @@ pub fn select<'a, T: Table + Default>(columns: Vec<Column<'a>>) -> SelectQueryBu
     SelectQueryBuilder::new(columns)
 }
 
-/// A builder for constructing SELECT queries.
-#[derive(Clone)]
-pub struct SelectQueryBuilder<'a, T: Table + Default> {
-    table: Option<T>,
-    columns: Vec<Column<'a>>,
-    where_condition: Option<Condition<'a>>,
-    distinct: bool,
-    group_by: Option<Vec<String>>,
-    order_by: Option<HashMap<Vec<String>, String>>,
-    limit: Option<usize>,
-    offset: Option<usize>,
-    having_condition: Option<Condition<'a>>,
-    except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
-    union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
-    joins: Option<Vec<Join<'a>>>,
-}
-
-impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
-    /// Creates a new `SelectQueryBuilder` instance.
-    ///
-    /// # Arguments
-    ///
-    /// * `columns` - A vector of strings representing the columns to be selected.
-    pub fn new(columns: Vec<Column<'a>>) -> Self {
-        SelectQueryBuilder {
-            table: None,
-            columns,
-            where_condition: None,
-            distinct: false,
-            group_by: None,
-            order_by: None,
-            limit: None,
-            offset: None,
-            having_condition: None,
-            except_clauses: None,
-            union_clauses: None,
-            joins: None,
-        }
-    }
+/// A builder for constructing SELECT queries on SQLite.
+pub type SelectQueryBuilder<'a, T> = builder::SelectQueryBuilder<'a, T, SqliteDialect>;
 
-    /// Sets the columns to be selected.
-    ///
-    /// # Arguments
-    ///
-    /// * `columns` - A vector of strings representing the columns to be selected.
-    pub fn select(mut self, columns: Vec<Column<'a>>) -> Self {
-        self.columns = columns;
-        self
-    }
-
-    /// Sets the DISTINCT keyword for the query.
-    pub fn distinct(mut self) -> Self {
-        self.distinct = true;
-        self
-    }
-
-    /// Sets the table from which to select data.
-    ///
-    /// # Arguments
-    ///
-    /// * `table` - The table from which to select data.
-    pub fn from(mut self, table: T) -> Self {
-        self.table = Some(table);
-        self
-    }
-
-    /// Sets the WHERE clause condition.
-    ///
-    /// # Arguments
-    ///
-    /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
-        self
-    }
-
-    /// Sets the GROUP BY clause columns.
-    ///
-    /// # Arguments
-    ///
-    /// * `columns` - A vector of strings representing the columns to be grouped by.
-    pub fn group_by(mut self, columns: Vec<String>) -> Self {
-        self.group_by = Some(columns);
-        self
-    }
-
-    /// Sets the ORDER BY clause columns and order direction.
-    ///
-    /// # Arguments
-    ///
-    /// * `col_and_order` - A HashMap containing column names as keys and order direction as values.
-    pub fn order_by(mut self, col_and_order: HashMap<Vec<String>, String>) -> Self {
-        self.order_by = Some(col_and_order);
-        self
-    }
-
-    /// Sets the LIMIT clause for the query.
-    ///
-    /// # Arguments
-    ///
-    /// * `count` - The number of rows to limit the result to.
-    pub fn limit(mut self, count: usize) -> Self {
-        self.limit = Some(count);
-        self
-    }
-
-    /// Sets the OFFSET clause for the query.
+impl<'a, T: Table + FromRow + Default> SelectQueryBuilder<'a, T> {
+    /// Builds and executes the SELECT query.
     ///
     /// # Arguments
     ///
-    /// * `offset` - The number of rows to skip.
-    pub fn offset(mut self, offset: usize) -> Self {
-        self.offset = Some(offset);
-        self
-    }
-
-    /// Sets the HAVING clause condition.
+    /// * `conn` - A reference to the database connection.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
-        self
+    /// A `Result` containing a vector of selected table rows if successful,
+    /// or a `SqliteError` if the query fails or a row cannot be decoded.
+    pub fn build(self, conn: &Connection) -> Result<Vec<T>, SqliteError> {
+        self.build_as(conn)
     }
 
-    /// Adds an EXCEPT clause to the query, allowing you to exclude results from another query.
+    /// Builds and executes the SELECT query, decoding the rows one at a time.
     ///
-    /// This method modifies the current query builder to exclude the results of the specified
-    /// `other_query`. If there are already existing EXCEPT clauses, the new clause will be added
-    /// to the list. If no EXCEPT clauses exist, a new list will be created with the provided
-    /// query.
+    /// Unlike `build`, the rows are not collected; each one is read from the
+    /// database when the iterator reaches it.
     ///
     /// # Arguments
     ///
-    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
-    ///   should be excluded from the current query.
+    /// * `conn` - A reference to the database connection.
     ///
     /// # Returns
     ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new EXCEPT clause added.
-    pub fn except(mut self, other_query: SelectQueryBuilder<'a, T>) -> Self {
-        match self.except_clauses {
-            Some(ref mut clauses) => clauses.push(other_query),
-            None => self.except_clauses = Some(vec![other_query]),
+    /// A `Result` containing an iterator over the selected table rows if successful,
+    /// or a `SqliteError` if the query could not be executed.
+    pub fn build_iter(self, conn: &Connection) -> Result<SelectIter<'_, T>, SqliteError> {
+        let (final_query, params) = self.render();
+
+        let statement = conn
+            .prepare(&final_query)
+            .map_err(SqliteError::SelectError)?;
+        let columns = column_names(&statement);
+
+        let cursor = CursorTryBuilder {
+            statement,
+            rows_builder: |statement: &mut Statement<'_>| {
+                statement.query(params_from_iter(params.values()))
+            },
         }
-        self
+        .try_build()
+        .map_err(SqliteError::SelectError)?;
+
+        Ok(SelectIter {
+            cursor,
+            columns,
+            marker: PhantomData,
+        })
     }
 
-    /// Adds a UNION clause to the query, allowing you to combine results from another query.
+    /// Builds and executes the SELECT query, decoding the rows into `R` instead of `T`.
     ///
-    /// This method modifies the current query builder to include the results of the specified
-    /// `other_query`. If there are already existing UNION clauses, the new clause will be added
-    /// to the list. If no UNION clauses exist, a new list will be created with the provided
-    /// query.
+    /// `R` is decoded by column name or alias, so it can be a struct deriving `FromRow`
+    /// with only some of the columns, a tuple of single values, or a tuple of structs
+    /// for the tables of a join, e.g. `(User, Option<Address>)` for a LEFT JOIN.
     ///
     /// # Arguments
     ///
-    /// * `other_query` - A `SelectQueryBuilder` instance that represents the query whose results
-    ///   should be combined with the current query.
+    /// * `conn` - A reference to the database connection.
     ///
     /// # Returns
     ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new UNION clause added.
-    pub fn union(mut self, other_query: SelectQueryBuilder<'a, T>) -> Self {
-        match self.union_clauses {
-            Some(ref mut clauses) => clauses.push(other_query),
-            None => self.union_clauses = Some(vec![other_query]),
-        }
-        self
+    /// A `Result` containing a vector of the decoded rows if successful,
+    /// or a `SqliteError` if the query fails or a row cannot be decoded.
+    pub fn build_as<R: FromRow>(self, conn: &Connection) -> Result<Vec<R>, SqliteError> {
+        let (final_query, params) = self.render();
+
+        execute_query(&final_query, &params, conn)
     }
+}
 
-    /// Adds a JOIN clause to the query, allowing you to combine rows from two or more tables based on a related column.
-    ///
-    /// This method modifies the current query builder to include a join clause with the specified join type,
-    /// target table, and condition for the join. If there are already existing JOIN clauses, the new clause
-    /// will be added to the list. If no JOIN clauses exist, a new list will be created with the provided
-    /// join information.
+/// A builder for SELECT queries on SQLite loading related rows, created by `with`.
+pub type EagerQueryBuilder<'a, T, R> = builder::EagerQueryBuilder<'a, T, R, SqliteDialect>;
+
+impl<T, R> EagerQueryBuilder<'_, T, R>
+where
+    T: Related<R> + FromRow + Default,
+    R: Table + FromRow + Default + Clone,
+{
+    /// Builds and executes the SELECT query, then loads the related rows with one
+    /// batched query.
     ///
     /// # Arguments
     ///
-    /// * `join_type` - The type of join to perform (e.g., INNER, LEFT, RIGHT, FULL).
-    /// * `table` - The table to join with the current table.
-    /// * `on_condition` - The condition that specifies how the tables are related (the ON clause).
+    /// * `conn` - A reference to the database connection.
     ///
     /// # Returns
     ///
-    /// Returns the modified `SelectQueryBuilder` instance with the new JOIN clause added.
-    pub fn join(
-        mut self,
-        join_type: JoinType,
-        table: Arc<dyn Table>,
-        on_condition: Condition<'a>,
-    ) -> Self {
-        match self.joins {
-            Some(ref mut joins) => joins.push(Join::new(join_type, table, on_condition)),
-            None => self.joins = Some(vec![Join::new(join_type, table, on_condition)]),
-        }
-        self
-    }
-
-    /// Builds the query string, this function should be used internally.
-    pub fn build_query(&self) -> String {
-        let columns_str = self
-            .columns
-            .iter()
-            .map(|c| c.build())
-            .collect::<Vec<String>>()
-            .join(", ");
-
-        let table_name = self
-            .table
-            .as_ref()
-            .map(|t| t.get_name().to_string())
-            .unwrap_or("".to_string());
-
-        // Generate JOIN clauses, if any
-        let join_clauses: Vec<String> = match &self.joins {
-            Some(joins) => joins
-                .iter()
-                .map(|join| {
-                    let join_type_str = match join.join_type {
-                        JoinType::Inner => "INNER JOIN",
-                        JoinType::Left => "LEFT JOIN",
-                        JoinType::Right => "RIGHT JOIN",
-                        JoinType::Full => "FULL OUTER JOIN",
-                    };
-                    format!(
-                        "{} {} ON {}",
-                        join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
-                    )
-                })
-                .collect(),
-            None => Vec::new(),
-        };
+    /// A `Result` containing the selected table rows, each paired with its related rows,
+    /// or a `SqliteError` if a query fails or a row cannot be decoded.
+    pub fn build(self, conn: &Connection) -> Result<Vec<(T, Vec<R>)>, SqliteError> {
+        let (final_query, params) = self.select().render();
+        let parents: Vec<T> = execute_query(&final_query, &params, conn)?;
 
-        let distinct_str = if self.distinct { "DISTINCT " } else { "" };
-        let where_condition_str = generate_where_condition_str(self.where_condition.clone());
-        let group_by_str = generate_group_by_str(&self.group_by);
-        let order_by_str = generate_order_by_str(&self.order_by);
-        let limit_str = generate_limit_str(self.limit);
-        let offset_str = generate_offset_str(self.offset);
-        let having_str =
-            generate_having_str(self.group_by.is_some(), self.having_condition.as_ref());
+        let mut related = Vec::new();
+        for query in self.related_queries(&parents)? {
+            let (final_query, params) = query.render();
+            related.extend(execute_query(&final_query, &params, conn)?);
+        }
 
-        // Create the JOIN clause or an empty string
-        let join_clause = if !join_clauses.is_empty() {
-            join_clauses.join(" ")
-        } else {
-            String::new()
-        };
+        Ok(self.group(parents, related))
+    }
+}
 
-        let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {} {}",
-            distinct_str,
-            columns_str,
-            table_name,
-            join_clause,
-            where_condition_str,
-            group_by_str,
-            having_str,
-            order_by_str,
-            format!("{} {}", limit_str, offset_str),
-        );
+/// An iterator over the rows of a SELECT query, created by `build_iter`.
+///
+/// The iterator owns the prepared statement, so it may outlive the query builder
+/// but not the connection.
+pub struct SelectIter<'conn, T> {
+    cursor: Cursor<'conn>,
+    columns: Vec<String>,
+    marker: PhantomData<T>,
+}
 
-        // Handle EXCEPT clauses
-        if let Some(except_clauses) = &self.except_clauses {
-            for except_query in except_clauses {
-                let except_sql = except_query.build_query();
-                query = format!("{} EXCEPT {}", query, except_sql);
-            }
-        }
+impl<T: FromRow> Iterator for SelectIter<'_, T> {
+    type Item = Result<T, SqliteError>;
 
-        // Handle UNION clauses
-        if let Some(union_clauses) = &self.union_clauses {
-            for union_query in union_clauses {
-                let union_sql = union_query.build_query();
-                query = format!("{} UNION {}", query, union_sql);
-            }
-        }
+    fn next(&mut self) -> Option<Self::Item> {
+        let columns = &self.columns;
 
-        query
+        self.cursor
+            .with_rows_mut(|rows| rows.next().transpose())
+            .map(|row| {
+                row.map_err(SqliteError::SelectError)
+                    .and_then(|row| decode_row(row, columns))
+            })
     }
+}
 
-    /// Builds and executes the SELECT query.
-    ///
-    /// # Arguments
-    ///
-    /// * `conn` - A reference to the database connection.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing a vector of selected table rows if successful,
-    /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub fn build(self, conn: &Connection) -> Result<Vec<T>> {
-        let final_query = self.build_query();
-
-        raw_execute(&final_query, conn)
-    }
+/// A prepared statement together with the rows it is reading.
+#[self_referencing]
+struct Cursor<'conn> {
+    statement: Statement<'conn>,
+    #[borrows(mut statement)]
+    #[covariant]
+    rows: Rows<'this>,
 }
 
-/// Implement `QueryBuilder` for `SelectQueryBuilder`
+/// Executes a raw SQL query and returns the decoded rows.
 ///
-/// The where statement ensures the T is long lived
-impl<'a, T> QueryBuilder<'a> for SelectQueryBuilder<'a, T>
-where
-    T: Table + Default + Clone + 'a, // Added 'a bound here
-{
-    fn to_sql(&self) -> String {
-        self.build_query()
-    }
+/// # Arguments
+///
+/// * `sql` - The SQL query to execute.
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `SqliteError` if the query fails or a row cannot be decoded.
+pub fn raw_execute<R: FromRow>(sql: &str, conn: &Connection) -> Result<Vec<R>, SqliteError> {
+    execute_query(sql, &QueryParams::new(PlaceholderStyle::QuestionMark), conn)
 }
 
-/// Executes a raw SQL query and returns a vector of table rows.
+/// Executes a SQL query with its bound parameters and returns the decoded rows.
 ///
 /// # Arguments
 ///
 /// * `sql` - The SQL query to execute.
+/// * `params` - The values bound to the placeholders in `sql`.
 /// * `conn` - A reference to the database connection.
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of table rows if successful,
-/// or a `rusqlite::Error` if an error occurs during the execution.
-pub fn raw_execute<T: Table + Default>(sql: &str, conn: &Connection) -> Result<Vec<T>> {
-    let mut binding = conn.prepare(sql)?;
-    let iter = binding.query_map((), |row| {
-        let mut instance = T::default();
-        let columns = instance.get_column_fields();
-
-        for (index, column) in columns.iter().enumerate() {
-            let value = row.get::<usize, Value>(index)?;
-
-            let string_value = match value {
-                Value::Integer(val) => val.to_string(),
-                Value::Null => String::new(),
-                Value::Real(val) => val.to_string(),
-                Value::Text(val) => val.to_string(),
-                Value::Blob(val) => String::from_utf8_lossy(&val).to_string(),
-            };
-
-            instance.set_column_value(column, &string_value);
-        }
+/// A `Result` containing a vector of the decoded rows if successful,
+/// or a `SqliteError` if the query fails or a row cannot be decoded.
+fn execute_query<R: FromRow>(
+    sql: &str,
+    params: &QueryParams,
+    conn: &Connection,
+) -> Result<Vec<R>, SqliteError> {
+    let mut statement = conn.prepare(sql).map_err(SqliteError::SelectError)?;
+    let columns = column_names(&statement);
+    let mut rows = statement
+        .query(params_from_iter(params.values()))
+        .map_err(SqliteError::SelectError)?;
+
+    let mut results = Vec::new();
+    while let Some(row) = rows.next().map_err(SqliteError::SelectError)? {
+        results.push(decode_row(row, &columns)?);
+    }
 
-        Ok(instance)
-    })?;
+    Ok(results)
+}
 
-    let result: Result<Vec<T>> = iter
-        .map(|row_result| row_result.and_then(|row| Ok(row)))
-        .collect::<Result<Vec<T>>>();
+/// Returns the names of the columns a prepared statement selects.
+///
+/// # Arguments
+///
+/// * `statement` - The prepared statement.
+fn column_names(statement: &Statement<'_>) -> Vec<String> {
+    statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
 
-    result.map_err(|err| err.into())
+/// Decodes a result row, reading each column with its SQLite storage class.
+///
+/// # Arguments
+///
+/// * `row` - The row read from the database.
+/// * `columns` - The names of the columns of the row.
+fn decode_row<R: FromRow>(row: &Row<'_>, columns: &[String]) -> Result<R, SqliteError> {
+    let values = (0..columns.len())
+        .map(|index| row.get::<usize, Param>(index))
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(SqliteError::SelectError)?;
+
+    Ok(R::from_row(&ResultRow::new(columns, &values))?)
 }
@@ -29,12 +29,17 @@
 
 use std::collections::HashMap;
 
+use crate::{query::Param, row::DecodeError, schema::ColumnDefinition};
+
 #[allow(unused_imports)]
 use njord_derive::Table;
 
 /// The Table trait.
 ///
 /// It is used for structs that want need the behaviour of an SQL Table.
+///
+/// Rows are read into a table with `FromRow`, which `#[derive(Table)]` implements
+/// as well, so each field is decoded from the native value of its column.
 pub trait Table {
     /// Get the name of the table.
     ///
@@ -44,35 +49,67 @@ pub trait Table {
     /// Get the columns of the table.
     ///
     /// Returns a reference to a `HashMap` where the keys are column names,
-    /// and the values are column types represented as strings.
+    /// and the values are column types represented as strings. Read-only,
+    /// generated and defaulted columns are included, skipped fields are not.
     fn get_columns(&self) -> HashMap<String, String>;
 
     /// Get the names of the columns.
     ///
-    /// Returns a `Vec<String>` containing the names of the columns in the same order
-    /// as they appear in the table.
+    /// Returns a `Vec<String>` containing the names of the columns written by
+    /// INSERT and UPDATE statements, in the same order as they appear in the table.
     fn get_column_fields(&self) -> Vec<String>;
 
     /// Get the values of the columns.
     ///
-    /// Returns a `Vec<String>` containing the values of the columns in the same order
-    /// as they appear in the table.
-    fn get_column_values(&self) -> Vec<String>;
+    /// Returns a `Vec<Param>` containing the values of the columns in `get_column_fields`,
+    /// each with the type it is bound to a statement with. An `AutoIncrementPrimaryKey`
+    /// without a value is `Param::Null`.
+    fn get_column_values(&self) -> Vec<Param>;
 
-    /// Set the values of the columns.
-    fn set_column_value(&mut self, column: &str, value: &str);
+    /// Get the definitions of the columns.
+    ///
+    /// Returns the type, nullability, key and default of every column in
+    /// `get_columns`, in the order they appear in the table. It is what
+    /// `CreateTableBuilder::from_table` creates the table from.
+    fn column_definitions(&self) -> Vec<ColumnDefinition> {
+        Vec::new()
+    }
+
+    /// Get the columns of the primary key.
+    ///
+    /// Returns the names of the columns marked with `#[primary_key]` or holding a
+    /// `PrimaryKey` or `AutoIncrementPrimaryKey`, in the order they appear in the table.
+    /// A table without a primary key returns an empty `Vec`.
+    fn primary_key(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get the values of the primary key.
+    ///
+    /// Returns a `Vec<Param>` containing the values of the columns in `primary_key`.
+    fn primary_key_values(&self) -> Vec<Param> {
+        Vec::new()
+    }
+
+    /// Get the column holding an `AutoIncrementPrimaryKey`.
+    ///
+    /// Returns the name of the column, or `None` if the table has no such key.
+    fn get_auto_increment_primary_key(&self) -> Option<&str> {
+        None
+    }
 
-    /// Determines if the provided value represents an auto-incrementing primary key.
+    /// Sets the `AutoIncrementPrimaryKey` to a value generated by the database.
     ///
     /// # Arguments
     ///
-    /// * `value` - A string representing the value to check.
+    /// * `value` - The generated value, as read from the database.
     ///
     /// # Returns
     ///
-    /// * `true` if the value indicates an auto-incrementing primary key.
-    /// * `false` otherwise.
-    fn is_auto_increment_primary_key(&self, value: &str) -> bool;
+    /// A `Result` that is an error if the value cannot be decoded into the key.
+    fn set_auto_increment_primary_key(&mut self, _value: Param) -> Result<(), DecodeError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -145,7 +182,7 @@ mod tests {
             ("product_id".to_string(), "INTEGER".to_string()),
             ("product_name".to_string(), "TEXT".to_string()),
             ("price".to_string(), "REAL".to_string()),
-            ("in_stock".to_string(), "TEXT".to_string()),
+            ("in_stock".to_string(), "BOOLEAN".to_string()),
         ]
         .into_iter()
         .collect();
@@ -193,4 +230,141 @@ mod tests {
         assert_eq!(table_b.get_name(), "table_b");
         assert_eq!(table_c.get_name(), "table_c");
     }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn column_types_and_values() {
+        use crate::keys::{AutoIncrementPrimaryKey, PrimaryKey};
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+        #[derive(Table)]
+        #[table_name = "events"]
+        struct Event {
+            id: AutoIncrementPrimaryKey<i64>,
+            code: PrimaryKey<u64>,
+            day: NaiveDate,
+            starts_at: Option<NaiveDateTime>,
+            created_at: DateTime<Utc>,
+            payload: Vec<u8>,
+        }
+
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let created_at = day.and_hms_opt(8, 30, 0).unwrap().and_utc();
+        let event = Event {
+            id: AutoIncrementPrimaryKey::default(),
+            code: PrimaryKey::new(u64::MAX),
+            day,
+            starts_at: None,
+            created_at,
+            payload: vec![0, 255],
+        };
+
+        let columns = event.get_columns();
+        assert_eq!(columns["id"], "INTEGER");
+        assert_eq!(columns["code"], "INTEGER");
+        assert_eq!(columns["day"], "DATE");
+        assert_eq!(columns["starts_at"], "TIMESTAMP NULL");
+        assert_eq!(columns["created_at"], "TIMESTAMPTZ");
+        assert_eq!(columns["payload"], "BLOB");
+
+        assert_eq!(
+            event.get_column_values(),
+            vec![
+                Param::Null,
+                Param::Text(u64::MAX.to_string()),
+                Param::Date(day),
+                Param::Null,
+                Param::TimestampTz(created_at),
+                Param::Bytes(vec![0, 255]),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_auto_increment_primary_key() {
+        use crate::keys::AutoIncrementPrimaryKey;
+
+        #[derive(Table)]
+        #[table_name = "users"]
+        struct User {
+            id: AutoIncrementPrimaryKey<usize>,
+            name: String,
+        }
+
+        #[derive(Table)]
+        #[table_name = "tags"]
+        struct Tag {
+            name: String,
+        }
+
+        let mut user = User::default();
+        assert_eq!(user.get_auto_increment_primary_key(), Some("id"));
+
+        user.set_auto_increment_primary_key(Param::Int(42)).unwrap();
+        assert_eq!(user.id.get(), Some(&42));
+
+        assert_eq!(
+            user.set_auto_increment_primary_key(Param::Null),
+            Err(DecodeError::UnexpectedNull("id".to_string()))
+        );
+        assert!(matches!(
+            user.set_auto_increment_primary_key(Param::Int(-1)),
+            Err(DecodeError::InvalidValue { column, .. }) if column == "id"
+        ));
+        assert_eq!(user.id.get(), Some(&42));
+
+        let tag = Tag {
+            name: "rust".to_string(),
+        };
+        assert_eq!(tag.get_auto_increment_primary_key(), None);
+    }
+
+    #[test]
+    fn column_attributes() {
+        #[derive(Table)]
+        #[table_name = "accounts"]
+        struct Account {
+            #[column(name = "user_name")]
+            name: String,
+            #[default]
+            plan: String,
+            #[column(generated)]
+            name_length: i64,
+            #[column(read_only)]
+            created_at: String,
+            #[skip]
+            session: Option<String>,
+        }
+
+        let account = Account {
+            name: "mjovanc".to_string(),
+            plan: "pro".to_string(),
+            name_length: 7,
+            created_at: "2024-01-01".to_string(),
+            session: Some("token".to_string()),
+        };
+
+        let mut columns: Vec<String> = account.get_columns().into_keys().collect();
+        columns.sort();
+        assert_eq!(
+            columns,
+            vec!["created_at", "name_length", "plan", "user_name"]
+        );
+        assert_eq!(account.get_column_fields(), vec!["user_name".to_string()]);
+        assert_eq!(
+            account.get_column_values(),
+            vec![Param::Text("mjovanc".to_string())]
+        );
+        assert_eq!(Account::NAME.name(), "user_name");
+        assert_eq!(Account::CREATED_AT.name(), "created_at");
+        assert_eq!(
+            (
+                account.plan.as_str(),
+                account.name_length,
+                account.created_at.as_str()
+            ),
+            ("pro", 7, "2024-01-01")
+        );
+        assert_eq!(account.session.as_deref(), Some("token"));
+    }
 }
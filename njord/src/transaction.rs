@@ -0,0 +1,236 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Types shared by the transaction APIs of the database backends.
+//!
+//! Every backend module has a `transaction` function running a closure inside a
+//! transaction. It is committed when the closure returns `Ok` and rolled back when
+//! it returns `Err`. Transactions started from inside the closure with
+//! `Transaction::transaction` are nested with savepoints.
+
+use std::{future::Future, pin::Pin};
+
+use crate::dialect::Dialect;
+
+/// A boxed future, as returned by the closures of the asynchronous transaction APIs.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// The isolation level a transaction runs with.
+///
+/// Databases that don't support a level run the transaction with the next stricter
+/// one they have, as the SQL standard allows. SQLite and DuckDB always run
+/// serializable, for example.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// Changes of other transactions are visible before they are committed.
+    ReadUncommitted,
+    /// Only committed changes of other transactions are visible.
+    ReadCommitted,
+    /// Rows that have been read don't change for the rest of the transaction.
+    RepeatableRead,
+    /// The transaction behaves as if no other transaction ran at the same time.
+    Serializable,
+    /// The transaction reads from a snapshot taken when it started.
+    Snapshot,
+}
+
+impl IsolationLevel {
+    /// Returns the name of the level in SQL, e.g. `READ COMMITTED`.
+    ///
+    /// `Snapshot` has no standard name and is returned as `SNAPSHOT`.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+            IsolationLevel::Snapshot => "SNAPSHOT",
+        }
+    }
+}
+
+/// The statements starting and ending one level of a possibly nested transaction.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TransactionStatements {
+    pub(crate) begin: Vec<String>,
+    pub(crate) commit: Vec<String>,
+    pub(crate) rollback: Vec<String>,
+}
+
+impl TransactionStatements {
+    /// Renders the statements of a transaction at the given nesting depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the statements with.
+    /// * `depth` - `0` for a transaction, or the nesting depth of a savepoint.
+    /// * `isolation_level` - The isolation level of an outermost transaction.
+    ///
+    /// # Returns
+    ///
+    /// The statements, or `None` if the dialect has no savepoints to nest the
+    /// transaction with.
+    pub(crate) fn new(
+        dialect: &dyn Dialect,
+        depth: usize,
+        isolation_level: Option<IsolationLevel>,
+    ) -> Option<Self> {
+        if depth == 0 {
+            let reset: Vec<String> = isolation_level
+                .and_then(|level| dialect.reset_isolation_level(level))
+                .into_iter()
+                .collect();
+
+            return Some(TransactionStatements {
+                begin: dialect.begin_transaction(isolation_level),
+                commit: [vec![dialect.commit_transaction()], reset.clone()].concat(),
+                rollback: [vec![dialect.rollback_transaction()], reset].concat(),
+            });
+        }
+
+        Self::savepoint(dialect, &format!("njord_savepoint_{}", depth))
+    }
+
+    /// Renders the statements of a write that is undone as a whole when it fails,
+    /// e.g. an `expect_rows` check or an insert of several statements.
+    ///
+    /// Outside of transactions the write runs in a transaction of its own, inside of
+    /// one in a savepoint, so undoing it keeps the changes of the transaction and
+    /// ending it doesn't commit them.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the statements with.
+    /// * `in_transaction` - Whether the write runs inside a transaction.
+    ///
+    /// # Returns
+    ///
+    /// The statements, or `None` if the write runs inside a transaction and the
+    /// dialect has no savepoints.
+    pub(crate) fn guard(dialect: &dyn Dialect, in_transaction: bool) -> Option<Self> {
+        if in_transaction {
+            Self::savepoint(dialect, "njord_guard")
+        } else {
+            Self::new(dialect, 0, None)
+        }
+    }
+
+    fn savepoint(dialect: &dyn Dialect, name: &str) -> Option<Self> {
+        let release: Vec<String> = dialect.release_savepoint(name).into_iter().collect();
+
+        Some(TransactionStatements {
+            begin: vec![dialect.savepoint(name)?],
+            commit: release.clone(),
+            rollback: [vec![dialect.rollback_to_savepoint(name)], release].concat(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{DuckDbDialect, MssqlDialect, PostgresDialect};
+
+    #[test]
+    fn outermost_transaction() {
+        let statements =
+            TransactionStatements::new(&PostgresDialect, 0, Some(IsolationLevel::Serializable))
+                .unwrap();
+
+        assert_eq!(
+            statements.begin,
+            vec!["START TRANSACTION ISOLATION LEVEL SERIALIZABLE"]
+        );
+        assert_eq!(statements.commit, vec!["COMMIT"]);
+        assert_eq!(statements.rollback, vec!["ROLLBACK"]);
+    }
+
+    #[test]
+    fn nested_transaction() {
+        let statements = TransactionStatements::new(&PostgresDialect, 2, None).unwrap();
+
+        assert_eq!(statements.begin, vec!["SAVEPOINT njord_savepoint_2"]);
+        assert_eq!(
+            statements.commit,
+            vec!["RELEASE SAVEPOINT njord_savepoint_2"]
+        );
+        assert_eq!(
+            statements.rollback,
+            vec![
+                "ROLLBACK TO SAVEPOINT njord_savepoint_2",
+                "RELEASE SAVEPOINT njord_savepoint_2"
+            ]
+        );
+    }
+
+    #[test]
+    fn resets_the_isolation_level() {
+        let statements =
+            TransactionStatements::new(&MssqlDialect, 0, Some(IsolationLevel::Snapshot)).unwrap();
+
+        assert_eq!(
+            statements.commit,
+            vec![
+                "COMMIT TRANSACTION",
+                "SET TRANSACTION ISOLATION LEVEL READ COMMITTED"
+            ]
+        );
+        assert_eq!(
+            TransactionStatements::new(&MssqlDialect, 0, None)
+                .unwrap()
+                .commit,
+            vec!["COMMIT TRANSACTION"]
+        );
+    }
+
+    #[test]
+    fn nesting_without_savepoints() {
+        assert!(TransactionStatements::new(&DuckDbDialect, 1, None).is_none());
+    }
+
+    #[test]
+    fn guard() {
+        let statements = TransactionStatements::guard(&PostgresDialect, false).unwrap();
+
+        assert_eq!(statements.begin, vec!["START TRANSACTION"]);
+        assert_eq!(statements.rollback, vec!["ROLLBACK"]);
+
+        let statements = TransactionStatements::guard(&PostgresDialect, true).unwrap();
+
+        assert_eq!(statements.begin, vec!["SAVEPOINT njord_guard"]);
+        assert_eq!(
+            statements.rollback,
+            vec![
+                "ROLLBACK TO SAVEPOINT njord_guard",
+                "RELEASE SAVEPOINT njord_guard"
+            ]
+        );
+        assert!(TransactionStatements::guard(&DuckDbDialect, true).is_none());
+    }
+}
@@ -0,0 +1,395 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! The Rust types that can be stored in a table column.
+//!
+//! `#[derive(Table)]` accepts any field whose type implements [`SqlType`], which
+//! ties together how a value is bound to a statement ([`ToParam`]), how it is read
+//! back from a row ([`FromValue`]) and which SQL type its column has. Domain types
+//! become columns by implementing the three traits, usually by delegating to the
+//! type they wrap:
+//!
+//! ```rust
+//! use njord::dialect::{DataType, Dialect, MssqlDialect};
+//! use njord::query::{Param, ToParam};
+//! use njord::row::FromValue;
+//! use njord::types::SqlType;
+//!
+//! #[derive(Debug, Default, PartialEq)]
+//! struct Email(String);
+//!
+//! impl ToParam for Email {
+//!     fn to_param(&self) -> Param {
+//!         self.0.to_param()
+//!     }
+//! }
+//!
+//! impl FromValue for Email {
+//!     fn from_value(value: &Param) -> Result<Self, String> {
+//!         String::from_value(value).map(Email)
+//!     }
+//! }
+//!
+//! impl SqlType for Email {
+//!     fn data_type() -> DataType {
+//!         DataType::Varchar(320)
+//!     }
+//! }
+//!
+//! assert_eq!(Email::type_name(&MssqlDialect), "NVARCHAR(320)");
+//! ```
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::{
+    dialect::{DataType, Dialect},
+    keys::{AutoIncrementPrimaryKey, PrimaryKey},
+    query::ToParam,
+    row::FromValue,
+};
+
+/// A Rust type that can be stored in a table column.
+///
+/// Encoding and decoding come from the `ToParam` and `FromValue` supertraits,
+/// the column type from [`SqlType::data_type`].
+pub trait SqlType: ToParam + FromValue {
+    /// Returns the backend independent type of the column.
+    fn data_type() -> DataType;
+
+    /// Returns `true` if the column accepts `NULL`.
+    fn nullable() -> bool {
+        false
+    }
+
+    /// Returns the name of the column type on a backend.
+    ///
+    /// Override it for types that need a database specific type on some backend,
+    /// e.g. `CITEXT` on PostgreSQL.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect of the backend.
+    fn type_name(dialect: &dyn Dialect) -> String {
+        dialect.type_name(Self::data_type())
+    }
+}
+
+/// Returns the type of a column holding `T` as reported by `Table::get_columns`.
+///
+/// The type has its standard SQL name, followed by `NULL` if the column accepts `NULL`.
+pub fn declared_type<T: SqlType>() -> String {
+    if T::nullable() {
+        format!("{} NULL", T::data_type())
+    } else {
+        T::data_type().to_string()
+    }
+}
+
+/// Implements `SqlType` for the listed types and generates [`data_type_of`], so that
+/// schema tools reading the source of a table struct map its fields the same way.
+macro_rules! sql_types {
+    ($($(#[$attr:meta])* $ty:ty as $path:literal => $data_type:expr;)+) => {
+        $(
+            $(#[$attr])*
+            impl SqlType for $ty {
+                fn data_type() -> DataType {
+                    $data_type
+                }
+            }
+        )+
+
+        /// Returns the column type of a type njord implements `SqlType` for.
+        ///
+        /// Tools that read the source of `#[derive(Table)]` structs instead of compiling
+        /// them use it to find the column type of a field. Types behind a disabled
+        /// feature are included.
+        ///
+        /// # Arguments
+        ///
+        /// * `path` - The path of the type with its crate and type arguments, e.g.
+        ///   `chrono::NaiveDate` or `Vec<u8>`. Types of the prelude have no crate.
+        ///
+        /// # Returns
+        ///
+        /// The column type, or `None` if njord has no `SqlType` implementation for the type.
+        pub fn data_type_of(path: &str) -> Option<DataType> {
+            match path {
+                $($path => Some($data_type),)+
+                _ => None,
+            }
+        }
+    };
+}
+
+sql_types! {
+    bool as "bool" => DataType::Boolean;
+    i8 as "i8" => DataType::SmallInt;
+    i16 as "i16" => DataType::SmallInt;
+    u8 as "u8" => DataType::SmallInt;
+    i32 as "i32" => DataType::Integer;
+    u16 as "u16" => DataType::Integer;
+    i64 as "i64" => DataType::BigInt;
+    i128 as "i128" => DataType::BigInt;
+    isize as "isize" => DataType::BigInt;
+    u32 as "u32" => DataType::BigInt;
+    u64 as "u64" => DataType::BigInt;
+    u128 as "u128" => DataType::BigInt;
+    usize as "usize" => DataType::BigInt;
+    f32 as "f32" => DataType::Float;
+    f64 as "f64" => DataType::Double;
+    String as "String" => DataType::Text;
+    Vec<u8> as "Vec<u8>" => DataType::Blob;
+
+    #[cfg(feature = "chrono")]
+    NaiveDate as "chrono::NaiveDate" => DataType::Date;
+    #[cfg(feature = "chrono")]
+    NaiveTime as "chrono::NaiveTime" => DataType::Time;
+    #[cfg(feature = "chrono")]
+    NaiveDateTime as "chrono::NaiveDateTime" => DataType::Timestamp;
+    #[cfg(feature = "chrono")]
+    DateTime<Utc> as "chrono::DateTime<chrono::Utc>" => DataType::TimestampTz;
+
+    #[cfg(feature = "uuid")]
+    uuid::Uuid as "uuid::Uuid" => DataType::Uuid;
+
+    // Decimals get the 28 significant digits `rust_decimal` can hold, 10 of them
+    // after the decimal point.
+    #[cfg(feature = "decimal")]
+    rust_decimal::Decimal as "rust_decimal::Decimal" =>
+        DataType::Decimal { precision: 28, scale: 10 };
+
+    #[cfg(feature = "json")]
+    serde_json::Value as "serde_json::Value" => DataType::Json;
+}
+
+impl<T: SqlType> SqlType for Option<T> {
+    fn data_type() -> DataType {
+        T::data_type()
+    }
+
+    /// `None` is stored as `NULL`.
+    fn nullable() -> bool {
+        true
+    }
+
+    fn type_name(dialect: &dyn Dialect) -> String {
+        T::type_name(dialect)
+    }
+}
+
+impl<T: SqlType> SqlType for PrimaryKey<T> {
+    fn data_type() -> DataType {
+        T::data_type()
+    }
+
+    fn type_name(dialect: &dyn Dialect) -> String {
+        T::type_name(dialect)
+    }
+}
+
+impl<T: SqlType> SqlType for AutoIncrementPrimaryKey<T> {
+    fn data_type() -> DataType {
+        T::data_type()
+    }
+
+    fn type_name(dialect: &dyn Dialect) -> String {
+        T::type_name(dialect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MssqlDialect, OracleDialect, PostgresDialect, SqliteDialect};
+    use crate::query::{Param, PlaceholderStyle, QueryParams};
+    use crate::row::{FromRow, Row};
+    use crate::table::Table;
+    use njord_derive::{DbEnum, Table};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Email(String);
+
+    impl ToParam for Email {
+        fn to_param(&self) -> Param {
+            self.0.to_param()
+        }
+    }
+
+    impl FromValue for Email {
+        fn from_value(value: &Param) -> Result<Self, String> {
+            String::from_value(value).map(Email)
+        }
+    }
+
+    impl SqlType for Email {
+        fn data_type() -> DataType {
+            DataType::Varchar(320)
+        }
+
+        fn type_name(dialect: &dyn Dialect) -> String {
+            match dialect.name() {
+                "postgres" => "CITEXT".to_string(),
+                _ => dialect.type_name(Self::data_type()),
+            }
+        }
+    }
+
+    type Name = std::string::String;
+
+    #[derive(Table)]
+    #[table_name = "users"]
+    struct User {
+        id: AutoIncrementPrimaryKey<u64>,
+        name: Name,
+        email: Email,
+        backup_email: Option<Email>,
+    }
+
+    #[test]
+    fn type_names() {
+        assert_eq!(bool::type_name(&SqliteDialect), "INTEGER");
+        assert_eq!(bool::type_name(&MssqlDialect), "BIT");
+        assert_eq!(<Option<i32>>::type_name(&OracleDialect), "NUMBER(10)");
+        assert_eq!(Email::type_name(&OracleDialect), "VARCHAR2(320)");
+        assert_eq!(<Option<Email>>::type_name(&PostgresDialect), "CITEXT");
+    }
+
+    #[test]
+    fn declared_types() {
+        assert_eq!(declared_type::<String>(), "TEXT");
+        assert_eq!(declared_type::<Option<f64>>(), "REAL NULL");
+        assert_eq!(declared_type::<AutoIncrementPrimaryKey<usize>>(), "INTEGER");
+    }
+
+    #[test]
+    fn data_types_by_path() {
+        assert_eq!(data_type_of("u8"), Some(u8::data_type()));
+        assert_eq!(data_type_of("Vec<u8>"), Some(DataType::Blob));
+        #[cfg(feature = "chrono")]
+        assert_eq!(
+            data_type_of("chrono::DateTime<chrono::Utc>"),
+            Some(<DateTime<Utc>>::data_type())
+        );
+        assert_eq!(data_type_of("serde_json::Value"), Some(DataType::Json));
+        assert_eq!(data_type_of("Value"), None);
+        assert_eq!(data_type_of("Vec<String>"), None);
+    }
+
+    #[test]
+    fn newtype_fields() {
+        let user = User {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "Anna".to_string(),
+            email: Email("anna@example.com".to_string()),
+            backup_email: None,
+        };
+
+        let columns = user.get_columns();
+        assert_eq!(columns["name"], "TEXT");
+        assert_eq!(columns["email"], "VARCHAR(320)");
+        assert_eq!(columns["backup_email"], "VARCHAR(320) NULL");
+
+        assert_eq!(
+            user.get_column_values(),
+            vec![
+                Param::Null,
+                Param::Text("Anna".to_string()),
+                Param::Text("anna@example.com".to_string()),
+                Param::Null,
+            ]
+        );
+
+        let names = vec![
+            "id".to_string(),
+            "name".to_string(),
+            "email".to_string(),
+            "backup_email".to_string(),
+        ];
+        let values = vec![
+            Param::Int(1),
+            Param::Text("Anna".to_string()),
+            Param::Text("anna@example.com".to_string()),
+            Param::Text("anna@icloud.com".to_string()),
+        ];
+        let user = User::from_row(&Row::new(&names, &values)).unwrap();
+        assert_eq!(user.email, Email("anna@example.com".to_string()));
+        assert_eq!(
+            user.backup_email,
+            Some(Email("anna@icloud.com".to_string()))
+        );
+        assert_eq!(
+            User::EMAIL
+                .eq(Email("anna@example.com".to_string()))
+                .build(&mut QueryParams::new(PlaceholderStyle::QuestionMark)),
+            "email = ?"
+        );
+    }
+
+    #[derive(DbEnum, Debug, Default, PartialEq)]
+    enum Status {
+        #[default]
+        Open,
+        #[db(rename = "on_hold")]
+        OnHold,
+    }
+
+    #[derive(DbEnum, Debug, Default, PartialEq)]
+    #[db(integer)]
+    enum Priority {
+        #[default]
+        Low = 1,
+        High = 10,
+    }
+
+    #[test]
+    fn db_enums() {
+        assert_eq!(
+            Status::OnHold.to_param(),
+            Param::Text("on_hold".to_string())
+        );
+        assert_eq!(
+            Status::from_value(&Param::Text("Open".to_string())),
+            Ok(Status::Open)
+        );
+        assert_eq!(
+            Status::from_value(&Param::Text("closed".to_string())),
+            Err("unknown Status value 'closed'".to_string())
+        );
+        assert_eq!(declared_type::<Option<Status>>(), "TEXT NULL");
+
+        assert_eq!(Priority::High.to_param(), Param::Int(10));
+        assert_eq!(Priority::from_value(&Param::Int(1)), Ok(Priority::Low));
+        assert_eq!(
+            Priority::from_value(&Param::Int(5)),
+            Err("unknown Priority value 5".to_string())
+        );
+        assert_eq!(Priority::type_name(&OracleDialect), "NUMBER(10)");
+    }
+}
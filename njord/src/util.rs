@@ -74,6 +74,7 @@ impl<'a> Join<'a> {
     /// ```rust
     /// use std::sync::Arc;
     /// use njord::condition::{Condition, Value};
+    /// use njord::table::Table;
     /// use njord::util::{Join, JoinType};
     /// use njord_derive::Table;
     ///
@@ -106,7 +107,7 @@ impl<'a> Join<'a> {
     ///
     ///     let join_condition = Condition::Eq(
     ///         "categories.id".to_string(),
-    ///         Value::Literal("products.category_id".to_string()),
+    ///         Value::Column("products.category_id".to_string()),
     ///     );
     ///
     ///     let join = Join::new(
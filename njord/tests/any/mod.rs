@@ -0,0 +1,295 @@
+use njord::any::{self, AnyConnection, AnyError};
+use njord::column::Column;
+use njord::dialect::AnyDialect;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "users"]
+pub struct User {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub username: String,
+    pub email: String,
+}
+
+fn user(username: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+    }
+}
+
+async fn connect_with_users() -> AnyConnection {
+    let mut conn = njord::connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory database");
+
+    any::raw_execute(
+        &mut conn,
+        "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT NOT NULL, email TEXT NOT NULL);",
+    )
+    .await
+    .expect("Failed to create table");
+
+    any::insert(&mut conn, vec![user("mjovanc"), user("njord")])
+        .await
+        .expect("Failed to INSERT");
+
+    conn
+}
+
+fn columns<'a>() -> Vec<Column<'a>> {
+    vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+    ]
+}
+
+#[tokio::test]
+async fn connect_sqlite_in_memory() {
+    let conn = njord::connect("sqlite://:memory:").await;
+
+    match conn {
+        Ok(conn) => assert_eq!(conn.dialect(), AnyDialect::Sqlite),
+        Err(e) => panic!("Failed to connect: {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn connect_unsupported_backend() {
+    let result = njord::connect("postgresql://localhost/njord").await;
+
+    assert!(matches!(result, Err(AnyError::UnsupportedBackend(_))));
+}
+
+#[tokio::test]
+async fn connect_invalid_url() {
+    let result = njord::connect("localhost").await;
+
+    assert!(matches!(result, Err(AnyError::InvalidUrl(_))));
+}
+
+#[tokio::test]
+async fn select() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::select(columns())
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("njord"))
+        .build(&mut conn)
+        .await;
+
+    match result {
+        Ok(users) => {
+            assert_eq!(users.len(), 1);
+            assert_eq!(users[0].username, "njord");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[tokio::test]
+async fn select_as() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::select(vec![Column::Text("COUNT(*) AS total".to_string())])
+        .from(User::default())
+        .build_as::<i64>(&mut conn)
+        .await;
+    assert_eq!(result.unwrap(), vec![2]);
+
+    let result = any::select(vec![Column::Text("email".to_string())])
+        .from(User::default())
+        .build_as::<i64>(&mut conn)
+        .await;
+    assert!(matches!(result, Err(AnyError::Decode(_))));
+}
+
+#[tokio::test]
+async fn insert_returns_generated_keys() {
+    let mut conn = connect_with_users().await;
+
+    let inserted = any::insert(&mut conn, vec![user("alice"), user("bob")])
+        .await
+        .expect("Failed to INSERT");
+
+    let ids: Vec<Option<usize>> = inserted.iter().map(|row| row.id.get().copied()).collect();
+    assert_eq!(ids, vec![Some(3), Some(4)]);
+}
+
+#[tokio::test]
+async fn bulk_insert() {
+    let mut conn = connect_with_users().await;
+
+    let usernames: Vec<String> = (0..10).map(|index| format!("user{}", index)).collect();
+    let progress = any::bulk_insert(usernames.iter().map(|username| user(username)))
+        .chunk_size(4)
+        .build(&mut conn)
+        .await
+        .expect("Failed to INSERT");
+
+    assert_eq!(progress.rows_inserted, 10);
+    assert_eq!(progress.chunks, 3);
+    assert_eq!(count_users(&mut conn).await, 12);
+}
+
+#[tokio::test]
+async fn update() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::update(user("renamed"))
+        .set(["username"])
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .expect_rows(1)
+        .build(&mut conn)
+        .await;
+    assert_eq!(result.unwrap(), 1);
+
+    let users = any::select(columns())
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("renamed"))
+        .build(&mut conn)
+        .await
+        .expect("Failed to SELECT");
+
+    assert_eq!(users.len(), 1);
+}
+
+#[tokio::test]
+async fn delete() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::delete()
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .build(&mut conn)
+        .await;
+    assert!(result.is_ok());
+
+    let users = any::select(columns())
+        .from(User::default())
+        .build(&mut conn)
+        .await
+        .expect("Failed to SELECT");
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].username, "njord");
+}
+
+#[tokio::test]
+async fn delete_expect_rows_mismatch() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::delete()
+        .from(User::default())
+        .expect_rows(1)
+        .build(&mut conn)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(AnyError::UnexpectedRowCount {
+            expected: 1,
+            actual: 2
+        })
+    ));
+
+    let users = any::select(columns())
+        .from(User::default())
+        .build(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 2);
+}
+
+#[tokio::test]
+async fn upsert() {
+    let mut conn = connect_with_users().await;
+
+    let rows = vec![
+        User {
+            id: AutoIncrementPrimaryKey::new(Some(1)),
+            ..user("mjovanc_renamed")
+        },
+        User {
+            id: AutoIncrementPrimaryKey::new(Some(3)),
+            ..user("odin")
+        },
+    ];
+    let rows = any::upsert(rows)
+        .build(&mut conn)
+        .await
+        .expect("Failed to UPSERT");
+
+    let usernames: Vec<String> = any::select(columns())
+        .from(User::default())
+        .build(&mut conn)
+        .await
+        .expect("Failed to SELECT")
+        .into_iter()
+        .map(|row| row.username)
+        .collect();
+
+    assert_eq!(rows, 2);
+    assert_eq!(usernames, vec!["mjovanc_renamed", "njord", "odin"]);
+}
+
+async fn count_users(conn: &mut AnyConnection) -> usize {
+    any::select(columns())
+        .from(User::default())
+        .build(conn)
+        .await
+        .expect("Failed to SELECT")
+        .len()
+}
+
+#[tokio::test]
+async fn transaction_commit() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::transaction(&mut conn, |tx| {
+        Box::pin(async move {
+            any::insert(tx, vec![user("alice")]).await?;
+            any::delete()
+                .from(User::default())
+                .where_clause(User::USERNAME.eq("mjovanc"))
+                .build(tx)
+                .await?;
+            Ok::<_, AnyError>(())
+        })
+    })
+    .await;
+
+    assert!(result.is_ok());
+    assert_eq!(count_users(&mut conn).await, 2);
+}
+
+#[tokio::test]
+async fn transaction_rollback() {
+    let mut conn = connect_with_users().await;
+
+    let result = any::transaction(&mut conn, |tx| {
+        Box::pin(async move {
+            any::insert(tx, vec![user("alice")]).await?;
+
+            let nested = tx
+                .transaction(|nested| {
+                    Box::pin(async move {
+                        any::insert(nested, vec![user("bob")]).await?;
+                        Ok::<_, AnyError>(count_users(nested).await)
+                    })
+                })
+                .await?;
+            assert_eq!(nested, 4);
+
+            any::raw_execute(tx, "INSERT INTO missing_table VALUES (1)").await
+        })
+    })
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(count_users(&mut conn).await, 2);
+}
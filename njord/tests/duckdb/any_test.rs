@@ -0,0 +1,27 @@
+use njord::any::{self, AnyConnection};
+use njord::column::Column;
+
+use crate::User;
+
+#[tokio::test]
+async fn connect_duckdb_in_memory() {
+    let mut conn = njord::connect("duckdb::memory:").await.unwrap();
+    assert!(matches!(conn, AnyConnection::DuckDb(_)));
+
+    any::raw_execute(&mut conn, include_str!("../../db/test/duckdb.sql"))
+        .await
+        .unwrap();
+    any::insert(&mut conn, vec![crate::user("mjovanc")])
+        .await
+        .unwrap();
+
+    let rows = any::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("mjovanc"))
+        .build(&mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].email, "mjovanc@example.com");
+}
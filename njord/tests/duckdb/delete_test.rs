@@ -0,0 +1,106 @@
+use super::{open_with_schema, user, User};
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::duckdb::select::SelectQueryBuilder;
+use njord::duckdb::{self, DuckDbError};
+
+fn usernames(conn: &duckdb::Connection) -> Vec<String> {
+    duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .order_by([User::ID.asc()])
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|user| user.username)
+        .collect()
+}
+
+#[test]
+fn delete() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob")]).unwrap();
+
+    let result = duckdb::delete()
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("alice"))
+        .build(&conn);
+    assert_eq!(result.unwrap(), 1);
+
+    assert_eq!(usernames(&conn), ["bob"]);
+}
+
+#[test]
+fn delete_with_limit() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob"), user("carol")]).unwrap();
+
+    let condition = Condition::Eq(
+        "address".to_string(),
+        Value::Text("Some Random Address 1".to_string()),
+    );
+
+    let result = duckdb::delete()
+        .from(User::default())
+        .where_clause(condition)
+        .order_by([User::ID.desc()])
+        .limit(2)
+        .build(&conn);
+    assert!(result.is_ok());
+
+    assert_eq!(usernames(&conn), ["alice"]);
+}
+
+#[test]
+fn delete_with_subquery() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob")]).unwrap();
+
+    let sub_query = SelectQueryBuilder::<User>::new(vec![Column::Text("username".to_string())])
+        .from(User::default())
+        .where_clause(Condition::Eq("id".to_string(), Value::Int(2)))
+        .limit(1);
+
+    let condition = Condition::Eq("username".to_string(), Value::Subquery(Box::new(sub_query)));
+
+    let result = duckdb::delete()
+        .from(User::default())
+        .where_clause(condition)
+        .build(&conn);
+    assert!(result.is_ok());
+
+    assert_eq!(usernames(&conn), ["alice"]);
+}
+
+#[test]
+fn delete_expect_rows_mismatch() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice")]).unwrap();
+
+    let result = duckdb::delete()
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("bob"))
+        .expect_rows(1)
+        .build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(DuckDbError::UnexpectedRowCount {
+            expected: 1,
+            actual: 0
+        })
+    ));
+
+    let result = duckdb::delete()
+        .from(User::default())
+        .expect_rows(2)
+        .build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(DuckDbError::UnexpectedRowCount {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    assert_eq!(usernames(&conn), ["alice"]);
+}
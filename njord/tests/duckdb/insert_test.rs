@@ -0,0 +1,132 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::duckdb;
+
+use crate::{open_with_schema, user, User};
+
+#[test]
+fn insert_row() {
+    let conn = open_with_schema();
+
+    let result = duckdb::insert(&conn, vec![user("mjovanc")]);
+    assert!(result.is_ok());
+
+    let rows = duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id.to_string(), "1");
+    assert_eq!(rows[0].email, "mjovanc@example.com");
+}
+
+#[test]
+fn insert_rows() {
+    let conn = open_with_schema();
+
+    let inserted = duckdb::insert(&conn, vec![user("alice"), user("bob"), user("carol")]).unwrap();
+
+    let ids: Vec<Option<usize>> = inserted.iter().map(|row| row.id.get().copied()).collect();
+    assert_eq!(ids, vec![Some(1), Some(2), Some(3)]);
+
+    let rows = duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn insert_invalid_row() {
+    let conn = open_with_schema();
+    duckdb::raw_execute(&conn, "DROP TABLE users").unwrap();
+
+    let result = duckdb::insert(&conn, vec![user("mjovanc")]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn insert_with_sub_query() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("mjovanc")]).unwrap();
+
+    let sub_query = duckdb::select(vec![
+        Column::Text("username".to_string()),
+        Column::Text("'copy@example.com'".to_string()),
+        Column::Text("address".to_string()),
+    ])
+    .from(User::default())
+    .where_clause(Condition::Eq(
+        "username".to_string(),
+        Value::Text("mjovanc".to_string()),
+    ));
+
+    let result = duckdb::insert::into::<User>(
+        &conn,
+        vec![
+            "username".to_string(),
+            "email".to_string(),
+            "address".to_string(),
+        ],
+        Box::new(sub_query),
+    );
+    assert_eq!(result.ok(), Some(1));
+
+    let rows = duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .where_clause(User::EMAIL.eq("copy@example.com"))
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].username, "mjovanc");
+}
+
+#[test]
+fn bulk_insert() {
+    let conn = open_with_schema();
+
+    let usernames: Vec<String> = (0..250).map(|index| format!("user{}", index)).collect();
+    let progress = duckdb::bulk_insert(usernames.iter().map(|username| user(username)))
+        .chunk_size(100)
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(progress.rows_inserted, 250);
+    assert_eq!(progress.chunks, 3);
+
+    let rows = duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(rows.len(), 250);
+}
+
+#[test]
+fn bulk_insert_in_transaction() {
+    let conn = open_with_schema();
+
+    let result = duckdb::transaction(&conn, |tx| {
+        duckdb::bulk_insert([user("alice"), user("bob")])
+            .chunk_size(1)
+            .build(tx)?;
+
+        Err::<(), _>(duckdb::DuckDbError::UnexpectedRowCount {
+            expected: 0,
+            actual: 2,
+        })
+    });
+
+    // The bulk insert ran on the transaction, so rolling it back undoes the rows
+    assert!(result.is_err());
+
+    let rows = duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+
+    assert!(rows.is_empty());
+}
@@ -0,0 +1,108 @@
+mod any_test;
+mod delete_test;
+mod insert_test;
+mod open_test;
+mod raw_test;
+mod select_joins_test;
+mod select_test;
+mod transaction_test;
+mod update_test;
+mod upsert_test;
+
+use njord::duckdb::{self, Connection};
+use njord::keys::{AutoIncrementPrimaryKey, PrimaryKey};
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "users"]
+pub struct User {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub username: String,
+    pub email: String,
+    pub address: String,
+}
+
+#[derive(Table, Clone)]
+#[table_name = "categories"]
+pub struct Category {
+    pub id: PrimaryKey<usize>,
+    pub name: String,
+}
+
+#[derive(Table, Clone)]
+#[table_name = "products"]
+pub struct Product {
+    pub id: PrimaryKey<usize>,
+    pub name: String,
+    pub description: String,
+    pub price: f64,
+    pub stock_quantity: usize,
+    pub category_id: usize,
+    pub discount: f64,
+}
+
+#[derive(Table)]
+#[table_name = "categories"]
+pub struct CategoryWithJoin {
+    pub name: String,
+    pub price: f64,
+    pub stock_quantity: usize,
+}
+
+#[derive(Table)]
+#[table_name = "products"]
+pub struct StockPerCategory {
+    pub category_id: usize,
+    pub total_stock: usize,
+    pub average_price: f64,
+}
+
+/// Opens an in-memory database with the test schema loaded.
+pub fn open_with_schema() -> Connection {
+    let conn = duckdb::open_in_memory().expect("Failed to open DuckDB");
+    duckdb::raw_execute(&conn, include_str!("../../db/test/duckdb.sql"))
+        .expect("Failed to load the test schema");
+
+    conn
+}
+
+pub fn user(username: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+/// Inserts two categories with three products between them.
+pub fn insert_products(conn: &Connection) {
+    let categories = vec![
+        Category {
+            id: PrimaryKey::new(1),
+            name: "Books".to_string(),
+        },
+        Category {
+            id: PrimaryKey::new(2),
+            name: "Games".to_string(),
+        },
+    ];
+    duckdb::insert(conn, categories).expect("Failed to insert categories");
+
+    let products = [
+        (1, "Novel", 10.5, 4, 1),
+        (2, "Atlas", 20.0, 6, 1),
+        (3, "Chess", 15.25, 3, 2),
+    ]
+    .map(|(id, name, price, stock_quantity, category_id)| Product {
+        id: PrimaryKey::new(id),
+        name: name.to_string(),
+        description: format!("A {}", name.to_lowercase()),
+        price,
+        stock_quantity,
+        category_id,
+        discount: 0.0,
+    });
+    duckdb::insert(conn, products.into()).expect("Failed to insert products");
+}
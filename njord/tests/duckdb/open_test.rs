@@ -0,0 +1,18 @@
+use njord::duckdb;
+
+#[test]
+fn open_db() {
+    let db_path = std::env::temp_dir().join("njord_open_test.duckdb");
+
+    let result = duckdb::open(&db_path);
+    assert!(result.is_ok());
+
+    drop(result);
+    let _ = std::fs::remove_file(&db_path);
+}
+
+#[test]
+fn open_in_memory() {
+    let result = duckdb::open_in_memory();
+    assert!(result.is_ok());
+}
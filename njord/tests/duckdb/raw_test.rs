@@ -0,0 +1,45 @@
+use njord::duckdb;
+use njord_derive::sql;
+
+use crate::{open_with_schema, user, User};
+
+#[test]
+fn raw_execute() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("mjovanc"), user("alice")]).unwrap();
+
+    let username = "mjovanc";
+
+    let query = sql! {
+        SELECT *
+        FROM users
+        WHERE username = {username}
+    };
+
+    let result = duckdb::select::raw_execute::<User>(&query, &conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "mjovanc");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn raw_execute_statements() {
+    let conn = open_with_schema();
+
+    let result = duckdb::raw_execute(
+        &conn,
+        "INSERT INTO users (username, email, address) VALUES ('alice', 'alice@example.com', 'Main St');
+         INSERT INTO users (username, email, address) VALUES ('bob', 'bob@example.com', 'Main St');",
+    );
+    assert!(result.is_ok());
+
+    let rows = duckdb::select::raw_execute::<User>("SELECT * FROM users", &conn).unwrap();
+    assert_eq!(rows.len(), 2);
+
+    assert!(duckdb::raw_execute(&conn, "SELECT * FROM missing").is_err());
+}
@@ -0,0 +1,80 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::duckdb;
+use njord::util::JoinType;
+use std::sync::Arc;
+
+use crate::{insert_products, open_with_schema, Category, CategoryWithJoin, Product};
+
+fn join_condition() -> Condition<'static> {
+    Condition::Eq(
+        "categories.id".to_string(),
+        Value::Column("products.category_id".to_string()),
+    )
+}
+
+fn columns() -> Vec<Column<'static>> {
+    vec![
+        Column::Text("categories.name".to_string()),
+        Column::Text("products.price".to_string()),
+        Column::Text("products.stock_quantity".to_string()),
+    ]
+}
+
+#[test]
+fn select_inner_join() {
+    let conn = open_with_schema();
+    insert_products(&conn);
+
+    let result = duckdb::select(columns())
+        .from(CategoryWithJoin::default())
+        .join(
+            JoinType::Inner,
+            Arc::new(Product::default()),
+            join_condition(),
+        )
+        .order_by([(vec!["products.id".to_string()], "ASC".to_string())])
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 3);
+            assert_eq!(r[0].name, "Books");
+            assert_eq!(r[0].price, 10.5);
+            assert_eq!(r[2].name, "Games");
+            assert_eq!(r[2].stock_quantity, 3);
+        }
+        Err(e) => panic!("Failed to SELECT with JOIN: {:?}", e),
+    };
+}
+
+#[test]
+fn select_left_join() {
+    let conn = open_with_schema();
+    insert_products(&conn);
+    duckdb::insert(
+        &conn,
+        vec![Category {
+            id: njord::keys::PrimaryKey::new(3),
+            name: "Music".to_string(),
+        }],
+    )
+    .unwrap();
+
+    let result = duckdb::select(columns())
+        .from(CategoryWithJoin::default())
+        .join(
+            JoinType::Left,
+            Arc::new(Product::default()),
+            join_condition(),
+        )
+        .where_clause(Condition::IsNull("products.id".to_string()))
+        .build_as::<(String, Option<f64>, Option<i64>)>(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r, vec![("Music".to_string(), None, None)]);
+        }
+        Err(e) => panic!("Failed to SELECT with JOIN: {:?}", e),
+    };
+}
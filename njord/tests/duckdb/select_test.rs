@@ -0,0 +1,194 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::duckdb;
+
+use crate::{insert_products, open_with_schema, user, StockPerCategory, User};
+
+fn all_columns() -> Vec<Column<'static>> {
+    vec![
+        User::ID.into(),
+        User::USERNAME.into(),
+        User::EMAIL.into(),
+        User::ADDRESS.into(),
+    ]
+}
+
+#[test]
+fn select() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("mjovanc"), user("alice")]).unwrap();
+
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
+
+    let result = duckdb::select(all_columns())
+        .from(User::default())
+        .where_clause(condition)
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].id.to_string(), "1");
+            assert_eq!(r[0].email, "mjovanc@example.com");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn select_columns_by_name() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("mjovanc")]).unwrap();
+
+    let result = duckdb::select(vec![User::EMAIL.into(), User::USERNAME.into()])
+        .from(User::default())
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "mjovanc");
+            assert_eq!(r[0].email, "mjovanc@example.com");
+            assert_eq!(r[0].address, "");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn select_order_by_limit_offset() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob"), user("carol")]).unwrap();
+
+    let result = duckdb::select(all_columns())
+        .from(User::default())
+        .order_by([User::ID.desc()])
+        .limit(1)
+        .offset(1)
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "bob");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn select_predicates() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob"), user("carol")]).unwrap();
+
+    let count = |condition: Condition<'static>| {
+        duckdb::select(all_columns())
+            .from(User::default())
+            .where_clause(condition)
+            .build(&conn)
+            .unwrap()
+            .len()
+    };
+
+    assert_eq!(count(User::ID.in_([1, 3])), 2);
+    assert_eq!(count(User::USERNAME.like("%o%")), 2);
+    assert_eq!(
+        count(Condition::Between(
+            "id".to_string(),
+            Value::Int(2),
+            Value::Int(3)
+        )),
+        2
+    );
+    assert_eq!(count(Condition::IsNull("address".to_string())), 0);
+}
+
+#[test]
+fn select_group_by_having() {
+    let conn = open_with_schema();
+    insert_products(&conn);
+
+    let columns = vec![
+        Column::Text("category_id".to_string()),
+        Column::Text("SUM(stock_quantity) AS total_stock".to_string()),
+        Column::Text("AVG(price) AS average_price".to_string()),
+    ];
+
+    let result = duckdb::select(columns)
+        .from(StockPerCategory::default())
+        .group_by(vec!["category_id".to_string()])
+        .having(Condition::Gt(
+            "SUM(stock_quantity)".to_string(),
+            Value::Int(5),
+        ))
+        .order_by([StockPerCategory::CATEGORY_ID.asc()])
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].category_id, 1);
+            assert_eq!(r[0].total_stock, 10);
+            assert_eq!(r[0].average_price, 15.25);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn select_union() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob")]).unwrap();
+
+    let query = duckdb::select(all_columns())
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("alice"));
+    let other = duckdb::select(all_columns())
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("bob"));
+
+    let result = query.union(other).build(&conn);
+
+    match result {
+        Ok(r) => assert_eq!(r.len(), 2),
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn select_iter() {
+    let conn = open_with_schema();
+    let usernames: Vec<String> = (0..5).map(|index| format!("user{}", index)).collect();
+    duckdb::insert(&conn, usernames.iter().map(|name| user(name)).collect()).unwrap();
+
+    let iter = duckdb::select(all_columns())
+        .from(User::default())
+        .order_by([User::USERNAME.desc()])
+        .build_iter(&conn)
+        .unwrap();
+
+    let selected: Vec<String> = iter.take(2).map(|user| user.unwrap().username).collect();
+
+    assert_eq!(selected, vec!["user4", "user3"]);
+}
+
+#[test]
+fn select_as() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("anna"), user("bob")]).unwrap();
+
+    let names = duckdb::select(vec![User::USERNAME.into(), User::EMAIL.into()])
+        .from(User::default())
+        .order_by([User::USERNAME.asc()])
+        .build_as::<(String, String)>(&conn)
+        .unwrap();
+
+    let total = duckdb::select(vec![Column::Text("COUNT(*) AS total".to_string())])
+        .from(User::default())
+        .build_as::<i64>(&conn)
+        .unwrap();
+
+    assert_eq!(names[0].0, "anna");
+    assert_eq!(names[1].0, "bob");
+    assert_eq!(total, vec![2]);
+}
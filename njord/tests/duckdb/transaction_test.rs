@@ -0,0 +1,56 @@
+use njord::column::Column;
+use njord::duckdb::{self, Connection, DuckDbError};
+use njord::transaction::IsolationLevel;
+
+use crate::{open_with_schema, user, User};
+
+fn count_users(conn: &Connection) -> usize {
+    duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(conn)
+        .unwrap()
+        .len()
+}
+
+#[test]
+fn commit() {
+    let conn = open_with_schema();
+
+    let result = duckdb::transaction_with(&conn, IsolationLevel::Snapshot, |tx| {
+        duckdb::insert(tx, vec![user("alice")])?;
+        duckdb::insert(tx, vec![user("bob")])?;
+        Ok::<_, DuckDbError>(())
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(count_users(&conn), 2);
+}
+
+#[test]
+fn rollback_on_error() {
+    let conn = open_with_schema();
+
+    let result = duckdb::transaction(&conn, |tx| {
+        duckdb::insert(tx, vec![user("alice")])?;
+        duckdb::raw_execute(tx, "INSERT INTO missing_table VALUES (1)")
+    });
+
+    assert!(result.is_err());
+    assert_eq!(count_users(&conn), 0);
+}
+
+#[test]
+fn nested_transaction_is_rejected() {
+    let conn = open_with_schema();
+
+    let result = duckdb::transaction(&conn, |tx| {
+        duckdb::insert(tx, vec![user("alice")])?;
+        tx.transaction(|nested| {
+            duckdb::insert(nested, vec![user("bob")])?;
+            Ok::<_, DuckDbError>(())
+        })
+    });
+
+    assert!(matches!(result, Err(DuckDbError::NestedTransactionError)));
+    assert_eq!(count_users(&conn), 0);
+}
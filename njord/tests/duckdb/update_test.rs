@@ -0,0 +1,78 @@
+use super::{open_with_schema, user, User};
+use njord::column::Column;
+use njord::duckdb;
+use njord::keys::AutoIncrementPrimaryKey;
+
+fn emails(conn: &duckdb::Connection) -> Vec<String> {
+    duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .order_by([User::ID.asc()])
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|user| user.email)
+        .collect()
+}
+
+#[test]
+fn update() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob")]).unwrap();
+
+    let table_row = User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: "alice".to_string(),
+        email: "alice@njord.rs".to_string(),
+        address: String::new(),
+    };
+
+    let result = duckdb::update(table_row)
+        .set([User::EMAIL])
+        .where_clause(User::USERNAME.eq("alice"))
+        .build(&conn);
+    assert!(result.is_ok());
+
+    assert_eq!(emails(&conn), ["alice@njord.rs", "bob@example.com"]);
+}
+
+#[test]
+fn update_with_limit() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob"), user("carol")]).unwrap();
+
+    let table_row = User {
+        email: "updated@njord.rs".to_string(),
+        ..user("")
+    };
+
+    let result = duckdb::update(table_row)
+        .set([User::EMAIL])
+        .order_by([User::ID.desc()])
+        .limit(2)
+        .expect_rows(2)
+        .build(&conn);
+    assert_eq!(result.unwrap(), 2);
+
+    assert_eq!(
+        emails(&conn),
+        ["alice@example.com", "updated@njord.rs", "updated@njord.rs"]
+    );
+}
+
+#[test]
+fn update_invalid_value() {
+    let conn = open_with_schema();
+    duckdb::insert(&conn, vec![user("alice"), user("bob")]).unwrap();
+
+    // Alice already has the id, which the primary key rejects
+    let table_row = User {
+        id: AutoIncrementPrimaryKey::new(Some(1)),
+        ..User::default()
+    };
+
+    let result = duckdb::update(table_row)
+        .set([User::ID])
+        .where_clause(User::USERNAME.eq("bob"))
+        .build(&conn);
+    assert!(matches!(result, Err(duckdb::DuckDbError::UpdateError(_))));
+}
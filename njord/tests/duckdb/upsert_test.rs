@@ -0,0 +1,54 @@
+use njord::column::Column;
+use njord::duckdb::{self, Connection};
+
+use crate::{open_with_schema, user, User};
+
+fn emails(conn: &Connection) -> Vec<String> {
+    duckdb::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .order_by([User::USERNAME.asc()])
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|user| user.email)
+        .collect()
+}
+
+#[test]
+fn upsert_inserts_and_updates() {
+    let conn = open_with_schema();
+    let inserted = duckdb::insert(&conn, vec![user("alice"), user("bob")]).unwrap();
+
+    let changed: Vec<User> = inserted
+        .into_iter()
+        .take(1)
+        .map(|user| User {
+            email: "alice@new.com".to_string(),
+            ..user
+        })
+        .collect();
+
+    let rows = duckdb::upsert(changed).build(&conn).unwrap();
+
+    assert_eq!(rows, 1);
+    assert_eq!(emails(&conn), vec!["alice@new.com", "bob@example.com"]);
+}
+
+#[test]
+fn upsert_do_nothing() {
+    let conn = open_with_schema();
+    let inserted = duckdb::insert(&conn, vec![user("alice")]).unwrap();
+
+    let changed = User {
+        email: "alice@new.com".to_string(),
+        ..inserted[0].clone()
+    };
+
+    let rows = duckdb::upsert(vec![changed])
+        .do_nothing()
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(rows, 0);
+    assert_eq!(emails(&conn), vec!["alice@example.com"]);
+}
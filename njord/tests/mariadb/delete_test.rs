@@ -18,7 +18,7 @@ fn delete_row() {
                 .from(User::default())
                 .where_clause(Condition::Eq(
                     "username".to_string(),
-                    Value::Literal("chasewillden2".to_string()),
+                    Value::Text("chasewillden2".to_string()),
                 ))
                 .build(c);
             assert!(result.is_ok());
@@ -3,6 +3,7 @@ mod insert_test;
 mod open_test;
 mod select_joins_test;
 mod select_test;
+mod transaction_test;
 mod update_test;
 
 use njord::keys::AutoIncrementPrimaryKey;
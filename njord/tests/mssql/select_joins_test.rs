@@ -23,7 +23,7 @@ async fn delete_mock_data<T: Table + Clone + Default>(
 ) {
     let value_list: Vec<Value> = names
         .into_iter()
-        .map(Value::Literal) // Wrap each username as a Value::Literal
+        .map(Value::Text) // Wrap each username as a Value::Text
         .collect();
 
     let result = mssql::delete()
@@ -50,7 +50,7 @@ async fn select_inner_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "categories.id".to_string(),
-        Value::Literal("products.category_id".to_string()),
+        Value::Column("products.category_id".to_string()),
     );
     match conn {
         Ok(ref mut c) => {
@@ -129,7 +129,7 @@ async fn select_left_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "categories.id".to_string(),
-        Value::Literal("products.category_id".to_string()),
+        Value::Column("products.category_id".to_string()),
     );
     match conn {
         Ok(ref mut c) => {
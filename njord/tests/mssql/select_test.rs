@@ -16,7 +16,7 @@ async fn delete_mock_data(conn: &mut mssql::Connection, usernames: Vec<String>)
     // Transform Vec<String> into Vec<Value>
     let value_list: Vec<Value> = usernames
         .into_iter()
-        .map(Value::Literal) // Wrap each username as a Value::Literal
+        .map(Value::Text) // Wrap each username as a Value::Text
         .collect();
 
     let result = mssql::delete()
@@ -27,6 +27,26 @@ async fn delete_mock_data(conn: &mut mssql::Connection, usernames: Vec<String>)
     assert!(result.is_ok());
 }
 
+async fn count_users(conn: &mut mssql::Connection, condition: Condition<'_>) -> usize {
+    let columns = vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+        Column::Text("address".to_string()),
+    ];
+
+    let result = mssql::select(columns)
+        .from(User::default())
+        .where_clause(condition)
+        .build(conn)
+        .await;
+
+    match result {
+        Ok(r) => r.len(),
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
 #[tokio::test]
 async fn open_db() {
     let connection_string =
@@ -67,10 +87,7 @@ async fn update() {
 
     let columns = vec!["username".to_string()];
 
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::<usize>::new(Some(0)),
@@ -106,7 +123,7 @@ async fn delete() {
 
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address 1".to_string()),
+        Value::Text("Some Random Address 1".to_string()),
     );
 
     let mut order = HashMap::new();
@@ -142,7 +159,7 @@ async fn select() {
     ];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_test".to_string()),
+        Value::Text("select_test".to_string()),
     );
 
     match conn {
@@ -201,7 +218,7 @@ async fn select_distinct() {
     ];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_distinct_test".to_string()),
+        Value::Text("select_distinct_test".to_string()),
     );
 
     match conn {
@@ -272,7 +289,7 @@ async fn select_order_by() {
     ];
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address select_order_by".to_string()),
+        Value::Text("Some Random Address select_order_by".to_string()),
     );
     let group_by = vec!["username".to_string(), "email".to_string()];
 
@@ -338,7 +355,7 @@ async fn select_group_by() {
     ];
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address select_group_by".to_string()),
+        Value::Text("Some Random Address select_group_by".to_string()),
     );
     let group_by = vec!["username".to_string(), "email".to_string()];
 
@@ -400,7 +417,7 @@ async fn select_having() {
     ];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_having_test".to_string()),
+        Value::Text("select_having_test".to_string()),
     );
     let group_by = vec!["username".to_string(), "email".to_string()];
 
@@ -409,7 +426,7 @@ async fn select_having() {
 
     let having_condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_having_test".to_string()),
+        Value::Text("select_having_test".to_string()),
     );
 
     match conn {
@@ -460,15 +477,15 @@ async fn select_except() {
 
     let condition1 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_except_test".to_string()),
+        Value::Text("select_except_test".to_string()),
     );
     let condition2 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_except_test2".to_string()),
+        Value::Text("select_except_test2".to_string()),
     );
     let condition3 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_except_test3".to_string()),
+        Value::Text("select_except_test3".to_string()),
     );
 
     let query1 = mssql::select(columns.clone())
@@ -569,11 +586,11 @@ async fn select_except() {
 
 //     let condition1 = Condition::Eq(
 //         "username".to_string(),
-//         Value::Literal("select_union_test".to_string()),
+//         Value::Text("select_union_test".to_string()),
 //     );
 //     let condition2 = Condition::Eq(
 //         "username".to_string(),
-//         Value::Literal("select_union_test2".to_string()),
+//         Value::Text("select_union_test2".to_string()),
 //     );
 
 //     let query1 = mssql::select(columns.clone())
@@ -654,7 +671,7 @@ async fn select_sub_queries() {
                 .from(UserWithSubQuery::default())
                 .where_clause(Condition::Eq(
                     "username".to_string(),
-                    Value::Literal("select_sub_queries_test3".to_string()),
+                    Value::Text("select_sub_queries_test3".to_string()),
                 ));
 
             let columns = vec![
@@ -670,8 +687,8 @@ async fn select_sub_queries() {
                 .where_clause(Condition::In(
                     "username".to_string(),
                     vec![
-                        Value::Literal("select_sub_queries_test".to_string()),
-                        Value::Literal("select_sub_queries_test2".to_string()),
+                        Value::Text("select_sub_queries_test".to_string()),
+                        Value::Text("select_sub_queries_test2".to_string()),
                     ],
                 ))
                 .build(c)
@@ -679,7 +696,7 @@ async fn select_sub_queries() {
 
             match result {
                 Ok(r) => {
-                    assert!(r.len() > 0);
+                    assert!(!r.is_empty());
                     assert_eq!(r[0].additional_address, "SubQuery");
                 }
                 Err(e) => panic!("Failed to SELECT: {:?}", e),
@@ -716,13 +733,13 @@ async fn select_in() {
         Box::new(Condition::In(
             "username".to_string(),
             vec![
-                Value::Literal("select_in_test".to_string()),
-                Value::Literal("select_in_test2".to_string()),
+                Value::Text("select_in_test".to_string()),
+                Value::Text("select_in_test2".to_string()),
             ],
         )),
         Box::new(Condition::NotIn(
             "username".to_string(),
-            vec![Value::Literal("select_in_test3".to_string())],
+            vec![Value::Text("select_in_test3".to_string())],
         )),
     );
 
@@ -778,6 +795,156 @@ async fn select_in() {
     };
 }
 
+#[tokio::test]
+async fn select_predicates() {
+    let connection_string =
+        "jdbc:sqlserver://localhost;encrypt=true;username=sa;password=Njord_passw0rd;databaseName=NjordDatabase;";
+    let mut conn = mssql::open(connection_string).await;
+
+    match conn {
+        Ok(ref mut c) => {
+            insert_mock_data(
+                c,
+                vec![
+                    User {
+                        id: AutoIncrementPrimaryKey::default(),
+                        username: "select_predicates_test".to_string(),
+                        email: "select_predicates_test@example.com".to_string(),
+                        address: "Some Random Address 1".to_string(),
+                    },
+                    User {
+                        id: AutoIncrementPrimaryKey::default(),
+                        username: "select_predicates_test2".to_string(),
+                        email: "SELECT_PREDICATES_TEST2@EXAMPLE.COM".to_string(),
+                        address: "Some Random Address 1".to_string(),
+                    },
+                    User {
+                        id: AutoIncrementPrimaryKey::default(),
+                        username: "select_predicates_100%".to_string(),
+                        email: "select_predicates_100@example.com".to_string(),
+                        address: "Some Random Address 1".to_string(),
+                    },
+                ],
+            )
+            .await;
+
+            let prefix =
+                || Condition::Like("username".to_string(), "select_predicates%".into(), None);
+
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::Like(
+                        "username".to_string(),
+                        "select!_predicates!_100!%".into(),
+                        Some('!'),
+                    )
+                )
+                .await,
+                1
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::NotLike(
+                            "username".to_string(),
+                            "select_predicates_test%".into(),
+                            None,
+                        )),
+                    )
+                )
+                .await,
+                1
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::ILike(
+                            "email".to_string(),
+                            "%@Example.Com".into(),
+                            None,
+                        )),
+                    )
+                )
+                .await,
+                3
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::Between(
+                        "username".to_string(),
+                        "select_predicates_test".into(),
+                        "select_predicates_test2".into(),
+                    )
+                )
+                .await,
+                2
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::And(
+                            Box::new(Condition::IsNotNull("email".to_string())),
+                            Box::new(Condition::Not(Box::new(Condition::IsNull(
+                                "address".to_string()
+                            )))),
+                        )),
+                    )
+                )
+                .await,
+                3
+            );
+
+            let sub_query = mssql::select(vec![Column::Text("id".to_string())])
+                .from(User::default())
+                .where_clause(Condition::Eq(
+                    "username".to_string(),
+                    "select_predicates_test".into(),
+                ));
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::Exists(Box::new(sub_query.clone()))),
+                    )
+                )
+                .await,
+                3
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::NotExists(Box::new(sub_query))),
+                    )
+                )
+                .await,
+                0
+            );
+
+            delete_mock_data(
+                c,
+                vec![
+                    "select_predicates_test".to_string(),
+                    "select_predicates_test2".to_string(),
+                    "select_predicates_100%".to_string(),
+                ],
+            )
+            .await;
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
 #[tokio::test]
 async fn raw_execut() {
     let connection_string =
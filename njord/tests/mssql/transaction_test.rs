@@ -0,0 +1,89 @@
+use super::User;
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::mssql::{self, Connection, MSSQLError};
+use njord::transaction::IsolationLevel;
+
+const CONNECTION_STRING: &str =
+    "jdbc:sqlserver://localhost;encrypt=true;username=sa;password=Njord_passw0rd;databaseName=NjordDatabase;";
+
+fn user(username: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+async fn count(conn: &mut Connection, username: &str) -> usize {
+    mssql::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .where_clause(Condition::Eq(
+            "username".to_string(),
+            Value::Text(username.to_string()),
+        ))
+        .build(conn)
+        .await
+        .unwrap()
+        .len()
+}
+
+async fn clean_up(conn: &mut Connection, username: &str) {
+    mssql::raw_execute(
+        conn,
+        &format!("DELETE FROM users WHERE username = '{}'", username),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn commit() {
+    let mut conn = mssql::open(CONNECTION_STRING).await.unwrap();
+    clean_up(&mut conn, "transaction_commit").await;
+
+    let result = mssql::transaction_with(&mut conn, IsolationLevel::Serializable, |tx| {
+        Box::pin(async move {
+            mssql::insert(tx, vec![user("transaction_commit")]).await?;
+            Ok::<_, MSSQLError>(count(tx, "transaction_commit").await)
+        })
+    })
+    .await;
+
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(count(&mut conn, "transaction_commit").await, 1);
+    clean_up(&mut conn, "transaction_commit").await;
+}
+
+#[tokio::test]
+async fn nested_rollback_keeps_outer_changes() {
+    let mut conn = mssql::open(CONNECTION_STRING).await.unwrap();
+    clean_up(&mut conn, "transaction_outer").await;
+    clean_up(&mut conn, "transaction_inner").await;
+
+    let result = mssql::transaction(&mut conn, |tx| {
+        Box::pin(async move {
+            mssql::insert(tx, vec![user("transaction_outer")]).await?;
+
+            let nested = tx
+                .transaction(|nested| {
+                    Box::pin(async move {
+                        mssql::insert(nested, vec![user("transaction_inner")]).await?;
+                        mssql::raw_execute(nested, "INSERT INTO missing_table VALUES (1)").await
+                    })
+                })
+                .await;
+            assert!(nested.is_err());
+
+            Ok::<_, MSSQLError>(())
+        })
+    })
+    .await;
+
+    assert!(result.is_ok());
+    assert_eq!(count(&mut conn, "transaction_outer").await, 1);
+    assert_eq!(count(&mut conn, "transaction_inner").await, 0);
+    clean_up(&mut conn, "transaction_outer").await;
+}
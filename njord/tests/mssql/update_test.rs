@@ -15,7 +15,7 @@ async fn update_row() {
     let columns = vec!["username".to_string()];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("chasewillden2".to_string()),
+        Value::Text("chasewillden2".to_string()),
     );
 
     let table_row: User = User {
@@ -14,7 +14,7 @@ fn delete_row() {
     let columns = vec!["username".to_string()];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("chasewillden2".to_string()),
+        Value::Text("chasewillden2".to_string()),
     );
 
     match conn {
@@ -22,7 +22,7 @@ fn delete_mock_data<T: Table + Clone + Default>(
     // Transform Vec<String> into Vec<Value>
     let value_list: Vec<Value> = names
         .into_iter()
-        .map(Value::Literal) // Wrap each username as a Value::Literal
+        .map(Value::Text) // Wrap each username as a Value::Text
         .collect();
 
     let result = oracle::delete()
@@ -47,7 +47,7 @@ fn select_inner_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "categories.id".to_string(),
-        Value::Literal("products.category_id".to_string()),
+        Value::Column("products.category_id".to_string()),
     );
     match conn {
         Ok(ref mut c) => {
@@ -120,7 +120,7 @@ fn select_left_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "categories.id".to_string(),
-        Value::Literal("products.category_id".to_string()),
+        Value::Column("products.category_id".to_string()),
     );
     match conn {
         Ok(ref mut c) => {
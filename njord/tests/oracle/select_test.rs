@@ -16,7 +16,7 @@ fn insert_mock_data(conn: &mut Connection, table_rows: Vec<User>) {
 fn delete_mock_data(conn: &mut Connection, usernames: Vec<String>) {
     let value_list: Vec<Value> = usernames
         .into_iter()
-        .map(Value::Literal) // Wrap each username as a Value::Literal
+        .map(Value::Text) // Wrap each username as a Value::Text
         .collect();
 
     let result = oracle::delete()
@@ -26,6 +26,25 @@ fn delete_mock_data(conn: &mut Connection, usernames: Vec<String>) {
     assert!(result.is_ok());
 }
 
+fn count_users(conn: &mut Connection, condition: Condition<'_>) -> usize {
+    let columns = vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+        Column::Text("address".to_string()),
+    ];
+
+    let result = oracle::select(columns)
+        .from(User::default())
+        .where_clause(condition)
+        .build(conn);
+
+    match result {
+        Ok(r) => r.len(),
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
 #[test]
 fn open_db() {
     let connection_string = "//localhost:1521/FREEPDB1";
@@ -63,10 +82,7 @@ fn update() {
 
     let columns = vec!["username".to_string()];
 
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::<usize>::new(Some(0)),
@@ -103,7 +119,7 @@ fn delete() {
 
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address 1".to_string()),
+        Value::Text("Some Random Address 1".to_string()),
     );
 
     let mut order = HashMap::new();
@@ -140,7 +156,7 @@ fn select() {
     ];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_test".to_string()),
+        Value::Text("select_test".to_string()),
     );
 
     match conn {
@@ -195,7 +211,7 @@ fn select_distinct() {
     ];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_distinct_test".to_string()),
+        Value::Text("select_distinct_test".to_string()),
     );
 
     match conn {
@@ -262,7 +278,7 @@ fn select_order_by() {
     ];
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address select_order_by".to_string()),
+        Value::Text("Some Random Address select_order_by".to_string()),
     );
     let group_by = vec!["username".to_string(), "email".to_string()];
 
@@ -324,7 +340,7 @@ fn select_group_by() {
     ];
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address select_group_by".to_string()),
+        Value::Text("Some Random Address select_group_by".to_string()),
     );
     let group_by = vec!["username".to_string(), "email".to_string()];
 
@@ -382,7 +398,7 @@ fn select_having() {
     ];
     let condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_having_test".to_string()),
+        Value::Text("select_having_test".to_string()),
     );
     let group_by = vec!["username".to_string(), "email".to_string()];
 
@@ -391,7 +407,7 @@ fn select_having() {
 
     let having_condition = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_having_test".to_string()),
+        Value::Text("select_having_test".to_string()),
     );
 
     match conn {
@@ -439,15 +455,15 @@ fn select_except() {
 
     let condition1 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_except_test".to_string()),
+        Value::Text("select_except_test".to_string()),
     );
     let condition2 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_except_test2".to_string()),
+        Value::Text("select_except_test2".to_string()),
     );
     let condition3 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("select_except_test3".to_string()),
+        Value::Text("select_except_test3".to_string()),
     );
 
     let query1 = oracle::select(columns.clone())
@@ -546,11 +562,11 @@ fn select_except() {
 
 //     let condition1 = Condition::Eq(
 //         "username".to_string(),
-//         Value::Literal("select_union_test".to_string()),
+//         Value::Text("select_union_test".to_string()),
 //     );
 //     let condition2 = Condition::Eq(
 //         "username".to_string(),
-//         Value::Literal("select_union_test2".to_string()),
+//         Value::Text("select_union_test2".to_string()),
 //     );
 
 //     let query1 = oracle::select(columns.clone())
@@ -629,7 +645,7 @@ fn select_sub_queries() {
                 .from(UserWithSubQuery::default())
                 .where_clause(Condition::Eq(
                     "username".to_string(),
-                    Value::Literal("select_sub_queries_test3".to_string()),
+                    Value::Text("select_sub_queries_test3".to_string()),
                 ));
 
             let columns = vec![
@@ -645,15 +661,15 @@ fn select_sub_queries() {
                 .where_clause(Condition::In(
                     "username".to_string(),
                     vec![
-                        Value::Literal("select_sub_queries_test".to_string()),
-                        Value::Literal("select_sub_queries_test2".to_string()),
+                        Value::Text("select_sub_queries_test".to_string()),
+                        Value::Text("select_sub_queries_test2".to_string()),
                     ],
                 ))
                 .build(c);
 
             match result {
                 Ok(r) => {
-                    assert!(r.len() > 0);
+                    assert!(!r.is_empty());
                     assert_eq!(r[0].additional_address, "SubQuery");
                 }
                 Err(e) => panic!("Failed to SELECT: {:?}", e),
@@ -688,13 +704,13 @@ fn select_in() {
         Box::new(Condition::In(
             "username".to_string(),
             vec![
-                Value::Literal("select_in_test".to_string()),
-                Value::Literal("select_in_test2".to_string()),
+                Value::Text("select_in_test".to_string()),
+                Value::Text("select_in_test2".to_string()),
             ],
         )),
         Box::new(Condition::NotIn(
             "username".to_string(),
-            vec![Value::Literal("select_in_test3".to_string())],
+            vec![Value::Text("select_in_test3".to_string())],
         )),
     );
 
@@ -747,6 +763,146 @@ fn select_in() {
     };
 }
 
+#[test]
+fn select_predicates() {
+    let connection_string = "//localhost:1521/FREEPDB1";
+    let mut conn = oracle::open("njord_user", "njord_password", connection_string);
+
+    match conn {
+        Ok(ref mut c) => {
+            insert_mock_data(
+                c,
+                vec![
+                    User {
+                        id: AutoIncrementPrimaryKey::default(),
+                        username: "select_predicates_test".to_string(),
+                        email: "select_predicates_test@example.com".to_string(),
+                        address: "Some Random Address 1".to_string(),
+                    },
+                    User {
+                        id: AutoIncrementPrimaryKey::default(),
+                        username: "select_predicates_test2".to_string(),
+                        email: "SELECT_PREDICATES_TEST2@EXAMPLE.COM".to_string(),
+                        address: "Some Random Address 1".to_string(),
+                    },
+                    User {
+                        id: AutoIncrementPrimaryKey::default(),
+                        username: "select_predicates_100%".to_string(),
+                        email: "select_predicates_100@example.com".to_string(),
+                        address: "Some Random Address 1".to_string(),
+                    },
+                ],
+            );
+
+            let prefix =
+                || Condition::Like("username".to_string(), "select_predicates%".into(), None);
+
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::Like(
+                        "username".to_string(),
+                        "select!_predicates!_100!%".into(),
+                        Some('!'),
+                    )
+                ),
+                1
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::NotLike(
+                            "username".to_string(),
+                            "select_predicates_test%".into(),
+                            None,
+                        )),
+                    )
+                ),
+                1
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::ILike(
+                            "email".to_string(),
+                            "%@Example.Com".into(),
+                            None,
+                        )),
+                    )
+                ),
+                3
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::Between(
+                        "username".to_string(),
+                        "select_predicates_test".into(),
+                        "select_predicates_test2".into(),
+                    )
+                ),
+                2
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::And(
+                            Box::new(Condition::IsNotNull("email".to_string())),
+                            Box::new(Condition::Not(Box::new(Condition::IsNull(
+                                "address".to_string()
+                            )))),
+                        )),
+                    )
+                ),
+                3
+            );
+
+            let sub_query = oracle::select(vec![Column::Text("id".to_string())])
+                .from(User::default())
+                .where_clause(Condition::Eq(
+                    "username".to_string(),
+                    "select_predicates_test".into(),
+                ));
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::Exists(Box::new(sub_query.clone()))),
+                    )
+                ),
+                3
+            );
+            assert_eq!(
+                count_users(
+                    c,
+                    Condition::And(
+                        Box::new(prefix()),
+                        Box::new(Condition::NotExists(Box::new(sub_query))),
+                    )
+                ),
+                0
+            );
+
+            delete_mock_data(
+                c,
+                vec![
+                    "select_predicates_test".to_string(),
+                    "select_predicates_test2".to_string(),
+                    "select_predicates_100%".to_string(),
+                ],
+            );
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
 #[test]
 fn raw_execute() {
     let connection_string = "//localhost:1521/FREEPDB1";
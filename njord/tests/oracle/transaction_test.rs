@@ -0,0 +1,88 @@
+use super::User;
+use ::oracle::Connection;
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::oracle::{self, OracleError};
+use njord::transaction::IsolationLevel;
+
+fn open() -> Connection {
+    oracle::open("njord_user", "njord_password", "//localhost:1521/FREEPDB1")
+        .expect("Failed to connect")
+}
+
+fn clean_up(conn: &Connection, username: &str) {
+    oracle::raw_execute(
+        conn,
+        &format!("DELETE FROM users WHERE username = '{}'", username),
+    )
+    .unwrap();
+}
+
+fn user(username: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+fn count(conn: &Connection, username: &str) -> usize {
+    oracle::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .where_clause(Condition::Eq(
+            "username".to_string(),
+            Value::Text(username.to_string()),
+        ))
+        .build(conn)
+        .unwrap()
+        .len()
+}
+
+#[test]
+fn commit() {
+    let mut conn = open();
+    clean_up(&conn, "transaction_commit");
+
+    let result = oracle::transaction_with(&mut conn, IsolationLevel::ReadCommitted, |tx| {
+        oracle::insert(tx, vec![user("transaction_commit")]).expect("Failed to INSERT");
+        Ok::<_, OracleError>(count(tx, "transaction_commit"))
+    });
+
+    assert_eq!(result.unwrap(), 1);
+    assert_eq!(count(&conn, "transaction_commit"), 1);
+    clean_up(&conn, "transaction_commit");
+}
+
+#[test]
+fn rollback_on_error() {
+    let mut conn = open();
+    clean_up(&conn, "transaction_rollback");
+
+    let result = oracle::transaction(&mut conn, |tx| {
+        oracle::insert(tx, vec![user("transaction_rollback")]).expect("Failed to INSERT");
+        oracle::raw_execute(tx, "INSERT INTO missing_table VALUES (1)")
+    });
+
+    assert!(result.is_err());
+    assert_eq!(count(&conn, "transaction_rollback"), 0);
+}
+
+#[test]
+fn insert_with_keys_rolls_back_with_the_transaction() {
+    let mut conn = open();
+    clean_up(&conn, "transaction_keys");
+
+    // The rows are inserted one at a time to read their keys, inside of a savepoint
+    let result = oracle::transaction(&mut conn, |tx| {
+        let rows = oracle::insert(tx, vec![user("transaction_keys"), user("transaction_keys")])?;
+        Err::<(), _>(OracleError::UnexpectedRowCount {
+            expected: 0,
+            actual: rows.len() as u64,
+        })
+    });
+
+    assert!(result.is_err());
+    assert_eq!(count(&conn, "transaction_keys"), 0);
+}
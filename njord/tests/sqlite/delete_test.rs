@@ -1,20 +1,17 @@
-use super::User;
+use super::{open_users_table, seed_users, usernames, User};
 use njord::column::Column;
 use njord::condition::{Condition, Value};
-use njord::sqlite;
 use njord::sqlite::select::SelectQueryBuilder;
+use njord::sqlite::{self, SqliteError};
 use std::collections::HashMap;
-use std::path::Path;
 
 #[test]
 fn delete() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path);
+    let conn = open_users_table();
 
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address 1".to_string()),
+        Value::Text("Some Random Address 1".to_string()),
     );
 
     let mut order = HashMap::new();
@@ -22,6 +19,15 @@ fn delete() {
 
     match conn {
         Ok(ref c) => {
+            seed_users(
+                c,
+                &[
+                    ("mjovanc", "mjovanc@icloud.com", "Some Random Address 1"),
+                    ("alice", "alice@example.com", "Some Random Address 2"),
+                    ("bob", "bob@example.com", "Some Random Address 1"),
+                ],
+            );
+
             let result = sqlite::delete()
                 .from(User::default())
                 .where_clause(condition)
@@ -30,7 +36,8 @@ fn delete() {
                 .offset(0)
                 .build(c);
             println!("{:?}", result);
-            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), 2);
+            assert_eq!(usernames(c), ["alice"]);
         }
         Err(e) => {
             panic!("Failed to DELETE: {:?}", e);
@@ -40,21 +47,26 @@ fn delete() {
 
 #[test]
 fn delete_with_subquery() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path);
+    let conn = open_users_table();
 
     let mut order = HashMap::new();
     order.insert(vec!["id".to_string()], "DESC".to_string());
 
     match conn {
         Ok(ref c) => {
+            seed_users(
+                c,
+                &[
+                    ("mjovanc", "mjovanc@icloud.com", "Some Random Address 1"),
+                    ("alice", "alice@example.com", "mjovanc"),
+                    ("bob", "bob@example.com", "Some Random Address 1"),
+                ],
+            );
+
             let sub_query =
                 SelectQueryBuilder::<User>::new(vec![Column::Text("username".to_string())])
-                    .where_clause(Condition::Eq(
-                        "id".to_string(),
-                        Value::Literal(1.to_string()),
-                    ))
+                    .from(User::default())
+                    .where_clause(Condition::Eq("id".to_string(), Value::Int(1)))
                     .limit(1);
 
             let condition =
@@ -65,10 +77,102 @@ fn delete_with_subquery() {
                 .where_clause(condition)
                 .build(c);
             println!("{:?}", result);
-            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), 1);
+            assert_eq!(usernames(c), ["mjovanc", "bob"]);
         }
         Err(e) => {
             panic!("Failed to DELETE: {:?}", e);
         }
     }
 }
+
+#[test]
+fn delete_returns_affected_rows() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('alice', 'alice@example.com', 'Some Random Address 1'),
+            ('bob', 'bob@example.com', 'Some Random Address 1');",
+    )
+    .unwrap();
+
+    let result = sqlite::delete()
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("nobody"))
+        .expect_rows(1)
+        .build(&conn);
+    assert!(matches!(
+        result,
+        Err(SqliteError::UnexpectedRowCount {
+            expected: 1,
+            actual: 0
+        })
+    ));
+
+    // Both rows are deleted before the check fails, and restored
+    let result = sqlite::delete()
+        .from(User::default())
+        .expect_rows(1)
+        .build(&conn);
+    assert!(matches!(
+        result,
+        Err(SqliteError::UnexpectedRowCount {
+            expected: 1,
+            actual: 2
+        })
+    ));
+
+    let rows = sqlite::delete().from(User::default()).build(&conn);
+    assert_eq!(rows.unwrap(), 2);
+}
+
+#[test]
+fn delete_expect_rows_mismatch_in_transaction() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('alice', 'alice@example.com', 'Some Random Address 1'),
+            ('bob', 'bob@example.com', 'Some Random Address 1');",
+    )
+    .unwrap();
+
+    // The failed check only undoes its own delete, not the rest of the transaction
+    let result = sqlite::transaction(&conn, |tx| {
+        sqlite::delete()
+            .from(User::default())
+            .where_clause(User::USERNAME.eq("bob"))
+            .build(tx)?;
+
+        let result = sqlite::delete()
+            .from(User::default())
+            .expect_rows(2)
+            .build(tx);
+        assert!(matches!(
+            result,
+            Err(SqliteError::UnexpectedRowCount {
+                expected: 2,
+                actual: 1
+            })
+        ));
+
+        Ok::<_, SqliteError>(())
+    });
+    assert!(result.is_ok());
+
+    let rows = sqlite::delete().from(User::default()).build(&conn);
+    assert_eq!(rows.unwrap(), 1);
+}
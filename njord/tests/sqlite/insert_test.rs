@@ -1,16 +1,16 @@
-use super::User;
+use super::{open_users_table, User};
+use njord::builder::BuildError;
 use njord::column::Column;
 use njord::condition::{Condition, Value};
 use njord::keys::AutoIncrementPrimaryKey;
-use njord::sqlite;
-use std::path::Path;
+use njord::sqlite::{self, SqliteError};
+use njord::table::Table;
+use njord_derive::Table;
 use std::vec;
 
 #[test]
 fn insert_row() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let mut conn = sqlite::open(db_path);
+    let mut conn = open_users_table();
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::default(),
@@ -32,23 +32,27 @@ fn insert_row() {
 
 #[test]
 fn insert_with_sub_query() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let mut conn = sqlite::open(db_path);
+    let mut conn = open_users_table();
 
     match conn {
         Ok(ref mut c) => {
-            let subquery = sqlite::select(
-                vec![
-                    Column::Text("username".to_string()),
-                    Column::Text("email".to_string()),
-                    Column::Text("address".to_string()),
-                ],
-            )
+            let table_row: User = User {
+                id: AutoIncrementPrimaryKey::default(),
+                username: "mjovanc".to_string(),
+                email: "mjovanc@icloud.com".to_string(),
+                address: "Some Random Address 1".to_string(),
+            };
+            sqlite::insert(c, vec![table_row]).unwrap();
+
+            let subquery = sqlite::select(vec![
+                Column::Text("username".to_string()),
+                Column::Text("email".to_string()),
+                Column::Text("address".to_string()),
+            ])
             .from(User::default())
             .where_clause(Condition::Eq(
                 "username".to_string(),
-                Value::Literal("mjovanc".to_string()),
+                Value::Text("mjovanc".to_string()),
             ));
 
             let result = sqlite::insert::into::<User>(
@@ -60,7 +64,7 @@ fn insert_with_sub_query() {
                 ],
                 Box::new(subquery),
             );
-            assert!(result.is_ok());
+            assert_eq!(result.ok(), Some(1));
         }
         Err(e) => {
             panic!("Failed to INSERT: {:?}", e);
@@ -70,9 +74,7 @@ fn insert_with_sub_query() {
 
 #[test]
 fn insert_row_with_single_quotes() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let mut conn = sqlite::open(db_path);
+    let mut conn = open_users_table();
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::default(),
@@ -91,3 +93,193 @@ fn insert_row_with_single_quotes() {
         }
     }
 }
+
+#[test]
+fn insert_rows_returns_generated_keys() {
+    let conn = open_users_table().unwrap();
+
+    let table_rows: Vec<User> = ["key_user_1", "key_user_2"]
+        .iter()
+        .map(|username| User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            address: "Some Random Address 1".to_string(),
+        })
+        .collect();
+
+    let inserted = sqlite::insert(&conn, table_rows).unwrap();
+
+    assert_eq!(inserted.len(), 2);
+    assert_eq!(inserted[0].username, "key_user_1");
+
+    let first_id = *inserted[0].id.get().expect("generated key for first row");
+    let second_id = *inserted[1].id.get().expect("generated key for second row");
+    assert!(second_id > first_id);
+}
+
+#[test]
+fn insert_no_rows() {
+    let conn = open_users_table().unwrap();
+
+    let inserted = sqlite::insert(&conn, Vec::<User>::new()).unwrap();
+
+    assert!(inserted.is_empty());
+    assert_eq!(count_users(&conn), 0);
+}
+
+#[test]
+fn insert_rows_with_and_without_key_is_rejected() {
+    let conn = open_with_unique_users();
+
+    let mut with_key = generated_user(1);
+    with_key.id = AutoIncrementPrimaryKey::new(Some(10));
+    let table_rows = vec![generated_user(0), with_key];
+
+    let result = sqlite::insert(&conn, table_rows);
+
+    assert!(matches!(
+        result,
+        Err(SqliteError::BuildError(BuildError::MixedColumns { row: 1 }))
+    ));
+}
+
+fn open_with_unique_users() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL UNIQUE,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn generated_user(index: usize) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: format!("user{}", index),
+        email: format!("user{}@example.com", index),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+fn count_users(conn: &rusqlite::Connection) -> usize {
+    sqlite::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(conn)
+        .unwrap()
+        .len()
+}
+
+#[test]
+fn bulk_insert() {
+    let conn = open_with_unique_users();
+
+    let mut reported = 0;
+    let progress = sqlite::bulk_insert((0..25_000).map(generated_user))
+        .on_progress(|progress| reported = progress.rows_inserted)
+        .build(&conn)
+        .unwrap();
+
+    // Three columns are bound per row, 32766 / 3 rows fit in a statement
+    assert_eq!(progress.rows_inserted, 25_000);
+    assert_eq!(progress.chunks, 3);
+    assert_eq!(reported, 25_000);
+    assert_eq!(count_users(&conn), 25_000);
+}
+
+#[test]
+fn bulk_insert_rolls_back_on_error() {
+    let conn = open_with_unique_users();
+
+    // The last chunk repeats a username of the first one
+    let rows = (0..10).map(generated_user).chain([generated_user(0)]);
+    let result = sqlite::bulk_insert(rows).chunk_size(4).build(&conn);
+
+    assert!(result.is_err());
+    assert_eq!(count_users(&conn), 0);
+}
+
+#[test]
+fn bulk_insert_in_transaction() {
+    let conn = open_with_unique_users();
+
+    let result = sqlite::transaction(&conn, |tx| {
+        sqlite::bulk_insert((0..10).map(generated_user))
+            .chunk_size(4)
+            .build(tx)?;
+
+        // A failing bulk insert only undoes its own rows
+        let failed = sqlite::bulk_insert([generated_user(10), generated_user(0)]).build(tx);
+        assert!(failed.is_err());
+        assert_eq!(count_users(tx), 10);
+
+        Err::<(), _>(SqliteError::UnexpectedRowCount {
+            expected: 0,
+            actual: 10,
+        })
+    });
+
+    // Rolling the transaction back undoes the bulk insert as well
+    assert!(result.is_err());
+    assert_eq!(count_users(&conn), 0);
+}
+
+#[derive(Table, Debug)]
+#[table_name = "accounts"]
+struct Account {
+    id: AutoIncrementPrimaryKey<i64>,
+    #[column(name = "user_name")]
+    name: String,
+    #[default]
+    plan: String,
+    #[column(generated)]
+    name_length: i64,
+    #[column(read_only)]
+    created_at: String,
+    #[skip]
+    session: Option<String>,
+}
+
+#[test]
+fn insert_respects_column_attributes() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_name TEXT NOT NULL,
+            plan TEXT NOT NULL DEFAULT 'free',
+            name_length INTEGER GENERATED ALWAYS AS (length(user_name)),
+            created_at TEXT NOT NULL DEFAULT '2024-01-01'
+        )",
+    )
+    .unwrap();
+
+    let account = Account {
+        name: "mjovanc".to_string(),
+        plan: "ignored".to_string(),
+        session: Some("token".to_string()),
+        ..Default::default()
+    };
+    sqlite::insert(&conn, vec![account]).unwrap();
+
+    let accounts = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Account::default())
+        .where_clause(Account::NAME.eq("mjovanc"))
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id.get(), Some(&1));
+    assert_eq!(accounts[0].plan, "free");
+    assert_eq!(accounts[0].name_length, 7);
+    assert_eq!(accounts[0].created_at, "2024-01-01");
+    assert_eq!(accounts[0].session, None);
+}
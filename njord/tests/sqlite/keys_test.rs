@@ -0,0 +1,117 @@
+use njord::builder::BuildError;
+use njord::column::Column;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Debug, Clone, PartialEq)]
+#[table_name = "memberships"]
+struct Membership {
+    #[primary_key]
+    user_id: i64,
+    #[primary_key]
+    group_id: i64,
+    role: String,
+}
+
+fn membership(user_id: i64, group_id: i64, role: &str) -> Membership {
+    Membership {
+        user_id,
+        group_id,
+        role: role.to_string(),
+    }
+}
+
+fn open_memberships_db() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE memberships (
+            user_id INTEGER NOT NULL,
+            group_id INTEGER NOT NULL,
+            role TEXT NOT NULL,
+            PRIMARY KEY (user_id, group_id)
+        )",
+    )
+    .unwrap();
+    sqlite::insert(
+        &conn,
+        vec![
+            membership(1, 1, "owner"),
+            membership(1, 2, "member"),
+            membership(2, 1, "member"),
+        ],
+    )
+    .unwrap();
+
+    conn
+}
+
+fn find(conn: &rusqlite::Connection, user_id: i64, group_id: i64) -> Vec<Membership> {
+    sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Membership::default())
+        .find((user_id, group_id))
+        .unwrap()
+        .build(conn)
+        .unwrap()
+}
+
+#[test]
+fn find_by_composite_key() {
+    let conn = open_memberships_db();
+
+    assert_eq!(find(&conn, 1, 2), vec![membership(1, 2, "member")]);
+    assert_eq!(find(&conn, 2, 2), vec![]);
+}
+
+#[test]
+fn update_and_delete_by_composite_key() {
+    let conn = open_memberships_db();
+
+    let updated = sqlite::update(membership(2, 1, "admin"))
+        .set([Membership::ROLE])
+        .by_key()
+        .unwrap()
+        .build(&conn)
+        .unwrap();
+    assert_eq!(updated, 1);
+    assert_eq!(find(&conn, 2, 1), vec![membership(2, 1, "admin")]);
+    assert_eq!(find(&conn, 1, 1), vec![membership(1, 1, "owner")]);
+
+    let deleted = sqlite::delete()
+        .from(membership(1, 2, "member"))
+        .by_key()
+        .unwrap()
+        .build(&conn)
+        .unwrap();
+    assert_eq!(deleted, 1);
+    assert_eq!(find(&conn, 1, 2), vec![]);
+}
+
+#[test]
+fn upsert_on_composite_key() {
+    let conn = open_memberships_db();
+
+    sqlite::upsert(vec![membership(1, 1, "member"), membership(3, 1, "owner")])
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(find(&conn, 1, 1), vec![membership(1, 1, "member")]);
+    assert_eq!(find(&conn, 3, 1), vec![membership(3, 1, "owner")]);
+}
+
+#[test]
+fn find_with_wrong_number_of_key_values() {
+    let error = sqlite::select::<Membership>(vec![Column::Text("*".to_string())])
+        .from(Membership::default())
+        .find(1)
+        .err();
+
+    assert_eq!(
+        error,
+        Some(BuildError::KeyValueCount {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
@@ -1,12 +1,20 @@
 mod delete_test;
 mod insert_test;
+mod keys_test;
 mod open_test;
 mod raw_test;
+mod relations_test;
+mod schema_test;
 mod select_joins_test;
 mod select_test;
+mod transaction_test;
+mod types_test;
 mod update_test;
+mod upsert_test;
 
+use njord::column::Column;
 use njord::keys::{AutoIncrementPrimaryKey, PrimaryKey};
+use njord::sqlite;
 use njord::table::Table;
 use njord_derive::Table;
 
@@ -44,7 +52,8 @@ pub struct Product {
     pub description: String,
     pub price: f64,
     pub stock_quantity: usize,
-    pub category: Category, // one-to-one relationship
+    // pub category: Category, // one-to-one relationship
+    pub category_id: usize,
     pub discount: f64,
 }
 
@@ -55,3 +64,42 @@ pub struct UsersWithJoin {
     price: f64,
     name: String,
 }
+
+/// Opens an in-memory database with an empty `users` table.
+pub fn open_users_table() -> Result<rusqlite::Connection, rusqlite::Error> {
+    let conn = sqlite::open_in_memory()?;
+    conn.execute_batch(
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )?;
+
+    Ok(conn)
+}
+
+/// Inserts users given by their username, email and address into the `users` table.
+pub fn seed_users(conn: &rusqlite::Connection, users: &[(&str, &str, &str)]) {
+    let rows = users
+        .iter()
+        .map(|(username, email, address)| User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: email.to_string(),
+            address: address.to_string(),
+        })
+        .collect();
+
+    sqlite::insert(conn, rows).unwrap();
+}
+
+/// Returns the usernames in the `users` table, ordered by id.
+pub fn usernames(conn: &rusqlite::Connection) -> Vec<String> {
+    sqlite::select(vec![Column::Text("username".to_string())])
+        .from(User::default())
+        .order_by([User::ID.asc()])
+        .build_as::<String>(conn)
+        .unwrap()
+}
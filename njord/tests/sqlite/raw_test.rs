@@ -1,13 +1,11 @@
-use std::path::Path;
+use super::open_users_table;
 
 use njord::sqlite;
 use njord_derive::sql;
 
 #[test]
 fn execute_raw_sql() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path).unwrap();
+    let conn = open_users_table().unwrap();
 
     let sql = sql! {
         INSERT INTO users (username, email, address)
@@ -20,5 +18,5 @@ fn execute_raw_sql() {
 
     let results = sqlite::raw_execute(&conn, &sql);
 
-    assert!(!results.is_err());
+    assert!(results.is_ok());
 }
@@ -0,0 +1,125 @@
+use njord::column::Column;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Debug, Clone, PartialEq)]
+#[table_name = "authors"]
+#[has_many(Article)]
+struct Author {
+    id: AutoIncrementPrimaryKey<i64>,
+    name: String,
+}
+
+#[derive(Table, Debug, Clone, PartialEq)]
+#[table_name = "articles"]
+#[belongs_to(Author)]
+#[many_to_many(Tag, through = "article_tags")]
+struct Article {
+    id: AutoIncrementPrimaryKey<i64>,
+    author_id: i64,
+    title: String,
+}
+
+#[derive(Table, Debug, Clone, PartialEq)]
+#[table_name = "tags"]
+struct Tag {
+    id: AutoIncrementPrimaryKey<i64>,
+    label: String,
+}
+
+fn open_blog_db() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE authors (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+         CREATE TABLE articles (
+            id INTEGER PRIMARY KEY,
+            author_id INTEGER NOT NULL REFERENCES authors (id),
+            title TEXT NOT NULL
+         );
+         CREATE TABLE tags (id INTEGER PRIMARY KEY, label TEXT NOT NULL);
+         CREATE TABLE article_tags (
+            article_id INTEGER NOT NULL REFERENCES articles (id),
+            tag_id INTEGER NOT NULL REFERENCES tags (id),
+            PRIMARY KEY (article_id, tag_id)
+         );
+         INSERT INTO authors (id, name) VALUES (1, 'anna'), (2, 'bert'), (3, 'cleo');
+         INSERT INTO articles (id, author_id, title)
+            VALUES (1, 1, 'first'), (2, 2, 'second'), (3, 1, 'third');
+         INSERT INTO tags (id, label) VALUES (1, 'rust'), (2, 'sql');
+         INSERT INTO article_tags (article_id, tag_id) VALUES (1, 1), (1, 2), (3, 2);",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn titles(articles: &[Article]) -> Vec<&str> {
+    articles
+        .iter()
+        .map(|article| article.title.as_str())
+        .collect()
+}
+
+#[test]
+fn has_many_groups_rows_by_parent() {
+    let conn = open_blog_db();
+
+    let authors = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Author::default())
+        .order_by([Author::ID.asc()])
+        .with(Article::default())
+        .build(&conn)
+        .unwrap();
+
+    let grouped: Vec<(&str, Vec<&str>)> = authors
+        .iter()
+        .map(|(author, articles)| (author.name.as_str(), titles(articles)))
+        .collect();
+    assert_eq!(
+        grouped,
+        vec![
+            ("anna", vec!["first", "third"]),
+            ("bert", vec!["second"]),
+            ("cleo", vec![]),
+        ]
+    );
+}
+
+#[test]
+fn belongs_to_loads_the_referenced_row() {
+    let conn = open_blog_db();
+
+    let articles = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Article::default())
+        .where_clause(Article::TITLE.ne("second"))
+        .with(Author::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(articles.len(), 2);
+    for (_, authors) in &articles {
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "anna");
+    }
+}
+
+#[test]
+fn many_to_many_loads_through_join_table() {
+    let conn = open_blog_db();
+
+    let articles = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Article::default())
+        .order_by([Article::ID.asc()])
+        .with(Tag::default())
+        .build(&conn)
+        .unwrap();
+
+    let labels: Vec<Vec<&str>> = articles
+        .iter()
+        .map(|(_, tags)| tags.iter().map(|tag| tag.label.as_str()).collect())
+        .collect();
+    assert_eq!(labels, vec![vec!["rust", "sql"], vec![], vec!["sql"]]);
+}
@@ -0,0 +1,185 @@
+use njord::column::Column;
+use njord::dialect::{DataType, SqliteDialect};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::schema::{
+    self, ColumnDefinition, CreateTableBuilder, DropTableBuilder, TableSchema, UnknownTables,
+};
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Debug, PartialEq)]
+#[table_name = "notes"]
+struct Note {
+    id: AutoIncrementPrimaryKey<i64>,
+    #[column(length = 80)]
+    title: String,
+    body: Option<String>,
+    #[default = "'draft'"]
+    state: String,
+}
+
+#[test]
+fn create_table_from_model() {
+    let conn = sqlite::open_in_memory().unwrap();
+    for statement in CreateTableBuilder::<SqliteDialect>::from_table(&Note::default()).render() {
+        sqlite::raw_execute(&conn, &statement).unwrap();
+    }
+
+    let note = Note {
+        id: AutoIncrementPrimaryKey::default(),
+        title: "groceries".to_string(),
+        body: None,
+        state: String::new(),
+    };
+    sqlite::insert(&conn, vec![note]).unwrap();
+
+    let notes = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Note::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(
+        notes,
+        vec![Note {
+            id: AutoIncrementPrimaryKey::new(Some(1)),
+            title: "groceries".to_string(),
+            body: None,
+            state: "draft".to_string(),
+        }]
+    );
+
+    let missing_title = sqlite::raw_execute(&conn, "INSERT INTO notes (body) VALUES ('x')");
+    assert!(missing_title.is_err());
+
+    for statement in DropTableBuilder::<SqliteDialect>::from_table(&Note::default())
+        .if_exists()
+        .render()
+    {
+        sqlite::raw_execute(&conn, &statement).unwrap();
+    }
+    assert!(sqlite::raw_execute(&conn, "SELECT * FROM notes").is_err());
+}
+
+#[test]
+fn alter_table_and_indexes() {
+    let conn = sqlite::open_in_memory().unwrap();
+    for statement in CreateTableBuilder::<SqliteDialect>::from_table(&Note::default()).render() {
+        sqlite::raw_execute(&conn, &statement).unwrap();
+    }
+    sqlite::raw_execute(&conn, "INSERT INTO notes (title) VALUES ('groceries')").unwrap();
+
+    let alter = schema::alter_table::<SqliteDialect>("notes")
+        .add_column(ColumnDefinition::new("pinned", DataType::Boolean).default("0"))
+        .rename_column("body", "text")
+        .render()
+        .unwrap();
+    for statement in alter {
+        sqlite::raw_execute(&conn, &statement).unwrap();
+    }
+
+    let index = schema::create_index::<SqliteDialect>("notes_pinned_title", "notes")
+        .columns(["title"])
+        .unique()
+        .where_clause("pinned = 1")
+        .render()
+        .unwrap();
+    sqlite::raw_execute(&conn, &index).unwrap();
+
+    // Only pinned notes need a unique title
+    sqlite::raw_execute(&conn, "INSERT INTO notes (title) VALUES ('groceries')").unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO notes (title, text, pinned) VALUES ('todo', 'a', 1)",
+    )
+    .unwrap();
+    let duplicate = sqlite::raw_execute(
+        &conn,
+        "INSERT INTO notes (title, text, pinned) VALUES ('todo', 'b', 1)",
+    );
+    assert!(duplicate.is_err());
+
+    let drop_index = schema::drop_index::<SqliteDialect>("notes_pinned_title", "notes").render();
+    sqlite::raw_execute(&conn, &drop_index).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO notes (title, text, pinned) VALUES ('todo', 'b', 1)",
+    )
+    .unwrap();
+
+    let drop_column = schema::alter_table::<SqliteDialect>("notes")
+        .drop_column("pinned")
+        .render()
+        .unwrap();
+    for statement in drop_column {
+        sqlite::raw_execute(&conn, &statement).unwrap();
+    }
+    assert!(sqlite::raw_execute(&conn, "SELECT pinned FROM notes").is_err());
+    sqlite::raw_execute(&conn, "SELECT text FROM notes").unwrap();
+}
+
+// Only the schema of the model is used
+#[allow(dead_code)]
+#[derive(Table)]
+#[table_name = "notes"]
+struct NoteWithTags {
+    id: AutoIncrementPrimaryKey<i64>,
+    #[column(length = 80)]
+    title: String,
+    #[default = "'draft'"]
+    state: String,
+    tags: Option<String>,
+}
+
+#[test]
+fn diff_models_against_database() {
+    let conn = sqlite::open_in_memory().unwrap();
+    for statement in CreateTableBuilder::<SqliteDialect>::from_table(&Note::default()).render() {
+        sqlite::raw_execute(&conn, &statement).unwrap();
+    }
+    sqlite::raw_execute(&conn, "INSERT INTO notes (title) VALUES ('groceries')").unwrap();
+
+    let current = sqlite::introspect(&conn).unwrap();
+    let unchanged = schema::diff(
+        SqliteDialect,
+        &current,
+        &[TableSchema::from_table(&Note::default())],
+        UnknownTables::Keep,
+    );
+    assert!(unchanged.is_empty());
+
+    let migration = schema::diff(
+        SqliteDialect,
+        &current,
+        &[TableSchema::from_table(&NoteWithTags::default())],
+        UnknownTables::Keep,
+    );
+    assert_eq!(
+        migration.up,
+        vec![
+            "ALTER TABLE notes ADD COLUMN tags TEXT",
+            "ALTER TABLE notes DROP COLUMN body",
+        ]
+    );
+    assert_eq!(
+        migration.warnings,
+        vec!["dropping column notes.body deletes its values"]
+    );
+
+    sqlite::raw_execute(&conn, &migration.up.join(";\n")).unwrap();
+    let migrated = schema::diff(
+        SqliteDialect,
+        &sqlite::introspect(&conn).unwrap(),
+        &[TableSchema::from_table(&NoteWithTags::default())],
+        UnknownTables::Keep,
+    );
+    assert!(migrated.is_empty());
+
+    sqlite::raw_execute(&conn, &migration.down.join(";\n")).unwrap();
+    let reverted = schema::diff(
+        SqliteDialect,
+        &sqlite::introspect(&conn).unwrap(),
+        &[TableSchema::from_table(&Note::default())],
+        UnknownTables::Keep,
+    );
+    assert!(reverted.is_empty());
+}
@@ -1,11 +1,15 @@
 use njord::condition::Condition;
+use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite;
+use njord::table::Table;
 use njord::util::JoinType;
 use njord::{column::Column, condition::Value};
 use std::path::Path;
 use std::sync::Arc;
 
-use crate::{Product, UsersWithJoin};
+use njord_derive::Table;
+
+use crate::{Product, User, UsersWithJoin};
 
 #[test]
 fn select_inner_join() {
@@ -23,7 +27,7 @@ fn select_inner_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "users.id".to_string(),
-        Value::Literal("products.user_id".to_string()),
+        Value::Column("products.user_id".to_string()),
     );
     match conn {
         Ok(ref c) => {
@@ -64,14 +68,15 @@ fn select_left_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "users.id".to_string(),
-        Value::Literal("products.user_id".to_string()),
+        Value::Column("products.user_id".to_string()),
     );
     match conn {
         Ok(ref c) => {
+            // Users without products have NULL product columns
             let result = sqlite::select(columns)
                 .from(UsersWithJoin::default())
                 .join(JoinType::Left, Arc::new(Product::default()), join_condition)
-                .build(c);
+                .build_as::<(String, Option<String>, Option<f64>)>(c);
             match result {
                 Ok(r) => {
                     // Check the number of results and assert against expected values
@@ -85,3 +90,62 @@ fn select_left_join() {
         Err(e) => panic!("Failed to SELECT: {:?}", e),
     }
 }
+
+#[derive(Table, Debug)]
+#[table_name = "addresses"]
+struct Address {
+    id: AutoIncrementPrimaryKey<usize>,
+    user_id: usize,
+    city: String,
+}
+
+#[test]
+fn select_left_join_as_tuple() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        CREATE TABLE addresses (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            city TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address)
+        VALUES ('anna', 'anna@example.com', 'Street 1'), ('bob', 'bob@example.com', 'Street 2');
+        INSERT INTO addresses (user_id, city) VALUES (1, 'Gothenburg');",
+    )
+    .unwrap();
+
+    let join_condition = Condition::Eq(
+        "users.id".to_string(),
+        Value::Column("addresses.user_id".to_string()),
+    );
+
+    let mut rows = sqlite::select(vec![
+        Column::Text("users.*".to_string()),
+        Column::Text("addresses.*".to_string()),
+    ])
+    .from(User::default())
+    .join(JoinType::Left, Arc::new(Address::default()), join_condition)
+    .build_as::<(User, Option<Address>)>(&conn)
+    .unwrap();
+    rows.sort_by(|(a, _), (b, _)| a.username.cmp(&b.username));
+
+    assert_eq!(rows.len(), 2);
+
+    let (anna, address) = &rows[0];
+    assert_eq!(anna.username, "anna");
+    assert_eq!(anna.id.get(), Some(&1));
+    let address = address.as_ref().expect("anna has an address");
+    assert_eq!(address.city, "Gothenburg");
+    assert_eq!(address.user_id, 1);
+
+    let (bob, address) = &rows[1];
+    assert_eq!(bob.username, "bob");
+    assert!(address.is_none());
+}
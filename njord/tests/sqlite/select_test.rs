@@ -2,11 +2,11 @@ use njord::condition::Condition;
 use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite;
 use njord::{column::Column, condition::Value};
-use njord_derive::sql;
+use njord_derive::{sql, FromRow};
 use std::collections::HashMap;
 use std::path::Path;
 
-use crate::{User, UserWithSubQuery};
+use crate::{open_users_table, seed_users, usernames, User, UserWithSubQuery};
 
 #[test]
 fn open_db() {
@@ -19,9 +19,7 @@ fn open_db() {
 
 #[test]
 fn insert_row() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let mut conn = sqlite::open(db_path);
+    let mut conn = open_users_table();
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::default(),
@@ -43,20 +41,15 @@ fn insert_row() {
 
 #[test]
 fn update() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path);
+    let conn = open_users_table();
 
     let columns = vec!["username".to_string()];
 
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::<usize>::new(Some(0)),
-        username: "mjovanc".to_string(),
+        username: "marcus".to_string(),
         email: "mjovanc@icloud.com".to_string(),
         address: "Some Random Address 1".to_string(),
     };
@@ -66,6 +59,15 @@ fn update() {
 
     match conn {
         Ok(ref c) => {
+            seed_users(
+                c,
+                &[
+                    ("mjovanc", "mjovanc@icloud.com", "Some Random Address 1"),
+                    ("alice", "alice@example.com", "Some Random Address 1"),
+                    ("mjovanc", "mjovanc@example.com", "Some Random Address 2"),
+                ],
+            );
+
             let result = sqlite::update(table_row)
                 .set(columns)
                 .where_clause(condition)
@@ -74,7 +76,8 @@ fn update() {
                 .offset(0)
                 .build(c);
             println!("{:?}", result);
-            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), 2);
+            assert_eq!(usernames(c), ["marcus", "alice", "marcus"]);
         }
         Err(e) => {
             panic!("Failed to UPDATE: {:?}", e);
@@ -84,13 +87,11 @@ fn update() {
 
 #[test]
 fn delete() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path);
+    let conn = open_users_table();
 
     let condition = Condition::Eq(
         "address".to_string(),
-        Value::Literal("Some Random Address 1".to_string()),
+        Value::Text("Some Random Address 1".to_string()),
     );
 
     let mut order = HashMap::new();
@@ -98,6 +99,15 @@ fn delete() {
 
     match conn {
         Ok(ref c) => {
+            seed_users(
+                c,
+                &[
+                    ("mjovanc", "mjovanc@icloud.com", "Some Random Address 1"),
+                    ("alice", "alice@example.com", "Some Random Address 2"),
+                    ("bob", "bob@example.com", "Some Random Address 1"),
+                ],
+            );
+
             let result = sqlite::delete()
                 .from(User::default())
                 .where_clause(condition)
@@ -106,7 +116,8 @@ fn delete() {
                 .offset(0)
                 .build(c);
             println!("{:?}", result);
-            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), 2);
+            assert_eq!(usernames(c), ["alice"]);
         }
         Err(e) => {
             panic!("Failed to DELETE: {:?}", e);
@@ -126,10 +137,7 @@ fn select() {
         Column::Text("email".to_string()),
         Column::Text("address".to_string()),
     ];
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
 
     match conn {
         Ok(ref c) => {
@@ -159,10 +167,7 @@ fn select_distinct() {
         Column::Text("email".to_string()),
         Column::Text("address".to_string()),
     ];
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
 
     match conn {
         Ok(ref c) => {
@@ -197,10 +202,7 @@ fn select_group_by() {
         Column::Text("email".to_string()),
         Column::Text("address".to_string()),
     ];
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
     let group_by = vec!["username".to_string(), "email".to_string()];
 
     match conn {
@@ -232,10 +234,7 @@ fn select_order_by() {
         Column::Text("email".to_string()),
         Column::Text("address".to_string()),
     ];
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
     let group_by = vec!["username".to_string(), "email".to_string()];
 
     let mut order_by = HashMap::new();
@@ -271,10 +270,7 @@ fn select_limit_offset() {
         Column::Text("email".to_string()),
         Column::Text("address".to_string()),
     ];
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
     let group_by = vec!["username".to_string(), "email".to_string()];
 
     let mut order_by = HashMap::new();
@@ -312,16 +308,13 @@ fn select_having() {
         Column::Text("email".to_string()),
         Column::Text("address".to_string()),
     ];
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
     let group_by = vec!["username".to_string(), "email".to_string()];
 
     let mut order_by = HashMap::new();
     order_by.insert(vec!["email".to_string()], "DESC".to_string());
 
-    let having_condition = Condition::Gt("id".to_string(), Value::Literal("1".to_string()));
+    let having_condition = Condition::Gt("id".to_string(), Value::Int(1));
 
     match conn {
         Ok(ref c) => {
@@ -355,17 +348,11 @@ fn select_except() {
         Column::Text("address".to_string()),
     ];
 
-    let condition1 = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
-    let condition2 = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("otheruser".to_string()),
-    );
+    let condition1 = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
+    let condition2 = Condition::Eq("username".to_string(), Value::Text("otheruser".to_string()));
     let condition3 = Condition::Eq(
         "username".to_string(),
-        Value::Literal("anotheruser".to_string()),
+        Value::Text("anotheruser".to_string()),
     );
 
     match conn {
@@ -410,8 +397,8 @@ fn select_union() {
         Column::Text("address".to_string()),
     ];
 
-    let condition1 = Condition::Eq("id".to_string(), Value::Literal(42.to_string()));
-    let condition2 = Condition::Eq("id".to_string(), Value::Literal(43.to_string()));
+    let condition1 = Condition::Eq("id".to_string(), Value::Int(42));
+    let condition2 = Condition::Eq("id".to_string(), Value::Int(43));
 
     match conn {
         Ok(ref c) => {
@@ -501,13 +488,13 @@ fn select_in() {
         Box::new(Condition::In(
             "username".to_string(),
             vec![
-                Value::Literal("mjovanc".to_string()),
-                Value::Literal("otheruser".to_string()),
+                Value::Text("mjovanc".to_string()),
+                Value::Text("otheruser".to_string()),
             ],
         )),
         Box::new(Condition::NotIn(
             "username".to_string(),
-            vec![Value::Literal("chasewillden".to_string())],
+            vec![Value::Text("chasewillden".to_string())],
         )),
     );
 
@@ -527,6 +514,197 @@ fn select_in() {
     };
 }
 
+#[test]
+fn select_typed_values() {
+    let db_path = Path::new(":memory:");
+    let conn = sqlite::open(db_path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE users (id INTEGER primary key, username TEXT not null, email TEXT not null, address TEXT not null);",
+    )
+    .unwrap();
+
+    let rows = ["007", "7"].map(|username| User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        address: "0123 Zero Padded Street".to_string(),
+    });
+    assert!(sqlite::insert(&conn, rows.into()).is_ok());
+
+    let columns = vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+        Column::Text("address".to_string()),
+    ];
+
+    let condition = Condition::And(
+        Box::new(Condition::Eq("username".to_string(), "007".into())),
+        Box::new(Condition::Ge("id".to_string(), Value::Int(1))),
+    );
+
+    let result = sqlite::select(columns)
+        .from(User::default())
+        .where_clause(condition)
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "007");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
+#[test]
+fn select_predicates() {
+    let db_path = Path::new(":memory:");
+    let conn = sqlite::open(db_path).unwrap();
+    conn.execute_batch(
+        "CREATE TABLE users (id INTEGER primary key, username TEXT not null, email TEXT not null, address TEXT not null);",
+    )
+    .unwrap();
+
+    let rows = vec![
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "select_predicates_test".to_string(),
+            email: "select_predicates_test@example.com".to_string(),
+            address: "Some Random Address 1".to_string(),
+        },
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "select_predicates_test2".to_string(),
+            email: "SELECT_PREDICATES_TEST2@EXAMPLE.COM".to_string(),
+            address: "Some Random Address 1".to_string(),
+        },
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "select_predicates_100%".to_string(),
+            email: "select_predicates_100@example.com".to_string(),
+            address: "Some Random Address 1".to_string(),
+        },
+    ];
+    assert!(sqlite::insert(&conn, rows).is_ok());
+
+    let count = |condition: Condition| {
+        let columns = vec![
+            Column::Text("id".to_string()),
+            Column::Text("username".to_string()),
+            Column::Text("email".to_string()),
+            Column::Text("address".to_string()),
+        ];
+        let result = sqlite::select(columns)
+            .from(User::default())
+            .where_clause(condition)
+            .build(&conn);
+
+        match result {
+            Ok(r) => r.len(),
+            Err(e) => panic!("Failed to SELECT: {:?}", e),
+        }
+    };
+    let prefix = || Condition::Like("username".to_string(), "select_predicates%".into(), None);
+
+    assert_eq!(
+        count(Condition::Like(
+            "username".to_string(),
+            "select!_predicates!_100!%".into(),
+            Some('!'),
+        )),
+        1
+    );
+    assert_eq!(
+        count(Condition::And(
+            Box::new(prefix()),
+            Box::new(Condition::NotLike(
+                "username".to_string(),
+                "select_predicates_test%".into(),
+                None,
+            )),
+        )),
+        1
+    );
+    assert_eq!(
+        count(Condition::ILike(
+            "email".to_string(),
+            "%@Example.Com".into(),
+            None,
+        )),
+        3
+    );
+    assert_eq!(
+        count(Condition::Between(
+            "username".to_string(),
+            "select_predicates_test".into(),
+            "select_predicates_test2".into(),
+        )),
+        2
+    );
+    assert_eq!(
+        count(Condition::And(
+            Box::new(Condition::IsNotNull("email".to_string())),
+            Box::new(Condition::Not(Box::new(Condition::IsNull(
+                "address".to_string()
+            )))),
+        )),
+        3
+    );
+
+    let sub_query = sqlite::select(vec![Column::Text("id".to_string())])
+        .from(User::default())
+        .where_clause(Condition::Eq(
+            "username".to_string(),
+            "select_predicates_test".into(),
+        ));
+    assert_eq!(
+        count(Condition::And(
+            Box::new(prefix()),
+            Box::new(Condition::Exists(Box::new(sub_query.clone()))),
+        )),
+        3
+    );
+    assert_eq!(count(Condition::NotExists(Box::new(sub_query))), 0);
+}
+
+#[test]
+fn select_typed_columns() {
+    let db_relative_path = "./db/select.db";
+    let db_path = Path::new(&db_relative_path);
+    let conn = sqlite::open(db_path);
+
+    let columns = vec![
+        User::ID.into(),
+        User::USERNAME.into(),
+        User::EMAIL.into(),
+        User::ADDRESS.into(),
+    ];
+    let condition = Condition::And(
+        Box::new(User::USERNAME.eq("mjovanc")),
+        Box::new(User::ID.in_([42, 43])),
+    );
+
+    match conn {
+        Ok(ref c) => {
+            let result = sqlite::select(columns)
+                .from(User::default())
+                .where_clause(condition)
+                .order_by([User::ID.desc()])
+                .build(c);
+
+            match result {
+                Ok(r) => {
+                    assert_eq!(r.len(), 2);
+                    assert_eq!(r[0].id.to_string(), "43");
+                }
+                Err(e) => panic!("Failed to SELECT: {:?}", e),
+            };
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    };
+}
+
 #[test]
 fn sql_bang() {
     let user_id = 1;
@@ -582,3 +760,222 @@ fn raw_execute() {
         Err(e) => panic!("Failed to SELECT: {:?}", e),
     };
 }
+
+#[test]
+fn select_iter() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )
+    .unwrap();
+
+    let rows = (0..100).map(|index| User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: format!("user{}", index),
+        email: format!("user{}@example.com", index),
+        address: "Some Random Address 1".to_string(),
+    });
+    sqlite::bulk_insert(rows).build(&conn).unwrap();
+
+    let iter = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .where_clause(User::USERNAME.ne("user0"))
+        .order_by([User::ID.asc()])
+        .build_iter(&conn)
+        .unwrap();
+
+    // Stops reading after the third row
+    let usernames: Vec<String> = iter.take(3).map(|user| user.unwrap().username).collect();
+
+    assert_eq!(usernames, vec!["user1", "user2", "user3"]);
+}
+
+#[test]
+fn select_iter_invalid_query() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let result = sqlite::select::<User>(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build_iter(&conn);
+
+    assert!(result.is_err());
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+struct UserContact {
+    email: String,
+    username: String,
+}
+
+fn open_users_db() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )
+    .unwrap();
+
+    let rows = ["anna", "bob", "carl"].map(|username| User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        address: "Some Random Address 1".to_string(),
+    });
+    sqlite::bulk_insert(rows).build(&conn).unwrap();
+
+    conn
+}
+
+#[test]
+fn select_as_tuple() {
+    let conn = open_users_db();
+
+    let rows = sqlite::select(vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+    ])
+    .from(User::default())
+    .order_by([User::ID.asc()])
+    .build_as::<(i64, String)>(&conn)
+    .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            (1, "anna".to_string()),
+            (2, "bob".to_string()),
+            (3, "carl".to_string())
+        ]
+    );
+}
+
+#[test]
+fn select_as_aggregate() {
+    let conn = open_users_db();
+
+    let total = sqlite::select(vec![Column::Text("COUNT(*) AS total".to_string())])
+        .from(User::default())
+        .build_as::<i64>(&conn)
+        .unwrap();
+
+    assert_eq!(total, vec![3]);
+}
+
+#[test]
+fn select_as_struct_by_column_name() {
+    let conn = open_users_db();
+
+    // The columns are selected in another order than the fields are declared in
+    let contacts = sqlite::select(vec![
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+    ])
+    .from(User::default())
+    .where_clause(User::USERNAME.eq("bob"))
+    .build_as::<UserContact>(&conn)
+    .unwrap();
+
+    assert_eq!(
+        contacts,
+        vec![UserContact {
+            email: "bob@example.com".to_string(),
+            username: "bob".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn select_as_missing_column() {
+    let conn = open_users_db();
+
+    let result = sqlite::select(vec![Column::Text("username".to_string())])
+        .from(User::default())
+        .build_as::<UserContact>(&conn);
+
+    assert!(matches!(
+        result,
+        Err(sqlite::SqliteError::DecodeError(
+            njord::row::DecodeError::ColumnNotFound(column)
+        )) if column == "email"
+    ));
+}
+
+#[test]
+fn select_keeps_defaults_for_unselected_columns() {
+    let conn = open_users_db();
+
+    let users = sqlite::select(vec![Column::Text("username".to_string())])
+        .from(User::default())
+        .where_clause(User::USERNAME.eq("anna"))
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].username, "anna");
+    assert_eq!(users[0].email, "");
+}
+
+#[test]
+fn select_null_into_non_optional_field() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT
+        );
+        INSERT INTO users (username, email, address) VALUES ('anna', 'anna@example.com', NULL);",
+    )
+    .unwrap();
+
+    let result = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(sqlite::SqliteError::DecodeError(
+            njord::row::DecodeError::UnexpectedNull(column)
+        )) if column == "address"
+    ));
+}
+
+#[test]
+fn select_invalid_value() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let result = sqlite::select::raw_execute::<(i64,)>("SELECT 'abc' AS total", &conn);
+
+    assert!(matches!(
+        result,
+        Err(sqlite::SqliteError::DecodeError(
+            njord::row::DecodeError::InvalidValue { column, .. }
+        )) if column == "total"
+    ));
+}
+
+#[test]
+fn select_blob_and_float_values() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let rows = sqlite::select::raw_execute::<(Vec<u8>, f64, Option<String>)>(
+        "SELECT x'00ff80' AS data, 0.1 + 0.2 AS total, NULL AS note",
+        &conn,
+    )
+    .unwrap();
+
+    assert_eq!(rows, vec![(vec![0x00, 0xff, 0x80], 0.1 + 0.2, None)]);
+}
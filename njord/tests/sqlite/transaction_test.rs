@@ -0,0 +1,166 @@
+use super::User;
+use njord::column::Column;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite::{self, SqliteError};
+use njord::transaction::IsolationLevel;
+use rusqlite::Connection;
+
+fn open_with_users() -> Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn user(username: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+fn usernames(conn: &Connection) -> Vec<String> {
+    sqlite::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .order_by([User::ID.asc()])
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|user| user.username)
+        .collect()
+}
+
+#[test]
+fn commit() {
+    let conn = open_with_users();
+
+    let result = sqlite::transaction(&conn, |tx| {
+        sqlite::insert(tx, vec![user("alice")])?;
+        sqlite::insert(tx, vec![user("bob")])?;
+
+        Ok::<_, SqliteError>(usernames(tx).len())
+    });
+
+    assert_eq!(result.unwrap(), 2);
+    assert_eq!(usernames(&conn), vec!["alice", "bob"]);
+}
+
+#[test]
+fn rollback_on_error() {
+    let conn = open_with_users();
+
+    let result = sqlite::transaction(&conn, |tx| {
+        sqlite::insert(tx, vec![user("alice")])?;
+        sqlite::raw_execute(tx, "INSERT INTO missing_table VALUES (1)")
+    });
+
+    assert!(result.is_err());
+    assert!(usernames(&conn).is_empty());
+}
+
+#[test]
+fn nested_rollback_keeps_outer_changes() {
+    let conn = open_with_users();
+
+    let result = sqlite::transaction(&conn, |tx| {
+        sqlite::insert(tx, vec![user("alice")])?;
+
+        let nested = tx.transaction(|nested| {
+            sqlite::insert(nested, vec![user("bob")])?;
+            sqlite::raw_execute(nested, "INSERT INTO missing_table VALUES (1)")
+        });
+        assert!(nested.is_err());
+
+        tx.transaction(|nested| {
+            sqlite::insert(nested, vec![user("carol")])?;
+            Ok::<_, SqliteError>(())
+        })
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(usernames(&conn), vec!["alice", "carol"]);
+}
+
+#[test]
+fn isolation_level() {
+    let conn = open_with_users();
+
+    let result = sqlite::transaction_with(&conn, IsolationLevel::Serializable, |tx| {
+        sqlite::insert(tx, vec![user("alice")])?;
+        Ok::<_, SqliteError>(())
+    });
+
+    assert!(result.is_ok());
+    assert_eq!(usernames(&conn), vec!["alice"]);
+}
+
+#[test]
+fn rollback_on_panic() {
+    let conn = open_with_users();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = sqlite::transaction(&conn, |tx| -> Result<(), SqliteError> {
+            sqlite::insert(tx, vec![user("alice")])?;
+            panic!("order placement failed");
+        });
+    }));
+
+    assert!(result.is_err());
+    assert!(usernames(&conn).is_empty());
+}
+
+#[test]
+fn rollback_failure_keeps_the_error() {
+    let conn = open_with_users();
+
+    let result = sqlite::transaction(&conn, |tx| {
+        // Ends the transaction, so rolling it back fails as well
+        sqlite::raw_execute(tx, "ROLLBACK")?;
+        Err::<(), _>(SqliteError::UnexpectedRowCount {
+            expected: 1,
+            actual: 0,
+        })
+    });
+
+    assert!(matches!(
+        result,
+        Err(SqliteError::UnexpectedRowCount {
+            expected: 1,
+            actual: 0
+        })
+    ));
+}
+
+#[test]
+fn failed_commit_is_rolled_back() {
+    let conn = open_with_users();
+    sqlite::raw_execute(
+        &conn,
+        "PRAGMA foreign_keys = ON;
+        CREATE TABLE orders (
+            user_id INTEGER REFERENCES users (id) DEFERRABLE INITIALLY DEFERRED
+        )",
+    )
+    .unwrap();
+
+    // The foreign key is only checked, and violated, when the transaction commits
+    let result = sqlite::transaction(&conn, |tx| {
+        sqlite::insert(tx, vec![user("alice")])?;
+        sqlite::raw_execute(tx, "INSERT INTO orders VALUES (42)")
+    });
+
+    assert!(matches!(result, Err(SqliteError::TransactionError(_))));
+    assert!(conn.is_autocommit());
+    assert!(usernames(&conn).is_empty());
+}
@@ -0,0 +1,287 @@
+use njord::column::Column;
+use njord::condition::Condition;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::{DbEnum, Table};
+
+#[cfg(feature = "chrono")]
+mod date_time {
+    use super::*;
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    #[derive(Table, Debug, PartialEq)]
+    #[table_name = "events"]
+    struct Event {
+        id: AutoIncrementPrimaryKey<i64>,
+        title: String,
+        day: NaiveDate,
+        starts_at: NaiveDateTime,
+        ends_at: Option<NaiveDateTime>,
+        created_at: DateTime<Utc>,
+        attachment: Vec<u8>,
+    }
+
+    fn event(title: &str, day: u32) -> Event {
+        let day = NaiveDate::from_ymd_opt(2024, 5, day).unwrap();
+
+        Event {
+            id: AutoIncrementPrimaryKey::default(),
+            title: title.to_string(),
+            day,
+            starts_at: day.and_hms_milli_opt(9, 30, 0, 250).unwrap(),
+            ends_at: None,
+            created_at: day.and_hms_opt(8, 0, 0).unwrap().and_utc(),
+            attachment: vec![0x00, 0xff, 0x80],
+        }
+    }
+
+    fn open_events_db() -> rusqlite::Connection {
+        let conn = sqlite::open_in_memory().unwrap();
+        sqlite::raw_execute(
+            &conn,
+            "CREATE TABLE events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                day DATE NOT NULL,
+                starts_at TIMESTAMP NOT NULL,
+                ends_at TIMESTAMP,
+                created_at TIMESTAMPTZ NOT NULL,
+                attachment BLOB NOT NULL
+            )",
+        )
+        .unwrap();
+
+        conn
+    }
+
+    #[test]
+    fn insert_and_select_date_time_fields() {
+        let conn = open_events_db();
+        let mut second = event("release", 2);
+        second.ends_at = Some(second.starts_at + chrono::Duration::hours(2));
+        sqlite::bulk_insert(vec![event("planning", 1), second])
+            .build(&conn)
+            .unwrap();
+
+        let events = sqlite::select(vec![Column::Text("*".to_string())])
+            .from(Event::default())
+            .where_clause(Event::DAY.eq(NaiveDate::from_ymd_opt(2024, 5, 2).unwrap()))
+            .build(&conn)
+            .unwrap();
+
+        let mut expected = event("release", 2);
+        expected.id = AutoIncrementPrimaryKey::new(Some(2));
+        expected.ends_at = Some(expected.starts_at + chrono::Duration::hours(2));
+        assert_eq!(events, vec![expected]);
+    }
+
+    #[test]
+    fn filter_on_timestamp_with_time_zone() {
+        let conn = open_events_db();
+        sqlite::bulk_insert(vec![event("planning", 1), event("release", 2)])
+            .build(&conn)
+            .unwrap();
+
+        let since = NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc();
+        let titles = sqlite::select(vec![Column::Text("title".to_string())])
+            .from(Event::default())
+            .where_clause(Event::CREATED_AT.gt(since))
+            .build_as::<String>(&conn)
+            .unwrap();
+
+        assert_eq!(titles, vec!["release".to_string()]);
+    }
+}
+
+#[derive(DbEnum, Debug, Default, Clone, Copy, PartialEq)]
+enum Status {
+    #[default]
+    Open,
+    #[db(rename = "on_hold")]
+    OnHold,
+    Done,
+}
+
+#[derive(DbEnum, Debug, Default, Clone, Copy, PartialEq)]
+#[db(integer)]
+enum Priority {
+    #[default]
+    Low = 1,
+    High = 10,
+}
+
+#[derive(Table, Debug, PartialEq)]
+#[table_name = "tasks"]
+struct Task {
+    id: AutoIncrementPrimaryKey<i64>,
+    title: String,
+    status: Status,
+    priority: Option<Priority>,
+}
+
+fn open_tasks_db() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE tasks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            status TEXT NOT NULL,
+            priority INTEGER
+        )",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn task(title: &str, status: Status, priority: Option<Priority>) -> Task {
+    Task {
+        id: AutoIncrementPrimaryKey::default(),
+        title: title.to_string(),
+        status,
+        priority,
+    }
+}
+
+#[test]
+fn insert_and_select_enum_fields() {
+    let conn = open_tasks_db();
+    sqlite::bulk_insert(vec![
+        task("write", Status::Open, None),
+        task("review", Status::OnHold, Some(Priority::High)),
+        task("ship", Status::Done, Some(Priority::Low)),
+    ])
+    .build(&conn)
+    .unwrap();
+
+    let stored = sqlite::select(vec![Column::Text("status".to_string())])
+        .from(Task::default())
+        .where_clause(Task::TITLE.eq("review"))
+        .build_as::<String>(&conn)
+        .unwrap();
+    assert_eq!(stored, vec!["on_hold".to_string()]);
+
+    let tasks = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Task::default())
+        .where_clause(Condition::Or(
+            Box::new(Task::STATUS.eq(Status::OnHold)),
+            Box::new(Condition::Eq("priority".to_string(), Priority::Low.into())),
+        ))
+        .build(&conn)
+        .unwrap();
+
+    let titles: Vec<&str> = tasks.iter().map(|task| task.title.as_str()).collect();
+    assert_eq!(titles, vec!["review", "ship"]);
+    assert_eq!(tasks[0].status, Status::OnHold);
+    assert_eq!(tasks[0].priority, Some(Priority::High));
+}
+
+#[test]
+fn compare_optional_field_with_null() {
+    let conn = open_tasks_db();
+    sqlite::bulk_insert(vec![
+        task("write", Status::Open, None),
+        task("review", Status::OnHold, Some(Priority::High)),
+    ])
+    .build(&conn)
+    .unwrap();
+
+    let unprioritized = sqlite::select(vec![Column::Text("title".to_string())])
+        .from(Task::default())
+        .where_clause(Condition::Eq(
+            "priority".to_string(),
+            None::<Priority>.into(),
+        ))
+        .build_as::<String>(&conn)
+        .unwrap();
+    assert_eq!(unprioritized, vec!["write".to_string()]);
+
+    let prioritized = sqlite::select(vec![Column::Text("title".to_string())])
+        .from(Task::default())
+        .where_clause(Condition::Ne(
+            "priority".to_string(),
+            None::<Priority>.into(),
+        ))
+        .build_as::<String>(&conn)
+        .unwrap();
+    assert_eq!(prioritized, vec!["review".to_string()]);
+}
+
+#[test]
+fn unknown_enum_value_fails_to_decode() {
+    let conn = open_tasks_db();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO tasks (title, status) VALUES ('archive', 'archived')",
+    )
+    .unwrap();
+
+    let result = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(Task::default())
+        .build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(sqlite::SqliteError::DecodeError(
+            njord::row::DecodeError::InvalidValue { column, message }
+        )) if column == "status" && message == "unknown Status value 'archived'"
+    ));
+}
+
+#[cfg(all(feature = "uuid", feature = "decimal", feature = "json"))]
+mod optional_types {
+    use super::*;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    #[derive(Table, Debug, PartialEq)]
+    #[table_name = "orders"]
+    struct Order {
+        id: Uuid,
+        total: Decimal,
+        discount: Option<Decimal>,
+        details: serde_json::Value,
+    }
+
+    #[test]
+    fn insert_and_select_uuid_decimal_and_json_fields() {
+        let conn = sqlite::open_in_memory().unwrap();
+        sqlite::raw_execute(
+            &conn,
+            "CREATE TABLE orders (
+                id UUID PRIMARY KEY,
+                total DECIMAL NOT NULL,
+                discount DECIMAL,
+                details JSON NOT NULL
+            )",
+        )
+        .unwrap();
+
+        let order = Order {
+            id: Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+            total: "1234567890.05".parse().unwrap(),
+            discount: None,
+            details: serde_json::json!({ "items": [1, 2], "gift": true }),
+        };
+        sqlite::insert(&conn, vec![order]).unwrap();
+
+        let orders = sqlite::select(vec![Column::Text("*".to_string())])
+            .from(Order::default())
+            .where_clause(
+                Order::ID.eq(Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap()),
+            )
+            .build(&conn)
+            .unwrap();
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].total, "1234567890.05".parse::<Decimal>().unwrap());
+        assert_eq!(orders[0].discount, None);
+        assert_eq!(orders[0].details["items"], serde_json::json!([1, 2]));
+    }
+}
@@ -1,28 +1,25 @@
-use super::User;
+use super::{open_users_table, seed_users, usernames, User};
+use njord::builder::BuildError;
 use njord::column::Column;
 use njord::condition::{Condition, Value};
 use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite::select::SelectQueryBuilder;
-use njord::sqlite::{self};
+use njord::sqlite::{self, SqliteError};
+use njord::table::Table;
+use njord_derive::Table;
 use std::collections::HashMap;
-use std::path::Path;
 
 #[test]
 fn update() {
-    let db_relative_path = "./db/insert.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path);
+    let conn = open_users_table();
 
     let columns = vec!["username".to_string()];
 
-    let condition = Condition::Eq(
-        "username".to_string(),
-        Value::Literal("mjovanc".to_string()),
-    );
+    let condition = Condition::Eq("username".to_string(), Value::Text("mjovanc".to_string()));
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::<usize>::new(Some(0)),
-        username: "mjovanc".to_string(),
+        username: "marcus".to_string(),
         email: "mjovanc@icloud.com".to_string(),
         address: "Some Random Address 1".to_string(),
     };
@@ -32,6 +29,15 @@ fn update() {
 
     match conn {
         Ok(ref c) => {
+            seed_users(
+                c,
+                &[
+                    ("mjovanc", "mjovanc@icloud.com", "Some Random Address 1"),
+                    ("alice", "alice@example.com", "Some Random Address 1"),
+                    ("mjovanc", "mjovanc@example.com", "Some Random Address 2"),
+                ],
+            );
+
             let result = sqlite::update(table_row)
                 .set(columns)
                 .where_clause(condition)
@@ -40,7 +46,8 @@ fn update() {
                 .offset(0)
                 .build(c);
             println!("{:?}", result);
-            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), 2);
+            assert_eq!(usernames(c), ["marcus", "alice", "marcus"]);
         }
         Err(e) => {
             panic!("Failed to UPDATE: {:?}", e);
@@ -50,9 +57,7 @@ fn update() {
 
 #[test]
 fn update_with_sub_queries() {
-    let db_relative_path = "./db/update.db";
-    let db_path = Path::new(&db_relative_path);
-    let conn = sqlite::open(db_path);
+    let conn = open_users_table();
 
     let table_row: User = User {
         id: AutoIncrementPrimaryKey::<usize>::new(Some(0)),
@@ -65,11 +70,19 @@ fn update_with_sub_queries() {
 
     match conn {
         Ok(c) => {
+            seed_users(
+                &c,
+                &[
+                    ("mjovanc", "mjovanc@icloud.com", "Some Random Address 1"),
+                    ("alice", "alice@example.com", "Some Random Address 1"),
+                ],
+            );
+
             let sub_query = SelectQueryBuilder::new(vec![Column::Text("email".to_string())])
                 .from(User::default())
                 .where_clause(Condition::Eq(
                     "email".to_string(),
-                    Value::Literal("mjovanc@icloud.com".to_string()),
+                    Value::Text("mjovanc@icloud.com".to_string()),
                 ))
                 .limit(1);
 
@@ -80,13 +93,189 @@ fn update_with_sub_queries() {
                 .set_subqueries(set_subqueries)
                 .where_clause(Condition::Eq(
                     "username".to_owned(),
-                    Value::Literal("mjovanc".to_owned()),
+                    Value::Text("mjovanc".to_owned()),
                 ))
                 .build(&c);
 
             println!("{:?}", result);
-            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), 1);
+            assert_eq!(usernames(&c), ["mjovanc", "alice"]);
         }
         Err(e) => panic!("Failed to UPDATE: {:?}", e),
     };
 }
+
+fn open_with_users(usernames: &[&str]) -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )
+    .unwrap();
+
+    let rows = usernames
+        .iter()
+        .map(|username| User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            address: "Some Random Address 1".to_string(),
+        })
+        .collect();
+    sqlite::insert(&conn, rows).unwrap();
+
+    conn
+}
+
+#[test]
+fn update_returns_affected_rows() {
+    let conn = open_with_users(&["alice", "bob", "carol"]);
+
+    let table_row = User {
+        address: "Another Address".to_string(),
+        ..User::default()
+    };
+
+    let rows = sqlite::update(table_row)
+        .set(["address"])
+        .where_clause(User::USERNAME.ne("carol"))
+        .expect_rows(2)
+        .build(&conn);
+
+    assert_eq!(rows.unwrap(), 2);
+}
+
+#[test]
+fn update_expect_rows_mismatch() {
+    let conn = open_with_users(&["alice", "bob"]);
+
+    let table_row = User {
+        address: "Another Address".to_string(),
+        ..User::default()
+    };
+
+    let result = sqlite::update(table_row)
+        .set(["address"])
+        .where_clause(User::USERNAME.eq("alice"))
+        .expect_rows(2)
+        .build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(SqliteError::UnexpectedRowCount {
+            expected: 2,
+            actual: 1
+        })
+    ));
+
+    assert_eq!(
+        addresses(&conn),
+        vec!["Some Random Address 1", "Some Random Address 1"]
+    );
+}
+
+#[test]
+fn update_to_empty_text() {
+    let conn = open_with_users(&["alice"]);
+
+    let table_row = User {
+        address: String::new(),
+        ..User::default()
+    };
+
+    let rows = sqlite::update(table_row)
+        .set(["address"])
+        .where_clause(User::USERNAME.eq("alice"))
+        .build(&conn);
+
+    assert_eq!(rows.unwrap(), 1);
+    assert_eq!(addresses(&conn), vec![""]);
+}
+
+#[test]
+fn update_unknown_column() {
+    let conn = open_with_users(&["alice"]);
+
+    let result = sqlite::update(User::default())
+        .set(["nickname"])
+        .where_clause(User::USERNAME.eq("alice"))
+        .build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(SqliteError::BuildError(BuildError::UnknownColumn(column))) if column == "nickname"
+    ));
+}
+
+#[derive(Table, Debug)]
+#[table_name = "users"]
+struct UserAddress {
+    id: AutoIncrementPrimaryKey<usize>,
+    #[column(read_only)]
+    address: String,
+}
+
+#[test]
+fn update_read_only_column() {
+    let conn = open_with_users(&["alice"]);
+
+    let table_row = UserAddress {
+        address: "Another Address".to_string(),
+        ..UserAddress::default()
+    };
+    let result = sqlite::update(table_row).set(["address"]).build(&conn);
+
+    assert!(matches!(
+        result,
+        Err(SqliteError::BuildError(BuildError::ReadOnlyColumn(column))) if column == "address"
+    ));
+
+    let rows = sqlite::select(vec![Column::Text("*".to_string())])
+        .from(UserAddress::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(rows[0].address, "Some Random Address 1");
+}
+
+#[test]
+fn update_expect_rows_mismatch_in_transaction() {
+    let conn = open_with_users(&["alice", "bob"]);
+
+    let result = sqlite::transaction(&conn, |tx| {
+        let table_row = User {
+            address: "Another Address".to_string(),
+            ..User::default()
+        };
+
+        sqlite::update(table_row)
+            .set(["address"])
+            .where_clause(User::USERNAME.eq("alice"))
+            .expect_rows(2)
+            .build(tx)
+    });
+
+    assert!(matches!(
+        result,
+        Err(SqliteError::UnexpectedRowCount { .. })
+    ));
+    assert_eq!(
+        addresses(&conn),
+        vec!["Some Random Address 1", "Some Random Address 1"]
+    );
+}
+
+fn addresses(conn: &rusqlite::Connection) -> Vec<String> {
+    sqlite::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .order_by([User::ID.asc()])
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|user| user.address)
+        .collect()
+}
@@ -0,0 +1,90 @@
+use super::User;
+use njord::column::Column;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+
+fn open_with_unique_users() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL UNIQUE,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        )",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn user(username: &str, email: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: email.to_string(),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+fn emails(conn: &rusqlite::Connection) -> Vec<String> {
+    sqlite::select(vec![Column::Text("*".to_string())])
+        .from(User::default())
+        .order_by([User::USERNAME.asc()])
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|user| user.email)
+        .collect()
+}
+
+#[test]
+fn upsert_inserts_and_updates() {
+    let conn = open_with_unique_users();
+    sqlite::insert(&conn, vec![user("alice", "alice@old.com")]).unwrap();
+
+    let rows = sqlite::upsert(vec![
+        user("alice", "alice@new.com"),
+        user("bob", "bob@new.com"),
+    ])
+    .on_conflict([User::USERNAME])
+    .build(&conn)
+    .unwrap();
+
+    assert_eq!(rows, 2);
+    assert_eq!(emails(&conn), vec!["alice@new.com", "bob@new.com"]);
+}
+
+#[test]
+fn upsert_do_nothing() {
+    let conn = open_with_unique_users();
+    sqlite::insert(&conn, vec![user("alice", "alice@old.com")]).unwrap();
+
+    let rows = sqlite::upsert(vec![
+        user("alice", "alice@new.com"),
+        user("bob", "bob@new.com"),
+    ])
+    .on_conflict([User::USERNAME])
+    .do_nothing()
+    .build(&conn)
+    .unwrap();
+
+    assert_eq!(rows, 1);
+    assert_eq!(emails(&conn), vec!["alice@old.com", "bob@new.com"]);
+}
+
+#[test]
+fn upsert_by_primary_key() {
+    let conn = open_with_unique_users();
+    let inserted = sqlite::insert(&conn, vec![user("alice", "alice@old.com")]).unwrap();
+
+    let mut changed = user("alice", "alice@new.com");
+    changed.id = inserted[0].id.clone();
+
+    // The conflict target defaults to the AutoIncrementPrimaryKey
+    let rows = sqlite::upsert(vec![changed]).build(&conn).unwrap();
+
+    assert_eq!(rows, 1);
+    assert_eq!(emails(&conn), vec!["alice@new.com"]);
+}
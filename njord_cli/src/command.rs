@@ -4,6 +4,27 @@ use std::path::Path;
 
 use crate::migration::{generate, rollback, run};
 
+#[cfg(any(
+    all(
+        feature = "postgres",
+        any(
+            feature = "mysql",
+            feature = "mariadb",
+            feature = "oracle",
+            feature = "mssql"
+        )
+    ),
+    all(
+        feature = "mysql",
+        any(feature = "mariadb", feature = "oracle", feature = "mssql")
+    ),
+    all(feature = "mariadb", any(feature = "oracle", feature = "mssql")),
+    all(feature = "oracle", feature = "mssql"),
+))]
+compile_error!(
+    "only one of the postgres, mysql, mariadb, oracle and mssql features can be enabled"
+);
+
 /// Initializes Njord with an empty migrations directory and a `njord.toml` config file.
 ///
 /// This function is responsible for setting up the initial configuration for Njord, a migration
@@ -25,8 +46,15 @@ pub fn handle_setup() {
     // include content of njord.toml template
     let toml_content = include_str!("../templates/njord.toml");
 
-    // include the content of up.sql and down.sql templates
-    #[cfg(feature = "sqlite")]
+    // include the content of up.sql and down.sql templates, the sqlite ones unless
+    // another database feature is enabled
+    #[cfg(not(any(
+        feature = "postgres",
+        feature = "mysql",
+        feature = "mariadb",
+        feature = "oracle",
+        feature = "mssql"
+    )))]
     let (up_sql_content, down_sql_content) = (
         include_str!("../templates/migrations/00000000000000_njord_initial_setup/sqlite/up.sql"),
         include_str!("../templates/migrations/00000000000000_njord_initial_setup/sqlite/down.sql"),
@@ -142,8 +170,10 @@ pub fn handle_migration_subcommand(sub_matches: &ArgMatches) {
             let name = generate_matches.get_one::<String>("name");
             let env = generate_matches.get_one::<String>("env");
             let dry_run = generate_matches.get_one::<String>("dry-run");
+            let auto = generate_matches.get_flag("auto");
+            let drop_tables = generate_matches.get_flag("drop-tables");
 
-            generate(name, env, dry_run)
+            generate(name, env, dry_run, auto, drop_tables)
         }
         Some(("run", run_matches)) => {
             let env = run_matches.get_one::<String>("env");
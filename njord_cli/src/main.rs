@@ -1,7 +1,8 @@
 mod migration;
 mod command;
+mod models;
 mod util;
-use clap::Arg;
+use clap::{Arg, ArgAction};
 use command::handle_command;
 
 fn main() {
@@ -35,6 +36,17 @@ fn main() {
                         .arg(Arg::new("dir")
                             .help("Specifies the target directory for generated migration changes.")
                             .value_name("path"))
+
+                        .arg(Arg::new("auto")
+                            .long("auto")
+                            .action(ArgAction::SetTrue)
+                            .help("Fills up.sql and down.sql with the changes between the models in the schema file and the database."))
+
+                        .arg(Arg::new("drop-tables")
+                            .long("drop-tables")
+                            .action(ArgAction::SetTrue)
+                            .requires("auto")
+                            .help("Drops the tables of the database that no model in the schema file declares, which --auto leaves alone by default."))
                 )
                 .subcommand(
                     clap::command!("run")
@@ -1,9 +1,13 @@
-use std::{fs, path::Path};
+use std::{error::Error as StdError, fs, path::Path};
 
-use njord::sqlite;
+use njord::dialect::AnyDialect;
+use njord::schema::{self, Migration, TableSchema, UnknownTables};
+use njord::sqlite::{self, SqliteError};
 use rusqlite::{Connection, Error, ErrorCode};
+use toml::Value as TomlConfig;
 
-use crate::util::{create_migration_files, get_local_migration_versions, get_migrations_directory_path, get_next_migration_version, MigrationHistory, read_config, version_not_in_database};
+use crate::models::read_models;
+use crate::util::{create_migration_files, get_database_url, get_local_migration_versions, get_migrations_directory_path, get_next_migration_version, get_schema_file_path, MigrationHistory, read_config, version_not_in_database};
 
 /// Generates migration files with the specified name, environment, and dry-run option.
 ///
@@ -12,21 +16,46 @@ use crate::util::{create_migration_files, get_local_migration_versions, get_migr
 /// * `name` - Optional parameter representing the name of the migration file.
 /// * `env` - Optional parameter specifying the environment (e.g., development, test, staging, production).
 /// * `dry_run` - Optional parameter indicating whether to simulate the migration without applying changes.
+/// * `auto` - Whether to fill the migration files with the changes between the models in the
+///   schema file and the database, instead of leaving them empty.
+/// * `drop_tables` - Whether the changes drop the tables of the database that no model
+///   declares, instead of leaving them alone.
 ///
 /// # Example
 ///
 /// ```rust
-/// generate(Some("example_name"), Some("development"), Some("true"));
+/// generate(Some("example_name"), Some("development"), Some("true"), false, false);
 /// ```
-pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&String>) {
+pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&String>, auto: bool, drop_tables: bool) {
     if let Ok(config) = read_config() {
         if let Some(migrations_dir) = get_migrations_directory_path(&config) {
+            // compare the models with the database
+            let migration = if auto {
+                match auto_migration(&config, drop_tables) {
+                    Ok(migration) if migration.is_empty() => {
+                        println!("The database already matches the models. No migration generated.");
+                        return;
+                    }
+                    Ok(migration) => Some(migration),
+                    Err(err) => {
+                        eprintln!("Error comparing the models with the database: {}", err);
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+
             // get the next migration version based on existing ones
             if let Ok(next_version) = get_next_migration_version(&migrations_dir) {
                 let migration_name = name.map(|s| s.as_str()).unwrap_or("example_name");
+                let (up_sql, down_sql) = migration
+                    .as_ref()
+                    .map(|migration| (to_sql(&migration.up), to_sql(&migration.down)))
+                    .unwrap_or_default();
 
                 // create migration files
-                if let Err(err) = create_migration_files(&migrations_dir, &next_version, migration_name)
+                if let Err(err) = create_migration_files(&migrations_dir, &next_version, migration_name, &up_sql, &down_sql)
                 {
                     eprintln!("Error creating migration files: {}", err);
                     return;
@@ -37,6 +66,11 @@ pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&St
                 println!("Name: {}", migration_name);
                 println!("Environment: {:?}", env);
                 println!("Dry-run: {:?}", dry_run);
+
+                // point out the changes that can lose data before the migration is run
+                for warning in migration.iter().flat_map(|migration| &migration.warnings) {
+                    println!("Warning: {}", warning);
+                }
             } else {
                 eprintln!("Error determining next migration version.");
             }
@@ -48,6 +82,87 @@ pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&St
     }
 }
 
+/// Compares the models in the schema file with the tables of the database.
+///
+/// # Arguments
+///
+/// * `config` - A reference to a `toml::Value` representing the configuration.
+/// * `drop_tables` - Whether tables that no model declares are dropped or left alone.
+///
+/// # Returns
+///
+/// A `Result` containing the statements turning the database into the models and back,
+/// or an error if the schema file or the database set in `njord.toml` could not be read.
+///
+/// # Note
+///
+/// The `migration_history` table is managed by njord and left out of the comparison.
+fn auto_migration(config: &TomlConfig, drop_tables: bool) -> Result<Migration, Box<dyn StdError>> {
+    let schema_file = get_schema_file_path(config).ok_or("no schema file set in njord.toml")?;
+    let models = read_models(&schema_file)?;
+    let url = get_database_url(config).ok_or("no database url set in njord.toml")?;
+    let (dialect, tables) = introspect(&url)?;
+
+    let is_model_table = |table: &TableSchema| table.name != "migration_history";
+    let tables: Vec<TableSchema> = tables.into_iter().filter(is_model_table).collect();
+    let models: Vec<TableSchema> = models.into_iter().filter(is_model_table).collect();
+
+    let unknown_tables = if drop_tables {
+        UnknownTables::Drop
+    } else {
+        UnknownTables::Keep
+    };
+
+    Ok(schema::diff(dialect, &tables, &models, unknown_tables))
+}
+
+/// Reads the tables of a database together with the dialect of its backend.
+///
+/// # Arguments
+///
+/// * `url` - The database URL, e.g. `sqlite://sqlite.db`.
+///
+/// # Returns
+///
+/// A `Result` containing the dialect picked by the URL scheme and the tables of the
+/// database, or an error if the URL is invalid, its backend cannot be introspected yet
+/// or the database could not be read.
+fn introspect(url: &str) -> Result<(AnyDialect, Vec<TableSchema>), Box<dyn StdError>> {
+    let (scheme, rest) = url
+        .split_once(':')
+        .ok_or_else(|| format!("missing scheme in database url '{}'", url))?;
+
+    match scheme.to_ascii_lowercase().as_str() {
+        "sqlite" => {
+            let path = rest.strip_prefix("//").unwrap_or(rest);
+            let conn = if path.is_empty() || path == ":memory:" {
+                sqlite::open_in_memory()?
+            } else {
+                sqlite::open(Path::new(path))?
+            };
+            let tables = sqlite::introspect(&conn)
+                .map_err(|err| format!("error reading the database schema: {:?}", err))?;
+
+            Ok((AnyDialect::Sqlite, tables))
+        }
+        "mysql" | "mariadb" | "oracle" | "mssql" | "sqlserver" | "postgres" | "postgresql"
+        | "duckdb" => Err(format!(
+            "comparing the models with a {} database is not supported yet",
+            scheme
+        )
+        .into()),
+        _ => Err(format!("unknown database in url '{}'", url).into()),
+    }
+}
+
+/// Joins statements into the content of a migration file, one statement per line.
+fn to_sql(statements: &[String]) -> String {
+    statements
+        .iter()
+        .map(|statement| format!("{};\n", statement))
+        .collect()
+}
+
 /// Runs migration files with the specified environment and log level.
 ///
 /// # Arguments
@@ -94,23 +209,19 @@ pub fn run(env: Option<&String>, log_level: Option<&String>) {
                         // for example it can be run with 00000000000001_init_tables first and
                         // then run 00000000000000_njord_initial_setup which is not incremental
                         for local_version in &local_versions {
-                            match version_not_in_database(&conn, &local_version) {
-                                Ok(_) => {
-                                    println!("Migration {} not found in database. Executing migration...", local_version);
+                            if version_not_in_database(&conn, local_version).is_ok() {
+                                println!("Migration {} not found in database. Executing migration...", local_version);
 
-                                    let migrations_dir = format!("migrations/{}", local_version);
-                                    println!("migrations_dir: {}", migrations_dir);
+                                let migrations_dir = format!("migrations/{}", local_version);
+                                println!("migrations_dir: {}", migrations_dir);
 
-                                    let db_relative_path = "./sqlite.db";
-                                    let db_path = Path::new(&db_relative_path);
-                                    let conn = sqlite::open(db_path);
+                                let db_relative_path = "./sqlite.db";
+                                let db_path = Path::new(&db_relative_path);
+                                let conn = sqlite::open(db_path);
 
-                                    match conn {
-                                        Ok(c) => execute_pending_migration(c, &migrations_dir, &local_version).unwrap(),
-                                        Err(_) => {}
-                                    }
+                                if let Ok(c) = conn {
+                                    execute_pending_migration(c, &migrations_dir, local_version).unwrap();
                                 }
-                                Err(_) => {}
                             }
                         }
                     } else {
@@ -258,13 +369,13 @@ fn execute_sql_from_file(
 ///
 /// # Errors
 ///
-/// Returns a `rusqlite::Error` if there is an issue executing the SQL scripts or inserting into the database.
+/// Returns a `SqliteError` if the migration version could not be inserted into the database.
 fn execute_pending_migration(
     conn: Connection,
     migrations_dir: &str,
     next_version: &str,
-) -> Result<(), Error> {
-    match execute_sql_from_file(&conn, &migrations_dir, "up.sql") {
+) -> Result<(), SqliteError> {
+    match execute_sql_from_file(&conn, migrations_dir, "up.sql") {
         Ok(_) => {
             println!("up.sql executed successfully.");
             // insert new row with the version into the database
@@ -275,7 +386,7 @@ fn execute_pending_migration(
             eprintln!("Error executing up.sql: {}", up_err);
 
             // TODO: we need to make sure that this is only run on error when it's not "table already exists" kind of errors
-            if let Err(down_err) = execute_sql_from_file(&conn, &migrations_dir, "down.sql") {
+            if let Err(down_err) = execute_sql_from_file(&conn, migrations_dir, "down.sql") {
                 eprintln!("Error executing down.sql: {}", down_err);
             } else {
                 println!("down.sql executed successfully.");
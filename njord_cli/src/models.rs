@@ -0,0 +1,710 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use njord::dialect::DataType;
+use njord::schema::{ColumnDefinition, TableSchema};
+use njord::types::data_type_of;
+use syn::{
+    Attribute, Expr, ExprLit, Field, GenericArgument, ImplItem, Item, Lit, Member, Meta,
+    PathArguments, Stmt, Type, UseTree,
+};
+
+/// Reads the tables declared by the `#[derive(Table)]` structs of a schema file.
+///
+/// The file is parsed rather than compiled, so the column types are found by resolving
+/// the field types through the `use` items and type aliases of the file. A field can
+/// have a type njord implements `SqlType` for, see `njord::types::data_type_of`, a
+/// `#[derive(DbEnum)]` enum declared in the same file, a type with an `impl SqlType`
+/// in the same file, or a type alias of these, wrapped in `Option` or a primary key
+/// wrapper.
+///
+/// # Arguments
+///
+/// * `path` - The path to the schema file, e.g. `src/schema.rs`.
+///
+/// # Returns
+///
+/// A `Result` containing the tables in file order, or an error if the file cannot be
+/// read or parsed, or a field has a type other than the ones above.
+pub fn read_models(path: &Path) -> Result<Vec<TableSchema>, Box<dyn Error>> {
+    let file = syn::parse_file(&fs::read_to_string(path)?)?;
+
+    let mut types = Types::default();
+    collect_enums(&file.items, &mut types.enums)?;
+    collect_sql_types(&file.items, &Scope::default(), &mut types.sql_types);
+
+    let mut tables = Vec::new();
+    collect_tables(&file.items, &Scope::default(), &types, &mut tables)?;
+
+    Ok(tables)
+}
+
+/// The types declared in the schema file that columns can have.
+#[derive(Default)]
+struct Types {
+    /// The `DbEnum` names mapped to their data type.
+    enums: HashMap<String, DataType>,
+    /// The names of the types with an `impl SqlType`, mapped to the scope of the
+    /// implementation and the expression its `data_type` returns.
+    sql_types: HashMap<String, (Scope, Expr)>,
+}
+
+/// The names the items of a module of the schema file bring into scope.
+#[derive(Clone, Default)]
+struct Scope {
+    /// The names imported with `use`, mapped to the paths they were imported from.
+    uses: HashMap<String, String>,
+    /// The names of the type aliases, mapped to their types.
+    aliases: HashMap<String, Type>,
+}
+
+impl Scope {
+    /// Returns the scope of a module, the scope of its parent with the `use` items and
+    /// type aliases of the module added.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items of the module.
+    fn nested(&self, items: &[Item]) -> Scope {
+        let mut scope = self.clone();
+        for item in items {
+            match item {
+                Item::Use(item) => collect_uses(&item.tree, "", &mut scope.uses),
+                Item::Type(item) => {
+                    scope
+                        .aliases
+                        .insert(item.ident.to_string(), (*item.ty).clone());
+                }
+                _ => {}
+            }
+        }
+        scope
+    }
+
+    /// Follows type aliases until a type that is not an alias.
+    fn resolve_alias<'a>(&'a self, mut ty: &'a Type) -> &'a Type {
+        // an alias of itself does not compile, the limit only stops broken files
+        for _ in 0..=self.aliases.len() {
+            let alias = match ty {
+                Type::Path(type_path) if type_path.qself.is_none() => type_path
+                    .path
+                    .get_ident()
+                    .and_then(|ident| self.aliases.get(&ident.to_string())),
+                _ => None,
+            };
+            match alias {
+                Some(alias) => ty = alias,
+                None => break,
+            }
+        }
+        ty
+    }
+
+    /// Returns the path of a type with its crate and type arguments, as expected by
+    /// `njord::types::data_type_of`, or `None` if it is not a path.
+    fn type_path(&self, ty: &Type) -> Option<String> {
+        let Type::Path(type_path) = self.resolve_alias(ty) else {
+            return None;
+        };
+        if type_path.qself.is_some() {
+            return None;
+        }
+
+        let mut segments = Vec::new();
+        for (index, segment) in type_path.path.segments.iter().enumerate() {
+            let mut name = segment.ident.to_string();
+            if index == 0 && type_path.path.leading_colon.is_none() {
+                if let Some(path) = self.uses.get(&name) {
+                    name = path.clone();
+                }
+            }
+            if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                let arguments = arguments
+                    .args
+                    .iter()
+                    .map(|argument| match argument {
+                        GenericArgument::Type(ty) => self.type_path(ty),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                name = format!("{}<{}>", name, arguments.join(", "));
+            }
+            segments.push(name);
+        }
+
+        let path = segments.join("::");
+        let prelude = [
+            "std::string::",
+            "std::vec::",
+            "alloc::string::",
+            "alloc::vec::",
+        ];
+        match prelude.iter().find_map(|prefix| path.strip_prefix(prefix)) {
+            Some(path) => Some(path.to_string()),
+            None => Some(path),
+        }
+    }
+}
+
+/// Collects the names a `use` item imports, mapped to the paths they are imported from.
+///
+/// Glob imports are skipped, the names they import are not known without compiling.
+///
+/// # Arguments
+///
+/// * `tree` - The imported tree.
+/// * `prefix` - The path leading to the tree, ending with `::` unless empty.
+/// * `uses` - The imported names found so far.
+fn collect_uses(tree: &UseTree, prefix: &str, uses: &mut HashMap<String, String>) {
+    match tree {
+        UseTree::Path(path) => {
+            collect_uses(&path.tree, &format!("{}{}::", prefix, path.ident), uses)
+        }
+        UseTree::Name(name) if name.ident == "self" => {
+            let path = prefix.trim_end_matches("::");
+            if let Some(name) = path.rsplit("::").next() {
+                uses.insert(name.to_string(), path.to_string());
+            }
+        }
+        UseTree::Name(name) => {
+            uses.insert(name.ident.to_string(), format!("{}{}", prefix, name.ident));
+        }
+        UseTree::Rename(rename) => {
+            uses.insert(
+                rename.rename.to_string(),
+                format!("{}{}", prefix, rename.ident),
+            );
+        }
+        UseTree::Glob(_) => {}
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                collect_uses(tree, prefix, uses);
+            }
+        }
+    }
+}
+
+/// Collects the `#[derive(DbEnum)]` enums and the data type they are stored as.
+///
+/// # Arguments
+///
+/// * `items` - The items of the file or of an inline module.
+/// * `enums` - The enum names mapped to their data type.
+fn collect_enums(items: &[Item], enums: &mut HashMap<String, DataType>) -> syn::Result<()> {
+    for item in items {
+        match item {
+            Item::Enum(item) if derives(&item.attrs, "DbEnum") => {
+                let mut integer = false;
+                for attr in item.attrs.iter().filter(|attr| attr.path().is_ident("db")) {
+                    attr.parse_nested_meta(|meta| {
+                        integer |= meta.path.is_ident("integer");
+                        Ok(())
+                    })?;
+                }
+
+                let data_type = if integer {
+                    DataType::Integer
+                } else {
+                    DataType::Text
+                };
+                enums.insert(item.ident.to_string(), data_type);
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_enums(items, enums)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the types with an `impl SqlType` and the expression their `data_type` returns.
+///
+/// # Arguments
+///
+/// * `items` - The items of the file or of an inline module.
+/// * `scope` - The scope of the parent module, empty for the file.
+/// * `sql_types` - The type names mapped to the scope of the implementation and the
+///   returned expression.
+fn collect_sql_types(
+    items: &[Item],
+    scope: &Scope,
+    sql_types: &mut HashMap<String, (Scope, Expr)>,
+) {
+    let scope = scope.nested(items);
+
+    for item in items {
+        match item {
+            Item::Impl(item) => {
+                let implements_sql_type = item.trait_.as_ref().is_some_and(|(_, path, _)| {
+                    path.segments
+                        .last()
+                        .is_some_and(|segment| segment.ident == "SqlType")
+                });
+                let Type::Path(self_ty) = &*item.self_ty else {
+                    continue;
+                };
+                let Some(name) = self_ty.path.get_ident() else {
+                    continue;
+                };
+                if !implements_sql_type {
+                    continue;
+                }
+
+                let returned = item.items.iter().find_map(|item| match item {
+                    ImplItem::Fn(function) if function.sig.ident == "data_type" => {
+                        match function.block.stmts.last()? {
+                            Stmt::Expr(expr, None) => Some(expr.clone()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                });
+                if let Some(returned) = returned {
+                    sql_types.insert(name.to_string(), (scope.clone(), returned));
+                }
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_sql_types(items, &scope, sql_types);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collects the tables declared by the `#[derive(Table)]` structs.
+///
+/// # Arguments
+///
+/// * `items` - The items of the file or of an inline module.
+/// * `scope` - The scope of the parent module, empty for the file.
+/// * `types` - The types declared in the schema file.
+/// * `tables` - The tables found so far.
+fn collect_tables(
+    items: &[Item],
+    scope: &Scope,
+    types: &Types,
+    tables: &mut Vec<TableSchema>,
+) -> syn::Result<()> {
+    let scope = scope.nested(items);
+
+    for item in items {
+        match item {
+            Item::Struct(item) if derives(&item.attrs, "Table") => {
+                let name =
+                    table_name(&item.attrs).unwrap_or_else(|| "default_table_name".to_string());
+                let mut columns = Vec::new();
+
+                for field in &item.fields {
+                    if let Some(column) = column_definition(&name, field, &scope, types)? {
+                        columns.push(column);
+                    }
+                }
+
+                tables.push(TableSchema::new(name, columns));
+            }
+            Item::Mod(module) => {
+                if let Some((_, items)) = &module.content {
+                    collect_tables(items, &scope, types, tables)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the attributes derive the trait with the given name.
+fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .any(|attr| {
+            let mut found = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= meta
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == name);
+                Ok(())
+            });
+            found
+        })
+}
+
+/// Returns the value of the `#[table_name = "..."]` attribute, if there is one.
+fn table_name(attrs: &[Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("table_name") => match &meta.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(name),
+                ..
+            }) => Some(name.value()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Returns the definition of the column stored by a field, or `None` for a `#[skip]` field.
+///
+/// # Arguments
+///
+/// * `table` - The name of the table, used in errors.
+/// * `field` - The field of the table struct.
+/// * `scope` - The scope of the module declaring the table struct.
+/// * `types` - The types declared in the schema file.
+fn column_definition(
+    table: &str,
+    field: &Field,
+    scope: &Scope,
+    types: &Types,
+) -> syn::Result<Option<ColumnDefinition>> {
+    let mut name = field
+        .ident
+        .as_ref()
+        .map(|ident| ident.to_string().trim_start_matches("r#").to_string())
+        .unwrap_or_default();
+    let mut primary_key = false;
+    let mut default = None;
+    let mut length = None;
+    let mut precision = None;
+    let mut scale = 0;
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("skip") {
+            return Ok(None);
+        } else if attr.path().is_ident("primary_key") {
+            primary_key = true;
+        } else if attr.path().is_ident("default") {
+            if let Meta::NameValue(meta) = &attr.meta {
+                if let Expr::Lit(ExprLit {
+                    lit: Lit::Str(expression),
+                    ..
+                }) = &meta.value
+                {
+                    default = Some(expression.value());
+                }
+            }
+        } else if attr.path().is_ident("column") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<syn::LitStr>()?.value();
+                } else if meta.path.is_ident("length") {
+                    length = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("precision") {
+                    precision = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("scale") {
+                    scale = meta.value()?.parse::<syn::LitInt>()?.base10_parse()?;
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    let mut column = ColumnDefinition::new(name, DataType::Text);
+    let mut ty = scope.resolve_alias(&field.ty);
+
+    // Unwrap `Option<T>`, `PrimaryKey<T>` and `AutoIncrementPrimaryKey<T>`
+    while let Some((wrapper, inner)) = generic_type(ty) {
+        match wrapper.as_str() {
+            "Option" => column.nullable = true,
+            "PrimaryKey" => primary_key = true,
+            "AutoIncrementPrimaryKey" => {
+                primary_key = true;
+                column.auto_increment = true;
+            }
+            _ => break,
+        }
+        ty = scope.resolve_alias(inner);
+    }
+
+    column.data_type = if let Some(precision) = precision {
+        DataType::Decimal { precision, scale }
+    } else if let Some(length) = length {
+        DataType::Varchar(length)
+    } else {
+        data_type(ty, scope, types, 0).ok_or_else(|| {
+            syn::Error::new_spanned(
+                ty,
+                format!(
+                    "unknown type `{}` of {}.{}, expected a type njord implements `SqlType` \
+                     for, a `DbEnum` or a type with an `impl SqlType` declared in the schema \
+                     file, or a type alias of these; for a type declared elsewhere, set the \
+                     column type with `#[column(length = ...)]` or `#[column(precision = ...)]`",
+                    scope.type_path(ty).unwrap_or_default(),
+                    table,
+                    column.name
+                ),
+            )
+        })?
+    };
+    column.primary_key = primary_key;
+    column.default = default;
+
+    Ok(Some(column))
+}
+
+/// Splits a type with a single type argument, e.g. `Option<T>`, into its name and argument.
+fn generic_type(ty: &Type) -> Option<(String, &Type)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) => Some((segment.ident.to_string(), inner)),
+        _ => None,
+    }
+}
+
+/// Returns the data type a field type is stored as, or `None` for an unknown type.
+///
+/// # Arguments
+///
+/// * `ty` - The field type, without `Option` and the primary key wrappers.
+/// * `scope` - The scope of the module declaring the table struct.
+/// * `types` - The types declared in the schema file.
+/// * `depth` - The number of `impl SqlType` delegations followed to reach the type.
+fn data_type(ty: &Type, scope: &Scope, types: &Types, depth: usize) -> Option<DataType> {
+    let ty = scope.resolve_alias(ty);
+    if let Type::Path(type_path) = ty {
+        if let Some(ident) = type_path.path.get_ident() {
+            if let Some(data_type) = types.enums.get(&ident.to_string()) {
+                return Some(*data_type);
+            }
+            // a type delegating to itself does not terminate, the limit only stops broken files
+            if let Some((scope, returned)) = types.sql_types.get(&ident.to_string()) {
+                if depth > types.sql_types.len() {
+                    return None;
+                }
+                return declared_data_type(returned, scope, types, depth + 1);
+            }
+        }
+    }
+
+    data_type_of(&scope.type_path(ty)?)
+}
+
+/// Returns the data type an `impl SqlType` declares, or `None` if it cannot be read
+/// without compiling.
+///
+/// The `data_type` function has to end with a `DataType` value, e.g. `DataType::Text`,
+/// or with the `data_type` of another type, e.g. `String::data_type()` or
+/// `<String as SqlType>::data_type()`.
+///
+/// # Arguments
+///
+/// * `returned` - The expression the `data_type` function ends with.
+/// * `scope` - The scope of the module declaring the implementation.
+/// * `types` - The types declared in the schema file.
+/// * `depth` - The number of `impl SqlType` delegations followed so far.
+fn declared_data_type(
+    returned: &Expr,
+    scope: &Scope,
+    types: &Types,
+    depth: usize,
+) -> Option<DataType> {
+    match returned {
+        Expr::Path(expr) => match expr.path.segments.last()?.ident.to_string().as_str() {
+            "Boolean" => Some(DataType::Boolean),
+            "SmallInt" => Some(DataType::SmallInt),
+            "Integer" => Some(DataType::Integer),
+            "BigInt" => Some(DataType::BigInt),
+            "Float" => Some(DataType::Float),
+            "Double" => Some(DataType::Double),
+            "Text" => Some(DataType::Text),
+            "Blob" => Some(DataType::Blob),
+            "Date" => Some(DataType::Date),
+            "Time" => Some(DataType::Time),
+            "Timestamp" => Some(DataType::Timestamp),
+            "TimestampTz" => Some(DataType::TimestampTz),
+            "Uuid" => Some(DataType::Uuid),
+            "Json" => Some(DataType::Json),
+            _ => None,
+        },
+        Expr::Struct(expr) if expr.path.segments.last()?.ident == "Decimal" => {
+            let mut precision = None;
+            let mut scale = None;
+            for field in &expr.fields {
+                let Member::Named(name) = &field.member else {
+                    return None;
+                };
+                let value = integer(&field.expr)?;
+                if name == "precision" {
+                    precision = Some(value);
+                } else if name == "scale" {
+                    scale = Some(value);
+                }
+            }
+            Some(DataType::Decimal {
+                precision: u8::try_from(precision?).ok()?,
+                scale: u8::try_from(scale?).ok()?,
+            })
+        }
+        Expr::Call(call) => {
+            let Expr::Path(function) = &*call.func else {
+                return None;
+            };
+            let segments = &function.path.segments;
+            match segments.last()?.ident.to_string().as_str() {
+                "Varchar" if call.args.len() == 1 => Some(DataType::Varchar(
+                    u32::try_from(integer(call.args.first()?)?).ok()?,
+                )),
+                "data_type" if call.args.is_empty() => {
+                    let ty = match &function.qself {
+                        Some(qself) => (*qself.ty).clone(),
+                        None => {
+                            let mut path = function.path.clone();
+                            path.segments.pop();
+                            path.segments.pop_punct();
+                            Type::Path(syn::TypePath { qself: None, path })
+                        }
+                    };
+                    data_type(&ty, scope, types, depth)
+                }
+                _ => None,
+            }
+        }
+        Expr::Paren(expr) => declared_data_type(&expr.expr, scope, types, depth),
+        Expr::Block(expr) => match expr.block.stmts.last()? {
+            Stmt::Expr(expr, None) => declared_data_type(expr, scope, types, depth),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the value of an integer literal, or `None` for any other expression.
+fn integer(expr: &Expr) -> Option<u64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) => value.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_schema(name: &str, schema: &str) -> Result<Vec<TableSchema>, Box<dyn Error>> {
+        let path = std::env::temp_dir().join(format!("njord_models_{}.rs", name));
+        fs::write(&path, schema).unwrap();
+        let models = read_models(&path);
+        fs::remove_file(&path).unwrap();
+        models
+    }
+
+    #[test]
+    fn read_newtype_columns() {
+        let tables = read_schema(
+            "newtype",
+            r#"
+            use njord::dialect::DataType;
+            use njord::types::SqlType;
+
+            struct Email(String);
+
+            impl SqlType for Email {
+                fn data_type() -> DataType {
+                    <String as SqlType>::data_type()
+                }
+            }
+
+            struct Cents(i64);
+
+            impl SqlType for Cents {
+                fn data_type() -> DataType {
+                    DataType::Decimal { precision: 12, scale: 2 }
+                }
+            }
+
+            #[derive(Table)]
+            #[table_name = "users"]
+            struct User {
+                id: AutoIncrementPrimaryKey<i64>,
+                email: Email,
+                balance: Option<Cents>,
+            }
+            "#,
+        )
+        .unwrap();
+
+        let columns = &tables[0].columns;
+        assert_eq!(columns[1].data_type, DataType::Text);
+        assert_eq!(
+            columns[2].data_type,
+            DataType::Decimal {
+                precision: 12,
+                scale: 2
+            }
+        );
+        assert!(columns[2].nullable);
+    }
+
+    #[test]
+    fn read_unknown_newtype_column() {
+        let err = read_schema(
+            "unknown_newtype",
+            r#"
+            use crate::types::Email;
+
+            #[derive(Table)]
+            #[table_name = "users"]
+            struct User {
+                id: AutoIncrementPrimaryKey<i64>,
+                email: Email,
+            }
+            "#,
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("`crate::types::Email` of users.email"));
+        assert!(err.contains("#[column(length = ...)]"));
+    }
+}
@@ -20,6 +20,7 @@ pub enum ConfigError {
 pub struct Config {
     migrations_directory: Option<MigrationsDirectory>,
     schema_file: Option<SchemaFile>,
+    database: Option<Database>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,6 +35,12 @@ pub struct SchemaFile {
     file: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct Database {
+    url: String,
+}
+
 #[derive(Table)]
 #[table_name = "migration_history"]
 pub struct MigrationHistory {
@@ -183,7 +190,7 @@ pub fn get_local_migration_versions(migrations_dir: &Path) -> Result<HashSet<Str
 /// Returns a `rusqlite::Error` if there is an issue with the database query.
 pub fn version_not_in_database(conn: &Connection, version: &str) -> Result<bool, Error> {
     let query = "SELECT EXISTS(SELECT 1 FROM migration_history WHERE version = ?)";
-    let result: Result<i32, Error> = conn.query_row(query, &[&version], |row| row.get(0));
+    let result: Result<i32, Error> = conn.query_row(query, [&version], |row| row.get(0));
 
     match result {
         Ok(1) => Ok(false),
@@ -204,6 +211,8 @@ pub fn version_not_in_database(conn: &Connection, version: &str) -> Result<bool,
 /// * `migrations_dir` - The path to the directory where migration files will be created.
 /// * `version` - The version of the migration.
 /// * `name` - The name of the migration.
+/// * `up_sql` - The content of `up.sql`, empty for a migration written by hand.
+/// * `down_sql` - The content of `down.sql`, empty for a migration written by hand.
 ///
 /// # Errors
 ///
@@ -213,6 +222,8 @@ pub fn create_migration_files(
     migrations_dir: &Path,
     version: &str,
     name: &str,
+    up_sql: &str,
+    down_sql: &str,
 ) -> Result<(), std::io::Error> {
     let mut dir_path = PathBuf::from(migrations_dir);
     dir_path.push(format!("{}_{}", version, name));
@@ -224,8 +235,8 @@ pub fn create_migration_files(
     let up_sql_path = dir_path.join("up.sql");
     let down_sql_path = dir_path.join("down.sql");
 
-    fs::File::create(up_sql_path)?;
-    fs::File::create(down_sql_path)?;
+    fs::write(up_sql_path, up_sql)?;
+    fs::write(down_sql_path, down_sql)?;
 
     Ok(())
 }
@@ -253,3 +264,47 @@ pub fn get_migrations_directory_path(config: &TomlConfig) -> Option<PathBuf> {
 
     migrations_dir
 }
+
+/// Retrieves the path to the schema file from the configuration.
+///
+/// The schema file holds the `#[derive(Table)]` models compared with the database
+/// by `njord migration generate --auto`. It is set by the `file` key of the `schema`
+/// section.
+///
+/// # Arguments
+///
+/// * `config` - A reference to a `toml::Value` representing the configuration.
+///
+/// # Returns
+///
+/// An `Option<PathBuf>` containing the path to the schema file if found in the configuration,
+/// or `None` if the schema file is not present or cannot be extracted.
+pub fn get_schema_file_path(config: &TomlConfig) -> Option<PathBuf> {
+    config
+        .get("schema")
+        .and_then(|value| value.get("file"))
+        .and_then(|file| file.as_str())
+        .map(PathBuf::from)
+}
+
+/// Retrieves the database URL from the configuration.
+///
+/// The database is compared with the models by `njord migration generate --auto`, and the
+/// scheme of its URL decides the dialect of the generated SQL, e.g. `sqlite://sqlite.db`.
+/// It is set by the `url` key of the `database` section.
+///
+/// # Arguments
+///
+/// * `config` - A reference to a `toml::Value` representing the configuration.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the database URL if found in the configuration,
+/// or `None` if the database URL is not present or cannot be extracted.
+pub fn get_database_url(config: &TomlConfig) -> Option<String> {
+    config
+        .get("database")
+        .and_then(|value| value.get("url"))
+        .and_then(|url| url.as_str())
+        .map(str::to_string)
+}
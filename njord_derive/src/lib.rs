@@ -35,9 +35,13 @@ use proc_macro2::TokenStream as TokenStream2;
 use syn::{parse_macro_input, DeriveInput, FieldsNamed};
 
 use proc_macro2::{Delimiter, TokenTree as TokenTree2};
-use quote::quote;
+use quote::{format_ident, quote};
 
-use util::{extract_table_name, has_default_impl};
+use util::{
+    column_value_type, db_enum_variant_name, default_foreign_key, extract_table_name,
+    has_default_impl, is_auto_increment_primary_key, is_integer_db_enum, ColumnAttributes,
+    RelationAttribute, RelationKind,
+};
 
 mod util;
 
@@ -50,6 +54,7 @@ mod util;
 /// # Example
 ///
 /// ```rust
+/// use njord::table::Table;
 /// use njord_derive::Table;
 ///
 /// #[derive(Table)]
@@ -57,7 +62,7 @@ mod util;
 /// struct User {
 ///     id: i32,
 ///     name: String,
-///     email: Option<String>,
+///     email: String,
 /// }
 /// ```
 ///
@@ -65,12 +70,131 @@ mod util;
 /// - `get_name()` - Returns the table name.
 /// - `get_columns()` - Returns column names and their SQL types.
 /// - `get_column_fields()` - Returns the field names as a vector.
-/// - `get_column_values()` - Returns the field values as strings.
-/// - `set_column_value()` - Updates a field value by column name.
-/// - `is_auto_increment_primary_key()` - Checks if a value is an auto-increment primary key.
+/// - `get_column_values()` - Returns the field values as statement parameters.
+/// - `column_definitions()` - Returns the type, key and default of every column.
+/// - `primary_key()` - Returns the columns of the primary key.
+/// - `primary_key_values()` - Returns the values of the primary key columns.
+/// - `get_auto_increment_primary_key()` - Returns the column holding an `AutoIncrementPrimaryKey`.
+/// - `set_auto_increment_primary_key()` - Sets that key to a value generated by the database.
+///
+/// Each field also gets a typed column handle named after it in upper case, e.g.
+/// `User::NAME`, which builds conditions checked against the field type:
+///
+/// ```rust
+/// # use njord::table::Table;
+/// # use njord_derive::Table;
+/// # #[derive(Table)]
+/// # #[table_name = "users"]
+/// # struct User {
+/// #     id: i32,
+/// #     name: String,
+/// #     email: String,
+/// # }
+/// let by_name = User::NAME.eq("mjovanc");
+/// let newer = User::ID.gt(10);
+/// let icloud = User::EMAIL.like("%@icloud.com");
+/// ```
+///
+/// The struct also implements `FromRow`, decoding each field from the column with its
+/// name, so query results are read into it without going through strings. Fields whose
+/// column was not selected keep their default value.
+///
+/// Fields may be of any type implementing `njord::types::SqlType`, which decides the
+/// column type and how values are bound and decoded. Besides integers, floats, `bool`,
+/// `String` and `Vec<u8>`, it is implemented for chrono's `NaiveDate`, `NaiveTime`,
+/// `NaiveDateTime` and `DateTime<Utc>`, as well as `uuid::Uuid`, `rust_decimal::Decimal`
+/// and `serde_json::Value` with njord's `uuid`, `decimal` and `json` features. Any of
+/// them may be wrapped in `Option` for a nullable column, and newtypes and other domain
+/// types become columns by implementing it themselves.
+///
+/// Fields accept these attributes:
+/// - `#[column(name = "...")]` stores the field in a column with a different name.
+/// - `#[skip]` leaves the field out of the table. It always gets its default value.
+/// - `#[default]` marks a column the database fills in with its default value, and
+///   `#[default = "CURRENT_TIMESTAMP"]` also gives the default of the created table.
+/// - `#[column(generated)]` marks a column the database computes.
+/// - `#[column(read_only)]` marks a column that is selected, but never written.
+/// - `#[primary_key]` makes the column part of the primary key. Fields of type
+///   `PrimaryKey` or `AutoIncrementPrimaryKey` are always part of it, and several
+///   fields together form a composite key in the order they are declared.
+/// - `#[column(length = 255)]` creates the column as `VARCHAR(255)`, and
+///   `#[column(precision = 10, scale = 2)]` as `DECIMAL(10, 2)`.
+///
+/// Columns with `#[default]`, `#[column(generated)]` or `#[column(read_only)]` are left
+/// out of `get_column_fields()` and `get_column_values()`, and so out of INSERT and
+/// UPDATE statements:
+///
+/// ```rust
+/// # use njord::table::Table;
+/// # use njord_derive::Table;
+/// #[derive(Table)]
+/// #[table_name = "users"]
+/// struct User {
+///     #[column(name = "user_name")]
+///     name: String,
+///     #[default]
+///     created_at: String,
+///     #[skip]
+///     cached_score: u32,
+/// }
+///
+/// let user = User::default();
+/// assert_eq!(user.get_column_fields(), vec!["user_name".to_string()]);
+/// assert_eq!(User::NAME.name(), "user_name");
+/// ```
+///
+/// Relationships to other tables are declared on the struct, and implement
+/// `njord::relation::Related` for the related table:
+/// - `#[belongs_to(User, fk = "user_id")]` - a column of this table references `User`.
+/// - `#[has_many(Post, fk = "user_id")]` - a column of `Post` references this table.
+/// - `#[many_to_many(Tag, through = "post_tags", fk = "post_id", related_fk = "tag_id")]` -
+///   the join table `post_tags` references both tables.
+///
+/// Foreign keys left out default to the snake case name of the referenced struct
+/// followed by `_id`. The related rows are loaded with `select(...).with(...)`:
+///
+/// ```rust
+/// # use njord::keys::AutoIncrementPrimaryKey;
+/// # use njord::relation::{Related, Relation};
+/// # use njord::table::Table;
+/// # use njord_derive::Table;
+/// #[derive(Table, Clone)]
+/// #[table_name = "posts"]
+/// #[belongs_to(User)]
+/// struct Post {
+///     id: AutoIncrementPrimaryKey<i64>,
+///     user_id: i64,
+///     title: String,
+/// }
+///
+/// #[derive(Table, Clone)]
+/// #[table_name = "users"]
+/// #[has_many(Post)]
+/// struct User {
+///     id: AutoIncrementPrimaryKey<i64>,
+///     name: String,
+/// }
+///
+/// assert_eq!(
+///     <User as Related<Post>>::relation(),
+///     Relation::HasMany { foreign_key: "user_id".to_string() }
+/// );
+/// ```
 ///
 /// Additional traits like `Default`, `Display`, and `FromStr` are also implemented if applicable.
-#[proc_macro_derive(Table, attributes(table_name))]
+#[proc_macro_derive(
+    Table,
+    attributes(
+        table_name,
+        column,
+        skip,
+        default,
+        primary_key,
+        belongs_to,
+        has_many,
+        many_to_many
+    )
+)]
 pub fn table_derive(input: TokenStream) -> TokenStream {
     let cloned_input = input.clone();
     let derive_input: DeriveInput = parse_macro_input!(cloned_input);
@@ -84,25 +208,50 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
     let mut columns_stream = TokenStream2::default();
     let mut column_fields_stream = TokenStream2::default();
     let mut column_values_stream = TokenStream2::default();
-    let mut set_column_values_stream = TokenStream2::default();
+    let mut column_definitions_stream = TokenStream2::default();
+    let mut primary_key_stream = TokenStream2::default();
     let mut is_auto_increment_primary_key_stream = TokenStream2::default();
 
     let mut display_impl = TokenStream2::default();
     let mut from_str_impl = TokenStream2::default();
     let mut default_impl = TokenStream2::default();
+    let mut column_handles_impl = TokenStream2::default();
+    let mut from_row_impl_stream = TokenStream2::default();
+    let mut relations_impl = TokenStream2::default();
 
     if let syn::Data::Struct(s) = data {
+        from_row_impl_stream = match from_row_impl(&ident, &derive_input.generics, &s.fields, true)
+        {
+            Ok(stream) => stream,
+            Err(error) => return error.to_compile_error().into(),
+        };
+
         if let syn::Fields::Named(FieldsNamed { named, .. }) = s.fields {
+            let mut columns = Vec::new();
+            for field in &named {
+                match ColumnAttributes::from_field(field) {
+                    Ok(attributes) if attributes.skip => {}
+                    Ok(attributes) => columns.push((field, attributes)),
+                    Err(error) => return error.to_compile_error().into(),
+                }
+            }
+            let written_columns = columns
+                .iter()
+                .filter(|(_, attributes)| attributes.is_written());
+
             let field_names = named.iter().map(|f| &f.ident);
-            let field_names_clone2 = field_names.clone();
-            let field_names_clone3 = field_names.clone();
-            let field_names_clone4 = field_names.clone();
-            let field_types = named.iter().map(|f| &f.ty);
-            let field_types_clone = named.iter().map(|f| &f.ty);
-            let field_values = named.iter().map(|f| {
+            let column_names = columns.iter().map(|(_, attributes)| &attributes.name);
+            let written_column_names = written_columns
+                .clone()
+                .map(|(_, attributes)| &attributes.name);
+            let field_values = written_columns.map(|(f, _)| {
                 let field_name = &f.ident;
-                quote! { self.#field_name.to_string() }
+                quote! { ::njord::query::ToParam::to_param(&self.#field_name) }
             }); // field_values
+            let column_types = columns.iter().map(|(f, _)| {
+                let field_type = &f.ty;
+                quote! { ::njord::types::declared_type::<#field_type>() }
+            });
 
             // Implement the std::fmt::Display trait
             display_impl.extend(quote! {
@@ -143,24 +292,9 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
                 fn get_columns(&self) -> std::collections::HashMap<String, String> {
                     let mut columns = std::collections::HashMap::new();
                     #(
-                        let column_type = match stringify!(#field_types) {
-                            "i64" | "i32" | "i16" | "i8" | "u64" | "u32" | "u16" | "u8" | "usize" => "INTEGER",
-                            "String" => "TEXT",
-                            "f64" | "f32" => "REAL",
-                            "Vec<u8>" => "BLOB",
-                            "Option<i64>" | "Option<i32>" | "Option<i16>" | "Option<i8>" | "Option<u64>" | "Option<u32>" | "Option<u16>" | "Option<u8>" | "Option<usize>" => "INTEGER NULL",
-                            "Option<String>" => "TEXT NULL",
-                            "Option<f64>" | "Option<f32>" => "REAL NULL",
-                            "Option<Vec<u8>>" => "BLOB NULL",
-                            "bool" => "TEXT",
-                            _ => {
-                                eprintln!("Warning: Unknown data type for column '{}'", stringify!(#field_names));
-                                "UNKNOWN_TYPE"
-                            }
-                        };
                         columns.insert(
-                            stringify!(#field_names).to_string(),
-                            column_type.to_string(),
+                            #column_names.to_string(),
+                            #column_types,
                         );
                     )*
                     columns
@@ -170,41 +304,129 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
             // Implement the get_column_fields() function
             column_fields_stream.extend(quote! {
                 fn get_column_fields(&self) -> Vec<String> {
-                    vec![#(stringify!(#field_names_clone2).to_string()),*]
+                    vec![#(#written_column_names.to_string()),*]
                 }
             }); // column_fields_stream
 
             // Implement the get_column_values() function
             column_values_stream.extend(quote! {
-                fn get_column_values(&self) -> Vec<String> {
+                fn get_column_values(&self) -> Vec<::njord::query::Param> {
                     vec![#(#field_values),*]
                 }
             }); // column_values_stream
 
-            set_column_values_stream.extend(quote! {
-                fn set_column_value(&mut self, column: &str, value: &str) {
-                    match column {
-                        #(
-                            stringify!(#field_names_clone3) => {
-                                if let Ok(val) = value.parse::<#field_types_clone>() {
-                                    self.#field_names_clone3 = val;
-                                } else {
-                                    eprintln!("Error: Failed to convert value for column '{}'", column);
-                                }
-                            }
-                        )*
+            // Implement the column_definitions() function
+            let column_definitions = columns.iter().map(|(f, attributes)| {
+                let field_type = &f.ty;
+                let column_name = &attributes.name;
+                let mut definition = quote! {
+                    ::njord::schema::ColumnDefinition::of_type::<#field_type>(#column_name)
+                };
+                if let Some(length) = attributes.length {
+                    definition.extend(quote! {
+                        .data_type(::njord::dialect::DataType::Varchar(#length))
+                    });
+                }
+                if let Some((precision, scale)) = attributes.precision {
+                    definition.extend(quote! {
+                        .data_type(::njord::dialect::DataType::Decimal {
+                            precision: #precision,
+                            scale: #scale,
+                        })
+                    });
+                }
+                if attributes.primary_key {
+                    definition.extend(quote! { .primary_key() });
+                }
+                if is_auto_increment_primary_key(&f.ty) {
+                    definition.extend(quote! { .auto_increment() });
+                }
+                if let Some(expression) = &attributes.default_expression {
+                    definition.extend(quote! { .default(#expression) });
+                }
+                definition
+            });
+            column_definitions_stream.extend(quote! {
+                fn column_definitions(&self) -> Vec<::njord::schema::ColumnDefinition> {
+                    vec![#(#column_definitions),*]
+                }
+            }); // column_definitions_stream
+
+            // Implement the primary_key() and primary_key_values() functions
+            let key_columns = columns
+                .iter()
+                .filter(|(_, attributes)| attributes.primary_key);
+            let key_names = key_columns.clone().map(|(_, attributes)| &attributes.name);
+            let key_values = key_columns.map(|(f, _)| {
+                let field_name = &f.ident;
+                quote! { ::njord::query::ToParam::to_param(&self.#field_name) }
+            });
+            primary_key_stream.extend(quote! {
+                fn primary_key(&self) -> Vec<String> {
+                    vec![#(#key_names.to_string()),*]
+                }
 
-                        _ => eprintln!("Warning: Unknown column '{}'", column),
-                    }
+                fn primary_key_values(&self) -> Vec<::njord::query::Param> {
+                    vec![#(#key_values),*]
+                }
+            }); // primary_key_stream
+
+            // Implement the Related trait for every declared relationship
+            let relations = match RelationAttribute::from_attrs(&attrs) {
+                Ok(relations) => relations,
+                Err(error) => return error.to_compile_error().into(),
+            };
+            for relation in relations {
+                match related_impl(&ident, &columns, &relation) {
+                    Ok(stream) => relations_impl.extend(stream),
+                    Err(error) => return error.to_compile_error().into(),
                 }
-            }); // set_column_values_stream
+            }
+
+            // Implement the functions for the AutoIncrementPrimaryKey field, if there is one
+            if let Some((field, attributes)) = columns
+                .iter()
+                .find(|(f, _)| is_auto_increment_primary_key(&f.ty))
+            {
+                let field_name = &field.ident;
+                let column_name = &attributes.name;
+                let value_type = column_value_type(&field.ty);
+
+                is_auto_increment_primary_key_stream.extend(quote! {
+                    fn get_auto_increment_primary_key(&self) -> Option<&str> {
+                        Some(#column_name)
+                    }
 
-            // Implement the is_auto_increment_primary_key function
-            is_auto_increment_primary_key_stream.extend(quote! {
-                fn is_auto_increment_primary_key(&self, value: &str) -> bool {
-                    value == "NULL"
+                    fn set_auto_increment_primary_key(
+                        &mut self,
+                        value: ::njord::query::Param,
+                    ) -> Result<(), ::njord::row::DecodeError> {
+                        let value = ::njord::row::decode_value::<#value_type>(#column_name, &value)?;
+                        self.#field_name.set(value);
+                        Ok(())
+                    }
+                });
+            }
+
+            // Generate a typed column handle for every stored field
+            let column_handles = columns.iter().map(|(f, attributes)| {
+                let field_name = f.ident.as_ref().unwrap().to_string();
+                let column_name = &attributes.name;
+                let handle_name =
+                    format_ident!("{}", field_name.trim_start_matches("r#").to_uppercase());
+                let value_type = column_value_type(&f.ty);
+                let doc = format!("Typed handle for the `{}` column.", column_name);
+                quote! {
+                    #[doc = #doc]
+                    pub const #handle_name: ::njord::column::TypedColumn<#ident, #value_type> =
+                        ::njord::column::TypedColumn::new(#column_name);
                 }
             });
+            column_handles_impl.extend(quote! {
+                impl #ident {
+                    #(#column_handles)*
+                }
+            }); // column_handles_impl
 
             // If Default trait is not implemented, generate an implementation
             default_impl = if !has_default_impl(&derive_input) {
@@ -213,7 +435,7 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
                         fn default() -> Self {
                             Self {
                                 #(
-                                    #field_names_clone4: Default::default(),
+                                    #field_names: Default::default(),
                                 )*
                             }
                         }
@@ -231,10 +453,14 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
             #columns_stream
             #column_fields_stream
             #column_values_stream
-            #set_column_values_stream
+            #column_definitions_stream
+            #primary_key_stream
             #is_auto_increment_primary_key_stream
         }
 
+        #column_handles_impl
+        #relations_impl
+        #from_row_impl_stream
         #default_impl
         #display_impl
         #from_str_impl
@@ -243,18 +469,411 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// Generates the `Related` implementation for a relationship declared on a table struct.
+///
+/// # Arguments
+///
+/// * `ident` - The name of the table struct.
+/// * `columns` - The stored fields of the struct with their attributes.
+/// * `relation` - The declared relationship.
+///
+/// # Returns
+///
+/// The implementation, or an error if the field the related rows are matched on
+/// cannot be found.
+fn related_impl(
+    ident: &syn::Ident,
+    columns: &[(&syn::Field, ColumnAttributes)],
+    relation: &RelationAttribute,
+) -> syn::Result<TokenStream2> {
+    let related = &relation.related;
+    let related_ident = &related
+        .segments
+        .last()
+        .ok_or_else(|| syn::Error::new_spanned(related, "expected a table struct"))?
+        .ident;
+
+    let (relation_stream, key_field) = match relation.kind {
+        RelationKind::BelongsTo => {
+            let foreign_key = relation
+                .foreign_key
+                .clone()
+                .unwrap_or_else(|| default_foreign_key(related_ident));
+            let key_field = columns
+                .iter()
+                .find(|(_, attributes)| attributes.name == foreign_key)
+                .map(|(field, _)| &field.ident)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        related,
+                        format!("no column `{}` holds the foreign key", foreign_key),
+                    )
+                })?;
+
+            (
+                quote! {
+                    ::njord::relation::Relation::BelongsTo {
+                        foreign_key: #foreign_key.to_string(),
+                    }
+                },
+                key_field,
+            )
+        }
+        RelationKind::HasMany | RelationKind::ManyToMany => {
+            let keys: Vec<_> = columns
+                .iter()
+                .filter(|(_, attributes)| attributes.primary_key)
+                .collect();
+            let [(key_field, _)] = keys.as_slice() else {
+                return Err(syn::Error::new_spanned(
+                    related,
+                    "relationships need a single-column primary key",
+                ));
+            };
+            let foreign_key = relation
+                .foreign_key
+                .clone()
+                .unwrap_or_else(|| default_foreign_key(ident));
+
+            let relation_stream = if relation.kind == RelationKind::HasMany {
+                quote! {
+                    ::njord::relation::Relation::HasMany {
+                        foreign_key: #foreign_key.to_string(),
+                    }
+                }
+            } else {
+                let through = relation.through.as_deref().unwrap_or_default();
+                let related_key = relation
+                    .related_key
+                    .clone()
+                    .unwrap_or_else(|| default_foreign_key(related_ident));
+                quote! {
+                    ::njord::relation::Relation::ManyToMany {
+                        through: #through.to_string(),
+                        foreign_key: #foreign_key.to_string(),
+                        related_key: #related_key.to_string(),
+                    }
+                }
+            };
+
+            (relation_stream, &key_field.ident)
+        }
+    };
+
+    Ok(quote! {
+        impl ::njord::relation::Related<#related> for #ident {
+            fn relation() -> ::njord::relation::Relation {
+                #relation_stream
+            }
+
+            fn relation_key(&self) -> ::njord::query::Param {
+                ::njord::query::ToParam::to_param(&self.#key_field)
+            }
+        }
+    })
+}
+
+/// Derives the `FromRow` trait for a struct.
+///
+/// The struct can then be the result type of a query without being a `Table`, e.g. a
+/// DTO holding the columns of a join or an aggregate. Fields of structs with named
+/// fields are decoded from the column with the same name or alias, whatever its
+/// position in the row; fields of tuple structs are decoded by position. Like with
+/// `#[derive(Table)]`, `#[column(name = "...")]` reads a field from a column with a
+/// different name and `#[skip]` gives it its default value instead.
+///
+/// # Example
+///
+/// ```rust
+/// use njord::row::{FromRow, Row};
+/// use njord::query::Param;
+/// use njord_derive::FromRow;
+///
+/// #[derive(FromRow)]
+/// struct OrderTotal {
+///     username: String,
+///     total: f64,
+/// }
+///
+/// let columns = vec!["total".to_string(), "username".to_string()];
+/// let values = vec![Param::Float(12.5), Param::Text("mjovanc".to_string())];
+///
+/// let order = OrderTotal::from_row(&Row::new(&columns, &values)).unwrap();
+/// assert_eq!(order.username, "mjovanc");
+/// ```
+#[proc_macro_derive(FromRow, attributes(column, skip))]
+pub fn from_row_derive(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident,
+        data,
+        generics,
+        ..
+    } = parse_macro_input!(input);
+
+    let fields = match data {
+        syn::Data::Struct(s) => s.fields,
+        _ => {
+            return syn::Error::new(ident.span(), "FromRow can only be derived for structs")
+                .to_compile_error()
+                .into()
+        }
+    };
+
+    match from_row_impl(&ident, &generics, &fields, false) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Derives the traits storing a fieldless enum in a table column.
+///
+/// Each variant is stored as its name in a TEXT column, or as the name given with
+/// `#[db(rename = "...")]`. With `#[db(integer)]` on the enum, variants are stored
+/// as their discriminant in an INTEGER column instead. A value matching no variant
+/// fails to decode with an error naming the enum and the value.
+///
+/// The enum can then be a field of a `#[derive(Table)]` struct, which also needs it
+/// to implement `Default`, and a value in conditions.
+///
+/// # Example
+///
+/// ```rust
+/// use njord::condition::Condition;
+/// use njord::table::Table;
+/// use njord_derive::{DbEnum, Table};
+///
+/// #[derive(DbEnum, Debug, Default, PartialEq)]
+/// enum Status {
+///     #[default]
+///     Open,
+///     #[db(rename = "on_hold")]
+///     OnHold,
+///     Done,
+/// }
+///
+/// #[derive(DbEnum, Debug, Default, PartialEq)]
+/// #[db(integer)]
+/// enum Priority {
+///     #[default]
+///     Low = 1,
+///     High = 10,
+/// }
+///
+/// #[derive(Table)]
+/// #[table_name = "tasks"]
+/// struct Task {
+///     title: String,
+///     status: Status,
+///     priority: Priority,
+/// }
+///
+/// let on_hold = Task::STATUS.eq(Status::OnHold);
+/// let urgent = Condition::Eq("priority".to_string(), Priority::High.into());
+/// ```
+#[proc_macro_derive(DbEnum, attributes(db))]
+pub fn db_enum_derive(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident,
+        data,
+        attrs,
+        generics,
+        ..
+    } = parse_macro_input!(input);
+
+    let variants = match data {
+        syn::Data::Enum(e) if generics.params.is_empty() => e.variants,
+        _ => {
+            return syn::Error::new(
+                ident.span(),
+                "DbEnum can only be derived for enums without generic parameters",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    match db_enum_impl(&ident, &attrs, &variants) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Generates the implementations of the `DbEnum` derive.
+///
+/// # Arguments
+///
+/// * `ident` - The name of the enum.
+/// * `attrs` - The attributes of the enum.
+/// * `variants` - The variants of the enum.
+fn db_enum_impl(
+    ident: &syn::Ident,
+    attrs: &[syn::Attribute],
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+) -> syn::Result<TokenStream2> {
+    let integer = is_integer_db_enum(attrs)?;
+
+    let mut variant_idents = Vec::new();
+    let mut variant_names = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "DbEnum can only be derived for enums without fields",
+            ));
+        }
+
+        let name = db_enum_variant_name(variant)?;
+        if integer && name.is_some() {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                "`#[db(rename)]` has no effect on enums stored as integers",
+            ));
+        }
+
+        variant_names.push(name.unwrap_or_else(|| variant.ident.to_string()));
+        variant_idents.push(&variant.ident);
+    }
+
+    let (to_param, from_value, data_type) = if integer {
+        (
+            quote! {
+                match self {
+                    #(Self::#variant_idents => ::njord::query::Param::Int(Self::#variant_idents as i64),)*
+                }
+            },
+            quote! {
+                let number = <i64 as ::njord::row::FromValue>::from_value(value)?;
+                #(
+                    if number == Self::#variant_idents as i64 {
+                        return Ok(Self::#variant_idents);
+                    }
+                )*
+                Err(format!("unknown {} value {}", stringify!(#ident), number))
+            },
+            quote! { ::njord::dialect::DataType::Integer },
+        )
+    } else {
+        (
+            quote! {
+                match self {
+                    #(Self::#variant_idents => ::njord::query::Param::Text(#variant_names.to_string()),)*
+                }
+            },
+            quote! {
+                let text = <String as ::njord::row::FromValue>::from_value(value)?;
+                match text.as_str() {
+                    #(#variant_names => Ok(Self::#variant_idents),)*
+                    _ => Err(format!("unknown {} value '{}'", stringify!(#ident), text)),
+                }
+            },
+            quote! { ::njord::dialect::DataType::Text },
+        )
+    };
+
+    Ok(quote! {
+        impl ::njord::query::ToParam for #ident {
+            fn to_param(&self) -> ::njord::query::Param {
+                #to_param
+            }
+        }
+
+        impl ::njord::row::FromValue for #ident {
+            fn from_value(value: &::njord::query::Param) -> Result<Self, String> {
+                #from_value
+            }
+        }
+
+        impl ::njord::types::SqlType for #ident {
+            fn data_type() -> ::njord::dialect::DataType {
+                #data_type
+            }
+        }
+
+        impl ::njord::row::FromRow for #ident {
+            fn column_count() -> usize {
+                1
+            }
+
+            fn from_row(row: &::njord::row::Row<'_>) -> Result<Self, ::njord::row::DecodeError> {
+                row.get_at(0)
+            }
+        }
+
+        impl<'a> From<#ident> for ::njord::condition::Value<'a> {
+            fn from(value: #ident) -> Self {
+                ::njord::query::ToParam::to_param(&value).into()
+            }
+        }
+    })
+}
+
+/// Generates the `FromRow` implementation shared by the `Table` and `FromRow` derives.
+///
+/// # Arguments
+///
+/// * `ident` - The name of the struct.
+/// * `generics` - The generic parameters of the struct.
+/// * `fields` - The fields of the struct.
+/// * `default_missing` - Whether fields without a column in the row get their default value.
+fn from_row_impl(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &syn::Fields,
+    default_missing: bool,
+) -> syn::Result<TokenStream2> {
+    let mut column_count = fields.len();
+    let construct = match fields {
+        syn::Fields::Named(FieldsNamed { named, .. }) => {
+            let mut fields = Vec::new();
+            for f in named {
+                let field_name = &f.ident;
+                let attributes = ColumnAttributes::from_field(f)?;
+                let column_name = &attributes.name;
+                fields.push(if attributes.skip {
+                    column_count -= 1;
+                    quote! { #field_name: Default::default() }
+                } else if default_missing {
+                    quote! { #field_name: row.get_or_default(#column_name)? }
+                } else {
+                    quote! { #field_name: row.get(#column_name)? }
+                });
+            }
+            quote! { Self { #(#fields),* } }
+        }
+        syn::Fields::Unnamed(unnamed) => {
+            let fields = (0..unnamed.unnamed.len()).map(|index| quote! { row.get_at(#index)? });
+            quote! { Self(#(#fields),*) }
+        }
+        syn::Fields::Unit => quote! { Self },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::njord::row::FromRow for #ident #ty_generics #where_clause {
+            fn column_count() -> usize {
+                #column_count
+            }
+
+            fn from_row(row: &::njord::row::Row<'_>) -> Result<Self, ::njord::row::DecodeError> {
+                Ok(#construct)
+            }
+        }
+    })
+}
+
 /// A procedural macro `sql!` that takes SQL-like syntax and transforms it into a formatted string.
 ///
 /// # Example
 ///
 /// ```rust
 /// use njord_derive::sql;
-/// let id = 1;
+/// let username = "mjovanc";
 ///
 /// let query = sql! {
-///     SELECT * FROM user WHERE id = {id}
+///     SELECT * FROM user WHERE username = {username}
 /// };
-/// assert_eq!(query, "SELECT * FROM user WHERE id = 1");
+/// assert_eq!(query, "SELECT * FROM user WHERE username = 'mjovanc'");
 /// ```
 ///
 /// This macro supports embedding expressions within SQL queries, ensuring proper formatting
@@ -298,8 +917,8 @@ pub fn sql(input: TokenStream) -> TokenStream {
                 let needs_quotes = !expr_str.contains("as")
                     && !expr_str.contains("::")
                     && !expr_str.starts_with("Some")
-                    && !expr_str.parse::<f64>().is_ok()
-                    && !expr_str.parse::<i64>().is_ok();
+                    && expr_str.parse::<f64>().is_err()
+                    && expr_str.parse::<i64>().is_err();
 
                 if needs_quotes {
                     sql_parts.push("'{}'".to_string());
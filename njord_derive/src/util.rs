@@ -112,7 +112,7 @@ pub fn extract_table_name(attrs: &[syn::Attribute]) -> String {
 pub fn is_option_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(type_path) = ty {
         let segments = &type_path.path.segments;
-        if segments.len() == 1 && segments.first().unwrap().ident.to_string() == "Option" {
+        if segments.len() == 1 && segments.first().unwrap().ident == "Option" {
             if let syn::PathArguments::AngleBracketed(args) = &segments.first().unwrap().arguments {
                 if args.args.len() == 1 {
                     return true;
@@ -122,3 +122,388 @@ pub fn is_option_type(ty: &syn::Type) -> bool {
     }
     false
 }
+
+/// Returns the type values of a column are checked against.
+///
+/// `Option<T>`, `PrimaryKey<T>` and `AutoIncrementPrimaryKey<T>` are unwrapped to `T`,
+/// any other type is returned as is.
+///
+/// # Arguments
+///
+/// * `ty` - A reference to the `syn::Type` of the field.
+///
+/// # Returns
+///
+/// The `syn::Type` used for the column handle of the field.
+pub fn column_value_type(ty: &syn::Type) -> syn::Type {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            let is_wrapper = ["Option", "PrimaryKey", "AutoIncrementPrimaryKey"]
+                .iter()
+                .any(|wrapper| segment.ident == wrapper);
+
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let (true, Some(syn::GenericArgument::Type(inner))) =
+                    (is_wrapper, args.args.first())
+                {
+                    return column_value_type(inner);
+                }
+            }
+        }
+    }
+    ty.clone()
+}
+
+/// Checks if a field holds an `AutoIncrementPrimaryKey`.
+///
+/// # Arguments
+///
+/// * `ty` - A reference to the `syn::Type` of the field.
+///
+/// # Returns
+///
+/// `true` if the field type is `AutoIncrementPrimaryKey<T>`, `false` otherwise.
+pub fn is_auto_increment_primary_key(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "AutoIncrementPrimaryKey"),
+        _ => false,
+    }
+}
+
+/// Checks if a field holds a `PrimaryKey` or an `AutoIncrementPrimaryKey`.
+///
+/// # Arguments
+///
+/// * `ty` - A reference to the `syn::Type` of the field.
+///
+/// # Returns
+///
+/// `true` if the field type is one of the primary key wrappers, `false` otherwise.
+pub fn is_primary_key_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+            segment.ident == "PrimaryKey" || segment.ident == "AutoIncrementPrimaryKey"
+        }),
+        _ => false,
+    }
+}
+
+/// Checks if a `DbEnum` is stored as an integer, i.e. has a `#[db(integer)]` attribute.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of the enum.
+///
+/// # Returns
+///
+/// `true` if the variants are stored as their discriminant, `false` if they are stored
+/// as text, or an error for an unknown `db` attribute.
+pub fn is_integer_db_enum(attrs: &[syn::Attribute]) -> syn::Result<bool> {
+    let mut integer = false;
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("db")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("integer") {
+                integer = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `integer`"))
+            }
+        })?;
+    }
+
+    Ok(integer)
+}
+
+/// Extracts the name a `DbEnum` variant is stored as from its `#[db(rename = "...")]` attribute.
+///
+/// # Arguments
+///
+/// * `variant` - A reference to the `syn::Variant`.
+///
+/// # Returns
+///
+/// The name given to the variant, `None` if it is not renamed, or an error for an
+/// unknown `db` attribute.
+pub fn db_enum_variant_name(variant: &syn::Variant) -> syn::Result<Option<String>> {
+    let mut name = None;
+
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("db"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                name = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename = \"...\"`"))
+            }
+        })?;
+    }
+
+    Ok(name)
+}
+
+/// The attributes of a field of a `#[derive(Table)]` or `#[derive(FromRow)]` struct.
+pub struct ColumnAttributes {
+    /// The name of the column, given with `#[column(name = "...")]` or else the field name.
+    pub name: String,
+    /// `#[skip]`: the field is not stored in any column.
+    pub skip: bool,
+    /// `#[default]` or `#[default = "..."]`: the database fills in the default value
+    /// of the column.
+    pub db_default: bool,
+    /// The SQL expression given with `#[default = "..."]`.
+    pub default_expression: Option<String>,
+    /// `#[column(generated)]`: the database computes the value of the column.
+    pub generated: bool,
+    /// `#[column(read_only)]`: the column is selected, but never written.
+    pub read_only: bool,
+    /// `#[column(length = ...)]`: the column is a `VARCHAR` of the given length.
+    pub length: Option<u32>,
+    /// `#[column(precision = ..., scale = ...)]`: the column is a `DECIMAL` with the
+    /// given number of digits, `scale` of them after the decimal point.
+    pub precision: Option<(u8, u8)>,
+    /// `#[primary_key]` or a `PrimaryKey`/`AutoIncrementPrimaryKey` field: the column is
+    /// part of the primary key.
+    pub primary_key: bool,
+}
+
+impl ColumnAttributes {
+    /// Extracts the attributes of a field.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - A reference to the named `syn::Field`.
+    ///
+    /// # Returns
+    ///
+    /// The attributes of the field, or an error for a malformed attribute.
+    pub fn from_field(field: &syn::Field) -> syn::Result<Self> {
+        let field_name = field.ident.as_ref().unwrap().to_string();
+        let mut attributes = ColumnAttributes {
+            name: field_name.trim_start_matches("r#").to_string(),
+            skip: false,
+            db_default: false,
+            default_expression: None,
+            generated: false,
+            read_only: false,
+            length: None,
+            precision: None,
+            primary_key: is_primary_key_type(&field.ty),
+        };
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("skip") {
+                attr.meta.require_path_only()?;
+                attributes.skip = true;
+            } else if attr.path().is_ident("primary_key") {
+                attr.meta.require_path_only()?;
+                attributes.primary_key = true;
+            } else if attr.path().is_ident("default") {
+                match &attr.meta {
+                    Meta::NameValue(syn::MetaNameValue {
+                        value:
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(expression),
+                                ..
+                            }),
+                        ..
+                    }) => attributes.default_expression = Some(expression.value()),
+                    meta => {
+                        meta.require_path_only()?;
+                    }
+                }
+                attributes.db_default = true;
+            } else if attr.path().is_ident("column") {
+                let mut scale = None;
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("name") {
+                        let value: syn::LitStr = meta.value()?.parse()?;
+                        attributes.name = value.value();
+                    } else if meta.path.is_ident("generated") {
+                        attributes.generated = true;
+                    } else if meta.path.is_ident("read_only") {
+                        attributes.read_only = true;
+                    } else if meta.path.is_ident("length") {
+                        let value: syn::LitInt = meta.value()?.parse()?;
+                        attributes.length = Some(value.base10_parse()?);
+                    } else if meta.path.is_ident("precision") {
+                        let value: syn::LitInt = meta.value()?.parse()?;
+                        attributes.precision = Some((value.base10_parse()?, 0));
+                    } else if meta.path.is_ident("scale") {
+                        let value: syn::LitInt = meta.value()?.parse()?;
+                        scale = Some((value.base10_parse()?, value.span()));
+                    } else {
+                        return Err(meta.error(
+                            "expected `name = \"...\"`, `generated`, `read_only`, `length = ...`, \
+                             `precision = ...` or `scale = ...`",
+                        ));
+                    }
+                    Ok(())
+                })?;
+
+                if let Some((scale, span)) = scale {
+                    match &mut attributes.precision {
+                        Some((_, precision_scale)) => *precision_scale = scale,
+                        None => {
+                            return Err(syn::Error::new(span, "`scale` needs a `precision`"));
+                        }
+                    }
+                }
+                if attributes.length.is_some() && attributes.precision.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "a column has either a `length` or a `precision`",
+                    ));
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+
+    /// Returns `true` if the column is written by INSERT and UPDATE statements.
+    pub fn is_written(&self) -> bool {
+        !(self.skip || self.db_default || self.generated || self.read_only)
+    }
+}
+
+/// The kind of a relationship declared on a table struct.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RelationKind {
+    /// `#[belongs_to(...)]`
+    BelongsTo,
+    /// `#[has_many(...)]`
+    HasMany,
+    /// `#[many_to_many(...)]`
+    ManyToMany,
+}
+
+/// A relationship declared with `#[belongs_to]`, `#[has_many]` or `#[many_to_many]`.
+pub struct RelationAttribute {
+    /// The kind of the relationship.
+    pub kind: RelationKind,
+    /// The path of the related table struct.
+    pub related: syn::Path,
+    /// `fk = "..."`: the foreign key column referencing the table or, for
+    /// `belongs_to`, the related table.
+    pub foreign_key: Option<String>,
+    /// `related_fk = "..."`: the join table column referencing the related table.
+    pub related_key: Option<String>,
+    /// `through = "..."`: the join table of a `many_to_many` relationship.
+    pub through: Option<String>,
+}
+
+impl RelationAttribute {
+    /// Extracts the relationships declared on a struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `attrs` - A slice of `syn::Attribute` representing the attributes of the struct.
+    ///
+    /// # Returns
+    ///
+    /// The relationships in the order they are declared, or an error for a malformed
+    /// attribute.
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Vec<Self>> {
+        let mut relations = Vec::new();
+
+        for attr in attrs {
+            let kind = if attr.path().is_ident("belongs_to") {
+                RelationKind::BelongsTo
+            } else if attr.path().is_ident("has_many") {
+                RelationKind::HasMany
+            } else if attr.path().is_ident("many_to_many") {
+                RelationKind::ManyToMany
+            } else {
+                continue;
+            };
+
+            let relation = attr.parse_args_with(|input: syn::parse::ParseStream| {
+                let mut relation = RelationAttribute {
+                    kind,
+                    related: input.parse()?,
+                    foreign_key: None,
+                    related_key: None,
+                    through: None,
+                };
+
+                while !input.is_empty() {
+                    input.parse::<syn::Token![,]>()?;
+                    if input.is_empty() {
+                        break;
+                    }
+
+                    let name: syn::Ident = input.parse()?;
+                    input.parse::<syn::Token![=]>()?;
+                    let value = input.parse::<syn::LitStr>()?.value();
+
+                    match name.to_string().as_str() {
+                        "fk" => relation.foreign_key = Some(value),
+                        "related_fk" if kind == RelationKind::ManyToMany => {
+                            relation.related_key = Some(value)
+                        }
+                        "through" if kind == RelationKind::ManyToMany => {
+                            relation.through = Some(value)
+                        }
+                        _ if kind == RelationKind::ManyToMany => {
+                            return Err(syn::Error::new(
+                                name.span(),
+                                "expected `fk`, `related_fk` or `through`",
+                            ))
+                        }
+                        _ => return Err(syn::Error::new(name.span(), "expected `fk`")),
+                    }
+                }
+
+                if kind == RelationKind::ManyToMany && relation.through.is_none() {
+                    return Err(
+                        input.error("`many_to_many` needs a `through = \"...\"` join table")
+                    );
+                }
+
+                Ok(relation)
+            })?;
+
+            relations.push(relation);
+        }
+
+        Ok(relations)
+    }
+}
+
+/// Returns the default foreign key column referencing a struct, e.g. `user_id` for
+/// `User` or `order_item_id` for `OrderItem`.
+///
+/// # Arguments
+///
+/// * `ident` - The name of the referenced struct.
+pub fn default_foreign_key(ident: &syn::Ident) -> String {
+    let mut key = String::new();
+
+    for (index, c) in ident
+        .to_string()
+        .trim_start_matches("r#")
+        .chars()
+        .enumerate()
+    {
+        if c.is_uppercase() {
+            if index > 0 {
+                key.push('_');
+            }
+            key.extend(c.to_lowercase());
+        } else {
+            key.push(c);
+        }
+    }
+
+    key + "_id"
+}
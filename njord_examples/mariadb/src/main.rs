@@ -43,7 +43,7 @@ fn select() -> Result<(), Box<dyn std::error::Error>> {
 
     match results {
         Ok(data) => println!("Selected: {:#?}", data.len()),
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     }
 
     Ok(())
@@ -84,7 +84,7 @@ async fn insert() -> Result<(), Box<dyn std::error::Error>> {
 
     match mysql::insert(&mut conn, near_earth_objects) {
         Ok(_) => println!("Near Earth Objects inserted successfully"),
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     };
 
     Ok(())
@@ -46,7 +46,7 @@ async fn select() -> Result<(), Box<dyn std::error::Error>> {
 
     match results {
         Ok(data) => println!("Selected: {:#?}", data.len()),
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     }
 
     Ok(())
@@ -86,10 +86,10 @@ async fn insert() -> Result<(), Box<dyn std::error::Error>> {
         "jdbc:sqlserver://localhost;encrypt=true;username=sa;password=Njord_passw0rd;databaseName=NjordDatabase;";
     let mut conn = mssql::open(connection_string).await?;
 
-    let _ = match mssql::insert(&mut conn, near_earth_objects).await {
+    match mssql::insert(&mut conn, near_earth_objects).await {
         Ok(_) => println!("Near Earth Objects inserted successfully"),
         Err(err) => eprintln!("Error: {:?}", err),
-    };
+    }
 
     Ok(())
 }
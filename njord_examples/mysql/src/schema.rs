@@ -7,6 +7,7 @@ use serde::Deserialize;
 #[derive(Table, Deserialize, Debug)]
 #[table_name = "neo"]
 pub struct NearEarthObject {
+    #[serde(skip)]
     pub id: AutoIncrementPrimaryKey<usize>,
     #[serde(rename = "id")]
     pub neo_id: String,
@@ -36,15 +36,15 @@ async fn main() -> Result<(), Error> {
 
 fn select() -> Result<(), Box<dyn std::error::Error>> {
     let connection_string = "//localhost:1521/FREEPDB1";
-    let mut conn = oracle::open("njord_user", "njord_password", connection_string).unwrap();
+    let conn = oracle::open("njord_user", "njord_password", connection_string).unwrap();
 
     let results = oracle::select(vec![Column::Text("id".to_string())])
         .from(NeoId::default())
-        .build(&mut conn);
+        .build(&conn);
 
     match results {
         Ok(data) => println!("Selected: {:#?}", data.len()),
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     }
 
     Ok(())
@@ -77,7 +77,7 @@ async fn insert() -> Result<(), Box<dyn std::error::Error>> {
                 near_earth_objects.push(near_earth_obj);
             }
         }
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     }
 
     let connection_string = "//localhost:1521/FREEPDB1";
@@ -85,7 +85,7 @@ async fn insert() -> Result<(), Box<dyn std::error::Error>> {
 
     match oracle::insert(&mut conn, near_earth_objects) {
         Ok(_) => println!("Near Earth Objects inserted successfully"),
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     };
 
     Ok(())
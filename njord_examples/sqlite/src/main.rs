@@ -55,7 +55,7 @@ async fn main() -> Result<(), SqliteError> {
                 near_earth_objects.push(near_earth_obj);
             }
         }
-        Err(err) => eprintln!("Error: {}", err),
+        Err(err) => eprintln!("Error: {:?}", err),
     }
 
     let conn = sqlite::open(db_path)?;